@@ -59,12 +59,13 @@ where
     SC: SelectChain<Block> + 'static,
 
     C::Api: sp_consensus_babe::BabeApi<Block>,
-    C::Api: committee_rpc::CmStorageRuntimeApi<Block, AccountId>,
+    C::Api: committee_rpc::CmStorageRuntimeApi<Block, AccountId, BlockNumber, Balance>,
     C::Api: simple_rpc_rpc::SrStorageRuntimeApi<Block, AccountId, Balance>,
     C::Api: online_profile_rpc::OpStorageRuntimeApi<Block, AccountId, Balance, BlockNumber>,
     C::Api: online_committee_rpc::OcStorageRuntimeApi<Block, AccountId, BlockNumber, Balance>,
     C::Api: rent_machine_rpc::RmStorageRuntimeApi<Block, AccountId, BlockNumber, Balance>,
     C::Api: terminating_rental_rpc::IrStorageRuntimeApi<Block, AccountId, Balance, BlockNumber>,
+    C::Api: maintain_committee_rpc::MtStorageRuntimeApi<Block, AccountId, Balance, BlockNumber>,
 {
     use dbc_client_rpc_debug::{Debug, DebugServer};
     use dbc_client_rpc_trace::{Trace, TraceServer};
@@ -79,6 +80,7 @@ where
     use substrate_frame_rpc_system::{System, SystemApiServer};
 
     use committee_rpc::{CmRpcApiServer, CmStorage};
+    use maintain_committee_rpc::{MtRpcApiServer, MtStorage};
     use online_committee_rpc::{OcRpcApiServer, OcStorage};
     use online_profile_rpc::{OpRpcApiServer, OpStorage};
     use rent_machine_rpc::{RmRpcApiServer, RmStorage};
@@ -146,6 +148,7 @@ where
     io.merge(OpStorage::new(client.clone()).into_rpc()).ok();
     io.merge(RmStorage::new(client.clone()).into_rpc()).ok();
     io.merge(IrStorage::new(client.clone()).into_rpc()).ok();
+    io.merge(MtStorage::new(client.clone()).into_rpc()).ok();
     io.merge(
         EthFilter::new(
             client.clone(),
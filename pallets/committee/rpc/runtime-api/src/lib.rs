@@ -3,14 +3,23 @@
 #![allow(clippy::unnecessary_mut_passed)]
 #![warn(unused_crate_dependencies)]
 
-use committee::CommitteeList;
+use committee::{CommitteeList, CommitteeOverview, CommitteeReputationInfo};
 
 // Here we declare the runtime API. It is implemented it the `impl` block in
 // runtime amalgamator file (the `runtime/src/lib.rs`)
 sp_api::decl_runtime_apis! {
-    pub trait CmRpcApi<AccountId> where
+    // Version 2 adds `get_committee_reputation` and `get_committee_overview`, and the
+    // `BlockNumber` and `Balance` type parameters they need
+    #[api_version(2)]
+    pub trait CmRpcApi<AccountId, BlockNumber, Balance> where
         AccountId: parity_scale_codec::Codec + Ord,
+        BlockNumber: parity_scale_codec::Codec,
+        Balance: parity_scale_codec::Codec,
     {
         fn get_committee_list() -> CommitteeList<AccountId>;
+        #[api_version(2)]
+        fn get_committee_reputation(who: AccountId) -> CommitteeReputationInfo;
+        #[api_version(2)]
+        fn get_committee_overview(who: AccountId) -> CommitteeOverview<BlockNumber, Balance>;
     }
 }
@@ -1,25 +1,41 @@
 #![warn(unused_crate_dependencies)]
 
-use committee::CommitteeList;
+use committee::{CommitteeList, CommitteeOverview, CommitteeReputationInfo, CommitteeSlashInfo};
 pub use committee_runtime_api::CmRpcApi as CmStorageRuntimeApi;
+use dbc_support::rpc_types::RpcBalance;
 use jsonrpsee::{
     core::{Error as JsonRpseeError, RpcResult},
     proc_macros::rpc,
     types::error::{CallError, ErrorCode, ErrorObject},
 };
 use parity_scale_codec::Codec;
-use sp_api::ProvideRuntimeApi;
+use sp_api::{ApiExt, ProvideRuntimeApi};
 use sp_blockchain::HeaderBackend;
-use sp_runtime::traits::Block as BlockT;
-use std::sync::Arc;
+use sp_runtime::traits::{Block as BlockT, MaybeDisplay};
+use std::{fmt::Display, str::FromStr, sync::Arc};
 
 #[rpc(client, server)]
-pub trait CmRpcApi<BlockHash, AccountId>
+pub trait CmRpcApi<BlockHash, AccountId, BlockNumber, Balance>
 where
     AccountId: Ord,
+    Balance: Display + FromStr,
 {
     #[method(name = "committee_getCommitteeList")]
     fn get_committee_list(&self, at: Option<BlockHash>) -> RpcResult<CommitteeList<AccountId>>;
+
+    #[method(name = "committee_getCommitteeReputation")]
+    fn get_committee_reputation(
+        &self,
+        who: AccountId,
+        at: Option<BlockHash>,
+    ) -> RpcResult<CommitteeReputationInfo>;
+
+    #[method(name = "committee_getCommitteeOverview")]
+    fn get_committee_overview(
+        &self,
+        who: AccountId,
+        at: Option<BlockHash>,
+    ) -> RpcResult<CommitteeOverview<BlockNumber, RpcBalance<Balance>>>;
 }
 
 pub struct CmStorage<C, M> {
@@ -33,14 +49,17 @@ impl<C, M> CmStorage<C, M> {
     }
 }
 
-impl<C, Block, AccountId> CmRpcApiServer<<Block as BlockT>::Hash, AccountId> for CmStorage<C, Block>
+impl<C, Block, AccountId, BlockNumber, Balance>
+    CmRpcApiServer<<Block as BlockT>::Hash, AccountId, BlockNumber, Balance> for CmStorage<C, Block>
 where
     Block: BlockT,
     AccountId: Clone + std::fmt::Display + Codec + Ord,
+    BlockNumber: Clone + std::fmt::Display + Codec,
+    Balance: Codec + MaybeDisplay + Copy + FromStr,
     C: Send + Sync + 'static,
     C: ProvideRuntimeApi<Block>,
     C: HeaderBackend<Block>,
-    C::Api: CmStorageRuntimeApi<Block, AccountId>,
+    C::Api: CmStorageRuntimeApi<Block, AccountId, BlockNumber, Balance>,
 {
     fn get_committee_list(&self, at: Option<Block::Hash>) -> RpcResult<CommitteeList<AccountId>> {
         let api = self.client.runtime_api();
@@ -55,4 +74,89 @@ where
         })?;
         Ok(runtime_api_result)
     }
+
+    fn get_committee_reputation(
+        &self,
+        who: AccountId,
+        at: Option<Block::Hash>,
+    ) -> RpcResult<CommitteeReputationInfo> {
+        let api = self.client.runtime_api();
+        let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        ensure_api_version::<_, Block, AccountId, BlockNumber, Balance>(&api, at_hash)?;
+
+        let runtime_api_result = api.get_committee_reputation(at_hash, who).map_err(|e| {
+            JsonRpseeError::Call(CallError::Custom(ErrorObject::owned(
+                ErrorCode::InternalError.code(),
+                "Something wrong",
+                Some(e.to_string()),
+            )))
+        })?;
+        Ok(runtime_api_result)
+    }
+
+    fn get_committee_overview(
+        &self,
+        who: AccountId,
+        at: Option<Block::Hash>,
+    ) -> RpcResult<CommitteeOverview<BlockNumber, RpcBalance<Balance>>> {
+        let api = self.client.runtime_api();
+        let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        ensure_api_version::<_, Block, AccountId, BlockNumber, Balance>(&api, at_hash)?;
+
+        let runtime_api_result = api
+            .get_committee_overview(at_hash, who)
+            .map(|overview| CommitteeOverview {
+                pending_tasks: overview.pending_tasks,
+                reputation: overview.reputation,
+                slash: CommitteeSlashInfo {
+                    slash_count: overview.slash.slash_count,
+                    slashed_amount: overview.slash.slashed_amount.into(),
+                },
+                era_rewards: overview
+                    .era_rewards
+                    .into_iter()
+                    .map(|(era, reward)| (era, reward.into()))
+                    .collect(),
+                can_claim_reward: overview.can_claim_reward.into(),
+                claimed_reward: overview.claimed_reward.into(),
+            })
+            .map_err(|e| {
+                JsonRpseeError::Call(CallError::Custom(ErrorObject::owned(
+                    ErrorCode::InternalError.code(),
+                    "Something wrong",
+                    Some(e.to_string()),
+                )))
+            })?;
+        Ok(runtime_api_result)
+    }
+}
+
+// get_committee_reputation and get_committee_overview are only available since runtime api
+// version 2
+fn ensure_api_version<Api, Block, AccountId, BlockNumber, Balance>(
+    api: &Api,
+    at_hash: <Block as BlockT>::Hash,
+) -> RpcResult<()>
+where
+    Block: BlockT,
+    AccountId: Codec + Ord,
+    BlockNumber: Codec,
+    Balance: Codec,
+    Api: ApiExt<Block> + CmStorageRuntimeApi<Block, AccountId, BlockNumber, Balance>,
+{
+    let api_version = api
+        .api_version::<dyn CmStorageRuntimeApi<Block, AccountId, BlockNumber, Balance>>(at_hash)
+        .ok()
+        .flatten()
+        .unwrap_or_default();
+    if api_version < 2 {
+        return Err(JsonRpseeError::Call(CallError::Custom(ErrorObject::owned(
+            ErrorCode::MethodNotFound.code(),
+            "Not supported by the runtime at this block",
+            None::<String>,
+        ))))
+    }
+    Ok(())
 }
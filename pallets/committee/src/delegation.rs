@@ -0,0 +1,78 @@
+use crate::{BalanceOf, CommitteeDelegation, Config, Delegation, Pallet};
+use dbc_support::ItemList;
+use frame_support::traits::{BalanceStatus, ReservableCurrency};
+use sp_runtime::{
+    traits::{Saturating, Zero},
+    Perbill,
+};
+
+impl<T: Config> Pallet<T> {
+    // 委员会自己质押的数量(不含委托)
+    pub fn own_stake(committee: &T::AccountId) -> BalanceOf<T> {
+        Self::committee_stake(committee)
+            .staked_amount
+            .saturating_sub(Self::committee_delegation(committee).total_amount)
+    }
+
+    // 委员会获得的奖励按委托占总质押的比例分给委托人，返回委托人分得的总奖励
+    // - Writes: Delegation.can_claim_reward
+    pub(crate) fn share_reward_to_delegators(
+        committee: &T::AccountId,
+        staked_amount: BalanceOf<T>,
+        reward: BalanceOf<T>,
+    ) -> BalanceOf<T> {
+        let mut shared_reward: BalanceOf<T> = Zero::zero();
+        if staked_amount.is_zero() {
+            return shared_reward
+        }
+
+        for delegator in Self::committee_delegation(committee).delegators {
+            Delegation::<T>::mutate(committee, &delegator, |delegation| {
+                let share = Perbill::from_rational(delegation.amount, staked_amount) * reward;
+                delegation.can_claim_reward = delegation.can_claim_reward.saturating_add(share);
+                shared_reward = shared_reward.saturating_add(share);
+            });
+        }
+        shared_reward
+    }
+
+    // 委员会被惩罚时，委托人按委托占总质押的比例分担。
+    // 分担的部分从委托人转到委员会的reserved中，与委员会自己的部分一起被惩罚；
+    // 委托人reserved不足而未能转出的部分，由委员会自己的质押承担
+    // - Writes: Delegation.amount, CommitteeDelegation
+    pub(crate) fn share_slash_to_delegators(
+        committee: &T::AccountId,
+        staked_amount: BalanceOf<T>,
+        slash_amount: BalanceOf<T>,
+    ) {
+        let mut committee_delegation = Self::committee_delegation(committee);
+        if committee_delegation.delegators.is_empty() || staked_amount.is_zero() {
+            return
+        }
+
+        for delegator in committee_delegation.delegators.clone() {
+            let mut delegation = Self::delegation(committee, &delegator);
+            let share = (Perbill::from_rational(delegation.amount, staked_amount) * slash_amount)
+                .min(delegation.amount);
+
+            // 只按实际转出的数量减少委托
+            let remainder = <T as Config>::Currency::repatriate_reserved(
+                &delegator,
+                committee,
+                share,
+                BalanceStatus::Reserved,
+            )
+            .unwrap_or(share);
+            let moved = share.saturating_sub(remainder);
+
+            delegation.on_slashed(moved);
+            committee_delegation.total_amount =
+                committee_delegation.total_amount.saturating_sub(moved);
+            if delegation.amount.is_zero() {
+                ItemList::rm_item(&mut committee_delegation.delegators, &delegator);
+            }
+            Delegation::<T>::insert(committee, &delegator, delegation);
+        }
+        CommitteeDelegation::<T>::insert(committee, committee_delegation);
+    }
+}
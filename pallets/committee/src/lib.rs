@@ -3,11 +3,11 @@
 #![warn(unused_crate_dependencies)]
 
 // pub mod migrations;
+mod delegation;
 mod rpc;
 mod traits;
 mod types;
-// #[allow(clippy::all)]
-// pub mod weights;
+pub mod weights;
 
 #[cfg(any(feature = "runtime-benchmarks", test))]
 mod benchmarking;
@@ -16,14 +16,17 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
-use dbc_support::ItemList;
+use dbc_support::{traits::GNOps, EraIndex, ItemList};
 use frame_support::{
     ensure,
     pallet_prelude::*,
     traits::{Currency, ReservableCurrency},
 };
 use frame_system::pallet_prelude::*;
-use sp_runtime::traits::{CheckedAdd, CheckedSub, Saturating, Zero};
+use sp_runtime::{
+    traits::{CheckedAdd, CheckedSub, Saturating, Zero},
+    Perbill, SaturatedConversion,
+};
 use sp_std::{prelude::*, str};
 
 type BalanceOf<T> =
@@ -31,7 +34,7 @@ type BalanceOf<T> =
 
 pub use pallet::*;
 pub use types::*;
-// pub use weights::WeightInfo;
+pub use weights::WeightInfo;
 
 #[frame_support::pallet]
 pub mod pallet {
@@ -41,7 +44,10 @@ pub mod pallet {
     pub trait Config: frame_system::Config {
         type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
         type Currency: ReservableCurrency<Self::AccountId>;
-        // type WeightInfo: WeightInfo;
+        // 惩罚与奖励由其他模块执行，分给委员会的部分同样记入委员会每个Era的奖励
+        type SlashAndReward: GNOps<AccountId = Self::AccountId, Balance = BalanceOf<Self>>;
+        type CurrentEra: Get<EraIndex>;
+        type WeightInfo: WeightInfo;
     }
 
     #[pallet::pallet]
@@ -67,6 +73,83 @@ pub mod pallet {
     pub(super) type CommitteeStake<T: Config> =
         StorageMap<_, Blake2_128Concat, T::AccountId, CommitteeStakeInfo<BalanceOf<T>>, ValueQuery>;
 
+    /// 委员会信誉，由各模块的审核结果更新
+    #[pallet::storage]
+    #[pallet::getter(fn committee_reputation)]
+    pub(super) type CommitteeReputation<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, CommitteeReputationInfo, ValueQuery>;
+
+    /// 委员会被惩罚的记录
+    #[pallet::storage]
+    #[pallet::getter(fn committee_slash)]
+    pub(super) type CommitteeSlash<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, CommitteeSlashInfo<BalanceOf<T>>, ValueQuery>;
+
+    /// 委员会每个Era获得的奖励
+    #[pallet::storage]
+    #[pallet::getter(fn committee_era_reward)]
+    pub(super) type CommitteeEraReward<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Blake2_128Concat,
+        EraIndex,
+        BalanceOf<T>,
+        ValueQuery,
+    >;
+
+    #[pallet::type_value]
+    pub(super) fn VerifierCountDefault<T: Config>() -> u32 {
+        DEFAULT_VERIFIER_COUNT
+    }
+
+    /// 审核机器上线及机器故障报告的委员会数量，为3到9之间的奇数
+    #[pallet::storage]
+    #[pallet::getter(fn verifier_count)]
+    pub(super) type VerifierCount<T: Config> =
+        StorageValue<_, u32, ValueQuery, VerifierCountDefault<T>>;
+
+    /// 委托人对委员会的委托: committee, delegator -> DelegationInfo
+    #[pallet::storage]
+    #[pallet::getter(fn delegation)]
+    pub(super) type Delegation<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Blake2_128Concat,
+        T::AccountId,
+        DelegationInfo<T::BlockNumber, BalanceOf<T>>,
+        ValueQuery,
+    >;
+
+    /// 委员会收到的委托
+    #[pallet::storage]
+    #[pallet::getter(fn committee_delegation)]
+    pub(super) type CommitteeDelegation<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        CommitteeDelegationInfo<T::AccountId, BalanceOf<T>>,
+        ValueQuery,
+    >;
+
+    #[pallet::type_value]
+    pub(super) fn DeadlineRemindersDefault<T: Config>() -> Vec<Perbill> {
+        DEFAULT_DEADLINE_REMINDERS.to_vec()
+    }
+
+    /// 委员会任务剩余时间占时间窗口的比例为其中之一时发出提醒事件，按从大到小排列
+    #[pallet::storage]
+    #[pallet::getter(fn deadline_reminders)]
+    pub(super) type DeadlineReminders<T: Config> =
+        StorageValue<_, Vec<Perbill>, ValueQuery, DeadlineRemindersDefault<T>>;
+
+    /// 委员会带版本的box_pubkey，更换公钥后旧公钥在宽限期内仍然有效
+    /// 未记录时，CommitteeStake中的box_pubkey为版本0
+    #[pallet::storage]
+    pub(super) type CommitteeBoxKeys<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, CommitteeBoxKeyInfo<T::BlockNumber>>;
+
     // The current storage version.
     #[pallet::storage]
     #[pallet::getter(fn storage_version)]
@@ -142,15 +225,21 @@ pub mod pallet {
                 return Err(Error::<T>::StatusNotAllowed.into())
             }
 
+            let now = <frame_system::Pallet<T>>::block_number();
+            let mut box_keys = Self::committee_box_keys(&committee);
+            box_keys.rotate(box_pubkey, now);
+
             CommitteeStake::<T>::mutate(&committee, |committee_stake| {
                 committee_stake.box_pubkey = box_pubkey;
             });
+            CommitteeBoxKeys::<T>::insert(&committee, box_keys.clone());
 
             Self::deposit_event(Event::StakeAdded(
                 committee.clone(),
                 committee_stake_params.stake_baseline,
             ));
-            Self::deposit_event(Event::CommitteeSetBoxPubkey(committee, box_pubkey));
+            Self::deposit_event(Event::CommitteeSetBoxPubkey(committee.clone(), box_pubkey));
+            Self::deposit_event(Event::BoxKeyRotated(committee, box_keys.current.version));
             Ok(().into())
         }
 
@@ -190,6 +279,8 @@ pub mod pallet {
             let committee = ensure_signed(origin)?;
             let committee_list = Self::committee();
             ensure!(committee_list.is_normal(&committee), Error::<T>::NotInNormalList);
+            // 只能减少自己的质押，委托的部分由委托人解除
+            ensure!(amount <= Self::own_stake(&committee), Error::<T>::BalanceNotEnough);
 
             // 减少委员会质押
             Self::check_stake_health(&committee, amount, false)
@@ -282,15 +373,216 @@ pub mod pallet {
             ensure!(committee_list.is_chill(&committee), Error::<T>::StatusNotFeat);
 
             ItemList::rm_item(&mut committee_list.chill_list, &committee);
-            let _ = <T as Config>::Currency::unreserve(&committee, committee_stake.staked_amount);
+            // 只退还委员会自己的质押，委托的部分由委托人解除后取回
+            let _ = <T as Config>::Currency::unreserve(&committee, Self::own_stake(&committee));
 
-            committee_stake.staked_amount = Zero::zero();
+            committee_stake.staked_amount = Self::committee_delegation(&committee).total_amount;
 
             CommitteeStake::<T>::insert(&committee, committee_stake);
             Committee::<T>::put(committee_list);
             Self::deposit_event(Event::ExitFromCandidacy(committee));
             Ok(().into())
         }
+
+        /// 设置审核机器的委员会数量，需为奇数以避免平票
+        #[pallet::call_index(9)]
+        #[pallet::weight(<T as Config>::WeightInfo::set_verifier_count())]
+        pub fn set_verifier_count(origin: OriginFor<T>, count: u32) -> DispatchResultWithPostInfo {
+            ensure_root(origin)?;
+            ensure!(
+                count % 2 == 1 && (MIN_VERIFIER_COUNT..=MAX_VERIFIER_COUNT).contains(&count),
+                Error::<T>::InvalidVerifierCount
+            );
+
+            VerifierCount::<T>::put(count);
+            Self::deposit_event(Event::VerifierCountChanged(count));
+            Ok(().into())
+        }
+
+        /// 设置任务截止前发出提醒的时间(剩余时间占提交Hash或原始信息时间窗口的比例)，为空时不提醒
+        #[pallet::call_index(10)]
+        #[pallet::weight(frame_support::weights::Weight::from_parts(10000, 0))]
+        pub fn set_deadline_reminders(
+            origin: OriginFor<T>,
+            mut reminders: Vec<Perbill>,
+        ) -> DispatchResultWithPostInfo {
+            ensure_root(origin)?;
+            ensure!(
+                reminders.len() <= MAX_DEADLINE_REMINDERS as usize &&
+                    !reminders.iter().any(|reminder| reminder.is_zero()),
+                Error::<T>::InvalidDeadlineReminders
+            );
+
+            reminders.sort_unstable_by(|a, b| b.cmp(a));
+            reminders.dedup();
+
+            DeadlineReminders::<T>::put(reminders.clone());
+            Self::deposit_event(Event::DeadlineRemindersChanged(reminders));
+            Ok(().into())
+        }
+
+        /// DBC持有人将币委托给委员会，增加委员会的质押，并按比例分享奖励、分担惩罚
+        #[pallet::call_index(11)]
+        #[pallet::weight(frame_support::weights::Weight::from_parts(10000, 0))]
+        pub fn delegate(
+            origin: OriginFor<T>,
+            committee: T::AccountId,
+            amount: BalanceOf<T>,
+        ) -> DispatchResultWithPostInfo {
+            let delegator = ensure_signed(origin)?;
+            let mut committee_list = Self::committee();
+            let stake_per_order = Self::committee_stake_params()
+                .ok_or(Error::<T>::GetStakeParamsFailed)?
+                .stake_per_order;
+
+            ensure!(delegator != committee, Error::<T>::StatusNotAllowed);
+            ensure!(committee_list.is_committee(&committee), Error::<T>::NotCommittee);
+            ensure!(!committee_list.is_waiting_puk(&committee), Error::<T>::PubkeyNotSet);
+            ensure!(amount >= stake_per_order, Error::<T>::DelegationTooSmall);
+
+            let mut committee_delegation = Self::committee_delegation(&committee);
+            if committee_delegation.delegators.binary_search(&delegator).is_err() {
+                ensure!(
+                    committee_delegation.delegators.len() < MAX_DELEGATORS as usize,
+                    Error::<T>::TooManyDelegators
+                );
+                ItemList::add_item(&mut committee_delegation.delegators, delegator.clone());
+            }
+
+            <T as Config>::Currency::reserve(&delegator, amount)
+                .map_err(|_| Error::<T>::BalanceNotEnough)?;
+
+            let mut committee_stake = Self::committee_stake(&committee);
+            committee_stake.staked_amount = committee_stake.staked_amount.saturating_add(amount);
+            committee_delegation.total_amount =
+                committee_delegation.total_amount.saturating_add(amount);
+            Delegation::<T>::mutate(&committee, &delegator, |delegation| {
+                delegation.amount = delegation.amount.saturating_add(amount);
+            });
+
+            if Self::do_change_status_when_stake_changed(
+                committee.clone(),
+                &mut committee_list,
+                &committee_stake,
+            ) {
+                Committee::<T>::put(committee_list);
+            }
+            CommitteeStake::<T>::insert(&committee, committee_stake);
+            CommitteeDelegation::<T>::insert(&committee, committee_delegation);
+
+            Self::deposit_event(Event::Delegated(delegator, committee, amount));
+            Ok(().into())
+        }
+
+        /// 申请解除委托，需等待解锁后才能取回，取回前仍会分担委员会的惩罚
+        #[pallet::call_index(12)]
+        #[pallet::weight(frame_support::weights::Weight::from_parts(10000, 0))]
+        pub fn undelegate(
+            origin: OriginFor<T>,
+            committee: T::AccountId,
+            amount: BalanceOf<T>,
+        ) -> DispatchResultWithPostInfo {
+            let delegator = ensure_signed(origin)?;
+            let now = <frame_system::Pallet<T>>::block_number();
+            let mut delegation = Self::delegation(&committee, &delegator);
+
+            ensure!(
+                !amount.is_zero() &&
+                    delegation.amount.saturating_sub(delegation.total_unbonding()) >= amount,
+                Error::<T>::DelegationNotEnough
+            );
+            ensure!(
+                delegation.unbonding.len() < MAX_UNBONDING_CHUNKS as usize,
+                Error::<T>::TooManyUnbondingChunks
+            );
+
+            delegation.unbonding.push(UnbondingChunk {
+                amount,
+                unlock_at: now.saturating_add(DELEGATION_UNBONDING_PERIOD.into()),
+            });
+
+            Delegation::<T>::insert(&committee, &delegator, delegation);
+            Self::deposit_event(Event::Undelegated(delegator, committee, amount));
+            Ok(().into())
+        }
+
+        /// 解锁后取回解除委托的币
+        #[pallet::call_index(13)]
+        #[pallet::weight(frame_support::weights::Weight::from_parts(10000, 0))]
+        pub fn withdraw_delegation(
+            origin: OriginFor<T>,
+            committee: T::AccountId,
+        ) -> DispatchResultWithPostInfo {
+            let delegator = ensure_signed(origin)?;
+            let now = <frame_system::Pallet<T>>::block_number();
+            let mut delegation = Self::delegation(&committee, &delegator);
+            let mut committee_delegation = Self::committee_delegation(&committee);
+            let mut committee_stake = Self::committee_stake(&committee);
+            let mut committee_list = Self::committee();
+
+            ensure!(!delegation.unbonding.is_empty(), Error::<T>::DelegationNotEnough);
+
+            // 只取回已解锁的部分
+            let amount = delegation.take_unlocked(now);
+            ensure!(!amount.is_zero(), Error::<T>::DelegationLocked);
+
+            // 委员会正在处理的订单仍需要足够的质押
+            let staked_amount = committee_stake.staked_amount.saturating_sub(amount);
+            ensure!(staked_amount >= committee_stake.used_stake, Error::<T>::JobNotDone);
+
+            let _ = <T as Config>::Currency::unreserve(&delegator, amount);
+
+            committee_stake.staked_amount = staked_amount;
+            committee_delegation.total_amount =
+                committee_delegation.total_amount.saturating_sub(amount);
+            delegation.amount = delegation.amount.saturating_sub(amount);
+            if delegation.amount.is_zero() {
+                ItemList::rm_item(&mut committee_delegation.delegators, &delegator);
+            }
+
+            if Self::do_change_status_when_stake_changed(
+                committee.clone(),
+                &mut committee_list,
+                &committee_stake,
+            ) {
+                Committee::<T>::put(committee_list);
+            }
+            CommitteeStake::<T>::insert(&committee, committee_stake);
+            CommitteeDelegation::<T>::insert(&committee, committee_delegation);
+            Delegation::<T>::insert(&committee, &delegator, delegation);
+
+            Self::deposit_event(Event::DelegationWithdrawn(delegator, committee, amount));
+            Ok(().into())
+        }
+
+        /// 委托人领取委托获得的奖励
+        #[pallet::call_index(14)]
+        #[pallet::weight(frame_support::weights::Weight::from_parts(10000, 0))]
+        pub fn claim_delegation_reward(
+            origin: OriginFor<T>,
+            committee: T::AccountId,
+        ) -> DispatchResultWithPostInfo {
+            let delegator = ensure_signed(origin)?;
+            let mut delegation = Self::delegation(&committee, &delegator);
+            ensure!(!delegation.can_claim_reward.is_zero(), Error::<T>::NothingToClaim);
+
+            let can_claim_reward = delegation.can_claim_reward;
+            delegation.claimed_reward = delegation.claimed_reward.saturating_add(can_claim_reward);
+            delegation.can_claim_reward = Zero::zero();
+
+            let im_balance =
+                <T as Config>::Currency::deposit_into_existing(&delegator, can_claim_reward)
+                    .map_err(|_| Error::<T>::ClaimRewardFailed)?;
+            drop(im_balance);
+
+            Delegation::<T>::insert(&committee, &delegator, delegation);
+            Self::deposit_event(Event::ClaimDelegationReward(
+                delegator,
+                committee,
+                can_claim_reward,
+            ));
+            Ok(().into())
+        }
     }
 
     #[pallet::event]
@@ -304,9 +596,18 @@ pub mod pallet {
         UndoChill(T::AccountId),
         ExitFromCandidacy(T::AccountId),
         CommitteeSetBoxPubkey(T::AccountId, [u8; 32]),
+        // committee, version of the new box_pubkey
+        BoxKeyRotated(T::AccountId, u32),
         StakeAdded(T::AccountId, BalanceOf<T>),
         StakeReduced(T::AccountId, BalanceOf<T>),
         ClaimReward(T::AccountId, BalanceOf<T>),
+        VerifierCountChanged(u32),
+        DeadlineRemindersChanged(Vec<Perbill>),
+        // delegator, committee, amount
+        Delegated(T::AccountId, T::AccountId, BalanceOf<T>),
+        Undelegated(T::AccountId, T::AccountId, BalanceOf<T>),
+        DelegationWithdrawn(T::AccountId, T::AccountId, BalanceOf<T>),
+        ClaimDelegationReward(T::AccountId, T::AccountId, BalanceOf<T>),
     }
 
     #[pallet::error]
@@ -327,10 +628,29 @@ pub mod pallet {
         NotInNormalList,
         StatusNotFeat,
         ChangeReservedFailed,
+        InvalidVerifierCount,
+        InvalidDeadlineReminders,
+        DelegationTooSmall,
+        TooManyDelegators,
+        DelegationNotEnough,
+        DelegationLocked,
+        /// 解除委托的申请过多，需先取回已解锁的部分
+        TooManyUnbondingChunks,
     }
 }
 
 impl<T: Config> Pallet<T> {
+    // 委员会带版本的box_pubkey，兼容未记录版本的委员会
+    pub fn committee_box_keys(who: &T::AccountId) -> CommitteeBoxKeyInfo<T::BlockNumber> {
+        CommitteeBoxKeys::<T>::get(who).unwrap_or_else(|| CommitteeBoxKeyInfo {
+            current: BoxKeyInfo {
+                box_pubkey: Self::committee_stake(who).box_pubkey,
+                ..Default::default()
+            },
+            previous: None,
+        })
+    }
+
     fn check_stake_health(
         who: &T::AccountId,
         amount: BalanceOf<T>,
@@ -382,11 +702,23 @@ impl<T: Config> Pallet<T> {
             }
         }
 
+        if !is_add && !change_reserve {
+            CommitteeSlash::<T>::mutate(&who, |slash_info| slash_info.on_slashed(amount));
+        }
         CommitteeStake::<T>::insert(&who, committee_stake);
 
         Ok(())
     }
 
+    // 委员会被惩罚，委托人按比例分担后减少质押
+    fn do_slash_stake(who: T::AccountId, amount: BalanceOf<T>) -> Result<(), ()> {
+        let staked_amount = Self::committee_stake(&who).staked_amount;
+        ensure!(staked_amount >= amount, ());
+
+        Self::share_slash_to_delegators(&who, staked_amount, amount);
+        Self::do_change_reserved(who, amount, false, false)
+    }
+
     fn do_change_used_stake(
         who: T::AccountId,
         amount: BalanceOf<T>,
@@ -416,6 +748,20 @@ impl<T: Config> Pallet<T> {
         Ok(())
     }
 
+    // 派单权重：质押数量为每单质押的倍数 × 信誉分
+    fn do_get_selection_weight(who: &T::AccountId) -> u64 {
+        let stake_per_order = Self::committee_stake_params()
+            .map(|params| params.stake_per_order)
+            .unwrap_or_default();
+        let staked_amount = Self::committee_stake(who).staked_amount;
+        let stake_multiple: u64 = if stake_per_order.is_zero() {
+            1
+        } else {
+            (staked_amount / stake_per_order).saturated_into()
+        };
+        stake_multiple.saturating_mul(Self::committee_reputation(who).score as u64)
+    }
+
     // 根据当前质押量，修改committee状态
     fn do_change_status_when_stake_changed(
         committee: T::AccountId,
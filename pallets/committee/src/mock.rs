@@ -1,5 +1,9 @@
 pub use crate as committee;
-use frame_support::{parameter_types, traits::ConstU32};
+use dbc_support::{traits::GNOps, EraIndex};
+use frame_support::{
+    parameter_types,
+    traits::{BalanceStatus, ConstU32, ReservableCurrency},
+};
 pub use frame_system::RawOrigin;
 pub use sp_core::{sr25519, H256};
 pub use sp_keyring::sr25519::Keyring as Sr25519Keyring;
@@ -69,10 +73,48 @@ impl pallet_balances::Config for TestRuntime {
     type MaxFreezes = ();
 }
 
+parameter_types! {
+    pub const CurrentEra: EraIndex = 1;
+}
+
+// 从被惩罚人的reserved中平分给奖励的人
+pub struct SlashAndReward;
+impl GNOps for SlashAndReward {
+    type AccountId = sr25519::Public;
+    type Balance = u128;
+
+    fn slash_and_pay(
+        slash_who: Vec<Self::AccountId>,
+        each_slash: Self::Balance,
+        reward_who: Vec<Self::AccountId>,
+    ) -> Result<Vec<(Self::AccountId, Self::Balance)>, ()> {
+        let mut paid: Vec<_> = reward_who.into_iter().map(|a_reward| (a_reward, 0)).collect();
+        if paid.is_empty() {
+            return Ok(paid)
+        }
+        let reward_each_get = each_slash / paid.len() as u128;
+        for a_slash_person in &slash_who {
+            for (a_reward, a_paid) in paid.iter_mut() {
+                let remaining = Balances::repatriate_reserved(
+                    a_slash_person,
+                    a_reward,
+                    reward_each_get,
+                    BalanceStatus::Free,
+                )
+                .map_err(|_| ())?;
+                *a_paid += reward_each_get - remaining;
+            }
+        }
+        Ok(paid)
+    }
+}
+
 impl committee::Config for TestRuntime {
     type Currency = Balances;
     type RuntimeEvent = RuntimeEvent;
-    // type WeightInfo = ();
+    type SlashAndReward = SlashAndReward;
+    type CurrentEra = CurrentEra;
+    type WeightInfo = ();
 }
 
 // Configure a mock runtime to test the pallet.
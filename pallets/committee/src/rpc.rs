@@ -1,7 +1,36 @@
-use crate::{types::CommitteeList, Config, Pallet};
+use crate::{
+    types::{CommitteeList, CommitteeOverview, CommitteeReputationInfo},
+    BalanceOf, CommitteeEraReward, Config, Pallet,
+};
+use dbc_support::committee_task::CommitteeTask;
+use sp_std::vec::Vec;
 
 impl<T: Config> Pallet<T> {
     pub fn get_committee_list() -> CommitteeList<T::AccountId> {
         Self::committee()
     }
+
+    pub fn get_committee_reputation(who: T::AccountId) -> CommitteeReputationInfo {
+        Self::committee_reputation(who)
+    }
+
+    // pending_tasks 由各审核模块提供
+    pub fn get_committee_overview(
+        who: T::AccountId,
+        pending_tasks: Vec<CommitteeTask<T::BlockNumber>>,
+    ) -> CommitteeOverview<T::BlockNumber, BalanceOf<T>> {
+        let committee_stake = Self::committee_stake(&who);
+
+        let mut era_rewards: Vec<_> = CommitteeEraReward::<T>::iter_prefix(&who).collect();
+        era_rewards.sort_unstable_by_key(|(era, _)| *era);
+
+        CommitteeOverview {
+            pending_tasks,
+            reputation: Self::committee_reputation(&who),
+            slash: Self::committee_slash(&who),
+            era_rewards,
+            can_claim_reward: committee_stake.can_claim_reward,
+            claimed_reward: committee_stake.claimed_reward,
+        }
+    }
 }
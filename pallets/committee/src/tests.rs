@@ -1,8 +1,16 @@
 #![cfg_attr(rustfmt, rustfmt_skip)]
 
 use super::Error;
-use crate::{mock::*, CommitteeList};
-use frame_support::{assert_noop, assert_ok};
+use crate::{
+    mock::*, BoxKeyInfo, CommitteeList, CommitteeOverview, CommitteeReputationInfo,
+    CommitteeSlashInfo, BOX_KEY_GRACE_PERIOD, DELEGATION_UNBONDING_PERIOD, MIN_REPUTATION,
+};
+use dbc_support::{
+    traits::{GNOps, ManageCommittee},
+    ONE_DAY,
+};
+use frame_support::{assert_noop, assert_ok, traits::ReservableCurrency};
+use sp_runtime::{traits::Zero, Perbill};
 use std::convert::TryInto;
 
 #[test]
@@ -92,6 +100,45 @@ fn committee_set_box_pubkey_works() {
     })
 }
 
+#[test]
+fn committee_rotate_box_key_works() {
+    new_test_with_init_params_ext().execute_with(|| {
+        let committee1 = sr25519::Public::from(Sr25519Keyring::Ferdie);
+
+        assert_ok!(Committee::add_committee(RawOrigin::Root.into(), committee1));
+        assert_ok!(Committee::committee_set_box_pubkey(RuntimeOrigin::signed(committee1), [1; 32]));
+
+        // 第一次设置公钥，版本为0
+        let box_keys = Committee::committee_box_keys(&committee1);
+        assert_eq!(box_keys.current.version, 0);
+        assert_eq!(box_keys.current.box_pubkey, [1; 32]);
+        assert_eq!(box_keys.previous, None);
+        assert!(<Committee as ManageCommittee>::box_key_valid(&committee1, 0));
+        assert!(!<Committee as ManageCommittee>::box_key_valid(&committee1, 1));
+
+        // 更换公钥，版本加1，旧公钥在宽限期内仍然有效
+        System::set_block_number(10);
+        assert_ok!(Committee::committee_set_box_pubkey(RuntimeOrigin::signed(committee1), [2; 32]));
+        let box_keys = Committee::committee_box_keys(&committee1);
+        assert_eq!(box_keys.current, BoxKeyInfo { version: 1, box_pubkey: [2; 32], activated_at: 10 });
+        assert_eq!(box_keys.previous.map(|key| key.box_pubkey), Some([1; 32]));
+        assert_eq!(Committee::committee_stake(&committee1).box_pubkey, [2; 32]);
+        assert!(<Committee as ManageCommittee>::box_key_valid(&committee1, 0));
+        assert!(<Committee as ManageCommittee>::box_key_valid(&committee1, 1));
+
+        // 宽限期后旧公钥失效
+        System::set_block_number(10 + BOX_KEY_GRACE_PERIOD as u64);
+        assert!(!<Committee as ManageCommittee>::box_key_valid(&committee1, 0));
+        assert!(<Committee as ManageCommittee>::box_key_valid(&committee1, 1));
+
+        // 再次更换后，只保留上一个公钥
+        assert_ok!(Committee::committee_set_box_pubkey(RuntimeOrigin::signed(committee1), [3; 32]));
+        assert!(!<Committee as ManageCommittee>::box_key_valid(&committee1, 0));
+        assert!(<Committee as ManageCommittee>::box_key_valid(&committee1, 1));
+        assert!(<Committee as ManageCommittee>::box_key_valid(&committee1, 2));
+    })
+}
+
 #[test]
 fn committee_add_stake_works() {
     new_test_with_init_params_ext().execute_with(|| {
@@ -392,3 +439,346 @@ fn change_committee_status_when_stake_changed_works() {
         );
     })
 }
+
+#[test]
+fn set_verifier_count_works() {
+    new_test_with_init_params_ext().execute_with(|| {
+        let committee1 = sr25519::Public::from(Sr25519Keyring::Ferdie);
+        assert_eq!(Committee::verifier_count(), 3);
+
+        assert_noop!(
+            Committee::set_verifier_count(RuntimeOrigin::signed(committee1), 5),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        // 需为3到9之间的奇数
+        for count in [1, 4, 11] {
+            assert_noop!(
+                Committee::set_verifier_count(RawOrigin::Root.into(), count),
+                Error::<TestRuntime>::InvalidVerifierCount
+            );
+        }
+
+        assert_ok!(Committee::set_verifier_count(RawOrigin::Root.into(), 5));
+        assert_eq!(Committee::verifier_count(), 5);
+        assert_eq!(<Committee as ManageCommittee>::verifier_count(), 5);
+    })
+}
+
+#[test]
+fn set_deadline_reminders_works() {
+    new_test_with_init_params_ext().execute_with(|| {
+        let committee1 = sr25519::Public::from(Sr25519Keyring::Ferdie);
+        assert_eq!(
+            Committee::deadline_reminders(),
+            vec![Perbill::from_percent(50), Perbill::from_percent(10)]
+        );
+
+        assert_noop!(
+            Committee::set_deadline_reminders(
+                RuntimeOrigin::signed(committee1),
+                vec![Perbill::from_percent(10)]
+            ),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        // 不能为0，且数量不超过上限
+        assert_noop!(
+            Committee::set_deadline_reminders(
+                RawOrigin::Root.into(),
+                vec![Perbill::from_percent(10), Perbill::zero()]
+            ),
+            Error::<TestRuntime>::InvalidDeadlineReminders
+        );
+        assert_noop!(
+            Committee::set_deadline_reminders(
+                RawOrigin::Root.into(),
+                (1..=6).map(Perbill::from_percent).collect()
+            ),
+            Error::<TestRuntime>::InvalidDeadlineReminders
+        );
+
+        // 按从大到小排列并去重
+        assert_ok!(Committee::set_deadline_reminders(
+            RawOrigin::Root.into(),
+            vec![Perbill::from_percent(10), Perbill::from_percent(25), Perbill::from_percent(10)]
+        ));
+        assert_eq!(
+            <Committee as ManageCommittee>::deadline_reminders(),
+            vec![Perbill::from_percent(25), Perbill::from_percent(10)]
+        );
+
+        // 设置为空时不再提醒
+        assert_ok!(Committee::set_deadline_reminders(RawOrigin::Root.into(), vec![]));
+        assert!(Committee::deadline_reminders().is_empty());
+    })
+}
+
+// 派单权重为 质押倍数 × 信誉分，信誉分随审核结果变化
+#[test]
+fn update_reputation_and_selection_weight_works() {
+    new_test_with_init_params_ext().execute_with(|| {
+        let committee1 = sr25519::Public::from(Sr25519Keyring::Ferdie);
+        let committee2 = sr25519::Public::from(Sr25519Keyring::One);
+        let committee3 = sr25519::Public::from(Sr25519Keyring::Two);
+
+        for a_committee in [committee1, committee2, committee3] {
+            assert_ok!(Committee::add_committee(RawOrigin::Root.into(), a_committee));
+            assert_ok!(Committee::committee_set_box_pubkey(RuntimeOrigin::signed(a_committee), [1; 32]));
+        }
+
+        // 初始信誉分100，质押为每单质押的20倍
+        assert_eq!(Committee::committee_reputation(&committee1), CommitteeReputationInfo::default());
+        assert_eq!(Committee::selection_weight(&committee1), 20 * 100);
+
+        Committee::update_reputation(&[committee1], &[committee2], &[committee3]);
+        assert_eq!(
+            Committee::committee_reputation(&committee1),
+            CommitteeReputationInfo { score: 101, valid_count: 1, invalid_count: 0, unruly_count: 0 }
+        );
+        assert_eq!(
+            Committee::committee_reputation(&committee2),
+            CommitteeReputationInfo { score: 90, valid_count: 0, invalid_count: 1, unruly_count: 0 }
+        );
+        assert_eq!(
+            Committee::committee_reputation(&committee3),
+            CommitteeReputationInfo { score: 80, valid_count: 0, invalid_count: 0, unruly_count: 1 }
+        );
+        assert_eq!(Committee::selection_weight(&committee2), 20 * 90);
+
+        // 增加质押后权重增加
+        assert_ok!(Committee::committee_add_stake(RuntimeOrigin::signed(committee1), 5000 * ONE_DBC));
+        assert_eq!(Committee::selection_weight(&committee1), 25 * 101);
+
+        // 信誉分不会低于下限
+        for _ in 0..10 {
+            Committee::update_reputation(&[], &[], &[committee3]);
+        }
+        assert_eq!(Committee::committee_reputation(&committee3).score, MIN_REPUTATION);
+        assert_eq!(Committee::selection_weight(&committee3), 20 * MIN_REPUTATION as u64);
+    })
+}
+
+// 委员会概况：被惩罚的记录及每个Era获得的奖励
+#[test]
+fn committee_overview_works() {
+    new_test_with_init_params_ext().execute_with(|| {
+        let committee1 = sr25519::Public::from(Sr25519Keyring::Ferdie);
+
+        assert_ok!(Committee::add_committee(RawOrigin::Root.into(), committee1));
+        assert_ok!(Committee::committee_set_box_pubkey(RuntimeOrigin::signed(committee1), [1; 32]));
+        assert_ok!(Committee::committee_add_stake(RuntimeOrigin::signed(committee1), 5000 * ONE_DBC));
+
+        // 退还质押不算作惩罚
+        assert_ok!(Committee::change_total_stake(committee1, 1000 * ONE_DBC, false, true));
+        assert_eq!(Committee::committee_slash(&committee1), CommitteeSlashInfo::default());
+
+        assert_ok!(Committee::change_total_stake(committee1, 1000 * ONE_DBC, false, false));
+        assert_ok!(Committee::change_total_stake(committee1, 500 * ONE_DBC, false, false));
+
+        Committee::add_reward(committee1, 2, 20 * ONE_DBC);
+        Committee::add_reward(committee1, 1, 10 * ONE_DBC);
+        Committee::add_reward(committee1, 2, 5 * ONE_DBC);
+        // 奖励为0时不记录
+        Committee::add_reward(committee1, 3, 0);
+
+        Committee::update_reputation(&[committee1], &[], &[]);
+
+        assert_eq!(
+            Committee::get_committee_overview(committee1, vec![]),
+            CommitteeOverview {
+                pending_tasks: vec![],
+                reputation: CommitteeReputationInfo {
+                    score: 101,
+                    valid_count: 1,
+                    invalid_count: 0,
+                    unruly_count: 0
+                },
+                slash: CommitteeSlashInfo { slash_count: 2, slashed_amount: 1500 * ONE_DBC },
+                era_rewards: vec![(1, 10 * ONE_DBC), (2, 25 * ONE_DBC)],
+                can_claim_reward: 35 * ONE_DBC,
+                claimed_reward: 0,
+            }
+        );
+    })
+}
+
+// 惩罚分给委员会的金额同样记入委员会当前Era的奖励
+#[test]
+fn slash_reward_recorded_as_era_reward() {
+    new_test_with_init_params_ext().execute_with(|| {
+        let committee1 = sr25519::Public::from(Sr25519Keyring::Ferdie);
+        let reporter = sr25519::Public::from(Sr25519Keyring::Bob);
+        let slashed = sr25519::Public::from(Sr25519Keyring::Alice);
+
+        assert_ok!(Committee::add_committee(RawOrigin::Root.into(), committee1));
+        assert_ok!(Balances::reserve(&slashed, 1000 * ONE_DBC));
+
+        // 委员会与非委员会平分惩罚，只记录委员会获得的部分
+        assert_ok!(<Committee as GNOps>::slash_and_reward(
+            vec![slashed],
+            1000 * ONE_DBC,
+            vec![committee1, reporter]
+        ));
+        assert_eq!(Balances::free_balance(&committee1), INIT_BALANCE + 500 * ONE_DBC);
+        assert_eq!(Committee::committee_era_reward(&committee1, 1), 500 * ONE_DBC);
+        assert_eq!(Committee::committee_era_reward(&reporter, 1), 0);
+
+        // 没有实际转账时不记录
+        assert_ok!(<Committee as GNOps>::slash_and_reward(
+            vec![slashed],
+            1000 * ONE_DBC,
+            vec![committee1]
+        ));
+        assert_eq!(Balances::free_balance(&committee1), INIT_BALANCE + 500 * ONE_DBC);
+        assert_eq!(Committee::committee_era_reward(&committee1, 1), 500 * ONE_DBC);
+
+        // 委员会概况中显示所有来源的奖励，但惩罚所得已直接到账，不计入可领取的奖励
+        Committee::add_reward(committee1, 1, 10 * ONE_DBC);
+        let overview = Committee::get_committee_overview(committee1, vec![]);
+        assert_eq!(overview.era_rewards, vec![(1, 510 * ONE_DBC)]);
+        assert_eq!(overview.can_claim_reward, 10 * ONE_DBC);
+    })
+}
+
+// 委托人增加委员会质押，按比例分享奖励、分担惩罚，解除委托需等待解锁
+#[test]
+fn committee_delegation_works() {
+    new_test_with_init_params_ext().execute_with(|| {
+        let committee1 = sr25519::Public::from(Sr25519Keyring::Ferdie);
+        let delegator1 = sr25519::Public::from(Sr25519Keyring::Alice);
+        let delegator2 = sr25519::Public::from(Sr25519Keyring::Bob);
+
+        assert_noop!(
+            Committee::delegate(RuntimeOrigin::signed(delegator1), committee1, 15000 * ONE_DBC),
+            Error::<TestRuntime>::NotCommittee
+        );
+
+        assert_ok!(Committee::add_committee(RawOrigin::Root.into(), committee1));
+        assert_noop!(
+            Committee::delegate(RuntimeOrigin::signed(delegator1), committee1, 15000 * ONE_DBC),
+            Error::<TestRuntime>::PubkeyNotSet
+        );
+        assert_ok!(Committee::committee_set_box_pubkey(RuntimeOrigin::signed(committee1), [1; 32]));
+
+        // 委托数量不能小于每单质押数量
+        assert_noop!(
+            Committee::delegate(RuntimeOrigin::signed(delegator1), committee1, 500 * ONE_DBC),
+            Error::<TestRuntime>::DelegationTooSmall
+        );
+        assert_noop!(
+            Committee::delegate(RuntimeOrigin::signed(committee1), committee1, 1000 * ONE_DBC),
+            Error::<TestRuntime>::StatusNotAllowed
+        );
+
+        assert_ok!(Committee::delegate(RuntimeOrigin::signed(delegator1), committee1, 15000 * ONE_DBC));
+        assert_ok!(Committee::delegate(RuntimeOrigin::signed(delegator2), committee1, 5000 * ONE_DBC));
+        assert_eq!(Committee::committee_stake(&committee1).staked_amount, 40000 * ONE_DBC);
+        assert_eq!(Committee::own_stake(&committee1), 20000 * ONE_DBC);
+        assert_eq!(Balances::reserved_balance(&delegator1), 15000 * ONE_DBC);
+        assert_eq!(Committee::selection_weight(&committee1), 40 * 100);
+
+        // 奖励按比例分配：delegator1 3/8, delegator2 1/8
+        Committee::add_reward(committee1, 1, 400 * ONE_DBC);
+        assert_eq!(Committee::committee_stake(&committee1).can_claim_reward, 200 * ONE_DBC);
+        assert_eq!(Committee::committee_era_reward(&committee1, 1), 200 * ONE_DBC);
+        assert_eq!(Committee::delegation(&committee1, &delegator1).can_claim_reward, 150 * ONE_DBC);
+        assert_eq!(Committee::delegation(&committee1, &delegator2).can_claim_reward, 50 * ONE_DBC);
+
+        // 惩罚按比例分担，委托人分担的部分转到委员会的reserved中
+        assert_ok!(Committee::slash_stake(committee1, 4000 * ONE_DBC));
+        assert_eq!(Committee::delegation(&committee1, &delegator1).amount, 13500 * ONE_DBC);
+        assert_eq!(Committee::delegation(&committee1, &delegator2).amount, 4500 * ONE_DBC);
+        assert_eq!(Committee::committee_delegation(&committee1).total_amount, 18000 * ONE_DBC);
+        assert_eq!(Balances::reserved_balance(&delegator1), 13500 * ONE_DBC);
+        assert_eq!(Balances::reserved_balance(&committee1), 22000 * ONE_DBC);
+        assert_eq!(Committee::committee_stake(&committee1).staked_amount, 36000 * ONE_DBC);
+
+        // 委员会只能减少自己的质押
+        assert_noop!(
+            Committee::committee_reduce_stake(RuntimeOrigin::signed(committee1), 19000 * ONE_DBC),
+            Error::<TestRuntime>::BalanceNotEnough
+        );
+
+        assert_noop!(
+            Committee::undelegate(RuntimeOrigin::signed(delegator1), committee1, 14000 * ONE_DBC),
+            Error::<TestRuntime>::DelegationNotEnough
+        );
+        // 每次解除委托单独计算解锁时间
+        let unbond_at = System::block_number();
+        assert_ok!(Committee::undelegate(RuntimeOrigin::signed(delegator1), committee1, 10000 * ONE_DBC));
+        assert_noop!(
+            Committee::withdraw_delegation(RuntimeOrigin::signed(delegator1), committee1),
+            Error::<TestRuntime>::DelegationLocked
+        );
+        System::set_block_number(unbond_at + ONE_DAY as u64);
+        assert_ok!(Committee::undelegate(RuntimeOrigin::signed(delegator1), committee1, 3500 * ONE_DBC));
+
+        System::set_block_number(unbond_at + DELEGATION_UNBONDING_PERIOD as u64);
+        assert_ok!(Committee::withdraw_delegation(RuntimeOrigin::signed(delegator1), committee1));
+        assert_eq!(Balances::reserved_balance(&delegator1), 3500 * ONE_DBC);
+        assert_eq!(Committee::delegation(&committee1, &delegator1).amount, 3500 * ONE_DBC);
+        assert_noop!(
+            Committee::withdraw_delegation(RuntimeOrigin::signed(delegator1), committee1),
+            Error::<TestRuntime>::DelegationLocked
+        );
+
+        System::set_block_number(unbond_at + (ONE_DAY + DELEGATION_UNBONDING_PERIOD) as u64);
+        assert_ok!(Committee::withdraw_delegation(RuntimeOrigin::signed(delegator1), committee1));
+        assert_eq!(Balances::reserved_balance(&delegator1), 0);
+        assert_eq!(Committee::committee_stake(&committee1).staked_amount, 22500 * ONE_DBC);
+        assert_eq!(Committee::committee_delegation(&committee1).delegators, vec![delegator2]);
+
+        assert_ok!(Committee::claim_delegation_reward(RuntimeOrigin::signed(delegator1), committee1));
+        assert_eq!(Balances::free_balance(&delegator1), INIT_BALANCE - 1500 * ONE_DBC + 150 * ONE_DBC);
+        assert_noop!(
+            Committee::claim_delegation_reward(RuntimeOrigin::signed(delegator1), committee1),
+            Error::<TestRuntime>::NothingToClaim
+        );
+    })
+}
+
+// 委托人reserved不足时，只按实际转出的数量减少委托，不足的部分由委员会自己的质押承担
+#[test]
+fn share_slash_with_insufficient_delegator_reserve() {
+    new_test_with_init_params_ext().execute_with(|| {
+        let committee1 = sr25519::Public::from(Sr25519Keyring::Ferdie);
+        let delegator1 = sr25519::Public::from(Sr25519Keyring::Alice);
+        let delegator2 = sr25519::Public::from(Sr25519Keyring::Bob);
+
+        assert_ok!(Committee::add_committee(RawOrigin::Root.into(), committee1));
+        assert_ok!(Committee::committee_set_box_pubkey(RuntimeOrigin::signed(committee1), [1; 32]));
+        assert_ok!(Committee::delegate(RuntimeOrigin::signed(delegator1), committee1, 15000 * ONE_DBC));
+        assert_ok!(Committee::delegate(RuntimeOrigin::signed(delegator2), committee1, 5000 * ONE_DBC));
+
+        // delegator2的reserved只剩100 DBC
+        let _ = Balances::unreserve(&delegator2, 4900 * ONE_DBC);
+
+        // delegator1分担1500 DBC，delegator2应分担500 DBC但只能转出100 DBC
+        assert_ok!(Committee::slash_stake(committee1, 4000 * ONE_DBC));
+        assert_eq!(Committee::delegation(&committee1, &delegator1).amount, 13500 * ONE_DBC);
+        assert_eq!(Committee::delegation(&committee1, &delegator2).amount, 4900 * ONE_DBC);
+        assert_eq!(Committee::committee_delegation(&committee1).total_amount, 18400 * ONE_DBC);
+        assert_eq!(Balances::reserved_balance(&delegator2), 0);
+        assert_eq!(Balances::reserved_balance(&committee1), 21600 * ONE_DBC);
+        assert_eq!(Committee::committee_stake(&committee1).staked_amount, 36000 * ONE_DBC);
+        assert_eq!(Committee::own_stake(&committee1), 17600 * ONE_DBC);
+    })
+}
+
+// 没收申请复审的质押不是惩罚，不分给委托人
+#[test]
+fn forfeited_stake_not_shared_with_delegators() {
+    new_test_with_init_params_ext().execute_with(|| {
+        let committee1 = sr25519::Public::from(Sr25519Keyring::Ferdie);
+        let delegator1 = sr25519::Public::from(Sr25519Keyring::Alice);
+
+        assert_ok!(Committee::add_committee(RawOrigin::Root.into(), committee1));
+        assert_ok!(Committee::committee_set_box_pubkey(RuntimeOrigin::signed(committee1), [1; 32]));
+        assert_ok!(Committee::delegate(RuntimeOrigin::signed(delegator1), committee1, 20000 * ONE_DBC));
+
+        assert_ok!(Committee::change_total_stake(committee1, 1000 * ONE_DBC, false, false));
+        assert_eq!(Committee::delegation(&committee1, &delegator1).amount, 20000 * ONE_DBC);
+        assert_eq!(Committee::committee_delegation(&committee1).total_amount, 20000 * ONE_DBC);
+        assert_eq!(Balances::reserved_balance(&delegator1), 20000 * ONE_DBC);
+        assert_eq!(Committee::own_stake(&committee1), 19000 * ONE_DBC);
+    })
+}
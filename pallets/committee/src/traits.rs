@@ -1,6 +1,16 @@
-use crate::{BalanceOf, CommitteeStake, Config, Pallet};
-use dbc_support::{traits::ManageCommittee, ReportId};
-use sp_runtime::traits::Saturating;
+use crate::{
+    BalanceOf, CommitteeEraReward, CommitteeReputation, CommitteeStake, Config, DeadlineReminders,
+    Pallet, VerifierCount,
+};
+use dbc_support::{
+    traits::{GNOps, ManageCommittee},
+    EraIndex, ReportId,
+};
+use frame_support::traits::Get;
+use sp_runtime::{
+    traits::{Saturating, Zero},
+    Perbill,
+};
 use sp_std::vec::Vec;
 
 impl<T: Config> ManageCommittee for Pallet<T> {
@@ -41,14 +51,98 @@ impl<T: Config> ManageCommittee for Pallet<T> {
         Self::do_change_reserved(committee, amount, is_add, change_reserve)
     }
 
+    // 惩罚委员会的质押，委托人按委托占总质押的比例分担
+    // - Writes: CommitteeStake.staked_amount, CommitteeSlash, Delegation, CommitteeDelegation
+    fn slash_stake(committee: T::AccountId, amount: BalanceOf<T>) -> Result<(), ()> {
+        Self::do_slash_stake(committee, amount)
+    }
+
     fn stake_per_order() -> Option<BalanceOf<T>> {
         Some(Self::committee_stake_params()?.stake_per_order)
     }
 
-    fn add_reward(committee: T::AccountId, reward: BalanceOf<T>) {
-        CommitteeStake::<T>::mutate(&committee, |committee_stake| {
-            committee_stake.can_claim_reward =
-                committee_stake.can_claim_reward.saturating_add(reward);
+    // 奖励按委托占总质押的比例分给委托人，CommitteeEraReward只记录委员会自己获得的部分
+    // - Writes: CommitteeStake.can_claim_reward, CommitteeEraReward, Delegation.can_claim_reward
+    fn add_reward(committee: T::AccountId, era: EraIndex, reward: BalanceOf<T>) {
+        if reward.is_zero() {
+            return
+        }
+
+        let mut committee_stake = Self::committee_stake(&committee);
+        let delegator_reward =
+            Self::share_reward_to_delegators(&committee, committee_stake.staked_amount, reward);
+        let committee_reward = reward.saturating_sub(delegator_reward);
+
+        committee_stake.can_claim_reward =
+            committee_stake.can_claim_reward.saturating_add(committee_reward);
+        CommitteeStake::<T>::insert(&committee, committee_stake);
+        CommitteeEraReward::<T>::mutate(&committee, era, |era_reward| {
+            *era_reward = era_reward.saturating_add(committee_reward)
         });
     }
+
+    fn verifier_count() -> u32 {
+        VerifierCount::<T>::get()
+    }
+
+    fn deadline_reminders() -> Vec<Perbill> {
+        DeadlineReminders::<T>::get()
+    }
+
+    fn box_key_valid(committee: &T::AccountId, version: u32) -> bool {
+        let now = <frame_system::Pallet<T>>::block_number();
+        Self::committee_box_keys(committee).is_valid_version(version, now)
+    }
+
+    fn box_key_version(committee: &T::AccountId) -> u32 {
+        Self::committee_box_keys(committee).current.version
+    }
+
+    fn selection_weight(who: &T::AccountId) -> u64 {
+        Self::do_get_selection_weight(who)
+    }
+
+    // 与多数结果一致的委员会增加信誉，结果不一致或未完成工作的委员会扣除信誉
+    // - Writes: CommitteeReputation
+    fn update_reputation(
+        valid: &[T::AccountId],
+        invalid: &[T::AccountId],
+        unruly: &[T::AccountId],
+    ) {
+        valid.iter().for_each(|a_committee| {
+            CommitteeReputation::<T>::mutate(a_committee, |reputation| reputation.on_valid_vote())
+        });
+        invalid.iter().for_each(|a_committee| {
+            CommitteeReputation::<T>::mutate(a_committee, |reputation| reputation.on_invalid_vote())
+        });
+        unruly.iter().for_each(|a_committee| {
+            CommitteeReputation::<T>::mutate(a_committee, |reputation| reputation.on_unruly())
+        });
+    }
+}
+
+impl<T: Config> GNOps for Pallet<T> {
+    type AccountId = T::AccountId;
+    type Balance = BalanceOf<T>;
+
+    // 惩罚分给委员会的金额直接转入委员会的余额，按实际到账的金额记入当前Era的奖励
+    // - Writes: CommitteeEraReward
+    fn slash_and_pay(
+        slash_who: Vec<T::AccountId>,
+        each_slash: BalanceOf<T>,
+        reward_who: Vec<T::AccountId>,
+    ) -> Result<Vec<(T::AccountId, BalanceOf<T>)>, ()> {
+        let paid = T::SlashAndReward::slash_and_pay(slash_who, each_slash, reward_who)?;
+
+        let committee_list = Self::committee();
+        let era = T::CurrentEra::get();
+        for (a_reward, reward) in &paid {
+            if !reward.is_zero() && committee_list.is_committee(a_reward) {
+                CommitteeEraReward::<T>::mutate(a_reward, era, |era_reward| {
+                    *era_reward = era_reward.saturating_add(*reward)
+                });
+            }
+        }
+        Ok(paid)
+    }
 }
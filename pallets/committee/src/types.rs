@@ -2,8 +2,12 @@ use scale_info::TypeInfo;
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
 
+use dbc_support::{committee_task::CommitteeTask, BoxPubkey, EraIndex, ONE_DAY};
 use parity_scale_codec::{Decode, Encode};
-use sp_runtime::{Perbill, RuntimeDebug};
+use sp_runtime::{
+    traits::{AtLeast32BitUnsigned, Saturating, Zero},
+    Perbill, RuntimeDebug,
+};
 use sp_std::{vec, vec::Vec};
 
 // 处于不同状态的委员会的列表
@@ -73,3 +77,227 @@ pub struct CommitteeStakeInfo<Balance> {
     pub can_claim_reward: Balance,
     pub claimed_reward: Balance,
 }
+
+/// 默认审核机器上线/机器故障报告的委员会数量
+pub const DEFAULT_VERIFIER_COUNT: u32 = 3;
+/// 审核委员会数量下限
+pub const MIN_VERIFIER_COUNT: u32 = 3;
+/// 审核委员会数量上限，不超过机器上线审核的验证时间段数
+pub const MAX_VERIFIER_COUNT: u32 = 9;
+
+/// 默认在任务剩余时间为时间窗口的50%及10%时提醒委员会
+pub const DEFAULT_DEADLINE_REMINDERS: [Perbill; 2] =
+    [Perbill::from_percent(50), Perbill::from_percent(10)];
+/// 截止提醒的最大数量
+pub const MAX_DEADLINE_REMINDERS: u32 = 5;
+
+/// 委员会初始信誉分
+pub const INIT_REPUTATION: u32 = 100;
+/// 委员会信誉分上限
+pub const MAX_REPUTATION: u32 = 200;
+/// 委员会信誉分下限，保证信誉低的委员会仍有机会被派单以恢复信誉
+pub const MIN_REPUTATION: u32 = 10;
+/// 与多数委员会结果一致时增加的信誉分
+pub const VALID_VOTE_REWARD: u32 = 1;
+/// 与多数委员会结果不一致时扣除的信誉分
+pub const INVALID_VOTE_PENALTY: u32 = 10;
+/// 未按时完成工作时扣除的信誉分
+pub const UNRULY_PENALTY: u32 = 20;
+
+/// 委员会信誉，派单时按 质押倍数 × 信誉分 加权抽取委员会
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct CommitteeReputationInfo {
+    /// 信誉分
+    pub score: u32,
+    /// 与多数委员会结果一致的次数
+    pub valid_count: u32,
+    /// 与多数委员会结果不一致的次数
+    pub invalid_count: u32,
+    /// 未按时完成工作的次数
+    pub unruly_count: u32,
+}
+
+impl Default for CommitteeReputationInfo {
+    fn default() -> Self {
+        Self { score: INIT_REPUTATION, valid_count: 0, invalid_count: 0, unruly_count: 0 }
+    }
+}
+
+impl CommitteeReputationInfo {
+    pub fn on_valid_vote(&mut self) {
+        self.valid_count = self.valid_count.saturating_add(1);
+        self.score = self.score.saturating_add(VALID_VOTE_REWARD).min(MAX_REPUTATION);
+    }
+
+    pub fn on_invalid_vote(&mut self) {
+        self.invalid_count = self.invalid_count.saturating_add(1);
+        self.score = self.score.saturating_sub(INVALID_VOTE_PENALTY).max(MIN_REPUTATION);
+    }
+
+    pub fn on_unruly(&mut self) {
+        self.unruly_count = self.unruly_count.saturating_add(1);
+        self.score = self.score.saturating_sub(UNRULY_PENALTY).max(MIN_REPUTATION);
+    }
+}
+
+/// 委员会被惩罚的情况
+#[derive(PartialEq, Eq, Clone, Encode, Decode, Default, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct CommitteeSlashInfo<Balance> {
+    /// 被惩罚的次数
+    pub slash_count: u32,
+    /// 被惩罚的总金额
+    pub slashed_amount: Balance,
+}
+
+impl<Balance: Saturating + Copy> CommitteeSlashInfo<Balance> {
+    pub fn on_slashed(&mut self, amount: Balance) {
+        self.slash_count = self.slash_count.saturating_add(1);
+        self.slashed_amount = self.slashed_amount.saturating_add(amount);
+    }
+}
+
+/// 委员会的工作与收益概况
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct CommitteeOverview<BlockNumber, Balance> {
+    /// 未完成的任务及截止时间
+    pub pending_tasks: Vec<CommitteeTask<BlockNumber>>,
+    /// 信誉分及历史审核结果统计
+    pub reputation: CommitteeReputationInfo,
+    /// 被惩罚的情况
+    pub slash: CommitteeSlashInfo<Balance>,
+    /// 每个Era获得的奖励，按Era排序
+    pub era_rewards: Vec<(EraIndex, Balance)>,
+    pub can_claim_reward: Balance,
+    pub claimed_reward: Balance,
+}
+
+/// 每个委员会最多接受的委托人数
+pub const MAX_DELEGATORS: u32 = 64;
+/// 申请解除委托后，需等待7天才能取回
+pub const DELEGATION_UNBONDING_PERIOD: u32 = 7 * ONE_DAY;
+/// 每个委托最多同时存在的解除委托申请
+pub const MAX_UNBONDING_CHUNKS: u32 = 32;
+
+/// 一次解除委托的申请
+#[derive(PartialEq, Eq, Clone, Encode, Decode, Default, RuntimeDebug, TypeInfo)]
+pub struct UnbondingChunk<BlockNumber, Balance> {
+    pub amount: Balance,
+    /// 可以取回的时间
+    pub unlock_at: BlockNumber,
+}
+
+/// 委托人对某个委员会的委托
+#[derive(PartialEq, Eq, Clone, Encode, Decode, Default, RuntimeDebug, TypeInfo)]
+pub struct DelegationInfo<BlockNumber, Balance> {
+    /// 委托的数量，计入委员会的质押，按比例分担委员会的惩罚
+    pub amount: Balance,
+    /// 解除委托的申请，每次申请单独计算解锁时间，取回前仍会被惩罚
+    pub unbonding: Vec<UnbondingChunk<BlockNumber, Balance>>,
+    pub can_claim_reward: Balance,
+    pub claimed_reward: Balance,
+}
+
+impl<BlockNumber: PartialOrd, Balance: Saturating + Copy + Ord + Zero>
+    DelegationInfo<BlockNumber, Balance>
+{
+    pub fn total_unbonding(&self) -> Balance {
+        self.unbonding
+            .iter()
+            .fold(Zero::zero(), |total, chunk| total.saturating_add(chunk.amount))
+    }
+
+    // 分担惩罚，解除中的委托超过剩余委托时，从最后申请的开始减少
+    pub fn on_slashed(&mut self, amount: Balance) {
+        self.amount = self.amount.saturating_sub(amount);
+
+        let mut excess = self.total_unbonding().saturating_sub(self.amount);
+        while !excess.is_zero() {
+            let chunk = match self.unbonding.last_mut() {
+                Some(chunk) => chunk,
+                None => break,
+            };
+            let reduce = chunk.amount.min(excess);
+            chunk.amount = chunk.amount.saturating_sub(reduce);
+            excess = excess.saturating_sub(reduce);
+            if chunk.amount.is_zero() {
+                self.unbonding.pop();
+            }
+        }
+    }
+
+    // 移除已解锁的申请，返回可以取回的数量
+    pub fn take_unlocked(&mut self, now: BlockNumber) -> Balance {
+        let mut unlocked = Zero::zero();
+        self.unbonding.retain(|chunk| {
+            if chunk.unlock_at <= now {
+                unlocked = unlocked.saturating_add(chunk.amount);
+                false
+            } else {
+                true
+            }
+        });
+        unlocked
+    }
+}
+
+/// 委员会收到的委托
+#[derive(PartialEq, Eq, Clone, Encode, Decode, Default, RuntimeDebug, TypeInfo)]
+pub struct CommitteeDelegationInfo<AccountId, Balance> {
+    /// 委托人列表
+    pub delegators: Vec<AccountId>,
+    /// 委托的总数量，委员会自己的质押为 staked_amount - total_amount
+    pub total_amount: Balance,
+}
+
+/// 更换box_pubkey后，旧的公钥仍然有效的时间
+pub const BOX_KEY_GRACE_PERIOD: u32 = ONE_DAY;
+
+/// 委员会用于非对称加密的公钥，每次更换时版本号加1
+#[derive(PartialEq, Eq, Clone, Encode, Decode, Default, RuntimeDebug, TypeInfo)]
+pub struct BoxKeyInfo<BlockNumber> {
+    pub version: u32,
+    pub box_pubkey: BoxPubkey,
+    /// 公钥生效的时间
+    pub activated_at: BlockNumber,
+}
+
+/// 委员会当前的公钥，及在宽限期内仍然有效的上一个公钥
+#[derive(PartialEq, Eq, Clone, Encode, Decode, Default, RuntimeDebug, TypeInfo)]
+pub struct CommitteeBoxKeyInfo<BlockNumber> {
+    pub current: BoxKeyInfo<BlockNumber>,
+    pub previous: Option<BoxKeyInfo<BlockNumber>>,
+}
+
+impl<BlockNumber: AtLeast32BitUnsigned + Copy> CommitteeBoxKeyInfo<BlockNumber> {
+    // 更换公钥：新公钥立即生效，旧公钥在宽限期内仍然有效
+    // 第一次设置公钥时不保留旧公钥
+    pub fn rotate(&mut self, box_pubkey: BoxPubkey, now: BlockNumber) {
+        if self.current.box_pubkey == BoxPubkey::default() {
+            self.current.box_pubkey = box_pubkey;
+            self.current.activated_at = now;
+            return
+        }
+
+        let new_key = BoxKeyInfo {
+            version: self.current.version.saturating_add(1),
+            box_pubkey,
+            activated_at: now,
+        };
+        self.previous = Some(sp_std::mem::replace(&mut self.current, new_key));
+    }
+
+    // 该版本的公钥当前是否可以用于加密
+    pub fn is_valid_version(&self, version: u32, now: BlockNumber) -> bool {
+        if version == self.current.version {
+            return true
+        }
+        matches!(&self.previous, Some(previous) if previous.version == version) &&
+            now < self.current.activated_at.saturating_add(BOX_KEY_GRACE_PERIOD.into())
+    }
+}
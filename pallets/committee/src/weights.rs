@@ -1,26 +1,7 @@
-//! Autogenerated weights for committee
+//! Weights for committee
 //!
-//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 3.0.0
-//! DATE: 2022-02-18, STEPS: [10, ], REPEAT: 20, LOW RANGE: [], HIGH RANGE: []
-//! EXECUTION: Some(Wasm), WASM-EXECUTION: Interpreted, CHAIN: Some("dev"), DB CACHE: 128
-
-// Executed Command:
-// ./target/release/dbc-chain
-// benchmark
-// --chain
-// dev
-// --execution=wasm
-// --pallet
-// committee
-// --extrinsic
-// add_committee
-// --steps
-// 10
-// --repeat
-// 20
-// --output
-// ./pallets/committee/src/weights.rs
-// --template=./scripts/frame-weight-template.hbs
+//! add_committee 的权重来自 2022-02-18 的基准测试(STEPS: 10, REPEAT: 20)，
+//! 其余调用的权重按读写的存储项数量估算
 
 #![allow(unused_parens)]
 #![allow(unused_imports)]
@@ -34,27 +15,35 @@ use sp_std::marker::PhantomData;
 /// Weight functions needed for committee.
 pub trait WeightInfo {
     fn add_committee(u: u32) -> Weight;
+    fn set_verifier_count() -> Weight;
 }
 
 /// Weights for committee using the Substrate node and recommended hardware.
 pub struct SubstrateWeight<T>(PhantomData<T>);
 impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
     fn add_committee(u: u32) -> Weight {
-        (45_961_000 as Weight)
+        Weight::from_parts(45_961_000, 0)
             // Standard Error: 1_000
-            .saturating_add((3_000 as Weight).saturating_mul(u as Weight))
-            .saturating_add(T::DbWeight::get().reads(1 as Weight))
-            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+            .saturating_add(Weight::from_parts(3_000, 0).saturating_mul(u as u64))
+            .saturating_add(T::DbWeight::get().reads(1))
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+    // Storage: VerifierCount
+    fn set_verifier_count() -> Weight {
+        Weight::from_parts(10000, 0).saturating_add(T::DbWeight::get().writes(1))
     }
 }
 
 // For backwards compatibility and tests
 impl WeightInfo for () {
     fn add_committee(u: u32) -> Weight {
-        (45_961_000 as Weight)
+        Weight::from_parts(45_961_000, 0)
             // Standard Error: 1_000
-            .saturating_add((3_000 as Weight).saturating_mul(u as Weight))
-            .saturating_add(RocksDbWeight::get().reads(1 as Weight))
-            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+            .saturating_add(Weight::from_parts(3_000, 0).saturating_mul(u as u64))
+            .saturating_add(RocksDbWeight::get().reads(1))
+            .saturating_add(RocksDbWeight::get().writes(1))
+    }
+    fn set_verifier_count() -> Weight {
+        Weight::from_parts(10000, 0).saturating_add(RocksDbWeight::get().writes(1))
     }
 }
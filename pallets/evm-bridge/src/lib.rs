@@ -0,0 +1,155 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+mod types;
+pub mod weights;
+
+use dbc_support::ONE_DAY;
+use frame_support::{
+    pallet_prelude::*,
+    traits::{Currency, ExistenceRequirement::KeepAlive},
+};
+use frame_system::pallet_prelude::*;
+use pallet_evm::AddressMapping;
+use sp_core::{H160, U256};
+use sp_runtime::{
+    traits::{Saturating, Zero},
+    SaturatedConversion,
+};
+
+pub use pallet::*;
+pub use types::*;
+pub use weights::WeightInfo;
+
+/// EVM中DBC的精度为18，原生DBC的精度为15
+pub const EVM_DECIMALS_FACTOR: u64 = 1000;
+
+/// EVM金额转换为原生金额，EVM金额的最后三位不为0或溢出时返回None
+pub fn evm_amount_to_balance<Balance: TryFrom<U256>>(evm_amount: U256) -> Option<Balance> {
+    let factor = U256::from(EVM_DECIMALS_FACTOR);
+    if !(evm_amount % factor).is_zero() {
+        return None
+    }
+    (evm_amount / factor).try_into().ok()
+}
+
+/// 原生金额转换为EVM金额
+pub fn balance_to_evm_amount<Balance: Into<U256>>(balance: Balance) -> U256 {
+    balance.into().saturating_mul(U256::from(EVM_DECIMALS_FACTOR))
+}
+
+type BalanceOf<T> =
+    <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+#[frame_support::pallet]
+pub mod pallet {
+    use super::*;
+
+    #[pallet::config]
+    pub trait Config: frame_system::Config {
+        type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+        type Currency: Currency<Self::AccountId>;
+        /// H160到AccountId的映射，需与pallet_evm一致
+        type AddressMapping: AddressMapping<Self::AccountId>;
+        type WeightInfo: WeightInfo;
+    }
+
+    #[pallet::pallet]
+    #[pallet::without_storage_info]
+    pub struct Pallet<T>(_);
+
+    /// 每个账户每天最多可跨链到EVM的数量，为0时不允许跨链
+    #[pallet::storage]
+    #[pallet::getter(fn daily_bridge_limit)]
+    pub type DailyBridgeLimit<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
+
+    /// 账户当天已跨链到EVM的数量
+    #[pallet::storage]
+    #[pallet::getter(fn account_bridged)]
+    pub type AccountBridged<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, DailyBridged<BalanceOf<T>>, ValueQuery>;
+
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        /// 将原生DBC转到EVM地址`to`对应的账户
+        #[pallet::call_index(0)]
+        #[pallet::weight(<T as Config>::WeightInfo::transfer_to_evm())]
+        pub fn transfer_to_evm(
+            origin: OriginFor<T>,
+            to: H160,
+            amount: BalanceOf<T>,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            ensure!(!amount.is_zero(), Error::<T>::ZeroAmount);
+
+            let day = Self::current_day();
+            let mut bridged = Self::account_bridged(&who);
+            ensure!(
+                bridged.bridged_on(day).saturating_add(amount) <= Self::daily_bridge_limit(),
+                Error::<T>::ExceedDailyBridgeLimit
+            );
+
+            let to_account = T::AddressMapping::into_account_id(to);
+            <T as Config>::Currency::transfer(&who, &to_account, amount, KeepAlive)?;
+
+            bridged.add(day, amount);
+            AccountBridged::<T>::insert(&who, bridged);
+
+            Self::deposit_event(Event::TransferToEVM(
+                who,
+                to,
+                amount,
+                balance_to_evm_amount(amount.saturated_into::<u128>()),
+            ));
+            Ok(().into())
+        }
+
+        #[pallet::call_index(1)]
+        #[pallet::weight(frame_support::weights::Weight::from_parts(10000, 0))]
+        pub fn set_daily_bridge_limit(
+            origin: OriginFor<T>,
+            limit: BalanceOf<T>,
+        ) -> DispatchResultWithPostInfo {
+            ensure_root(origin)?;
+            DailyBridgeLimit::<T>::put(limit);
+            Self::deposit_event(Event::DailyBridgeLimitSet(limit));
+            Ok(().into())
+        }
+    }
+
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(super) fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// from, to, 原生金额, EVM金额
+        TransferToEVM(T::AccountId, H160, BalanceOf<T>, U256),
+        /// from, to, 原生金额, EVM金额
+        TransferFromEVM(H160, T::AccountId, BalanceOf<T>, U256),
+        DailyBridgeLimitSet(BalanceOf<T>),
+    }
+
+    #[pallet::error]
+    pub enum Error<T> {
+        ZeroAmount,
+        ExceedDailyBridgeLimit,
+    }
+}
+
+impl<T: Config> Pallet<T> {
+    fn current_day() -> u32 {
+        let now = <frame_system::Pallet<T>>::block_number().saturated_into::<u32>();
+        now / ONE_DAY
+    }
+
+    /// EVM到原生账户的跨链(bridge预编译合约)完成后记录事件
+    pub fn on_transfer_from_evm(from: H160, to: T::AccountId, amount: BalanceOf<T>) {
+        Self::deposit_event(Event::TransferFromEVM(
+            from,
+            to,
+            amount,
+            balance_to_evm_amount(amount.saturated_into::<u128>()),
+        ));
+    }
+}
@@ -0,0 +1,120 @@
+use crate as evm_bridge;
+use frame_support::{
+    parameter_types,
+    traits::{ConstU128, ConstU32, OnFinalize, OnInitialize},
+};
+use pallet_evm::{AddressMapping, HashedAddressMapping};
+pub use sp_core::{H160, H256};
+use sp_runtime::{
+    generic::Header,
+    traits::{BlakeTwo256, IdentityLookup},
+    AccountId32,
+};
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<TestRuntime>;
+type BlockNumber = u32;
+type Block = frame_system::mocking::MockBlock<TestRuntime>;
+type Balance = u128;
+
+pub const ONE_DBC: Balance = 1_000_000_000_000_000;
+pub const INIT_BALANCE: Balance = 10_000 * ONE_DBC;
+
+parameter_types! {
+    pub const BlockHashCount: BlockNumber = 250;
+    pub const SS58Prefix: u8 = 42;
+}
+
+impl frame_system::Config for TestRuntime {
+    type BaseCallFilter = ();
+    type BlockWeights = ();
+    type BlockLength = ();
+    type DbWeight = ();
+    type RuntimeOrigin = RuntimeOrigin;
+    type RuntimeCall = RuntimeCall;
+    type Index = u64;
+    type BlockNumber = BlockNumber;
+    type Hash = H256;
+    type Hashing = BlakeTwo256;
+    type AccountId = AccountId32;
+    type Lookup = IdentityLookup<Self::AccountId>;
+    type Header = Header<BlockNumber, BlakeTwo256>;
+    type RuntimeEvent = RuntimeEvent;
+    type BlockHashCount = BlockHashCount;
+    type Version = ();
+    type PalletInfo = PalletInfo;
+    type AccountData = pallet_balances::AccountData<Balance>;
+    type OnNewAccount = ();
+    type OnKilledAccount = ();
+    type SystemWeightInfo = ();
+    type SS58Prefix = SS58Prefix;
+    type OnSetCode = ();
+    type MaxConsumers = ConstU32<16>;
+}
+
+impl pallet_balances::Config for TestRuntime {
+    type MaxLocks = ConstU32<50>;
+    type MaxReserves = ConstU32<50>;
+    type ReserveIdentifier = [u8; 8];
+    type Balance = Balance;
+    type RuntimeEvent = RuntimeEvent;
+    type DustRemoval = ();
+    type ExistentialDeposit = ConstU128<ONE_DBC>;
+    type AccountStore = System;
+    type WeightInfo = ();
+    type HoldIdentifier = ();
+    type FreezeIdentifier = ();
+    type MaxHolds = ();
+    type MaxFreezes = ();
+}
+
+impl evm_bridge::Config for TestRuntime {
+    type RuntimeEvent = RuntimeEvent;
+    type Currency = Balances;
+    type AddressMapping = HashedAddressMapping<BlakeTwo256>;
+    type WeightInfo = ();
+}
+
+frame_support::construct_runtime!(
+    pub enum TestRuntime
+    where
+        Block = Block,
+        NodeBlock = Block,
+        UncheckedExtrinsic = UncheckedExtrinsic, {
+            System: frame_system,
+            Balances: pallet_balances,
+            EvmBridge: evm_bridge,
+    }
+);
+
+pub fn alice() -> AccountId32 {
+    AccountId32::new([1u8; 32])
+}
+
+pub fn evm_account_of(address: H160) -> AccountId32 {
+    HashedAddressMapping::<BlakeTwo256>::into_account_id(address)
+}
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+    let mut storage =
+        frame_system::GenesisConfig::default().build_storage::<TestRuntime>().unwrap();
+
+    pallet_balances::GenesisConfig::<TestRuntime> { balances: vec![(alice(), INIT_BALANCE)] }
+        .assimilate_storage(&mut storage)
+        .unwrap();
+
+    let mut ext = sp_io::TestExternalities::from(storage);
+    ext.execute_with(|| run_to_block(1));
+    ext
+}
+
+pub fn run_to_block(n: BlockNumber) {
+    for b in System::block_number()..=n {
+        System::on_finalize(b);
+        EvmBridge::on_finalize(b);
+
+        System::set_block_number(b + 1);
+
+        System::on_initialize(b + 1);
+        EvmBridge::on_initialize(b + 1);
+    }
+}
@@ -0,0 +1,97 @@
+use crate::{
+    balance_to_evm_amount, evm_amount_to_balance, mock::*, AccountBridged, DailyBridged, Error,
+    Event,
+};
+use dbc_support::ONE_DAY;
+use frame_support::{assert_noop, assert_ok};
+use sp_core::U256;
+
+fn evm_address() -> H160 {
+    H160::from_low_u64_be(0x1001)
+}
+
+#[test]
+fn decimals_conversion_works() {
+    // EVM精度18，原生精度15
+    assert_eq!(balance_to_evm_amount(ONE_DBC), U256::from(ONE_DBC) * U256::from(1000));
+    assert_eq!(
+        evm_amount_to_balance::<u128>(U256::from(ONE_DBC) * U256::from(1000)),
+        Some(ONE_DBC)
+    );
+    assert_eq!(
+        evm_amount_to_balance::<u128>(balance_to_evm_amount(123 * ONE_DBC + 1)),
+        Some(123 * ONE_DBC + 1)
+    );
+
+    // 最后三位不为0时无法转换
+    assert_eq!(
+        evm_amount_to_balance::<u128>(U256::from(ONE_DBC) * U256::from(1000) + U256::one()),
+        None
+    );
+    // 溢出
+    assert_eq!(evm_amount_to_balance::<u128>(U256::MAX - U256::MAX % U256::from(1000)), None);
+}
+
+#[test]
+fn transfer_to_evm_works() {
+    new_test_ext().execute_with(|| {
+        let to = evm_address();
+
+        // 未设置每日限额时不能跨链
+        assert_noop!(
+            EvmBridge::transfer_to_evm(RuntimeOrigin::signed(alice()), to, 10 * ONE_DBC),
+            Error::<TestRuntime>::ExceedDailyBridgeLimit
+        );
+        assert_noop!(
+            EvmBridge::set_daily_bridge_limit(RuntimeOrigin::signed(alice()), 100 * ONE_DBC),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_ok!(EvmBridge::set_daily_bridge_limit(RuntimeOrigin::root(), 100 * ONE_DBC));
+
+        assert_noop!(
+            EvmBridge::transfer_to_evm(RuntimeOrigin::signed(alice()), to, 0),
+            Error::<TestRuntime>::ZeroAmount
+        );
+
+        assert_ok!(EvmBridge::transfer_to_evm(RuntimeOrigin::signed(alice()), to, 10 * ONE_DBC));
+        assert_eq!(Balances::free_balance(alice()), INIT_BALANCE - 10 * ONE_DBC);
+        assert_eq!(Balances::free_balance(evm_account_of(to)), 10 * ONE_DBC);
+        assert_eq!(
+            AccountBridged::<TestRuntime>::get(alice()),
+            DailyBridged { day: 0, amount: 10 * ONE_DBC }
+        );
+        System::assert_last_event(
+            Event::TransferToEVM(
+                alice(),
+                to,
+                10 * ONE_DBC,
+                U256::from(10 * ONE_DBC) * U256::from(1000),
+            )
+            .into(),
+        );
+    })
+}
+
+#[test]
+fn daily_bridge_limit_works() {
+    new_test_ext().execute_with(|| {
+        let to = evm_address();
+        assert_ok!(EvmBridge::set_daily_bridge_limit(RuntimeOrigin::root(), 100 * ONE_DBC));
+
+        assert_ok!(EvmBridge::transfer_to_evm(RuntimeOrigin::signed(alice()), to, 60 * ONE_DBC));
+        assert_noop!(
+            EvmBridge::transfer_to_evm(RuntimeOrigin::signed(alice()), to, 41 * ONE_DBC),
+            Error::<TestRuntime>::ExceedDailyBridgeLimit
+        );
+        assert_ok!(EvmBridge::transfer_to_evm(RuntimeOrigin::signed(alice()), to, 40 * ONE_DBC));
+
+        // 第二天限额重新计算
+        run_to_block(ONE_DAY);
+        assert_ok!(EvmBridge::transfer_to_evm(RuntimeOrigin::signed(alice()), to, 100 * ONE_DBC));
+        assert_eq!(
+            AccountBridged::<TestRuntime>::get(alice()),
+            DailyBridged { day: 1, amount: 100 * ONE_DBC }
+        );
+        assert_eq!(Balances::free_balance(evm_account_of(to)), 200 * ONE_DBC);
+    })
+}
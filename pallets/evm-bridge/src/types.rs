@@ -0,0 +1,30 @@
+use parity_scale_codec::{Decode, Encode};
+use scale_info::TypeInfo;
+use sp_runtime::{
+    traits::{AtLeast32BitUnsigned, Saturating},
+    RuntimeDebug,
+};
+
+/// 账户当天已跨链到EVM的数量
+#[derive(PartialEq, Eq, Clone, Encode, Decode, Default, RuntimeDebug, TypeInfo)]
+pub struct DailyBridged<Balance> {
+    /// 区块高度 / ONE_DAY
+    pub day: u32,
+    pub amount: Balance,
+}
+
+impl<Balance: AtLeast32BitUnsigned + Copy> DailyBridged<Balance> {
+    // 跨天后已跨链数量清零
+    pub fn bridged_on(&self, day: u32) -> Balance {
+        if self.day == day {
+            self.amount
+        } else {
+            Balance::zero()
+        }
+    }
+
+    pub fn add(&mut self, day: u32, amount: Balance) {
+        self.amount = self.bridged_on(day).saturating_add(amount);
+        self.day = day;
+    }
+}
@@ -0,0 +1,37 @@
+//! Weights for evm_bridge
+//!
+//! 权重按调用时读写的存储项数量估算
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{
+    traits::Get,
+    weights::{constants::RocksDbWeight, Weight},
+};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for evm_bridge.
+pub trait WeightInfo {
+    fn transfer_to_evm() -> Weight;
+}
+
+/// Weights for evm_bridge using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+    // Storage: AccountBridged, DailyBridgeLimit, System.Account(2)
+    fn transfer_to_evm() -> Weight {
+        Weight::from_parts(10000, 0)
+            .saturating_add(T::DbWeight::get().reads(4))
+            .saturating_add(T::DbWeight::get().writes(3))
+    }
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+    fn transfer_to_evm() -> Weight {
+        Weight::from_parts(10000, 0)
+            .saturating_add(RocksDbWeight::get().reads(4))
+            .saturating_add(RocksDbWeight::get().writes(3))
+    }
+}
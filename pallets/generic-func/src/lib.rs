@@ -4,6 +4,7 @@
 
 mod traits;
 
+use dbc_support::verify_online::SelectionProof;
 use frame_support::{
     pallet_prelude::*,
     traits::{Currency, OnUnbalanced, Randomness, ReservableCurrency},
@@ -15,11 +16,14 @@ use rand_chacha::{
     ChaChaRng,
 };
 use sp_core::H256;
-use sp_runtime::traits::Saturating;
+use sp_runtime::traits::{BlakeTwo256, Hash, Saturating};
 use sp_std::prelude::*;
 
 pub use pallet::*;
 
+/// 分派委员会时从随机源获取随机数使用的 subject，同时用于区分种子的生成方式
+pub const SELECTION_SUBJECT: &[u8] = b"dbc_committee_selection/v2";
+
 type BalanceOf<T> =
     <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
 type NegativeImbalanceOf<T> = <<T as Config>::Currency as Currency<
@@ -34,6 +38,7 @@ pub mod pallet {
     pub trait Config: frame_system::Config {
         type Currency: ReservableCurrency<Self::AccountId>;
         type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+        /// 随机源，生产环境使用BABE的epoch VRF随机数，区块生产者无法操纵
         type RandomnessSource: Randomness<H256, BlockNumberFor<Self>>;
         type FixedTxFee: OnUnbalanced<NegativeImbalanceOf<Self>>;
         type Slash: OnUnbalanced<NegativeImbalanceOf<Self>>;
@@ -198,10 +203,14 @@ pub mod pallet {
 impl<T: Config> Pallet<T> {
     // Add randomness
     fn update_nonce() -> Vec<u8> {
+        Self::next_nonce().encode()
+    }
+
+    fn next_nonce() -> u64 {
         let nonce = RandNonce::<T>::get();
         let nonce: u64 = if nonce == u64::MAX { 0 } else { RandNonce::<T>::get() + 1 };
         RandNonce::<T>::put(nonce);
-        nonce.encode()
+        nonce
     }
 
     /// Pick a new PRN, in the range [0, `max`) (exclusive).
@@ -218,6 +227,125 @@ impl<T: Config> Pallet<T> {
         Self::pick_u32(&mut rng, max)
     }
 
+    /// 按权重从 candidates 中随机抽取 num 个，每次抽取使用一个新的随机种子。
+    /// 种子由epoch随机数、subject(如机器ID)及 nonce 共同产生；返回可以复现抽取过程的证明
+    pub fn random_select<A: Clone + Encode>(
+        subject: &[u8],
+        candidates: &[A],
+        weights: &[u64],
+        num: usize,
+    ) -> SelectionProof<A, BlockNumberFor<T>> {
+        let (epoch_random, _) = T::RandomnessSource::random(SELECTION_SUBJECT);
+
+        let nonces: Vec<u64> = (0..num.min(candidates.len())).map(|_| Self::next_nonce()).collect();
+        let random_seeds = Self::selection_seeds(epoch_random, subject, &nonces);
+        let selected = Self::select_by_seeds(&random_seeds, candidates, weights);
+        SelectionProof {
+            selected_at: <frame_system::Pallet<T>>::block_number(),
+            epoch_random,
+            nonces,
+            candidates_hash: Self::candidates_hash(candidates, weights),
+            selected,
+        }
+    }
+
+    fn selection_seeds(epoch_random: H256, subject: &[u8], nonces: &[u64]) -> Vec<H256> {
+        nonces
+            .iter()
+            .map(|nonce| BlakeTwo256::hash_of(&(epoch_random, subject, nonce)))
+            .collect()
+    }
+
+    fn candidates_hash<A: Encode>(candidates: &[A], weights: &[u64]) -> H256 {
+        BlakeTwo256::hash_of(&(candidates, weights))
+    }
+
+    /// 依次以每个随机种子从剩余的候选者中按权重抽取一个，相同的种子和候选者总是得到相同的结果
+    pub fn select_by_seeds<A: Clone>(
+        random_seeds: &[H256],
+        candidates: &[A],
+        weights: &[u64],
+    ) -> Vec<A> {
+        let mut candidates = candidates.to_vec();
+        let mut weights = weights.to_vec();
+        weights.resize(candidates.len(), 0);
+
+        let mut selected = Vec::new();
+        for random_seed in random_seeds {
+            if candidates.is_empty() {
+                break
+            }
+            let mut rng = ChaChaRng::from_seed((*random_seed).into());
+            let lucky_index = Self::pick_weighted(&mut rng, &weights);
+            weights.remove(lucky_index);
+            selected.push(candidates.remove(lucky_index));
+        }
+        selected
+    }
+
+    /// 校验候选者及权重与证明一致，并以证明中记录的随机数复现抽取结果
+    pub fn verify_selection<A: Clone + PartialEq + Encode>(
+        proof: &SelectionProof<A, BlockNumberFor<T>>,
+        subject: &[u8],
+        candidates: &[A],
+        weights: &[u64],
+    ) -> bool {
+        if Self::candidates_hash(candidates, weights) != proof.candidates_hash {
+            return false
+        }
+        let random_seeds = Self::selection_seeds(proof.epoch_random, subject, &proof.nonces);
+        Self::select_by_seeds(&random_seeds, candidates, weights) == proof.selected
+    }
+
+    // 按权重抽取一个下标。权重先按最大公约数约简，权重都相同或都为0时退化为均匀抽取
+    fn pick_weighted<R: RngCore>(rng: &mut R, weights: &[u64]) -> usize {
+        let gcd = weights.iter().fold(0, |a, &b| Self::gcd(a, b));
+        if gcd == 0 {
+            return Self::pick_u64(rng, weights.len() as u64) as usize
+        }
+
+        let total = weights.iter().fold(0u64, |total, w| total.saturating_add(w / gcd));
+        let mut point = Self::pick_u64(rng, total);
+        for (index, weight) in weights.iter().enumerate() {
+            let weight = weight / gcd;
+            if point < weight {
+                return index
+            }
+            point -= weight;
+        }
+        weights.len() - 1
+    }
+
+    // 无偏地抽取 [0, `max`) 中的随机数：随机数落在最后一段不完整的区间时丢弃并重新抽取，
+    // 避免直接取模使较小的值更容易被抽中
+    fn pick_u64<R: RngCore>(rng: &mut R, max: u64) -> u64 {
+        if max <= u32::MAX as u64 {
+            let zone = (1u64 << 32) - (1u64 << 32) % max;
+            loop {
+                let random = rng.next_u32() as u64;
+                if random < zone {
+                    return random % max
+                }
+            }
+        }
+
+        let zone = (1u128 << 64) - (1u128 << 64) % max as u128;
+        loop {
+            let random = rng.next_u64() as u128;
+            if random < zone {
+                return (random % max as u128) as u64
+            }
+        }
+    }
+
+    fn gcd(a: u64, b: u64) -> u64 {
+        if b == 0 {
+            a
+        } else {
+            Self::gcd(b, a % b)
+        }
+    }
+
     /// 产生随机的ServerRoomId
     pub fn random_server_room() -> H256 {
         let subject = Self::update_nonce();
@@ -2,7 +2,7 @@ use crate::{BalanceOf, Config, Pallet};
 use dbc_support::traits::GNOps;
 use frame_support::traits::{BalanceStatus, OnUnbalanced, ReservableCurrency};
 use sp_runtime::{
-    traits::{CheckedSub, Zero},
+    traits::{CheckedSub, Saturating, Zero},
     Perbill,
 };
 use sp_std::prelude::Vec;
@@ -11,16 +11,19 @@ impl<T: Config> GNOps for Pallet<T> {
     type AccountId = T::AccountId;
     type Balance = BalanceOf<T>;
 
-    fn slash_and_reward(
+    // 返回每个奖励的人实际收到的金额
+    fn slash_and_pay(
         slash_who: Vec<T::AccountId>,
         each_slash: BalanceOf<T>, // 每个人惩罚的金额
         reward_who: Vec<T::AccountId>,
-    ) -> Result<(), ()> {
+    ) -> Result<Vec<(T::AccountId, BalanceOf<T>)>, ()> {
         // 如果reward_to为0，则将币转到国库
         let reward_to_num = reward_who.len() as u32;
+        let mut paid: Vec<_> =
+            reward_who.into_iter().map(|a_reward| (a_reward, Zero::zero())).collect();
 
         if slash_who.is_empty() || each_slash == Zero::zero() {
-            return Ok(())
+            return Ok(paid)
         }
 
         if reward_to_num == 0 {
@@ -32,32 +35,25 @@ impl<T: Config> GNOps for Pallet<T> {
                     T::Slash::on_unbalanced(imbalance);
                 }
             }
-            return Ok(())
+            return Ok(paid)
         }
 
         for a_slash_person in slash_who {
             let reward_each_get = Perbill::from_rational(1u32, reward_to_num) * each_slash;
             let mut left_reward = each_slash;
 
-            for a_committee in &reward_who {
+            for (a_committee, a_paid) in paid.iter_mut() {
                 if T::Currency::reserved_balance(&a_slash_person) >= left_reward {
-                    if left_reward >= reward_each_get {
-                        let _ = T::Currency::repatriate_reserved(
-                            &a_slash_person,
-                            a_committee,
-                            reward_each_get,
-                            BalanceStatus::Free,
-                        );
-                        left_reward = left_reward.checked_sub(&reward_each_get).ok_or(())?;
-                    } else {
-                        let _ = T::Currency::repatriate_reserved(
-                            &a_slash_person,
-                            a_committee,
-                            left_reward,
-                            BalanceStatus::Free,
-                        );
-                        left_reward = Zero::zero();
-                    }
+                    let amount = reward_each_get.min(left_reward);
+                    let remaining = T::Currency::repatriate_reserved(
+                        &a_slash_person,
+                        a_committee,
+                        amount,
+                        BalanceStatus::Free,
+                    )
+                    .unwrap_or(amount);
+                    *a_paid = a_paid.saturating_add(amount.saturating_sub(remaining));
+                    left_reward = left_reward.checked_sub(&amount).ok_or(())?;
                 }
             }
             if left_reward > Zero::zero() {
@@ -67,6 +63,6 @@ impl<T: Config> GNOps for Pallet<T> {
             }
         }
 
-        Ok(())
+        Ok(paid)
     }
 }
@@ -0,0 +1,284 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub mod migrations;
+#[cfg(test)]
+mod mock;
+#[allow(non_upper_case_globals)]
+#[cfg(test)]
+mod tests;
+mod types;
+pub mod weights;
+
+use dbc_support::{
+    traits::{HBOps, MTOps},
+    verify_slash::OPSlashReason,
+    MachineId, HALF_HOUR,
+};
+use frame_support::{pallet_prelude::*, traits::StorageVersion};
+use frame_system::pallet_prelude::*;
+use sp_runtime::{traits::Saturating, Perbill, SaturatedConversion};
+use sp_std::{str, vec, vec::Vec};
+
+pub use pallet::*;
+pub use types::*;
+pub use weights::WeightInfo;
+
+/// 机器发送心跳的周期
+pub const HEARTBEAT_INTERVAL: u32 = HALF_HOUR;
+/// 连续错过心跳达到该次数时，机器将被下线
+pub const MAX_MISSED_HEARTBEAT: u32 = 6;
+/// 每错过一次心跳，扣除机器当前Era得分的比例
+pub const MISSED_HEARTBEAT_GRADE_PERCENT: u32 = 10;
+/// 每个块最多检查的机器数量
+pub const MAX_HEARTBEAT_CHECK_PER_BLOCK: u32 = 100;
+
+const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
+#[frame_support::pallet]
+pub mod pallet {
+    use super::*;
+
+    #[pallet::config]
+    pub trait Config: frame_system::Config {
+        type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+        type HBOps: HBOps<
+            AccountId = Self::AccountId,
+            BlockNumber = Self::BlockNumber,
+            MachineId = MachineId,
+        >;
+        type MTOps: MTOps<
+            AccountId = Self::AccountId,
+            MachineId = MachineId,
+            FaultType = OPSlashReason<Self::BlockNumber>,
+        >;
+        type WeightInfo: WeightInfo;
+    }
+
+    #[pallet::pallet]
+    #[pallet::storage_version(STORAGE_VERSION)]
+    #[pallet::without_storage_info]
+    pub struct Pallet<T>(_);
+
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_initialize(block_number: T::BlockNumber) -> Weight {
+            let mut weight = T::DbWeight::get().reads(1);
+            // 每个心跳周期开始时记录所有在线的机器，之后每个块分批检查
+            if Self::is_heartbeat_active(block_number) &&
+                block_number.saturated_into::<u64>() % (HEARTBEAT_INTERVAL as u64) == 0
+            {
+                PendingHeartbeatCheck::<T>::put(T::HBOps::hb_online_machines());
+                weight = weight.saturating_add(T::DbWeight::get().reads_writes(1, 1));
+            }
+            weight.saturating_add(Self::check_heartbeat_weight())
+        }
+
+        fn on_finalize(block_number: T::BlockNumber) {
+            Self::check_missed_heartbeat(block_number);
+        }
+
+        fn on_runtime_upgrade() -> Weight {
+            migrations::seed_machine_heartbeat::<T>()
+        }
+    }
+
+    /// 开始检查心跳的区块高度，未设置时不检查心跳
+    /// 错过心跳的次数从该高度开始计算，给机器留出部署心跳程序的时间
+    #[pallet::storage]
+    #[pallet::getter(fn heartbeat_start_at)]
+    pub type HeartbeatStartAt<T: Config> = StorageValue<_, T::BlockNumber>;
+
+    /// 机器的心跳记录，没有记录的在线机器从上线时开始计算
+    #[pallet::storage]
+    #[pallet::getter(fn machine_heartbeat)]
+    pub type MachineHeartbeat<T: Config> =
+        StorageMap<_, Blake2_128Concat, MachineId, MachineHeartbeatInfo<T::BlockNumber>>;
+
+    /// 当前心跳周期内等待检查的机器
+    #[pallet::storage]
+    #[pallet::getter(fn pending_heartbeat_check)]
+    pub type PendingHeartbeatCheck<T: Config> = StorageValue<_, Vec<MachineId>, ValueQuery>;
+
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        /// 机器使用machine_id对应的私钥签名，定期发送心跳
+        /// msg = machine_id + 签名时的区块高度(十进制字符串)
+        #[pallet::call_index(0)]
+        #[pallet::weight(<T as Config>::WeightInfo::heartbeat())]
+        pub fn heartbeat(
+            origin: OriginFor<T>,
+            machine_id: MachineId,
+            msg: Vec<u8>,
+            sig: Vec<u8>,
+        ) -> DispatchResultWithPostInfo {
+            ensure_signed(origin)?;
+            let now = <frame_system::Pallet<T>>::block_number();
+
+            let (_, online_at) = T::HBOps::hb_online_machine(&machine_id)
+                .ok_or(Error::<T>::MachineStatusNotAllowed)?;
+            let signed_at = Self::check_heartbeat_msg(machine_id.clone(), msg, sig)?;
+
+            // 心跳需在上线后签名并在一个周期内上链，且不能重复提交
+            let mut heartbeat = Self::machine_heartbeat(&machine_id).unwrap_or_default();
+            ensure!(
+                signed_at <= now &&
+                    signed_at >= online_at &&
+                    now.saturating_sub(signed_at) < HEARTBEAT_INTERVAL.into() &&
+                    heartbeat.last_heartbeat < signed_at,
+                Error::<T>::ExpiredHeartbeat
+            );
+
+            heartbeat.on_heartbeat(signed_at);
+            MachineHeartbeat::<T>::insert(&machine_id, heartbeat);
+
+            Self::deposit_event(Event::MachineHeartbeat(machine_id, signed_at));
+            Ok(().into())
+        }
+
+        /// 设置开始检查心跳的区块高度，None表示停止检查
+        #[pallet::call_index(1)]
+        #[pallet::weight(frame_support::weights::Weight::from_parts(10000, 0))]
+        pub fn set_heartbeat_start_at(
+            origin: OriginFor<T>,
+            start_at: Option<T::BlockNumber>,
+        ) -> DispatchResultWithPostInfo {
+            ensure_root(origin)?;
+            match start_at {
+                Some(start_at) => HeartbeatStartAt::<T>::put(start_at),
+                None => {
+                    HeartbeatStartAt::<T>::kill();
+                    PendingHeartbeatCheck::<T>::kill();
+                },
+            }
+            Self::deposit_event(Event::HeartbeatStartAtSet(start_at));
+            Ok(().into())
+        }
+    }
+
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(super) fn deposit_event)]
+    pub enum Event<T: Config> {
+        MachineHeartbeat(MachineId, T::BlockNumber),
+        HeartbeatMissed(MachineId, u32),
+        HeartbeatOffline(MachineId),
+        HeartbeatStartAtSet(Option<T::BlockNumber>),
+    }
+
+    #[pallet::error]
+    pub enum Error<T> {
+        MachineStatusNotAllowed,
+        BadMsgLen,
+        BadMsg,
+        SigMachineIdNotEqualMachineId,
+        BadSignature,
+        ExpiredHeartbeat,
+    }
+}
+
+impl<T: Config> Pallet<T> {
+    // 验证msg: machine_id(64) + 签名时的区块高度，返回签名时的区块高度
+    fn check_heartbeat_msg(
+        machine_id: MachineId,
+        msg: Vec<u8>,
+        sig: Vec<u8>,
+    ) -> Result<T::BlockNumber, Error<T>> {
+        ensure!(msg.len() > 64, Error::<T>::BadMsgLen);
+
+        ensure!(machine_id == msg[..64].to_vec(), Error::<T>::SigMachineIdNotEqualMachineId);
+        let signed_at = str::from_utf8(&msg[64..])
+            .ok()
+            .and_then(|height| height.parse::<u64>().ok())
+            .ok_or(Error::<T>::BadMsg)?;
+
+        // 验证签名是否为MachineId发出
+        ensure!(
+            dbc_support::utils::verify_sig(msg, sig, machine_id).is_some(),
+            Error::<T>::BadSignature
+        );
+        Ok(signed_at.saturated_into())
+    }
+
+    fn is_heartbeat_active(now: T::BlockNumber) -> bool {
+        Self::heartbeat_start_at().map_or(false, |start_at| now >= start_at)
+    }
+
+    fn check_heartbeat_weight() -> Weight {
+        let count = PendingHeartbeatCheck::<T>::decode_len()
+            .unwrap_or_default()
+            .min(MAX_HEARTBEAT_CHECK_PER_BLOCK as usize) as u64;
+        // 每台机器：读机器状态，读写心跳记录及当前Era的扣分记录
+        T::DbWeight::get().reads_writes(1 + count * 4, 1 + count * 2)
+    }
+
+    // 每个心跳周期内每台在线机器检查一次，每个块最多检查MAX_HEARTBEAT_CHECK_PER_BLOCK台
+    fn check_missed_heartbeat(now: T::BlockNumber) {
+        let start_at = match Self::heartbeat_start_at() {
+            Some(start_at) if now >= start_at => start_at,
+            _ => return,
+        };
+        let mut pending_check = Self::pending_heartbeat_check();
+        if pending_check.is_empty() {
+            return
+        }
+
+        let check_num = pending_check.len().min(MAX_HEARTBEAT_CHECK_PER_BLOCK as usize);
+        let to_check = pending_check.split_off(pending_check.len() - check_num);
+        if pending_check.is_empty() {
+            PendingHeartbeatCheck::<T>::kill();
+        } else {
+            PendingHeartbeatCheck::<T>::put(pending_check);
+        }
+
+        for machine_id in to_check {
+            Self::check_machine_heartbeat(machine_id, start_at, now);
+        }
+    }
+
+    // 错过心跳扣除机器当前Era得分，连续错过多次则下线机器
+    fn check_machine_heartbeat(
+        machine_id: MachineId,
+        start_at: T::BlockNumber,
+        now: T::BlockNumber,
+    ) {
+        // 机器已经不在线(下线/退出等)，不再检查心跳，重新上线后从上线时开始计算
+        let (machine_stash, online_at) = match T::HBOps::hb_online_machine(&machine_id) {
+            Some(status) => status,
+            None => {
+                MachineHeartbeat::<T>::remove(&machine_id);
+                return
+            },
+        };
+
+        // 上线后没有发送过心跳的机器同样视为错过心跳，开始检查前上线的机器从开始检查时计算
+        let mut heartbeat = Self::machine_heartbeat(&machine_id).unwrap_or_default();
+        heartbeat.on_online(online_at.max(start_at));
+        if !heartbeat.is_missed(now, HEARTBEAT_INTERVAL.into()) {
+            return
+        }
+
+        heartbeat.on_missed();
+        let _ = T::HBOps::hb_reduce_machine_grade(
+            &machine_id,
+            Perbill::from_percent(MISSED_HEARTBEAT_GRADE_PERCENT),
+        );
+
+        if heartbeat.missed_count >= MAX_MISSED_HEARTBEAT {
+            // 心跳超时下线没有举报人，machine_stash只用于记录，不获得奖励
+            if T::MTOps::mt_machine_offline(
+                machine_stash,
+                vec![],
+                machine_id.clone(),
+                OPSlashReason::HeartbeatOffline(now),
+            )
+            .is_ok()
+            {
+                MachineHeartbeat::<T>::remove(&machine_id);
+                Self::deposit_event(Event::HeartbeatOffline(machine_id));
+                return
+            }
+        }
+
+        Self::deposit_event(Event::HeartbeatMissed(machine_id.clone(), heartbeat.missed_count));
+        MachineHeartbeat::<T>::insert(&machine_id, heartbeat);
+    }
+}
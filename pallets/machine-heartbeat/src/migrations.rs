@@ -0,0 +1,28 @@
+use crate::{Config, MachineHeartbeat, MachineHeartbeatInfo, Pallet};
+use dbc_support::traits::HBOps;
+use frame_support::{
+    traits::{Get, GetStorageVersion, StorageVersion},
+    weights::Weight,
+};
+
+// 升级时已在线的机器还没有心跳记录，以升级时的高度作为最近一次心跳，避免立即被视为错过心跳
+pub fn seed_machine_heartbeat<T: Config>() -> Weight {
+    if Pallet::<T>::on_chain_storage_version() >= 1 {
+        return T::DbWeight::get().reads(1)
+    }
+
+    let now = <frame_system::Pallet<T>>::block_number();
+    let online_machines = T::HBOps::hb_online_machines();
+    let machine_num = online_machines.len() as u64;
+    for machine_id in online_machines {
+        if !MachineHeartbeat::<T>::contains_key(&machine_id) {
+            MachineHeartbeat::<T>::insert(
+                &machine_id,
+                MachineHeartbeatInfo { last_heartbeat: now, missed_count: 0 },
+            );
+        }
+    }
+    StorageVersion::new(1).put::<Pallet<T>>();
+
+    T::DbWeight::get().reads_writes(2 + machine_num, 1 + machine_num)
+}
@@ -0,0 +1,178 @@
+use crate as machine_heartbeat;
+use dbc_support::{
+    traits::{HBOps, MTOps},
+    verify_slash::OPSlashReason,
+    MachineId,
+};
+use frame_support::{
+    parameter_types,
+    traits::{ConstU32, OnFinalize, OnInitialize},
+};
+pub use sp_core::{
+    sr25519::{self, Signature},
+    H256,
+};
+pub use sp_keyring::sr25519::Keyring as Sr25519Keyring;
+use sp_runtime::{
+    generic::Header,
+    traits::{BlakeTwo256, IdentityLookup},
+    Perbill,
+};
+use std::{cell::RefCell, collections::BTreeMap};
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<TestRuntime>;
+type BlockNumber = u32;
+type Block = frame_system::mocking::MockBlock<TestRuntime>;
+
+parameter_types! {
+    pub const BlockHashCount: BlockNumber = 250;
+    pub const SS58Prefix: u8 = 42;
+}
+
+impl frame_system::Config for TestRuntime {
+    type BaseCallFilter = ();
+    type BlockWeights = ();
+    type BlockLength = ();
+    type DbWeight = ();
+    type RuntimeOrigin = RuntimeOrigin;
+    type RuntimeCall = RuntimeCall;
+    type Index = u64;
+    type BlockNumber = BlockNumber;
+    type Hash = H256;
+    type Hashing = BlakeTwo256;
+    type AccountId = sr25519::Public;
+    type Lookup = IdentityLookup<Self::AccountId>;
+    type Header = Header<BlockNumber, BlakeTwo256>;
+    type RuntimeEvent = RuntimeEvent;
+    type BlockHashCount = BlockHashCount;
+    type Version = ();
+    type PalletInfo = PalletInfo;
+    type AccountData = ();
+    type OnNewAccount = ();
+    type OnKilledAccount = ();
+    type SystemWeightInfo = ();
+    type SS58Prefix = SS58Prefix;
+    type OnSetCode = ();
+    type MaxConsumers = ConstU32<16>;
+}
+
+// 模拟online_profile中的机器：machine_id -> (stash, 上线高度, 当前Era得分)
+thread_local! {
+    pub static MACHINES: RefCell<BTreeMap<MachineId, (sr25519::Public, BlockNumber, u64)>> =
+        RefCell::new(BTreeMap::new());
+    pub static OFFLINE_MACHINES: RefCell<Vec<(MachineId, OPSlashReason<BlockNumber>)>> =
+        RefCell::new(Vec::new());
+}
+
+pub const MACHINE_CALC_POINT: u64 = 1000;
+
+pub struct MockOnlineProfile;
+
+impl MockOnlineProfile {
+    pub fn add_machine(machine_id: MachineId, stash: sr25519::Public) {
+        let online_at = System::block_number();
+        MACHINES.with(|machines| {
+            machines.borrow_mut().insert(machine_id, (stash, online_at, MACHINE_CALC_POINT))
+        });
+    }
+
+    pub fn machine_grade(machine_id: &MachineId) -> Option<u64> {
+        MACHINES.with(|machines| machines.borrow().get(machine_id).map(|machine| machine.2))
+    }
+
+    pub fn offline_machines() -> Vec<(MachineId, OPSlashReason<BlockNumber>)> {
+        OFFLINE_MACHINES.with(|offline| offline.borrow().clone())
+    }
+}
+
+impl HBOps for MockOnlineProfile {
+    type AccountId = sr25519::Public;
+    type BlockNumber = BlockNumber;
+    type MachineId = MachineId;
+
+    fn hb_online_machines() -> Vec<MachineId> {
+        MACHINES.with(|machines| machines.borrow().keys().cloned().collect())
+    }
+
+    fn hb_online_machine(machine_id: &MachineId) -> Option<(sr25519::Public, BlockNumber)> {
+        MACHINES.with(|machines| machines.borrow().get(machine_id).map(|m| (m.0, m.1)))
+    }
+
+    fn hb_reduce_machine_grade(machine_id: &MachineId, percent: Perbill) -> Result<(), ()> {
+        MACHINES.with(|machines| {
+            let mut machines = machines.borrow_mut();
+            let machine = machines.get_mut(machine_id).ok_or(())?;
+            machine.2 = machine.2.saturating_sub(percent * MACHINE_CALC_POINT);
+            Ok(())
+        })
+    }
+}
+
+impl MTOps for MockOnlineProfile {
+    type AccountId = sr25519::Public;
+    type MachineId = MachineId;
+    type FaultType = OPSlashReason<BlockNumber>;
+    type Balance = u128;
+
+    fn mt_machine_offline(
+        _reporter: sr25519::Public,
+        _committee: Vec<sr25519::Public>,
+        machine_id: MachineId,
+        fault_type: OPSlashReason<BlockNumber>,
+    ) -> Result<(), ()> {
+        MACHINES.with(|machines| machines.borrow_mut().remove(&machine_id)).ok_or(())?;
+        OFFLINE_MACHINES.with(|offline| offline.borrow_mut().push((machine_id, fault_type)));
+        Ok(())
+    }
+
+    fn mt_change_staked_balance(_: sr25519::Public, _: u128, _: bool) -> Result<(), ()> {
+        Ok(())
+    }
+
+    fn mt_rm_stash_total_stake(_: sr25519::Public, _: u128) -> Result<(), ()> {
+        Ok(())
+    }
+}
+
+impl machine_heartbeat::Config for TestRuntime {
+    type RuntimeEvent = RuntimeEvent;
+    type HBOps = MockOnlineProfile;
+    type MTOps = MockOnlineProfile;
+    type WeightInfo = ();
+}
+
+frame_support::construct_runtime!(
+    pub enum TestRuntime
+    where
+        Block = Block,
+        NodeBlock = Block,
+        UncheckedExtrinsic = UncheckedExtrinsic, {
+            System: frame_system,
+            MachineHeartbeat: machine_heartbeat,
+    }
+);
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+    let storage = frame_system::GenesisConfig::default().build_storage::<TestRuntime>().unwrap();
+    let mut ext = sp_io::TestExternalities::from(storage);
+
+    ext.execute_with(|| {
+        MACHINES.with(|machines| machines.borrow_mut().clear());
+        OFFLINE_MACHINES.with(|offline| offline.borrow_mut().clear());
+        machine_heartbeat::HeartbeatStartAt::<TestRuntime>::put(1);
+        run_to_block(1);
+    });
+    ext
+}
+
+pub fn run_to_block(n: BlockNumber) {
+    for b in System::block_number()..=n {
+        System::on_finalize(b);
+        MachineHeartbeat::on_finalize(b);
+
+        System::set_block_number(b + 1);
+
+        System::on_initialize(b + 1);
+        MachineHeartbeat::on_initialize(b + 1);
+    }
+}
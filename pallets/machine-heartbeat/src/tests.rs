@@ -0,0 +1,256 @@
+use crate::{
+    migrations, mock::*, Error, MachineHeartbeatInfo, HEARTBEAT_INTERVAL,
+    MAX_HEARTBEAT_CHECK_PER_BLOCK, MAX_MISSED_HEARTBEAT,
+};
+use dbc_support::verify_slash::OPSlashReason;
+use frame_support::{
+    assert_noop, assert_ok,
+    traits::{GetStorageVersion, StorageVersion},
+};
+use frame_system::RawOrigin;
+use once_cell::sync::Lazy;
+
+const stash: Lazy<sp_core::sr25519::Public> =
+    Lazy::new(|| sr25519::Public::from(Sr25519Keyring::Alice));
+const sender: Lazy<sp_core::sr25519::Public> =
+    Lazy::new(|| sr25519::Public::from(Sr25519Keyring::Charlie));
+// machine_id 为 Bob 的公钥
+const machine_id: Lazy<Vec<u8>> = Lazy::new(|| {
+    "8eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a48"
+        .as_bytes()
+        .to_vec()
+});
+
+fn heartbeat_msg(signed_at: u32) -> Vec<u8> {
+    let mut msg = machine_id.clone();
+    msg.extend(signed_at.to_string().as_bytes());
+    msg
+}
+
+fn send_heartbeat(signed_at: u32) {
+    let msg = heartbeat_msg(signed_at);
+    let sig = Sr25519Keyring::Bob.sign(&msg).0.to_vec();
+    assert_ok!(MachineHeartbeat::heartbeat(
+        RuntimeOrigin::signed(*sender),
+        machine_id.clone(),
+        msg,
+        sig
+    ));
+}
+
+#[test]
+fn heartbeat_works() {
+    new_test_ext().execute_with(|| {
+        let msg = heartbeat_msg(2);
+        let sig = Sr25519Keyring::Bob.sign(&msg).0.to_vec();
+
+        // 机器不在线时不能发送心跳
+        assert_noop!(
+            MachineHeartbeat::heartbeat(
+                RuntimeOrigin::signed(*sender),
+                machine_id.clone(),
+                msg.clone(),
+                sig.clone()
+            ),
+            Error::<TestRuntime>::MachineStatusNotAllowed
+        );
+
+        MockOnlineProfile::add_machine(machine_id.clone(), *stash);
+
+        // 签名需由machine_id对应的私钥发出
+        let bad_sig = Sr25519Keyring::Alice.sign(&msg).0.to_vec();
+        assert_noop!(
+            MachineHeartbeat::heartbeat(
+                RuntimeOrigin::signed(*sender),
+                machine_id.clone(),
+                msg.clone(),
+                bad_sig
+            ),
+            Error::<TestRuntime>::BadSignature
+        );
+        // 签名高度不能超过当前高度
+        let future_msg = heartbeat_msg(3);
+        let future_sig = Sr25519Keyring::Bob.sign(&future_msg).0.to_vec();
+        assert_noop!(
+            MachineHeartbeat::heartbeat(
+                RuntimeOrigin::signed(*sender),
+                machine_id.clone(),
+                future_msg,
+                future_sig
+            ),
+            Error::<TestRuntime>::ExpiredHeartbeat
+        );
+
+        send_heartbeat(2);
+        let heartbeat = MachineHeartbeat::machine_heartbeat(&*machine_id).unwrap();
+        assert_eq!(heartbeat.last_heartbeat, 2);
+        assert_eq!(heartbeat.missed_count, 0);
+
+        // 不能重复提交同一个心跳
+        assert_noop!(
+            MachineHeartbeat::heartbeat(
+                RuntimeOrigin::signed(*sender),
+                machine_id.clone(),
+                msg.clone(),
+                sig.clone()
+            ),
+            Error::<TestRuntime>::ExpiredHeartbeat
+        );
+
+        // 超过一个周期的心跳不再有效
+        run_to_block(HEARTBEAT_INTERVAL + 2);
+        let new_msg = heartbeat_msg(3);
+        let new_sig = Sr25519Keyring::Bob.sign(&new_msg).0.to_vec();
+        assert_noop!(
+            MachineHeartbeat::heartbeat(
+                RuntimeOrigin::signed(*sender),
+                machine_id.clone(),
+                new_msg,
+                new_sig
+            ),
+            Error::<TestRuntime>::ExpiredHeartbeat
+        );
+    });
+}
+
+#[test]
+fn missed_heartbeat_reduce_grade_and_offline() {
+    new_test_ext().execute_with(|| {
+        // machine2 上线后没有发送过心跳，同样检查
+        let machine_id2 = "d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d"
+            .as_bytes()
+            .to_vec();
+        MockOnlineProfile::add_machine(machine_id.clone(), *stash);
+        MockOnlineProfile::add_machine(machine_id2.clone(), *stash);
+
+        send_heartbeat(2);
+
+        // 一个周期内有心跳或刚上线，不扣分
+        run_to_block(HEARTBEAT_INTERVAL);
+        assert_eq!(MockOnlineProfile::machine_grade(&*machine_id), Some(MACHINE_CALC_POINT));
+        assert_eq!(MockOnlineProfile::machine_grade(&machine_id2), Some(MACHINE_CALC_POINT));
+        assert_eq!(MachineHeartbeat::machine_heartbeat(&machine_id2), None);
+
+        // 错过一次心跳，扣除10%得分
+        run_to_block(2 * HEARTBEAT_INTERVAL);
+        assert_eq!(MachineHeartbeat::machine_heartbeat(&*machine_id).unwrap().missed_count, 1);
+        assert_eq!(MockOnlineProfile::machine_grade(&*machine_id), Some(900));
+        assert_eq!(MachineHeartbeat::machine_heartbeat(&machine_id2).unwrap().missed_count, 1);
+        assert_eq!(MockOnlineProfile::machine_grade(&machine_id2), Some(900));
+
+        // 重新发送心跳后，重新计算连续错过次数
+        send_heartbeat(2 * HEARTBEAT_INTERVAL + 1);
+        assert_eq!(MachineHeartbeat::machine_heartbeat(&*machine_id).unwrap().missed_count, 0);
+
+        // 一直没有发送心跳的machine2被下线，没有举报人
+        run_to_block((MAX_MISSED_HEARTBEAT + 1) * HEARTBEAT_INTERVAL);
+        assert_eq!(
+            MockOnlineProfile::offline_machines(),
+            vec![(
+                machine_id2.clone(),
+                OPSlashReason::HeartbeatOffline((MAX_MISSED_HEARTBEAT + 1) * HEARTBEAT_INTERVAL)
+            )]
+        );
+        assert_eq!(MachineHeartbeat::machine_heartbeat(&machine_id2), None);
+
+        run_to_block((MAX_MISSED_HEARTBEAT + 2) * HEARTBEAT_INTERVAL);
+        assert_eq!(
+            MachineHeartbeat::machine_heartbeat(&*machine_id).unwrap().missed_count,
+            MAX_MISSED_HEARTBEAT - 1
+        );
+        assert_eq!(MockOnlineProfile::machine_grade(&*machine_id), Some(400));
+        assert_eq!(MockOnlineProfile::offline_machines().len(), 1);
+
+        // 连续错过多次心跳，机器被下线
+        run_to_block((MAX_MISSED_HEARTBEAT + 3) * HEARTBEAT_INTERVAL);
+        assert_eq!(
+            MockOnlineProfile::offline_machines()[1],
+            (
+                machine_id.clone(),
+                OPSlashReason::HeartbeatOffline((MAX_MISSED_HEARTBEAT + 3) * HEARTBEAT_INTERVAL)
+            )
+        );
+        assert_eq!(MachineHeartbeat::machine_heartbeat(&*machine_id), None);
+
+        // 重新上线后，从上线时开始计算
+        MockOnlineProfile::add_machine(machine_id2.clone(), *stash);
+        run_to_block((MAX_MISSED_HEARTBEAT + 4) * HEARTBEAT_INTERVAL);
+        assert_eq!(MockOnlineProfile::machine_grade(&machine_id2), Some(MACHINE_CALC_POINT));
+        assert_eq!(MachineHeartbeat::machine_heartbeat(&machine_id2), None);
+    });
+}
+
+#[test]
+fn heartbeat_check_is_bounded_per_block() {
+    new_test_ext().execute_with(|| {
+        let machine_num = MAX_HEARTBEAT_CHECK_PER_BLOCK + 10;
+        for index in 0..machine_num {
+            MockOnlineProfile::add_machine(format!("{:064}", index).as_bytes().to_vec(), *stash);
+        }
+
+        // 心跳周期开始时记录所有在线的机器
+        run_to_block(2 * HEARTBEAT_INTERVAL - 1);
+        assert_eq!(MachineHeartbeat::pending_heartbeat_check().len(), machine_num as usize);
+
+        // 每个块最多检查MAX_HEARTBEAT_CHECK_PER_BLOCK台机器
+        run_to_block(2 * HEARTBEAT_INTERVAL);
+        assert_eq!(MachineHeartbeat::pending_heartbeat_check().len(), 10);
+        run_to_block(2 * HEARTBEAT_INTERVAL + 1);
+        assert!(MachineHeartbeat::pending_heartbeat_check().is_empty());
+
+        for index in 0..machine_num {
+            let machine = format!("{:064}", index).as_bytes().to_vec();
+            assert_eq!(MachineHeartbeat::machine_heartbeat(&machine).unwrap().missed_count, 1);
+        }
+    });
+}
+
+#[test]
+fn heartbeat_check_starts_from_start_at() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(MachineHeartbeat::set_heartbeat_start_at(RawOrigin::Root.into(), None));
+        MockOnlineProfile::add_machine(machine_id.clone(), *stash);
+
+        // 未设置开始高度时不检查心跳
+        run_to_block(MAX_MISSED_HEARTBEAT * HEARTBEAT_INTERVAL + 1);
+        assert!(MachineHeartbeat::pending_heartbeat_check().is_empty());
+        assert_eq!(MachineHeartbeat::machine_heartbeat(&*machine_id), None);
+        assert_eq!(MockOnlineProfile::machine_grade(&*machine_id), Some(MACHINE_CALC_POINT));
+
+        // 设置开始高度后，错过心跳的次数从开始高度计算
+        let start_at = (MAX_MISSED_HEARTBEAT + 1) * HEARTBEAT_INTERVAL + 10;
+        assert_ok!(MachineHeartbeat::set_heartbeat_start_at(
+            RawOrigin::Root.into(),
+            Some(start_at)
+        ));
+        run_to_block((MAX_MISSED_HEARTBEAT + 2) * HEARTBEAT_INTERVAL);
+        assert_eq!(MachineHeartbeat::machine_heartbeat(&*machine_id), None);
+        assert_eq!(MockOnlineProfile::machine_grade(&*machine_id), Some(MACHINE_CALC_POINT));
+
+        run_to_block((MAX_MISSED_HEARTBEAT + 3) * HEARTBEAT_INTERVAL);
+        assert_eq!(MachineHeartbeat::machine_heartbeat(&*machine_id).unwrap().missed_count, 1);
+        assert_eq!(MockOnlineProfile::machine_grade(&*machine_id), Some(900));
+        assert!(MockOnlineProfile::offline_machines().is_empty());
+    });
+}
+
+#[test]
+fn migration_seeds_online_machines() {
+    new_test_ext().execute_with(|| {
+        MockOnlineProfile::add_machine(machine_id.clone(), *stash);
+        run_to_block(10);
+
+        assert_eq!(MachineHeartbeat::on_chain_storage_version(), StorageVersion::new(0));
+        migrations::seed_machine_heartbeat::<TestRuntime>();
+        assert_eq!(MachineHeartbeat::on_chain_storage_version(), StorageVersion::new(1));
+        assert_eq!(
+            MachineHeartbeat::machine_heartbeat(&*machine_id),
+            Some(MachineHeartbeatInfo { last_heartbeat: 11, missed_count: 0 })
+        );
+
+        // 只执行一次
+        run_to_block(20);
+        migrations::seed_machine_heartbeat::<TestRuntime>();
+        assert_eq!(MachineHeartbeat::machine_heartbeat(&*machine_id).unwrap().last_heartbeat, 11);
+    });
+}
@@ -0,0 +1,39 @@
+use parity_scale_codec::{Decode, Encode};
+use scale_info::TypeInfo;
+use sp_runtime::{
+    traits::{AtLeast32BitUnsigned, Saturating},
+    RuntimeDebug,
+};
+
+/// 机器最近的心跳记录
+#[derive(PartialEq, Eq, Clone, Encode, Decode, Default, RuntimeDebug, TypeInfo)]
+pub struct MachineHeartbeatInfo<BlockNumber> {
+    /// 最近一次心跳签名时的区块高度
+    pub last_heartbeat: BlockNumber,
+    /// 连续错过心跳的次数
+    pub missed_count: u32,
+}
+
+impl<BlockNumber: AtLeast32BitUnsigned + Copy> MachineHeartbeatInfo<BlockNumber> {
+    // 机器(重新)上线后，从上线时开始计算心跳
+    pub fn on_online(&mut self, online_at: BlockNumber) {
+        if self.last_heartbeat < online_at {
+            self.last_heartbeat = online_at;
+            self.missed_count = 0;
+        }
+    }
+
+    pub fn on_heartbeat(&mut self, signed_at: BlockNumber) {
+        self.last_heartbeat = signed_at;
+        self.missed_count = 0;
+    }
+
+    // 在上一个心跳周期内没有收到心跳
+    pub fn is_missed(&self, now: BlockNumber, interval: BlockNumber) -> bool {
+        now.saturating_sub(self.last_heartbeat) >= interval
+    }
+
+    pub fn on_missed(&mut self) {
+        self.missed_count = self.missed_count.saturating_add(1);
+    }
+}
@@ -0,0 +1,37 @@
+//! Weights for machine_heartbeat
+//!
+//! 权重按调用时读写的存储项数量估算
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{
+    traits::Get,
+    weights::{constants::RocksDbWeight, Weight},
+};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for machine_heartbeat.
+pub trait WeightInfo {
+    fn heartbeat() -> Weight;
+}
+
+/// Weights for machine_heartbeat using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+    // Storage: MachinesInfo, MachineHeartbeat
+    fn heartbeat() -> Weight {
+        Weight::from_parts(10000, 0)
+            .saturating_add(T::DbWeight::get().reads(2))
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+    fn heartbeat() -> Weight {
+        Weight::from_parts(10000, 0)
+            .saturating_add(RocksDbWeight::get().reads(2))
+            .saturating_add(RocksDbWeight::get().writes(1))
+    }
+}
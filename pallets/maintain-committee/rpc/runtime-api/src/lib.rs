@@ -0,0 +1,38 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::too_many_arguments)]
+#![allow(clippy::unnecessary_mut_passed)]
+#![warn(unused_crate_dependencies)]
+
+use parity_scale_codec::Codec;
+use sp_runtime::traits::MaybeDisplay;
+use sp_std::prelude::Vec;
+
+use dbc_support::{
+    report::{
+        MTCommitteeOpsDetail, MTCommitteeOrderList, MTReportInfoDetail, MTReportResultInfo,
+        ReporterReportList,
+    },
+    ReportId,
+};
+use maintain_committee::types::MTPendingSlashReviewInfo;
+
+// Here we declare the runtime API. It is implemented it the `impl` block in
+// runtime amalgamator file (the `runtime/src/lib.rs`)
+sp_api::decl_runtime_apis! {
+    pub trait MtRpcApi<AccountId, Balance, BlockNumber> where
+        AccountId: Codec + Ord,
+        Balance: Codec + MaybeDisplay,
+        BlockNumber: Codec + MaybeDisplay,
+    {
+        fn get_report_info(report_id: ReportId) -> Option<MTReportInfoDetail<AccountId, BlockNumber, Balance>>;
+        fn get_report_result(report_id: ReportId) -> Option<MTReportResultInfo<AccountId, BlockNumber, Balance>>;
+        fn get_reporter_report(reporter: AccountId) -> ReporterReportList;
+
+        fn get_committee_order(committee: AccountId) -> MTCommitteeOrderList;
+        fn get_committee_ops(committee: AccountId, report_id: ReportId) -> MTCommitteeOpsDetail<BlockNumber, Balance>;
+
+        fn get_bookable_report() -> Vec<ReportId>;
+        fn get_pending_slash_review(report_id: ReportId) -> Option<MTPendingSlashReviewInfo<AccountId, Balance, BlockNumber>>;
+        fn get_pending_slash_review_list() -> Vec<ReportId>;
+    }
+}
@@ -0,0 +1,297 @@
+#![warn(unused_crate_dependencies)]
+
+use jsonrpsee::{
+    core::{Error as JsonRpseeError, RpcResult},
+    proc_macros::rpc,
+    types::error::{CallError, ErrorCode, ErrorObject},
+};
+use parity_scale_codec::Codec;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::traits::{Block as BlockT, MaybeDisplay};
+use std::{fmt::Display, str::FromStr, sync::Arc};
+
+use dbc_support::{
+    report::{
+        MTCommitteeOpsDetail, MTCommitteeOrderList, MTReportInfoDetail, MTReportResultInfo,
+        ReporterReportList,
+    },
+    rpc_types::RpcBalance,
+    ReportId,
+};
+use maintain_committee::types::MTPendingSlashReviewInfo;
+
+pub use maintain_committee_runtime_api::MtRpcApi as MtStorageRuntimeApi;
+
+#[rpc(client, server)]
+pub trait MtRpcApi<BlockHash, AccountId, Balance, BlockNumber>
+where
+    Balance: Display + FromStr,
+    AccountId: Ord,
+{
+    #[method(name = "maintainCommittee_getReportInfo")]
+    fn get_report_info(
+        &self,
+        report_id: ReportId,
+        at: Option<BlockHash>,
+    ) -> RpcResult<MTReportInfoDetail<AccountId, BlockNumber, RpcBalance<Balance>>>;
+
+    #[method(name = "maintainCommittee_getReportResult")]
+    fn get_report_result(
+        &self,
+        report_id: ReportId,
+        at: Option<BlockHash>,
+    ) -> RpcResult<MTReportResultInfo<AccountId, BlockNumber, RpcBalance<Balance>>>;
+
+    #[method(name = "maintainCommittee_getReporterReport")]
+    fn get_reporter_report(
+        &self,
+        reporter: AccountId,
+        at: Option<BlockHash>,
+    ) -> RpcResult<ReporterReportList>;
+
+    #[method(name = "maintainCommittee_getCommitteeOrder")]
+    fn get_committee_order(
+        &self,
+        committee: AccountId,
+        at: Option<BlockHash>,
+    ) -> RpcResult<MTCommitteeOrderList>;
+
+    #[method(name = "maintainCommittee_getCommitteeOps")]
+    fn get_committee_ops(
+        &self,
+        committee: AccountId,
+        report_id: ReportId,
+        at: Option<BlockHash>,
+    ) -> RpcResult<MTCommitteeOpsDetail<BlockNumber, RpcBalance<Balance>>>;
+
+    #[method(name = "maintainCommittee_getBookableReport")]
+    fn get_bookable_report(&self, at: Option<BlockHash>) -> RpcResult<Vec<ReportId>>;
+
+    #[method(name = "maintainCommittee_getPendingSlashReview")]
+    fn get_pending_slash_review(
+        &self,
+        report_id: ReportId,
+        at: Option<BlockHash>,
+    ) -> RpcResult<MTPendingSlashReviewInfo<AccountId, RpcBalance<Balance>, BlockNumber>>;
+
+    #[method(name = "maintainCommittee_getPendingSlashReviewList")]
+    fn get_pending_slash_review_list(&self, at: Option<BlockHash>) -> RpcResult<Vec<ReportId>>;
+}
+
+pub struct MtStorage<C, M> {
+    client: Arc<C>,
+    _marker: std::marker::PhantomData<M>,
+}
+
+impl<C, M> MtStorage<C, M> {
+    pub fn new(client: Arc<C>) -> Self {
+        Self { client, _marker: Default::default() }
+    }
+}
+
+impl<C, Block, AccountId, Balance, BlockNumber>
+    MtRpcApiServer<<Block as BlockT>::Hash, AccountId, Balance, BlockNumber> for MtStorage<C, Block>
+where
+    Block: BlockT,
+    AccountId: Clone + std::fmt::Display + Codec + Ord,
+    Balance: Codec + MaybeDisplay + Copy + FromStr,
+    BlockNumber: Clone + std::fmt::Display + Codec,
+    C: Send + Sync + 'static,
+    C: ProvideRuntimeApi<Block>,
+    C: HeaderBackend<Block>,
+    C::Api: MtStorageRuntimeApi<Block, AccountId, Balance, BlockNumber>,
+{
+    fn get_report_info(
+        &self,
+        report_id: ReportId,
+        at: Option<Block::Hash>,
+    ) -> RpcResult<MTReportInfoDetail<AccountId, BlockNumber, RpcBalance<Balance>>> {
+        let api = self.client.runtime_api();
+        let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        let runtime_api_result = api.get_report_info(at_hash, report_id);
+        if let Ok(Some(report_info)) = runtime_api_result {
+            return Ok(MTReportInfoDetail {
+                reporter: report_info.reporter,
+                report_time: report_info.report_time,
+                reporter_stake: report_info.reporter_stake.into(),
+                first_book_time: report_info.first_book_time,
+                machine_id: report_info.machine_id,
+                rent_order_id: report_info.rent_order_id,
+                err_info: report_info.err_info,
+                verifying_committee: report_info.verifying_committee,
+                booked_committee: report_info.booked_committee,
+                get_encrypted_info_committee: report_info.get_encrypted_info_committee,
+                hashed_committee: report_info.hashed_committee,
+                confirm_start: report_info.confirm_start,
+                confirmed_committee: report_info.confirmed_committee,
+                support_committee: report_info.support_committee,
+                against_committee: report_info.against_committee,
+                report_status: report_info.report_status,
+                machine_fault_type: report_info.machine_fault_type,
+                verifier_count: report_info.verifier_count,
+            })
+        }
+        return Err(JsonRpseeError::Call(CallError::Custom(ErrorObject::owned(
+            ErrorCode::InternalError.code(),
+            "Something wrong",
+            Some("NotFound"),
+        ))))
+    }
+
+    fn get_report_result(
+        &self,
+        report_id: ReportId,
+        at: Option<Block::Hash>,
+    ) -> RpcResult<MTReportResultInfo<AccountId, BlockNumber, RpcBalance<Balance>>> {
+        let api = self.client.runtime_api();
+        let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        let runtime_api_result = api.get_report_result(at_hash, report_id);
+        if let Ok(Some(report_result)) = runtime_api_result {
+            return Ok(MTReportResultInfo {
+                report_id: report_result.report_id,
+                reporter: report_result.reporter,
+                reporter_stake: report_result.reporter_stake.into(),
+                inconsistent_committee: report_result.inconsistent_committee,
+                unruly_committee: report_result.unruly_committee,
+                reward_committee: report_result.reward_committee,
+                committee_stake: report_result.committee_stake.into(),
+                machine_stash: report_result.machine_stash,
+                machine_id: report_result.machine_id,
+                slash_time: report_result.slash_time,
+                slash_exec_time: report_result.slash_exec_time,
+                report_result: report_result.report_result,
+                slash_result: report_result.slash_result,
+            })
+        }
+        return Err(JsonRpseeError::Call(CallError::Custom(ErrorObject::owned(
+            ErrorCode::InternalError.code(),
+            "Something wrong",
+            Some("NotFound"),
+        ))))
+    }
+
+    fn get_reporter_report(
+        &self,
+        reporter: AccountId,
+        at: Option<Block::Hash>,
+    ) -> RpcResult<ReporterReportList> {
+        let api = self.client.runtime_api();
+        let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        let runtime_api_result = api.get_reporter_report(at_hash, reporter).map_err(|e| {
+            JsonRpseeError::Call(CallError::Custom(ErrorObject::owned(
+                ErrorCode::InternalError.code(),
+                "Something wrong",
+                Some(e.to_string()),
+            )))
+        })?;
+        Ok(runtime_api_result)
+    }
+
+    fn get_committee_order(
+        &self,
+        committee: AccountId,
+        at: Option<Block::Hash>,
+    ) -> RpcResult<MTCommitteeOrderList> {
+        let api = self.client.runtime_api();
+        let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        let runtime_api_result = api.get_committee_order(at_hash, committee).map_err(|e| {
+            JsonRpseeError::Call(CallError::Custom(ErrorObject::owned(
+                ErrorCode::InternalError.code(),
+                "Something wrong",
+                Some(e.to_string()),
+            )))
+        })?;
+        Ok(runtime_api_result)
+    }
+
+    fn get_committee_ops(
+        &self,
+        committee: AccountId,
+        report_id: ReportId,
+        at: Option<Block::Hash>,
+    ) -> RpcResult<MTCommitteeOpsDetail<BlockNumber, RpcBalance<Balance>>> {
+        let api = self.client.runtime_api();
+        let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        let runtime_api_result = api
+            .get_committee_ops(at_hash, committee, report_id)
+            .map(|ops| MTCommitteeOpsDetail {
+                booked_time: ops.booked_time,
+                encrypted_err_info: ops.encrypted_err_info,
+                encrypted_time: ops.encrypted_time,
+                confirm_hash: ops.confirm_hash,
+                hash_time: ops.hash_time,
+                extra_err_info: ops.extra_err_info,
+                confirm_time: ops.confirm_time,
+                confirm_result: ops.confirm_result,
+                staked_balance: ops.staked_balance.into(),
+                order_status: ops.order_status,
+            })
+            .map_err(|e| {
+                JsonRpseeError::Call(CallError::Custom(ErrorObject::owned(
+                    ErrorCode::InternalError.code(),
+                    "Something wrong",
+                    Some(e.to_string()),
+                )))
+            })?;
+        Ok(runtime_api_result)
+    }
+
+    fn get_bookable_report(&self, at: Option<Block::Hash>) -> RpcResult<Vec<ReportId>> {
+        let api = self.client.runtime_api();
+        let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        let runtime_api_result = api.get_bookable_report(at_hash).map_err(|e| {
+            JsonRpseeError::Call(CallError::Custom(ErrorObject::owned(
+                ErrorCode::InternalError.code(),
+                "Something wrong",
+                Some(e.to_string()),
+            )))
+        })?;
+        Ok(runtime_api_result)
+    }
+
+    fn get_pending_slash_review(
+        &self,
+        report_id: ReportId,
+        at: Option<Block::Hash>,
+    ) -> RpcResult<MTPendingSlashReviewInfo<AccountId, RpcBalance<Balance>, BlockNumber>> {
+        let api = self.client.runtime_api();
+        let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        let runtime_api_result = api.get_pending_slash_review(at_hash, report_id);
+        if let Ok(Some(review)) = runtime_api_result {
+            return Ok(MTPendingSlashReviewInfo {
+                applicant: review.applicant,
+                staked_amount: review.staked_amount.into(),
+                apply_time: review.apply_time,
+                expire_time: review.expire_time,
+                reason: review.reason,
+            })
+        }
+        return Err(JsonRpseeError::Call(CallError::Custom(ErrorObject::owned(
+            ErrorCode::InternalError.code(),
+            "Something wrong",
+            Some("NotFound"),
+        ))))
+    }
+
+    fn get_pending_slash_review_list(&self, at: Option<Block::Hash>) -> RpcResult<Vec<ReportId>> {
+        let api = self.client.runtime_api();
+        let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        let runtime_api_result = api.get_pending_slash_review_list(at_hash).map_err(|e| {
+            JsonRpseeError::Call(CallError::Custom(ErrorObject::owned(
+                ErrorCode::InternalError.code(),
+                "Something wrong",
+                Some(e.to_string()),
+            )))
+        })?;
+        Ok(runtime_api_result)
+    }
+}
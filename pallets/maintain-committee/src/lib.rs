@@ -2,10 +2,14 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 #![warn(unused_crate_dependencies)]
 
-// mod migrations;
+mod liveness;
+mod migrations;
+pub mod probe;
+mod rpc;
 mod slash;
-mod types;
+pub mod types;
 mod utils;
+pub mod weights;
 
 #[cfg(test)]
 mod mock;
@@ -14,6 +18,7 @@ mod mock;
 mod tests;
 
 use dbc_support::{
+    committee_task::{CommitteeTaskId, CommitteeTaskKind},
     report::{
         MCSlashResult, MTCommitteeOpsDetail, MTCommitteeOrderList, MTLiveReportList, MTOrderStatus,
         MTReportInfoDetail, MTReportResultInfo, MachineFaultType, ReportConfirmStatus,
@@ -28,20 +33,28 @@ use dbc_support::{
 };
 use frame_support::{
     pallet_prelude::*,
-    traits::{Currency, OnUnbalanced, ReservableCurrency},
+    traits::{Currency, OnUnbalanced, ReservableCurrency, StorageVersion},
+};
+use frame_system::{
+    offchain::{AppCrypto, CreateSignedTransaction},
+    pallet_prelude::*,
 };
-use frame_system::pallet_prelude::*;
 use parity_scale_codec::alloc::string::ToString;
 use sp_runtime::traits::{Saturating, Zero};
 use sp_std::{str, vec, vec::Vec};
 
 pub use pallet::*;
+pub use weights::WeightInfo;
+use probe::{ProbeEndpoint, PROBE_INTERVAL};
 use types::*;
 type BalanceOf<T> =
     <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
 type NegativeImbalanceOf<T> = <<T as Config>::Currency as Currency<
     <T as frame_system::Config>::AccountId,
 >>::NegativeImbalance;
+
+const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 #[frame_support::pallet]
 pub mod pallet {
     use super::*;
@@ -49,7 +62,11 @@ pub mod pallet {
 
     #[pallet::config]
     pub trait Config:
-        frame_system::Config + online_profile::Config + generic_func::Config + rent_machine::Config
+        frame_system::Config
+        + online_profile::Config
+        + generic_func::Config
+        + rent_machine::Config
+        + CreateSignedTransaction<Call<Self>>
     {
         type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
         type Currency: ReservableCurrency<Self::AccountId>;
@@ -66,24 +83,43 @@ pub mod pallet {
         type Slash: OnUnbalanced<NegativeImbalanceOf<Self>>;
         type CancelSlashOrigin: EnsureOrigin<Self::RuntimeOrigin>;
         type SlashAndReward: GNOps<AccountId = Self::AccountId, Balance = BalanceOf<Self>>;
+        /// 委员会的链下工作机签名探测结果使用的密钥
+        type AuthorityId: AppCrypto<Self::Public, Self::Signature>;
+        /// 探测租用者声明的机器访问地址
+        type EndpointProbe: ProbeEndpoint;
+        type WeightInfo: WeightInfo;
     }
 
     #[pallet::pallet]
+    #[pallet::storage_version(STORAGE_VERSION)]
     #[pallet::without_storage_info]
     pub struct Pallet<T>(_);
 
     #[pallet::hooks]
     impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
-        fn on_initialize(_n: BlockNumberFor<T>) -> frame_support::weights::Weight {
+        fn on_initialize(n: BlockNumberFor<T>) -> frame_support::weights::Weight {
             let _ = Self::exec_review();
             let _ = Self::exec_slash();
-            Weight::zero()
+            Self::deadline_reminder_weight(n)
         }
 
-        fn on_finalize(_block_number: T::BlockNumber) {
+        fn on_finalize(block_number: T::BlockNumber) {
             // TODO: 记录惩罚时的当前租用人，当惩罚执行时所有租用人都能获得赔偿
             Self::summary_fault_hook();
             Self::summary_inaccessible_hook();
+            Self::remind_task_deadline(block_number);
+        }
+
+        // 运行链下工作机的委员会，每轮探测租用者声明的访问地址并提交签名的探测结果
+        fn offchain_worker(block_number: T::BlockNumber) {
+            if block_number % PROBE_INTERVAL.into() != Zero::zero() {
+                return
+            }
+            let _ = Self::probe_rent_endpoints(block_number);
+        }
+
+        fn on_runtime_upgrade() -> Weight {
+            migrations::migrate_report_info::<T>()
         }
     }
 
@@ -135,6 +171,19 @@ pub mod pallet {
         MTCommitteeOpsDetail<T::BlockNumber, BalanceOf<T>>,
         ValueQuery,
     >;
+
+    /// 报告人加密故障信息时使用的委员会box_pubkey版本
+    #[pallet::storage]
+    #[pallet::getter(fn encrypted_info_key_version)]
+    pub(super) type EncryptedInfoKeyVersion<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Blake2_128Concat,
+        ReportId,
+        u32,
+        ValueQuery,
+    >;
     /// 系统中还未完成的订单
     #[pallet::storage]
     #[pallet::getter(fn live_report)]
@@ -165,6 +214,23 @@ pub mod pallet {
     pub(super) type UnhandledReportResult<T: Config> =
         StorageMap<_, Blake2_128Concat, T::BlockNumber, Vec<ReportId>, ValueQuery>;
 
+    /// 委员会任务的截止提醒：块高 -> 在该块需要提醒的委员会及任务
+    #[pallet::storage]
+    #[pallet::getter(fn deadline_reminder)]
+    pub(super) type DeadlineReminder<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::BlockNumber,
+        Vec<(T::AccountId, CommitteeTaskId)>,
+        ValueQuery,
+    >;
+
+    /// 自动报告无法访问时，探测到该订单无法访问的委员会，这些委员会不能抢单审核该报告
+    #[pallet::storage]
+    #[pallet::getter(fn inaccessible_attesters)]
+    pub(super) type InaccessibleAttesters<T: Config> =
+        StorageMap<_, Blake2_128Concat, RentOrderId, Vec<T::AccountId>, ValueQuery>;
+
     #[pallet::storage]
     #[pallet::getter(fn pending_slash_review)]
     pub(super) type PendingSlashReview<T: Config> = StorageMap<
@@ -283,6 +349,10 @@ pub mod pallet {
             ReporterReport::<T>::mutate(&reporter, |reporter_report| {
                 reporter_report.cancel_report(report_id);
             });
+            if let MachineFaultType::RentedInaccessible(_, rent_id) = report_info.machine_fault_type
+            {
+                InaccessibleAttesters::<T>::remove(rent_id);
+            }
             ReportInfo::<T>::remove(&report_id);
 
             Self::deposit_event(Event::ReportCanceld(
@@ -309,7 +379,13 @@ pub mod pallet {
             let order_stake = Self::get_stake_per_order()?;
 
             // 支付手续费或押金: 10 DBC | 1000 DBC
-            if matches!(report_info.machine_fault_type, MachineFaultType::RentedInaccessible(..)) {
+            if let MachineFaultType::RentedInaccessible(_, rent_id) = report_info.machine_fault_type
+            {
+                // 探测到无法访问并促成自动报告的委员会不能审核该报告
+                ensure!(
+                    !Self::inaccessible_attesters(rent_id).contains(&committee),
+                    Error::<T>::NotProperCommittee
+                );
                 Self::pay_fixed_tx_fee(committee.clone())?;
             } else {
                 <T as Config>::ManageCommittee::change_used_stake(
@@ -336,30 +412,15 @@ pub mod pallet {
             encrypted_err_info: Vec<u8>,
         ) -> DispatchResultWithPostInfo {
             let reporter = ensure_signed(origin)?;
-            let now = <frame_system::Pallet<T>>::block_number();
-
-            let mut report_info = Self::report_info(&report_id).ok_or(Error::<T>::Unknown)?;
-            let mut committee_ops = Self::committee_ops(&to_committee, &report_id);
-
-            // 检查报告可以提供加密信息
-            // 该orde处于验证中, 且还没有提交过加密信息
-            report_info
-                .can_submit_encrypted_info(&reporter, &to_committee)
-                .map_err::<Error<T>, _>(Into::into)?;
-            ensure!(
-                committee_ops.order_status == MTOrderStatus::WaitingEncrypt,
-                Error::<T>::OrderStatusNotFeat
-            );
-
-            // report_info中插入已经收到了加密信息的委员会
-            ItemList::add_item(&mut report_info.get_encrypted_info_committee, to_committee.clone());
-            ReportInfo::<T>::insert(&report_id, report_info);
-
-            committee_ops.add_encry_info(encrypted_err_info, now);
-            CommitteeOps::<T>::insert(&to_committee, &report_id, committee_ops);
-
-            Self::deposit_event(Event::EncryptedInfoSent(reporter, to_committee, report_id));
-            Ok(().into())
+            // 使用委员会当前的公钥加密
+            let key_version = <T as Config>::ManageCommittee::box_key_version(&to_committee);
+            Self::do_add_encrypted_error_info(
+                reporter,
+                report_id,
+                to_committee,
+                key_version,
+                encrypted_err_info,
+            )
         }
 
         // 委员会提交验证之后的Hash
@@ -395,6 +456,7 @@ pub mod pallet {
                     report_id,
                     report_info.machine_fault_type.clone(),
                     report_info.hashed_committee.len(),
+                    report_info.book_limit(),
                 )
             });
             ReportInfo::<T>::insert(&report_id, report_info);
@@ -661,11 +723,23 @@ pub mod pallet {
             }
 
             let _ = Self::slash_and_reward(
-                should_slash,
+                should_slash.clone(),
                 report_result.committee_stake,
                 should_reward.clone(),
             );
 
+            // 应该被惩罚的委员会减少其total_stake和used_stake，原本被惩罚的委员会退还已使用的质押
+            let _ = Self::change_committee_stake_on_report_close(
+                should_slash,
+                report_result.committee_stake,
+                true,
+            );
+            let _ = Self::change_committee_stake_on_report_close(
+                report_result.inconsistent_committee.clone(),
+                report_result.committee_stake,
+                false,
+            );
+
             // remove from unhandled report result
             report_result.slash_result = MCSlashResult::Canceled;
 
@@ -674,6 +748,40 @@ pub mod pallet {
             PendingSlashReview::<T>::remove(slashed_report_id);
             Ok(().into())
         }
+
+        /// 委员会提交对租用订单访问地址的探测结果，由链下工作机签名提交
+        /// 足够多的委员会探测到无法访问时，自动以租用人的名义报告机器无法访问
+        #[pallet::call_index(12)]
+        #[pallet::weight(<T as Config>::WeightInfo::submit_liveness_attestation())]
+        pub fn submit_liveness_attestation(
+            origin: OriginFor<T>,
+            rent_id: RentOrderId,
+            round: T::BlockNumber,
+            is_online: bool,
+        ) -> DispatchResultWithPostInfo {
+            let committee = ensure_signed(origin)?;
+            Self::do_submit_liveness_attestation(committee, rent_id, round, is_online)
+        }
+
+        /// 报告人使用委员会指定版本的公钥(当前公钥或宽限期内的旧公钥)，提交加密后的故障信息
+        #[pallet::call_index(13)]
+        #[pallet::weight(frame_support::weights::Weight::from_parts(10000, 0))]
+        pub fn reporter_add_versioned_encrypted_error_info(
+            origin: OriginFor<T>,
+            report_id: ReportId,
+            to_committee: T::AccountId,
+            key_version: u32,
+            encrypted_err_info: Vec<u8>,
+        ) -> DispatchResultWithPostInfo {
+            let reporter = ensure_signed(origin)?;
+            Self::do_add_encrypted_error_info(
+                reporter,
+                report_id,
+                to_committee,
+                key_version,
+                encrypted_err_info,
+            )
+        }
     }
 
     #[pallet::event]
@@ -688,6 +796,11 @@ pub mod pallet {
         ReporterReduceStake(T::AccountId, BalanceOf<T>),
         ApplySlashReview(ReportId),
         CommitteeBookReport(T::AccountId, ReportId),
+        // rent_id, committee, is_online
+        LivenessAttested(RentOrderId, T::AccountId, bool),
+        // rent_id, report_id
+        InaccessibleAutoReported(RentOrderId, ReportId),
+        TaskDeadlineApproaching(T::AccountId, CommitteeTaskKind, CommitteeTaskId, T::BlockNumber),
     }
 
     #[pallet::error]
@@ -723,7 +836,13 @@ pub mod pallet {
         DuplicateHash,
         NotMachineRenter,
         ReduceUsedStakeFailed,
+        NoLocalAcctForSigning,
+        NoRentEndpoint,
+        ExpiredAttestation,
+        AlreadyAttested,
+        InaccessibleReportExist,
         Unknown,
+        InvalidBoxKeyVersion,
     }
 }
 
@@ -795,6 +914,7 @@ impl<T: Config> Pallet<T> {
                 report_time,
                 machine_fault_type.clone(),
                 stake_params.stake_per_report,
+                Self::get_verifier_count(),
             ),
         );
 
@@ -802,6 +922,46 @@ impl<T: Config> Pallet<T> {
         Ok(().into())
     }
 
+    // 记录报告人提交的加密信息及加密时使用的委员会公钥版本
+    fn do_add_encrypted_error_info(
+        reporter: T::AccountId,
+        report_id: ReportId,
+        to_committee: T::AccountId,
+        key_version: u32,
+        encrypted_err_info: Vec<u8>,
+    ) -> DispatchResultWithPostInfo {
+        let now = <frame_system::Pallet<T>>::block_number();
+
+        let mut report_info = Self::report_info(&report_id).ok_or(Error::<T>::Unknown)?;
+        let mut committee_ops = Self::committee_ops(&to_committee, &report_id);
+
+        // 检查报告可以提供加密信息
+        // 该orde处于验证中, 且还没有提交过加密信息
+        report_info
+            .can_submit_encrypted_info(&reporter, &to_committee)
+            .map_err::<Error<T>, _>(Into::into)?;
+        ensure!(
+            committee_ops.order_status == MTOrderStatus::WaitingEncrypt,
+            Error::<T>::OrderStatusNotFeat
+        );
+        // 需使用委员会当前的公钥，或宽限期内的旧公钥加密
+        ensure!(
+            <T as Config>::ManageCommittee::box_key_valid(&to_committee, key_version),
+            Error::<T>::InvalidBoxKeyVersion
+        );
+
+        // report_info中插入已经收到了加密信息的委员会
+        ItemList::add_item(&mut report_info.get_encrypted_info_committee, to_committee.clone());
+        ReportInfo::<T>::insert(&report_id, report_info);
+
+        committee_ops.add_encry_info(encrypted_err_info, now);
+        CommitteeOps::<T>::insert(&to_committee, &report_id, committee_ops);
+        EncryptedInfoKeyVersion::<T>::insert(&to_committee, &report_id, key_version);
+
+        Self::deposit_event(Event::EncryptedInfoSent(reporter, to_committee, report_id));
+        Ok(().into())
+    }
+
     fn book_report(
         committee: T::AccountId,
         report_id: ReportId,
@@ -819,10 +979,16 @@ impl<T: Config> Pallet<T> {
             committee_ops.book_report(mft.clone(), now, order_stake);
         });
         LiveReport::<T>::mutate(|live_report| {
-            live_report.book_report(report_id, mft, report_info.booked_committee.len());
+            live_report.book_report(
+                report_id,
+                mft,
+                report_info.booked_committee.len(),
+                report_info.book_limit(),
+            );
         });
 
         ReportInfo::<T>::insert(&report_id, report_info);
+        Self::schedule_task_reminders(committee, CommitteeTaskId::Report(report_id), now);
     }
 }
 
@@ -857,9 +1023,9 @@ impl<T: Config> Pallet<T> {
         // 根据状态筛选出需要执行summary的报告
         if matches!(report_info.report_status, ReportStatus::WaitingBook | ReportStatus::Verifying)
         {
-            // 当大于等于5分钟或者hashed的委员会已经达到3人，则更改报告状态，允许提交原始值
+            // 当大于等于5分钟或者hashed的委员会已经达到审核人数，则更改报告状态，允许提交原始值
             if now.saturating_sub(report_info.first_book_time) >= FIVE_MINUTES.into() ||
-                report_info.hashed_committee.len() == 3
+                report_info.hashed_committee.len() >= report_info.book_limit()
             {
                 live_report.time_to_submit_raw(report_id);
                 report_info.report_status = ReportStatus::SubmittingRaw;
@@ -896,6 +1062,7 @@ impl<T: Config> Pallet<T> {
                 // 添加未完成的委员会的记录，用于惩罚
                 report_result.add_unruly(a_committee.clone());
                 CommitteeOps::<T>::remove(&a_committee, report_id);
+                EncryptedInfoKeyVersion::<T>::remove(&a_committee, report_id);
             }
 
             CommitteeOrder::<T>::insert(&a_committee, committee_order);
@@ -917,6 +1084,7 @@ impl<T: Config> Pallet<T> {
             // 记录下report_result
             report_result.report_result = ReportResultType::NoConsensus;
             report_result.reporter_stake = Zero::zero();
+            Self::update_report_reputation(&report_result);
             // Should do slash at once
             if !report_result.unruly_committee.is_empty() {
                 Self::update_unhandled_report(report_id, true, report_result.slash_exec_time);
@@ -966,6 +1134,9 @@ impl<T: Config> Pallet<T> {
         }
 
         ReporterReport::<T>::insert(&report_info.reporter, reporter_report);
+        if let MachineFaultType::RentedInaccessible(_, rent_id) = report_info.machine_fault_type {
+            InaccessibleAttesters::<T>::remove(rent_id);
+        }
 
         report_info.report_status = ReportStatus::CommitteeConfirmed;
         ReportInfo::<T>::insert(report_id, report_info);
@@ -974,6 +1145,7 @@ impl<T: Config> Pallet<T> {
         live_report.clean_unfinished_report(&report_id);
         ItemList::add_item(&mut live_report.finished_report, report_id);
 
+        Self::update_report_reputation(&report_result);
         Self::update_unhandled_report(report_id, true, report_result.slash_exec_time);
         ReportResult::<T>::insert(report_id, report_result);
 
@@ -1087,6 +1259,7 @@ impl<T: Config> Pallet<T> {
                     false,
                 );
                 CommitteeOps::<T>::remove(a_committee, report_id);
+                EncryptedInfoKeyVersion::<T>::remove(a_committee, report_id);
 
                 CommitteeOrder::<T>::mutate(a_committee, |committee_order| {
                     committee_order.clean_unfinished_order(&report_id);
@@ -1112,6 +1285,7 @@ impl<T: Config> Pallet<T> {
 
             ReportInfo::<T>::insert(report_id, report_info.clone());
             CommitteeOps::<T>::remove(&verifying_committee, &report_id);
+            EncryptedInfoKeyVersion::<T>::remove(&verifying_committee, &report_id);
 
             // NOTE: should not insert directly when summary result, but should alert exist data
             ItemList::add_item(&mut report_result.unruly_committee, verifying_committee.clone());
@@ -1156,6 +1330,7 @@ impl<T: Config> Pallet<T> {
             );
 
             CommitteeOps::<T>::remove(&verifying_committee, report_id);
+            EncryptedInfoKeyVersion::<T>::remove(&verifying_committee, report_id);
 
             ReportInfo::<T>::try_mutate(report_id, |report_info| {
                 let report_info = report_info.as_mut().ok_or(())?;
@@ -1227,6 +1402,7 @@ impl<T: Config> Pallet<T> {
                 // 所有booked_committee都应该被惩罚
                 report_info.booked_committee.clone().iter().for_each(|a_committee| {
                     CommitteeOps::<T>::remove(&a_committee, report_id);
+                    EncryptedInfoKeyVersion::<T>::remove(&a_committee, report_id);
 
                     CommitteeOrder::<T>::mutate(&a_committee, |committee_order| {
                         ItemList::rm_item(&mut committee_order.booked_report, &report_id);
@@ -1240,7 +1416,7 @@ impl<T: Config> Pallet<T> {
                     report_info.reporter.clone(),
                     report_info.machine_fault_type.clone(),
                     Some(report_info.report_time),
-                    live_report,
+                        live_report,
                     &mut reporter_report,
                 );
                 ReporterReport::<T>::insert(&report_info.reporter, reporter_report);
@@ -1252,8 +1428,63 @@ impl<T: Config> Pallet<T> {
         if report_info.report_status != ReportStatus::Reported {
             report_info.report_status = ReportStatus::CommitteeConfirmed;
         }
+        Self::update_report_reputation(&report_result);
         ReportResult::<T>::insert(report_id, report_result);
         ReportInfo::<T>::insert(report_id, report_info);
         Ok(())
     }
 }
+
+impl<T: Config> Pallet<T> {
+    // 抢单时，按提交Hash及原始信息的时间窗口安排截止提醒
+    fn schedule_task_reminders(
+        committee: T::AccountId,
+        task_id: CommitteeTaskId,
+        now: T::BlockNumber,
+    ) {
+        let reminders = <T as Config>::ManageCommittee::deadline_reminders();
+        if reminders.is_empty() {
+            return
+        }
+        let task = match Self::get_committee_tasks(committee.clone())
+            .into_iter()
+            .find(|task| task.task_id == task_id)
+        {
+            Some(task) => task,
+            None => return,
+        };
+
+        for block in task.reminder_blocks(now, &reminders) {
+            DeadlineReminder::<T>::mutate(block, |reminder| {
+                ItemList::add_item(reminder, (committee.clone(), task_id.clone()));
+            });
+        }
+    }
+
+    fn deadline_reminder_weight(now: T::BlockNumber) -> Weight {
+        let count = DeadlineReminder::<T>::decode_len(now).unwrap_or_default() as u64;
+        // 每个提醒：读取委员会的抢单列表、报告信息及委员会操作
+        T::DbWeight::get().reads_writes(1 + count * 3, 1)
+    }
+
+    // Hook: 委员会提交Hash/原始信息的截止时间临近时，发出提醒事件
+    fn remind_task_deadline(now: T::BlockNumber) {
+        for (committee, task_id) in DeadlineReminder::<T>::take(now) {
+            let task = match Self::get_committee_tasks(committee.clone())
+                .into_iter()
+                .find(|task| task.task_id == task_id)
+            {
+                Some(task) => task,
+                None => continue,
+            };
+            if let Some(deadline) = task.reminded_deadline(now) {
+                Self::deposit_event(Event::TaskDeadlineApproaching(
+                    committee,
+                    task.task_kind,
+                    task.task_id,
+                    deadline,
+                ));
+            }
+        }
+    }
+}
@@ -0,0 +1,163 @@
+use crate::{
+    probe::{ProbeEndpoint, MAX_PROBES_PER_ROUND, PROBE_CURSOR_KEY, PROBE_INTERVAL},
+    Call, Config, Error, Event, InaccessibleAttesters, LiveReport, Pallet, ReporterReport,
+};
+use dbc_support::{
+    machine_type::MachineStatus, rental_type::RentStatus, report::MachineFaultType, MachineId,
+    RentOrderId,
+};
+use frame_support::{dispatch::DispatchResultWithPostInfo, ensure};
+use frame_system::offchain::{SendSignedTransaction, Signer};
+use rent_machine::{RentLiveness, RentOrderEndpoint};
+use sp_runtime::offchain::storage::StorageValueRef;
+use sp_std::vec::Vec;
+
+// 委员会的链下工作机探测租用者声明的访问地址，签名提交探测结果；
+// 足够多的委员会探测到无法访问时，自动以租用人的名义报告机器无法访问。
+// 与租用人自己报告一样占用租用人的报告质押，报告被拒绝时惩罚租用人，因此只探测质押足够的订单
+impl<T: Config> Pallet<T> {
+    pub fn probe_round(now: T::BlockNumber) -> T::BlockNumber {
+        now - now % PROBE_INTERVAL.into()
+    }
+
+    // 租用人已经有正在处理的该订单的无法访问报告
+    pub fn has_inaccessible_report(renter: &T::AccountId, rent_id: RentOrderId) -> bool {
+        Self::reporter_report(renter).processing_report.iter().any(|report_id| {
+            Self::report_info(report_id).map_or(false, |report_info| {
+                matches!(
+                    report_info.machine_fault_type,
+                    MachineFaultType::RentedInaccessible(_, id) if id == rent_id
+                )
+            })
+        })
+    }
+
+    pub(crate) fn probe_rent_endpoints(round: T::BlockNumber) -> Result<(), Error<T>> {
+        let signer = Signer::<T, T::AuthorityId>::any_account();
+        if !signer.can_sign() {
+            return Err(Error::<T>::NoLocalAcctForSigning)
+        }
+
+        // 每轮最多探测MAX_PROBES_PER_ROUND个地址，下一轮从上次结束的位置继续
+        let cursor = StorageValueRef::persistent(PROBE_CURSOR_KEY);
+        let mut endpoints = match cursor.get::<Vec<u8>>() {
+            Ok(Some(last_key)) => RentOrderEndpoint::<T>::iter_from(last_key),
+            _ => RentOrderEndpoint::<T>::iter(),
+        };
+
+        let mut probe_count = 0;
+        while probe_count < MAX_PROBES_PER_ROUND {
+            let (rent_id, endpoint) = match endpoints.next() {
+                Some(item) => item,
+                None => {
+                    // 已经探测完所有地址，下一轮从头开始
+                    cursor.clear();
+                    return Ok(())
+                },
+            };
+
+            let rent_info = match <rent_machine::Pallet<T>>::rent_info(rent_id) {
+                Some(rent_info) => rent_info,
+                None => continue,
+            };
+            if rent_info.rent_status != RentStatus::Renting ||
+                Self::has_inaccessible_report(&rent_info.renter, rent_id) ||
+                !Self::is_reporter_stake_enough(&rent_info.renter)
+            {
+                continue
+            }
+
+            probe_count += 1;
+            // 无法探测的地址不提交结果，不能据此认为机器无法访问
+            let is_online = match T::EndpointProbe::probe(&endpoint.host, endpoint.port) {
+                Some(is_online) => is_online,
+                None => continue,
+            };
+            let _ = signer.send_signed_transaction(|_account| Call::submit_liveness_attestation {
+                rent_id,
+                round,
+                is_online,
+            });
+        }
+
+        cursor.set(&endpoints.last_raw_key().to_vec());
+        Ok(())
+    }
+
+    pub(crate) fn do_submit_liveness_attestation(
+        committee: T::AccountId,
+        rent_id: RentOrderId,
+        round: T::BlockNumber,
+        is_online: bool,
+    ) -> DispatchResultWithPostInfo {
+        let now = <frame_system::Pallet<T>>::block_number();
+        Self::is_valid_committee(&committee)?;
+        ensure!(round == Self::probe_round(now), Error::<T>::ExpiredAttestation);
+
+        let rent_info =
+            <rent_machine::Pallet<T>>::rent_info(rent_id).ok_or(Error::<T>::NoRentEndpoint)?;
+        ensure!(
+            <rent_machine::Pallet<T>>::rent_order_endpoint(rent_id).is_some() &&
+                rent_info.rent_status == RentStatus::Renting,
+            Error::<T>::NoRentEndpoint
+        );
+        // 租用人不能证明自己租用的机器无法访问
+        ensure!(rent_info.renter != committee, Error::<T>::NotProperCommittee);
+        ensure!(
+            !Self::has_inaccessible_report(&rent_info.renter, rent_id),
+            Error::<T>::InaccessibleReportExist
+        );
+        ensure!(Self::is_reporter_stake_enough(&rent_info.renter), Error::<T>::StakeNotEnough);
+
+        let mut liveness = <rent_machine::Pallet<T>>::rent_liveness(rent_id);
+        liveness
+            .attest(round, committee.clone(), is_online, Self::get_verifier_count())
+            .map_err(|_| Error::<T>::AlreadyAttested)?;
+        Self::deposit_event(Event::LivenessAttested(rent_id, committee, is_online));
+
+        if liveness.is_offline_quorum() {
+            RentLiveness::<T>::remove(rent_id);
+            // 探测到无法访问的委员会不能再抢单审核自己促成的报告
+            InaccessibleAttesters::<T>::insert(rent_id, liveness.offline_committee);
+            Self::auto_report_inaccessible(rent_info.renter, rent_info.machine_id, rent_id)
+        } else {
+            RentLiveness::<T>::insert(rent_id, liveness);
+            Ok(().into())
+        }
+    }
+
+    // 与租用人报告无法访问一样，记录机器离线时的租用人并占用租用人的报告质押，
+    // 之后由委员会抢单确认
+    fn auto_report_inaccessible(
+        renter: T::AccountId,
+        machine_id: MachineId,
+        rent_id: RentOrderId,
+    ) -> DispatchResultWithPostInfo {
+        if let Some(machine_info) = <online_profile::Pallet<T>>::machines_info(&machine_id) {
+            if machine_info.machine_status == MachineStatus::Rented {
+                <online_profile::Pallet<T>>::add_offline_machine_to_renters(
+                    machine_id.clone(),
+                    machine_info.renters,
+                );
+            }
+        }
+
+        Self::pay_stake_when_report(renter.clone())?;
+
+        let report_id = Self::next_report_id();
+        let mut live_report = Self::live_report();
+        let mut reporter_report = Self::reporter_report(&renter);
+        Self::do_report_machine_fault(
+            renter.clone(),
+            MachineFaultType::RentedInaccessible(machine_id, rent_id),
+            None,
+            &mut live_report,
+            &mut reporter_report,
+        )?;
+
+        LiveReport::<T>::put(live_report);
+        ReporterReport::<T>::insert(&renter, reporter_report);
+        Self::deposit_event(Event::InaccessibleAutoReported(rent_id, report_id));
+        Ok(().into())
+    }
+}
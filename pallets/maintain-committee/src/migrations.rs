@@ -1,68 +1,85 @@
-use codec::{Decode, Encode};
+use crate::{BalanceOf, Config, Pallet, ReportInfo};
 use dbc_support::{
-    report::{MCSlashResult, MTReportResultInfo, ReportResultType},
-    MachineId, ReportId,
+    report::{MTReportInfoDetail, MachineFaultType, ReportStatus},
+    MachineId, RentOrderId,
 };
-use frame_support::{traits::Get, weights::Weight, IterableStorageMap, RuntimeDebug};
+use frame_support::{
+    traits::{Get, GetStorageVersion, StorageVersion},
+    weights::Weight,
+    RuntimeDebug,
+};
+use parity_scale_codec::{Decode, Encode};
 use scale_info::TypeInfo;
+use sp_std::vec::Vec;
 
-use crate::Config;
-
-// pub fn apply<T: Config>() -> Weight {
-//     frame_support::debug::RuntimeLogger::init();
-//     info!(
-//         target: "runtime::maintain_committee",
-//         "Running migration for maintainCommittee pallet"
-//     );
-// }
+// 升级前报告固定由3个委员会审核
+const LEGACY_VERIFIER_COUNT: u32 = 3;
 
 #[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
-pub struct OldMTReportResultInfo<AccountId, BlockNumber, Balance> {
-    pub report_id: ReportId,
-    // 变更为Option<AccountId>
+pub struct OldMTReportInfoDetail<AccountId, BlockNumber, Balance> {
     pub reporter: AccountId,
+    pub report_time: BlockNumber,
     pub reporter_stake: Balance,
-
-    pub inconsistent_committee: Vec<AccountId>,
-    pub unruly_committee: Vec<AccountId>,
-    pub reward_committee: Vec<AccountId>,
-    pub committee_stake: Balance,
-
-    // 变更为Option<AccountId>
-    pub machine_stash: AccountId,
+    pub first_book_time: BlockNumber,
     pub machine_id: MachineId,
-
-    pub slash_time: BlockNumber,
-    pub slash_exec_time: BlockNumber,
-    pub report_result: ReportResultType,
-    pub slash_result: MCSlashResult,
+    pub rent_order_id: RentOrderId,
+    pub err_info: Vec<u8>,
+    pub verifying_committee: Option<AccountId>,
+    pub booked_committee: Vec<AccountId>,
+    pub get_encrypted_info_committee: Vec<AccountId>,
+    pub hashed_committee: Vec<AccountId>,
+    pub confirm_start: BlockNumber,
+    pub confirmed_committee: Vec<AccountId>,
+    pub support_committee: Vec<AccountId>,
+    pub against_committee: Vec<AccountId>,
+    pub report_status: ReportStatus,
+    pub machine_fault_type: MachineFaultType,
+    // 新增verifier_count
 }
 
-impl<AccountId, BlockNumber, Balance> From<OldMTReportResultInfo<AccountId, BlockNumber, Balance>>
-    for MTReportResultInfo<AccountId, BlockNumber, Balance>
+impl<AccountId, BlockNumber, Balance> From<OldMTReportInfoDetail<AccountId, BlockNumber, Balance>>
+    for MTReportInfoDetail<AccountId, BlockNumber, Balance>
 {
     fn from(
-        info: OldMTReportResultInfo<AccountId, BlockNumber, Balance>,
-    ) -> MTReportResultInfo<AccountId, BlockNumber, Balance> {
-        MTReportResultInfo {
-            report_id: info.report_id,
+        info: OldMTReportInfoDetail<AccountId, BlockNumber, Balance>,
+    ) -> MTReportInfoDetail<AccountId, BlockNumber, Balance> {
+        MTReportInfoDetail {
             reporter: info.reporter,
+            report_time: info.report_time,
             reporter_stake: info.reporter_stake,
-            inconsistent_committee: info.inconsistent_committee,
-            unruly_committee: info.unruly_committee,
-            reward_committee: info.reward_committee,
-            committee_stake: info.committee_stake,
-            machine_stash: Some(info.machine_stash),
+            first_book_time: info.first_book_time,
             machine_id: info.machine_id,
-            slash_time: info.slash_time,
-            slash_exec_time: info.slash_exec_time,
-            report_result: info.report_result,
-            slash_result: info.slash_result,
+            rent_order_id: info.rent_order_id,
+            err_info: info.err_info,
+            verifying_committee: info.verifying_committee,
+            booked_committee: info.booked_committee,
+            get_encrypted_info_committee: info.get_encrypted_info_committee,
+            hashed_committee: info.hashed_committee,
+            confirm_start: info.confirm_start,
+            confirmed_committee: info.confirmed_committee,
+            support_committee: info.support_committee,
+            against_committee: info.against_committee,
+            report_status: info.report_status,
+            machine_fault_type: info.machine_fault_type,
+            verifier_count: LEGACY_VERIFIER_COUNT,
         }
     }
 }
 
-pub fn migrate_storage() -> Weight {
-    todo!();
-    Weight::zero()
+// 为升级前的报告记录审核委员会数量
+pub fn migrate_report_info<T: Config>() -> Weight {
+    if Pallet::<T>::on_chain_storage_version() >= 1 {
+        return T::DbWeight::get().reads(1)
+    }
+
+    let mut report_num = 0u64;
+    ReportInfo::<T>::translate(
+        |_report_id, old: OldMTReportInfoDetail<T::AccountId, T::BlockNumber, BalanceOf<T>>| {
+            report_num += 1;
+            Some(old.into())
+        },
+    );
+    StorageVersion::new(1).put::<Pallet<T>>();
+
+    T::DbWeight::get().reads_writes(1 + report_num, 1 + report_num)
 }
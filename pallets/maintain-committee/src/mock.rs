@@ -4,17 +4,17 @@ use dbc_support::{
     machine_type::{
         CommitteeUploadInfo, Latitude, Longitude, StakerCustomizeInfo, StandardGpuPointPrice,
     },
-    ONE_DAY,
+    EraIndex, ONE_DAY,
 };
 use frame_support::{
     assert_ok,
     pallet_prelude::Weight,
     parameter_types,
-    traits::{ConstU32, OnFinalize, OnInitialize},
+    traits::{AsEnsureOriginWithArg, ConstU128, ConstU32, OnFinalize, OnInitialize},
     PalletId,
 };
 pub use frame_system::RawOrigin;
-use frame_system::{EnsureRoot, EnsureWithSuccess};
+use frame_system::{EnsureRoot, EnsureSigned, EnsureWithSuccess};
 pub use sp_core::{
     sr25519::{self, Signature},
     H256,
@@ -39,6 +39,9 @@ pub const INIT_BALANCE: u128 = 10_000_000 * ONE_DBC;
 pub const INIT_TIMESTAMP: u64 = 30_000;
 pub const BLOCK_TIME: u64 = 30_000;
 
+pub const DLC_ASSET_ID: u32 = 88;
+pub const ONE_DLC: u128 = 1_000_000_000_000_000;
+
 pub type BlockNumber = u32;
 
 parameter_types! {
@@ -101,9 +104,43 @@ impl dbc_price_ocw::Config for TestRuntime {
     type RandomnessSource = RandomnessCollectiveFlip;
 }
 
+impl dlc_price_ocw::Config for TestRuntime {
+    type Currency = Balances;
+    type RuntimeEvent = RuntimeEvent;
+    type RandomnessSource = RandomnessCollectiveFlip;
+}
+
+parameter_types! {
+    pub const MinLockAmount: Balance = 100 * ONE_DLC;
+    pub const MaxLockDuration: BlockNumber = 1800 * ONE_DAY;
+}
+
+impl pallet_assets::Config for TestRuntime {
+    type RuntimeEvent = RuntimeEvent;
+    type Balance = Balance;
+    type AssetId = u32;
+    type AssetIdParameter = parity_scale_codec::Compact<u32>;
+    type Currency = Balances;
+    type CreateOrigin = AsEnsureOriginWithArg<EnsureSigned<Self::AccountId>>;
+    type ForceOrigin = EnsureRoot<Self::AccountId>;
+    type AssetDeposit = ConstU128<1>;
+    type AssetAccountDeposit = ConstU128<1>;
+    type MetadataDepositBase = ConstU128<1>;
+    type MetadataDepositPerByte = ConstU128<1>;
+    type ApprovalDeposit = ConstU128<1>;
+    type StringLimit = ConstU32<50>;
+    type Freezer = ();
+    type Extra = ();
+    type CallbackHandle = ();
+    type WeightInfo = ();
+    type RemoveItemsLimit = ConstU32<1000>;
+    type MinLockAmount = MinLockAmount;
+    type MaxLockDuration = MaxLockDuration;
+}
+
 impl pallet_insecure_randomness_collective_flip::Config for TestRuntime {}
 
-type TestExtrinsic = TestXt<RuntimeCall, ()>;
+pub type TestExtrinsic = TestXt<RuntimeCall, ()>;
 impl<LocalCall> frame_system::offchain::CreateSignedTransaction<LocalCall> for TestRuntime
 where
     RuntimeCall: From<LocalCall>,
@@ -201,10 +238,16 @@ impl pallet_timestamp::Config for TestRuntime {
     type WeightInfo = ();
 }
 
+parameter_types! {
+    pub CommitteeCurrentEra: EraIndex = OnlineProfile::current_era();
+}
+
 impl committee::Config for TestRuntime {
     type Currency = Balances;
     type RuntimeEvent = RuntimeEvent;
-    // type WeightInfo = ();
+    type SlashAndReward = GenericFunc;
+    type CurrentEra = CommitteeCurrentEra;
+    type WeightInfo = ();
 }
 
 impl online_committee::Config for TestRuntime {
@@ -214,7 +257,7 @@ impl online_committee::Config for TestRuntime {
     type ManageCommittee = Committee;
     type CancelSlashOrigin =
         pallet_collective::EnsureProportionAtLeast<Self::AccountId, TechnicalCollective, 2, 3>;
-    type SlashAndReward = GenericFunc;
+    type SlashAndReward = Committee;
 }
 
 parameter_types! {
@@ -231,7 +274,21 @@ impl online_profile::Config for TestRuntime {
     type Slash = Treasury;
     type CancelSlashOrigin =
         pallet_collective::EnsureProportionAtLeast<Self::AccountId, TechnicalCollective, 2, 3>;
-    type SlashAndReward = GenericFunc;
+    type SlashAndReward = Committee;
+    type WeightInfo = ();
+}
+
+parameter_types! {
+    // 测试中代替节点的TCP探测结果
+    pub static ProbeResult: Option<bool> = Some(true);
+}
+
+pub struct MockProbe;
+
+impl maintain_committee::probe::ProbeEndpoint for MockProbe {
+    fn probe(_host: &[u8], _port: u16) -> Option<bool> {
+        ProbeResult::get()
+    }
 }
 
 impl maintain_committee::Config for TestRuntime {
@@ -242,7 +299,15 @@ impl maintain_committee::Config for TestRuntime {
     type Slash = Treasury;
     type CancelSlashOrigin =
         pallet_collective::EnsureProportionAtLeast<Self::AccountId, TechnicalCollective, 2, 3>;
-    type SlashAndReward = GenericFunc;
+    type SlashAndReward = Committee;
+    type AuthorityId = maintain_committee::probe::crypto::ProbeAuthId;
+    type EndpointProbe = MockProbe;
+    type WeightInfo = ();
+}
+
+parameter_types! {
+    pub const DlcAssetId: u32 = DLC_ASSET_ID;
+    pub const RentMachinePalletId: PalletId = PalletId(*b"dbc/rent");
 }
 
 impl rent_machine::Config for TestRuntime {
@@ -250,6 +315,16 @@ impl rent_machine::Config for TestRuntime {
     type RuntimeEvent = RuntimeEvent;
     type RTOps = OnlineProfile;
     type DbcPrice = DBCPriceOCW;
+    type DlcPrice = DLCPriceOCW;
+    type SurgePricing = OnlineProfile;
+    type ManageCommittee = Committee;
+    type CancelSlashOrigin =
+        pallet_collective::EnsureProportionAtLeast<Self::AccountId, TechnicalCollective, 2, 3>;
+    type SlashAndReward = Committee;
+    type Assets = Assets;
+    type DlcAssetId = DlcAssetId;
+    type PalletId = RentMachinePalletId;
+    type WeightInfo = ();
 }
 // Configure a mock runtime to test the pallet.
 frame_support::construct_runtime!(
@@ -265,6 +340,8 @@ frame_support::construct_runtime!(
         Balances: pallet_balances,
         Committee: committee,
         DBCPriceOCW: dbc_price_ocw,
+        DLCPriceOCW: dlc_price_ocw,
+        Assets: pallet_assets,
         Treasury: pallet_treasury,
         GenericFunc: generic_func,
         Timestamp: pallet_timestamp,
@@ -0,0 +1,56 @@
+use dbc_support::ONE_MINUTE;
+use sp_core::crypto::KeyTypeId;
+
+/// 委员会的链下工作机签名探测结果时使用的密钥类型
+pub const KEY_TYPE: KeyTypeId = KeyTypeId(*b"mtpb");
+/// 每10分钟探测一轮，每轮每个委员会对每个订单只能提交一次探测结果
+pub const PROBE_INTERVAL: u32 = 10 * ONE_MINUTE;
+/// 单次探测的超时时间
+pub const PROBE_TIMEOUT_MS: u64 = 3_000;
+/// 每轮最多探测的访问地址数量，超出的地址在之后的轮次中继续探测
+pub const MAX_PROBES_PER_ROUND: u32 = 50;
+/// 链下存储中记录上一轮探测到的位置
+pub const PROBE_CURSOR_KEY: &[u8] = b"maintain_committee::probe_cursor";
+
+pub mod crypto {
+    use super::KEY_TYPE;
+    use sp_core::sr25519::Signature as Sr25519Signature;
+    use sp_runtime::{
+        app_crypto::{app_crypto, sr25519},
+        traits::Verify,
+        MultiSignature, MultiSigner,
+    };
+
+    app_crypto!(sr25519, KEY_TYPE);
+
+    pub struct ProbeAuthId;
+
+    impl frame_system::offchain::AppCrypto<MultiSigner, MultiSignature> for ProbeAuthId {
+        type RuntimeAppPublic = Public;
+        type GenericSignature = sp_core::sr25519::Signature;
+        type GenericPublic = sp_core::sr25519::Public;
+    }
+
+    impl frame_system::offchain::AppCrypto<<Sr25519Signature as Verify>::Signer, Sr25519Signature>
+        for ProbeAuthId
+    {
+        type RuntimeAppPublic = Public;
+        type GenericSignature = sp_core::sr25519::Signature;
+        type GenericPublic = sp_core::sr25519::Public;
+    }
+}
+
+/// 探测租用者声明的访问地址是否可以访问，无法探测(如不是公网地址)时返回None
+pub trait ProbeEndpoint {
+    fn probe(host: &[u8], port: u16) -> Option<bool>;
+}
+
+/// 通过节点的扩展函数建立TCP连接：连接成功即认为可以访问，不要求机器上运行HTTP服务
+/// 节点只探测公网地址，不会连接回环、内网及链路本地地址
+pub struct TcpProbe;
+
+impl ProbeEndpoint for TcpProbe {
+    fn probe(host: &[u8], port: u16) -> Option<bool> {
+        dbc_primitives_ext::probe_ext::tcp_connect(host.to_vec(), port, PROBE_TIMEOUT_MS)
+    }
+}
@@ -0,0 +1,85 @@
+use crate::{
+    types::MTPendingSlashReviewInfo, BalanceOf, Config, LiveReport, Pallet, PendingSlashReview,
+};
+use dbc_support::{
+    committee_task::{CommitteeTask, CommitteeTaskId, CommitteeTaskKind},
+    report::{
+        MTCommitteeOpsDetail, MTCommitteeOrderList, MTReportInfoDetail, MTReportResultInfo,
+        ReporterReportList,
+    },
+    ReportId,
+};
+use sp_std::vec::Vec;
+
+impl<T: Config> Pallet<T> {
+    /// 获取报告详情
+    pub fn get_report_info(
+        report_id: ReportId,
+    ) -> Option<MTReportInfoDetail<T::AccountId, T::BlockNumber, BalanceOf<T>>> {
+        Self::report_info(report_id)
+    }
+
+    /// 获取报告的处理结果
+    pub fn get_report_result(
+        report_id: ReportId,
+    ) -> Option<MTReportResultInfo<T::AccountId, T::BlockNumber, BalanceOf<T>>> {
+        Self::report_result(report_id)
+    }
+
+    /// 获取报告人的报告列表
+    pub fn get_reporter_report(reporter: T::AccountId) -> ReporterReportList {
+        Self::reporter_report(reporter)
+    }
+
+    /// 获取委员会抢单的报告列表
+    pub fn get_committee_order(committee: T::AccountId) -> MTCommitteeOrderList {
+        Self::committee_order(committee)
+    }
+
+    /// 获取委员会对报告的操作记录
+    pub fn get_committee_ops(
+        committee: T::AccountId,
+        report_id: ReportId,
+    ) -> MTCommitteeOpsDetail<T::BlockNumber, BalanceOf<T>> {
+        Self::committee_ops(committee, report_id)
+    }
+
+    /// 获取等待委员会抢单的报告
+    pub fn get_bookable_report() -> Vec<ReportId> {
+        LiveReport::<T>::get().bookable_report
+    }
+
+    /// 获取等待技术委员会审核的惩罚申述
+    pub fn get_pending_slash_review(
+        report_id: ReportId,
+    ) -> Option<MTPendingSlashReviewInfo<T::AccountId, BalanceOf<T>, T::BlockNumber>> {
+        Self::pending_slash_review(report_id)
+    }
+
+    pub fn get_pending_slash_review_list() -> Vec<ReportId> {
+        PendingSlashReview::<T>::iter_keys().collect()
+    }
+
+    /// 获取委员会未完成的报告处理任务：未提交Hash及未提交原始信息的报告
+    pub fn get_committee_tasks(committee: T::AccountId) -> Vec<CommitteeTask<T::BlockNumber>> {
+        let committee_order = Self::committee_order(&committee);
+
+        let booked = committee_order.booked_report.into_iter().map(|id| (id, false));
+        let hashed = committee_order.hashed_report.into_iter().map(|id| (id, true));
+
+        booked
+            .chain(hashed)
+            .filter_map(|(report_id, hash_submitted)| {
+                let report_info = Self::report_info(report_id)?;
+                let committee_ops = Self::committee_ops(&committee, report_id);
+                Some(CommitteeTask {
+                    task_kind: CommitteeTaskKind::FaultReport,
+                    task_id: CommitteeTaskId::Report(report_id),
+                    hash_submitted,
+                    hash_deadline: report_info.hash_deadline(committee_ops.booked_time),
+                    raw_deadline: report_info.raw_deadline(),
+                })
+            })
+            .collect()
+    }
+}
@@ -3,7 +3,7 @@ use crate::{
 };
 use dbc_support::{
     report::{MCSlashResult, MTReportResultInfo, ReportResultType},
-    traits::MTOps,
+    traits::{MTOps, ManageCommittee},
     ReportId,
 };
 use frame_support::IterableStorageMap;
@@ -105,10 +105,17 @@ impl<T: Config> Pallet<T> {
                     reporter_stake.change_stake_on_report_close(review_info.staked_amount, true);
                 })
             } else if is_slashed_committee {
-                let _ = Self::change_committee_stake_on_report_close(
-                    vec![review_info.applicant.clone()],
+                // 没收申请复审时的质押，由委员会自己承担，不分给委托人
+                let _ = <T as Config>::ManageCommittee::change_total_stake(
+                    review_info.applicant.clone(),
+                    review_info.staked_amount,
+                    false,
+                    false,
+                );
+                let _ = <T as Config>::ManageCommittee::change_used_stake(
+                    review_info.applicant.clone(),
                     review_info.staked_amount,
-                    true,
+                    false,
                 );
             } else if is_slashed_stash {
                 let _ = T::MTOps::mt_rm_stash_total_stake(
@@ -1,3 +1,4 @@
+pub mod test_liveness_probe;
 pub mod test_report_fault_works;
 pub mod test_report_inaccessible_works;
 pub mod test_report_individual_gpu;
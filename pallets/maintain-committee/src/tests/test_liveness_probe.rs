@@ -0,0 +1,324 @@
+use super::super::{mock::*, Error};
+use crate::{
+    probe::{ProbeEndpoint, TcpProbe, KEY_TYPE, PROBE_INTERVAL},
+    MTLiveReportList, MachineFaultType, ReporterReportList,
+};
+use dbc_support::rental_type::RentLivenessRound;
+use frame_support::{assert_noop, assert_ok, traits::OffchainWorker};
+use once_cell::sync::Lazy;
+use parity_scale_codec::Decode;
+use sp_core::offchain::{
+    testing::{TestOffchainExt, TestTransactionPoolExt},
+    OffchainDbExt, OffchainWorkerExt, TransactionPoolExt,
+};
+use sp_keystore::{testing::MemoryKeystore, Keystore, KeystoreExt};
+use std::{convert::TryInto, net::TcpListener};
+
+// 委员会的链下工作机探测租用者声明的访问地址
+// 3个委员会探测到无法访问时，自动以租用人的名义报告机器无法访问，占用租用人的报告质押
+
+const committee1: Lazy<sp_core::sr25519::Public> =
+    Lazy::new(|| sr25519::Public::from(Sr25519Keyring::One));
+const committee3: Lazy<sp_core::sr25519::Public> =
+    Lazy::new(|| sr25519::Public::from(Sr25519Keyring::Ferdie));
+const committee4: Lazy<sp_core::sr25519::Public> =
+    Lazy::new(|| sr25519::Public::from(Sr25519Keyring::Alice));
+// 租用人同时也是委员会
+const renter: Lazy<sp_core::sr25519::Public> =
+    Lazy::new(|| sr25519::Public::from(Sr25519Keyring::Two));
+
+const machine_id: Lazy<Vec<u8>> = Lazy::new(|| {
+    "8eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a48"
+        .as_bytes()
+        .to_vec()
+});
+
+fn add_committee4() {
+    assert_ok!(Committee::add_committee(RawOrigin::Root.into(), *committee4));
+    let box_pubkey =
+        hex::decode("9dccbab2d61405084eac440f877a6479bc827373b2e414e81a6170ebe5aadd12")
+            .unwrap()
+            .try_into()
+            .unwrap();
+    assert_ok!(Committee::committee_set_box_pubkey(RuntimeOrigin::signed(*committee4), box_pubkey));
+}
+
+#[test]
+fn liveness_attestation_auto_report_works() {
+    new_test_with_init_params_ext().execute_with(|| {
+        add_committee4();
+
+        // 未设置访问地址时不能提交探测结果
+        assert_noop!(
+            MaintainCommittee::submit_liveness_attestation(
+                RuntimeOrigin::signed(*committee1),
+                0,
+                0,
+                false
+            ),
+            Error::<TestRuntime>::NoRentEndpoint
+        );
+
+        assert_noop!(
+            RentMachine::set_rent_endpoint(
+                RuntimeOrigin::signed(*committee1),
+                0,
+                "127.0.0.1".as_bytes().to_vec(),
+                8080
+            ),
+            rent_machine::Error::<TestRuntime>::NotMachineRenter
+        );
+        assert_noop!(
+            RentMachine::set_rent_endpoint(RuntimeOrigin::signed(*renter), 0, vec![], 8080),
+            rent_machine::Error::<TestRuntime>::InvalidRentEndpoint
+        );
+        assert_ok!(RentMachine::set_rent_endpoint(
+            RuntimeOrigin::signed(*renter),
+            0,
+            "127.0.0.1".as_bytes().to_vec(),
+            8080
+        ));
+
+        // 租用人不能提交探测结果；只能提交当前轮次的探测结果
+        assert_noop!(
+            MaintainCommittee::submit_liveness_attestation(
+                RuntimeOrigin::signed(*renter),
+                0,
+                0,
+                false
+            ),
+            Error::<TestRuntime>::NotProperCommittee
+        );
+        assert_noop!(
+            MaintainCommittee::submit_liveness_attestation(
+                RuntimeOrigin::signed(*committee1),
+                0,
+                PROBE_INTERVAL,
+                false
+            ),
+            Error::<TestRuntime>::ExpiredAttestation
+        );
+        // 租用人没有报告质押时，不接受探测结果
+        assert_noop!(
+            MaintainCommittee::submit_liveness_attestation(
+                RuntimeOrigin::signed(*committee1),
+                0,
+                0,
+                false
+            ),
+            Error::<TestRuntime>::StakeNotEnough
+        );
+        assert_ok!(MaintainCommittee::reporter_add_stake(
+            RuntimeOrigin::signed(*renter),
+            20000 * ONE_DBC
+        ));
+
+        assert_ok!(MaintainCommittee::submit_liveness_attestation(
+            RuntimeOrigin::signed(*committee1),
+            0,
+            0,
+            false
+        ));
+        assert_noop!(
+            MaintainCommittee::submit_liveness_attestation(
+                RuntimeOrigin::signed(*committee1),
+                0,
+                0,
+                true
+            ),
+            Error::<TestRuntime>::AlreadyAttested
+        );
+        assert_ok!(MaintainCommittee::submit_liveness_attestation(
+            RuntimeOrigin::signed(*committee3),
+            0,
+            0,
+            false
+        ));
+        assert_eq!(RentMachine::rent_liveness(0).offline_committee.len(), 2);
+        assert_eq!(MaintainCommittee::live_report(), MTLiveReportList::default());
+
+        // 第3个委员会探测到无法访问，自动报告，与租用人自己报告一样占用报告质押
+        assert_ok!(MaintainCommittee::submit_liveness_attestation(
+            RuntimeOrigin::signed(*committee4),
+            0,
+            0,
+            false
+        ));
+        assert_eq!(RentMachine::rent_liveness(0), RentLivenessRound::default());
+        assert_eq!(
+            MaintainCommittee::live_report(),
+            MTLiveReportList { bookable_report: vec![0], ..Default::default() }
+        );
+        let report_info = MaintainCommittee::report_info(0).unwrap();
+        assert_eq!(report_info.reporter, *renter);
+        assert_eq!(report_info.reporter_stake, 1000 * ONE_DBC);
+        assert_eq!(
+            report_info.machine_fault_type,
+            MachineFaultType::RentedInaccessible(machine_id.clone(), 0)
+        );
+        assert_eq!(
+            MaintainCommittee::reporter_report(&*renter),
+            ReporterReportList { processing_report: vec![0], ..Default::default() }
+        );
+        assert_eq!(MaintainCommittee::reporter_stake(&*renter).used_stake, 1000 * ONE_DBC);
+        assert_eq!(
+            MaintainCommittee::inaccessible_attesters(0),
+            vec![*committee1, *committee3, *committee4]
+        );
+
+        // 促成自动报告的委员会不能抢单审核该报告
+        assert_noop!(
+            MaintainCommittee::committee_book_report(RuntimeOrigin::signed(*committee1), 0),
+            Error::<TestRuntime>::NotProperCommittee
+        );
+
+        // 报告处理中，不再接受探测结果
+        assert_noop!(
+            MaintainCommittee::submit_liveness_attestation(
+                RuntimeOrigin::signed(*committee1),
+                0,
+                0,
+                false
+            ),
+            Error::<TestRuntime>::InaccessibleReportExist
+        );
+    });
+}
+
+#[test]
+fn liveness_round_reset_works() {
+    new_test_with_init_params_ext().execute_with(|| {
+        add_committee4();
+        assert_ok!(RentMachine::set_rent_endpoint(
+            RuntimeOrigin::signed(*renter),
+            0,
+            "127.0.0.1".as_bytes().to_vec(),
+            8080
+        ));
+        assert_ok!(MaintainCommittee::reporter_add_stake(
+            RuntimeOrigin::signed(*renter),
+            20000 * ONE_DBC
+        ));
+
+        assert_ok!(MaintainCommittee::submit_liveness_attestation(
+            RuntimeOrigin::signed(*committee1),
+            0,
+            0,
+            false
+        ));
+        assert_ok!(MaintainCommittee::submit_liveness_attestation(
+            RuntimeOrigin::signed(*committee3),
+            0,
+            0,
+            true
+        ));
+
+        // 进入新的一轮，重新统计
+        run_to_block(PROBE_INTERVAL - 1);
+        assert_ok!(MaintainCommittee::submit_liveness_attestation(
+            RuntimeOrigin::signed(*committee4),
+            0,
+            PROBE_INTERVAL,
+            false
+        ));
+        assert_eq!(
+            RentMachine::rent_liveness(0),
+            RentLivenessRound {
+                round: PROBE_INTERVAL,
+                online_committee: vec![],
+                offline_committee: vec![*committee4],
+                quorum: 3,
+            }
+        );
+        assert_eq!(MaintainCommittee::live_report(), MTLiveReportList::default());
+
+        // 订单结束时，访问地址与探测结果一同移除
+        assert_ok!(RentMachine::terminate_rent(RuntimeOrigin::signed(*renter), 0));
+        assert_eq!(RentMachine::rent_order_endpoint(0), None);
+        assert_eq!(RentMachine::rent_liveness(0), RentLivenessRound::default());
+    });
+}
+
+#[test]
+fn offchain_worker_probe_endpoint_works() {
+    let mut ext = new_test_with_init_params_ext();
+    let (offchain, _offchain_state) = TestOffchainExt::new();
+    let (pool, pool_state) = TestTransactionPoolExt::new();
+    let keystore = MemoryKeystore::new();
+    keystore
+        .sr25519_generate_new(KEY_TYPE, Some(&Sr25519Keyring::One.to_seed()))
+        .unwrap();
+
+    ext.register_extension(OffchainWorkerExt::new(offchain.clone()));
+    ext.register_extension(OffchainDbExt::new(offchain));
+    ext.register_extension(TransactionPoolExt::new(pool));
+    ext.register_extension(KeystoreExt::new(keystore));
+
+    ext.execute_with(|| {
+        assert_ok!(RentMachine::set_rent_endpoint(
+            RuntimeOrigin::signed(*renter),
+            0,
+            "203.0.113.1".as_bytes().to_vec(),
+            8080
+        ));
+        assert_ok!(MaintainCommittee::reporter_add_stake(
+            RuntimeOrigin::signed(*renter),
+            20000 * ONE_DBC
+        ));
+
+        // 只在每轮开始时探测
+        MaintainCommittee::offchain_worker(System::block_number());
+        assert!(pool_state.read().transactions.is_empty());
+
+        ProbeResult::set(Some(true));
+        run_to_block(PROBE_INTERVAL - 1);
+        MaintainCommittee::offchain_worker(PROBE_INTERVAL);
+        let tx = pool_state.write().transactions.pop().unwrap();
+        let tx = TestExtrinsic::decode(&mut &*tx).unwrap();
+        assert_eq!(tx.signature.unwrap().0, 0);
+        assert_eq!(
+            tx.call,
+            RuntimeCall::MaintainCommittee(crate::Call::submit_liveness_attestation {
+                rent_id: 0,
+                round: PROBE_INTERVAL,
+                is_online: true
+            })
+        );
+
+        ProbeResult::set(Some(false));
+        run_to_block(2 * PROBE_INTERVAL - 1);
+        MaintainCommittee::offchain_worker(2 * PROBE_INTERVAL);
+        let tx = pool_state.write().transactions.pop().unwrap();
+        let tx = TestExtrinsic::decode(&mut &*tx).unwrap();
+        assert_eq!(
+            tx.call,
+            RuntimeCall::MaintainCommittee(crate::Call::submit_liveness_attestation {
+                rent_id: 0,
+                round: 2 * PROBE_INTERVAL,
+                is_online: false
+            })
+        );
+
+        // 无法探测的地址不提交探测结果
+        ProbeResult::set(None);
+        run_to_block(3 * PROBE_INTERVAL - 1);
+        MaintainCommittee::offchain_worker(3 * PROBE_INTERVAL);
+        assert!(pool_state.read().transactions.is_empty());
+    });
+}
+
+// 节点不探测回环、内网及链路本地地址，即使该地址上有服务在监听
+#[test]
+fn tcp_probe_skips_non_public_address() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+
+    assert_eq!(TcpProbe::probe("127.0.0.1".as_bytes(), port), None);
+    assert_eq!(TcpProbe::probe("localhost".as_bytes(), port), None);
+    assert_eq!(TcpProbe::probe("10.0.0.1".as_bytes(), port), None);
+    assert_eq!(TcpProbe::probe("192.168.1.1".as_bytes(), port), None);
+    assert_eq!(TcpProbe::probe("169.254.169.254".as_bytes(), 80), None);
+    assert_eq!(TcpProbe::probe("::1".as_bytes(), port), None);
+    assert_eq!(TcpProbe::probe("fe80::1".as_bytes(), port), None);
+    assert_eq!(TcpProbe::probe("fd00::1".as_bytes(), port), None);
+}
@@ -1,6 +1,6 @@
-use super::super::{mock::*, ReporterStakeInfo};
+use super::super::{mock::*, Error, ReporterStakeInfo};
 use dbc_support::{report::ReportStatus, ONE_DAY, ONE_HOUR};
-use frame_support::assert_ok;
+use frame_support::{assert_noop, assert_ok};
 use once_cell::sync::Lazy;
 use std::convert::TryInto;
 
@@ -71,6 +71,7 @@ fn report_machine_fault_works_case1() {
             support_committee: vec![],
             against_committee: vec![],
             report_status: ReportStatus::default(),
+            verifier_count: 3,
         };
         {
             assert_eq!(MaintainCommittee::report_info(0), Some(report_status.clone()));
@@ -350,6 +351,7 @@ fn report_machine_fault_works_case2() {
             support_committee: vec![],
             against_committee: vec![],
             report_status: ReportStatus::default(),
+            verifier_count: 3,
         };
         {
             assert_eq!(MaintainCommittee::report_info(0), Some(report_status.clone()));
@@ -684,6 +686,7 @@ fn report_machine_fault_works_case3() {
             support_committee: vec![],
             against_committee: vec![],
             report_status: ReportStatus::default(),
+            verifier_count: 3,
         };
         {
             assert_eq!(MaintainCommittee::report_info(0), Some(report_status.clone()));
@@ -1017,6 +1020,7 @@ fn report_machine_fault_works_case4() {
             support_committee: vec![],
             against_committee: vec![],
             report_status: ReportStatus::default(),
+            verifier_count: 3,
         };
         {
             assert_eq!(MaintainCommittee::report_info(0), Some(report_status.clone()));
@@ -1168,3 +1172,66 @@ fn report_machine_fault_works_case4() {
         }
     })
 }
+
+// 报告人可以使用委员会当前的公钥，或宽限期内的旧公钥加密故障信息
+#[test]
+fn reporter_add_versioned_encrypted_error_info_works() {
+    new_test_with_init_params_ext().execute_with(|| {
+        let reporter_boxpubkey =
+            hex::decode("1e71b5a83ccdeff1592062a1d4da4a272691f08e2024a1ca75a81d534a76210a")
+                .unwrap()
+                .try_into()
+                .unwrap();
+        let report_hash: [u8; 16] =
+            hex::decode("986fffc16e63d3f7c43fe1a272ba3ba1").unwrap().try_into().unwrap();
+        let encrypted_err_info: Vec<u8> =
+            hex::decode("01405deeef2a8b0f4a09380d14431dd10fde1ad62b3c27b3fbea4701311d")
+                .unwrap()
+                .try_into()
+                .unwrap();
+
+        assert_ok!(MaintainCommittee::report_machine_fault(
+            RuntimeOrigin::signed(*reporter),
+            crate::MachineFaultType::RentedHardwareMalfunction(report_hash, reporter_boxpubkey),
+        ));
+        assert_ok!(MaintainCommittee::committee_book_report(RuntimeOrigin::signed(*committee1), 0));
+        assert_ok!(MaintainCommittee::committee_book_report(RuntimeOrigin::signed(*committee3), 0));
+
+        // committee1更换公钥，旧公钥在宽限期内仍然可用
+        assert_ok!(Committee::committee_set_box_pubkey(
+            RuntimeOrigin::signed(*committee1),
+            [1; 32]
+        ));
+        assert_noop!(
+            MaintainCommittee::reporter_add_versioned_encrypted_error_info(
+                RuntimeOrigin::signed(*reporter),
+                0,
+                *committee1,
+                2,
+                encrypted_err_info.clone()
+            ),
+            Error::<TestRuntime>::InvalidBoxKeyVersion
+        );
+        assert_ok!(MaintainCommittee::reporter_add_versioned_encrypted_error_info(
+            RuntimeOrigin::signed(*reporter),
+            0,
+            *committee1,
+            0,
+            encrypted_err_info.clone()
+        ));
+        assert_eq!(MaintainCommittee::encrypted_info_key_version(&*committee1, 0), 0);
+
+        // 不指定版本时，使用委员会当前的公钥
+        assert_ok!(Committee::committee_set_box_pubkey(
+            RuntimeOrigin::signed(*committee3),
+            [3; 32]
+        ));
+        assert_ok!(MaintainCommittee::reporter_add_encrypted_error_info(
+            RuntimeOrigin::signed(*reporter),
+            0,
+            *committee3,
+            encrypted_err_info
+        ));
+        assert_eq!(MaintainCommittee::encrypted_info_key_version(&*committee3, 0), 1);
+    })
+}
@@ -1,8 +1,11 @@
-use super::super::{mock::*, Error};
+use super::super::{mock::*, Error, Event};
 use crate::{MTOrderStatus, ReportStatus};
 use dbc_support::{
-    live_machine::LiveMachine, machine_type::MachineStatus, verify_slash::OPSlashReason, ONE_DAY,
-    ONE_MINUTE,
+    committee_task::{CommitteeTaskId, CommitteeTaskKind},
+    live_machine::LiveMachine,
+    machine_type::MachineStatus,
+    verify_slash::OPSlashReason,
+    ONE_DAY, ONE_MINUTE,
 };
 use frame_support::{assert_noop, assert_ok};
 use once_cell::sync::Lazy;
@@ -75,7 +78,8 @@ fn report_machine_inaccessible_works1() {
                     confirm_start: 0,
                     confirmed_committee: vec![],
                     support_committee: vec![],
-                    against_committee: vec![]
+                    against_committee: vec![],
+                    verifier_count: 3,
                 })
             );
             assert_eq!(
@@ -123,7 +127,8 @@ fn report_machine_inaccessible_works1() {
                     hashed_committee: vec![],
                     confirmed_committee: vec![],
                     support_committee: vec![],
-                    against_committee: vec![]
+                    against_committee: vec![],
+                    verifier_count: 3,
                 })
             );
             assert_eq!(
@@ -185,7 +190,8 @@ fn report_machine_inaccessible_works1() {
                     get_encrypted_info_committee: vec![],
                     confirmed_committee: vec![],
                     support_committee: vec![],
-                    against_committee: vec![]
+                    against_committee: vec![],
+                    verifier_count: 3,
                 })
             );
             assert_eq!(
@@ -243,7 +249,8 @@ fn report_machine_inaccessible_works1() {
                     rent_order_id: 0,
                     err_info: vec![],
                     get_encrypted_info_committee: vec![],
-                    against_committee: vec![]
+                    against_committee: vec![],
+                    verifier_count: 3,
                 })
             );
             assert_eq!(
@@ -290,7 +297,8 @@ fn report_machine_inaccessible_works1() {
                     rent_order_id: 0,
                     err_info: vec![],
                     get_encrypted_info_committee: vec![],
-                    against_committee: vec![]
+                    against_committee: vec![],
+                    verifier_count: 3,
                 })
             );
             assert_eq!(
@@ -424,7 +432,8 @@ fn report_machine_inaccessible_works2() {
                     rent_order_id: 0,
                     err_info: vec![],
                     get_encrypted_info_committee: vec![],
-                    support_committee: vec![]
+                    support_committee: vec![],
+                    verifier_count: 3,
                 })
             );
             assert_eq!(
@@ -471,7 +480,8 @@ fn report_machine_inaccessible_works2() {
                     rent_order_id: 0,
                     err_info: vec![],
                     get_encrypted_info_committee: vec![],
-                    support_committee: vec![]
+                    support_committee: vec![],
+                    verifier_count: 3,
                 })
             );
             assert_eq!(
@@ -586,7 +596,8 @@ fn report_machine_inaccessible_works3() {
                     get_encrypted_info_committee: vec![],
                     confirmed_committee: vec![],
                     support_committee: vec![],
-                    against_committee: vec![]
+                    against_committee: vec![],
+                    verifier_count: 3,
                 })
             );
             assert_eq!(
@@ -668,7 +679,8 @@ fn report_machine_inaccessible_works3() {
                     confirm_start: 0,
                     confirmed_committee: vec![],
                     support_committee: vec![],
-                    against_committee: vec![]
+                    against_committee: vec![],
+                    verifier_count: 3,
                 })
             );
             assert_eq!(
@@ -733,7 +745,8 @@ fn report_machine_inaccessible_works4() {
                     hashed_committee: vec![],
                     confirmed_committee: vec![],
                     support_committee: vec![],
-                    against_committee: vec![]
+                    against_committee: vec![],
+                    verifier_count: 3,
                 })
             );
             assert_eq!(
@@ -840,7 +853,8 @@ fn report_machine_inaccessible_works5() {
                     hashed_committee: vec![],
                     confirmed_committee: vec![],
                     support_committee: vec![],
-                    against_committee: vec![]
+                    against_committee: vec![],
+                    verifier_count: 3,
                 })
             );
             assert_eq!(
@@ -940,7 +954,8 @@ fn report_machine_inaccessible_works5() {
                     get_encrypted_info_committee: vec![],
                     confirmed_committee: vec![],
                     support_committee: vec![],
-                    against_committee: vec![]
+                    against_committee: vec![],
+                    verifier_count: 3,
                 })
             );
             assert_eq!(
@@ -1012,7 +1027,8 @@ fn report_machine_inaccessible_works5() {
                     rent_order_id: 0,
                     err_info: vec![],
                     get_encrypted_info_committee: vec![],
-                    against_committee: vec![]
+                    against_committee: vec![],
+                    verifier_count: 3,
                 })
             );
             assert_eq!(
@@ -1064,7 +1080,8 @@ fn report_machine_inaccessible_works5() {
                     rent_order_id: 0,
                     err_info: vec![],
                     get_encrypted_info_committee: vec![],
-                    against_committee: vec![]
+                    against_committee: vec![],
+                    verifier_count: 3,
                 })
             );
             assert_eq!(
@@ -1217,7 +1234,8 @@ fn report_machine_inaccessible_works8() {
                     rent_order_id: 0,
                     err_info: vec![],
                     get_encrypted_info_committee: vec![],
-                    support_committee: vec![]
+                    support_committee: vec![],
+                    verifier_count: 3,
                 })
             );
             assert_eq!(
@@ -1265,7 +1283,8 @@ fn report_machine_inaccessible_works8() {
                     rent_order_id: 0,
                     err_info: vec![],
                     get_encrypted_info_committee: vec![],
-                    support_committee: vec![]
+                    support_committee: vec![],
+                    verifier_count: 3,
                 })
             );
             assert_eq!(
@@ -1322,3 +1341,46 @@ fn report_machine_inaccessible_works8() {
         // TODO: 机器在举报成功后会立即被下线
     })
 }
+
+// 无法访问的报告：提交Hash及提交原始信息的时间窗口均为5分钟，
+// 在剩余时间为时间窗口的50%及10%时提醒委员会
+#[test]
+fn remind_inaccessible_report_deadline_works() {
+    new_test_with_init_params_ext().execute_with(|| {
+        assert_ok!(MaintainCommittee::report_machine_fault(
+            RuntimeOrigin::signed(*reporter),
+            crate::MachineFaultType::RentedInaccessible(machine_id.clone(), 0),
+        ));
+        assert_ok!(MaintainCommittee::committee_book_report(RuntimeOrigin::signed(*committee), 0));
+
+        let reminder = vec![(*committee, CommitteeTaskId::Report(0))];
+        for block in [36, 56, 86, 106] {
+            assert_eq!(MaintainCommittee::deadline_reminder(block), reminder);
+        }
+
+        let deadline_events = || -> Vec<_> {
+            System::events()
+                .into_iter()
+                .filter_map(|record| match record.event {
+                    RuntimeEvent::MaintainCommittee(Event::TaskDeadlineApproaching(
+                        who,
+                        CommitteeTaskKind::FaultReport,
+                        CommitteeTaskId::Report(0),
+                        deadline,
+                    )) => Some((who, deadline)),
+                    _ => None,
+                })
+                .collect()
+        };
+
+        run_to_block(35);
+        assert!(deadline_events().is_empty());
+
+        run_to_block(36);
+        assert_eq!(deadline_events(), vec![(*committee, 11 + 5 * ONE_MINUTE)]);
+        assert!(MaintainCommittee::deadline_reminder(36).is_empty());
+
+        run_to_block(56);
+        assert_eq!(deadline_events().len(), 2);
+    })
+}
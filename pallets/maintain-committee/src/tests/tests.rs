@@ -79,7 +79,8 @@ fn test_heart_beat1() {
                 hashed_committee: vec![],
                 confirmed_committee: vec![],
                 support_committee: vec![],
-                against_committee: vec![]
+                against_committee: vec![],
+                verifier_count: 3,
             })
         );
         assert_eq!(
@@ -195,7 +196,8 @@ fn test_heart_beat2() {
                 hashed_committee: vec![],
                 confirmed_committee: vec![],
                 support_committee: vec![],
-                against_committee: vec![]
+                against_committee: vec![],
+                verifier_count: 3,
             })
         );
 
@@ -337,7 +339,8 @@ fn test_heart_beat3() {
                 verifying_committee: None,
                 confirmed_committee: vec![],
                 support_committee: vec![],
-                against_committee: vec![]
+                against_committee: vec![],
+                verifier_count: 3,
             })
         );
     })
@@ -408,7 +411,8 @@ fn test_apply_slash_review() {
                 hashed_committee: vec![],
                 confirmed_committee: vec![],
                 support_committee: vec![],
-                against_committee: vec![]
+                against_committee: vec![],
+                verifier_count: 3,
             })
         );
         assert_eq!(
@@ -491,6 +495,7 @@ fn test_apply_slash_review() {
                     report_hash,
                     reporter_boxpubkey
                 ),
+                verifier_count: 3,
             })
         );
         // check report report result
@@ -1,13 +1,20 @@
+#[cfg(feature = "std")]
+use dbc_support::rpc_types::serde_text;
 use parity_scale_codec::{Decode, Encode};
 use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
 use sp_runtime::RuntimeDebug;
 use sp_std::vec::Vec;
 
 #[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
 pub struct MTPendingSlashReviewInfo<AccountId, Balance, BlockNumber> {
     pub applicant: AccountId,
     pub staked_amount: Balance,
     pub apply_time: BlockNumber,
     pub expire_time: BlockNumber,
+    #[cfg_attr(feature = "std", serde(with = "serde_text"))]
     pub reason: Vec<u8>,
 }
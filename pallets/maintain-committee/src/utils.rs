@@ -1,6 +1,6 @@
 use crate::{BalanceOf, Config, Error, NextReportId, Pallet, ReporterStake, UnhandledReportResult};
 use dbc_support::{
-    report::{MTReportInfoDetail, ReportResultType},
+    report::{MTReportInfoDetail, MTReportResultInfo, ReportResultType},
     traits::{GNOps, ManageCommittee},
     ItemList, ReportHash, ReportId,
 };
@@ -13,6 +13,11 @@ impl<T: Config> Pallet<T> {
         <T as Config>::ManageCommittee::stake_per_order().ok_or(Error::<T>::GetStakeAmountFailed)
     }
 
+    // 审核报告的委员会数量
+    pub fn get_verifier_count() -> u32 {
+        <T as Config>::ManageCommittee::verifier_count()
+    }
+
     pub fn is_valid_committee(who: &T::AccountId) -> DispatchResultWithPostInfo {
         ensure!(<T as Config>::ManageCommittee::is_valid_committee(who), Error::<T>::NotCommittee);
         Ok(().into())
@@ -71,6 +76,17 @@ impl<T: Config> Pallet<T> {
         });
     }
 
+    // 报告处理完成后更新委员会信誉
+    pub fn update_report_reputation(
+        report_result: &MTReportResultInfo<T::AccountId, T::BlockNumber, BalanceOf<T>>,
+    ) {
+        <T as Config>::ManageCommittee::update_reputation(
+            &report_result.reward_committee,
+            &report_result.inconsistent_committee,
+            &report_result.unruly_committee,
+        );
+    }
+
     // 各种报告类型，都需要质押 1000 DBC
     // 如果是第一次绑定，则需要质押2w DBC，其他情况:
     pub fn pay_stake_when_report(reporter: T::AccountId) -> DispatchResultWithPostInfo {
@@ -96,6 +112,20 @@ impl<T: Config> Pallet<T> {
         })
     }
 
+    // 已经质押的报告人，剩余质押是否足够再提交一次报告
+    pub fn is_reporter_stake_enough(reporter: &T::AccountId) -> bool {
+        let stake_params = match Self::reporter_stake_params() {
+            Some(stake_params) => stake_params,
+            None => return false,
+        };
+        let reporter_stake = Self::reporter_stake(reporter);
+        let used_stake = reporter_stake.used_stake.saturating_add(stake_params.stake_per_report);
+
+        !reporter_stake.staked_amount.is_zero() &&
+            reporter_stake.staked_amount.saturating_sub(used_stake) >=
+                stake_params.min_free_stake_percent * reporter_stake.staked_amount
+    }
+
     // - Writes:
     // if is_slash: used_stake, total_stake
     // else:        used_stake
@@ -130,12 +160,7 @@ impl<T: Config> Pallet<T> {
     ) -> Result<(), ()> {
         for a_committee in committee_list {
             if is_slash {
-                <T as Config>::ManageCommittee::change_total_stake(
-                    a_committee.clone(),
-                    amount,
-                    false,
-                    false,
-                )?;
+                <T as Config>::ManageCommittee::slash_stake(a_committee.clone(), amount)?;
             }
 
             <T as Config>::ManageCommittee::change_used_stake(a_committee, amount, false)?;
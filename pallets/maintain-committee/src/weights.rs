@@ -0,0 +1,40 @@
+//! Weights for maintain_committee
+//!
+//! 权重按调用时读写的存储项数量估算
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{
+    traits::Get,
+    weights::{constants::RocksDbWeight, Weight},
+};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for maintain_committee.
+pub trait WeightInfo {
+    fn submit_liveness_attestation() -> Weight;
+}
+
+/// Weights for maintain_committee using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+    // Storage: Committee(2), RentInfo, RentOrderEndpoint, ReporterReport(2), ReportInfo(2),
+    //   ReporterStakeParams, ReporterStake, System.Account, RentLiveness, VerifierCount,
+    //   InaccessibleAttesters, MachinesInfo, OfflineMachine2renters, NextReportId, LiveReport
+    // 按达到离线数量后自动报告的情况估算
+    fn submit_liveness_attestation() -> Weight {
+        Weight::from_parts(10000, 0)
+            .saturating_add(T::DbWeight::get().reads(18))
+            .saturating_add(T::DbWeight::get().writes(9))
+    }
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+    fn submit_liveness_attestation() -> Weight {
+        Weight::from_parts(10000, 0)
+            .saturating_add(RocksDbWeight::get().reads(18))
+            .saturating_add(RocksDbWeight::get().writes(9))
+    }
+}
@@ -16,13 +16,15 @@ mod tests;
 mod utils;
 
 use dbc_support::{
+    committee_task::{CommitteeTaskId, CommitteeTaskKind},
     machine_type::CommitteeUploadInfo,
     traits::{GNOps, ManageCommittee, OCOps},
     utils::OnlineCommitteeSummary,
     verify_committee_slash::{OCPendingSlashInfo, OCSlashResult},
     verify_online::{
         OCBookResultType, OCCommitteeMachineList, OCCommitteeOps, OCMachineCommitteeList,
-        OCMachineStatus, OCVerifyStatus, Summary, VerifyResult, VerifySequence, SUBMIT_RAW_START,
+        OCMachineStatus, OCVerifyStatus, SelectionProof, Summary, VerifyResult, VerifySequence,
+        SUBMIT_RAW_START,
     },
     ItemList, MachineId, SlashId, TWO_DAYS,
 };
@@ -71,15 +73,16 @@ pub mod pallet {
 
     #[pallet::hooks]
     impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
-        fn on_initialize(_n: BlockNumberFor<T>) -> frame_support::weights::Weight {
+        fn on_initialize(n: BlockNumberFor<T>) -> frame_support::weights::Weight {
             Self::check_and_exec_pending_review();
             Self::check_and_exec_pending_slash();
-            Weight::zero()
+            Self::deadline_reminder_weight(n)
         }
 
-        fn on_finalize(_block_number: T::BlockNumber) {
+        fn on_finalize(block_number: T::BlockNumber) {
             Self::statistic_result();
             Self::distribute_machines();
+            Self::remind_task_deadline(block_number);
         }
 
         // fn on_runtime_upgrade() -> frame_support::weights::Weight {
@@ -106,6 +109,12 @@ pub mod pallet {
         ValueQuery,
     >;
 
+    /// 机器最近一次分派委员会的随机抽取证明
+    #[pallet::storage]
+    #[pallet::getter(fn machine_selection_proof)]
+    pub type MachineSelectionProof<T: Config> =
+        StorageMap<_, Blake2_128Concat, MachineId, SelectionProof<T::AccountId, T::BlockNumber>>;
+
     #[pallet::storage]
     #[pallet::getter(fn machine_submited_hash)]
     pub(super) type MachineSubmitedHash<T> =
@@ -149,6 +158,17 @@ pub mod pallet {
     #[pallet::getter(fn unhandled_slash)]
     pub(super) type UnhandledSlash<T: Config> = StorageValue<_, Vec<SlashId>, ValueQuery>;
 
+    /// 委员会任务的截止提醒：块高 -> 在该块需要提醒的委员会及任务
+    #[pallet::storage]
+    #[pallet::getter(fn deadline_reminder)]
+    pub(super) type DeadlineReminder<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::BlockNumber,
+        Vec<(T::AccountId, CommitteeTaskId)>,
+        ValueQuery,
+    >;
+
     #[pallet::call]
     impl<T: Config> Pallet<T> {
         #[pallet::call_index(0)]
@@ -303,6 +323,7 @@ pub mod pallet {
         AddConfirmHash(T::AccountId, [u8; 16]),
         AddConfirmRaw(T::AccountId, MachineId),
         MachineDistributed(MachineId, T::AccountId),
+        TaskDeadlineApproaching(T::AccountId, CommitteeTaskKind, CommitteeTaskId, T::BlockNumber),
     }
 
     #[pallet::error]
@@ -339,7 +360,8 @@ impl<T: Config> Pallet<T> {
                 MachineCommittee::<T>::remove(&machine_id);
             }
 
-            if let Some(committee_work_index) = Self::get_work_index() {
+            if let Some((committee_work_index, proof)) = Self::get_work_index(&machine_id) {
+                MachineSelectionProof::<T>::insert(&machine_id, proof);
                 for work_index in committee_work_index {
                     let _ = Self::book_one(machine_id.to_vec(), confirm_start, now, work_index);
                 }
@@ -349,25 +371,40 @@ impl<T: Config> Pallet<T> {
         }
     }
 
-    // 分派一个machineId给随机的委员会
-    // 返回3个随机顺序的账户及其对应的验证顺序
-    pub fn get_work_index() -> Option<Vec<VerifySequence<T::AccountId>>> {
-        let mut committee = <committee::Pallet<T>>::available_committee()?;
-        if committee.len() < 3 {
+    // 分派一个machineId给随机的委员会，质押越多、信誉越好的委员会越容易被选中
+    // 返回随机顺序的账户及其对应的验证顺序，以及可以复现分派过程的证明
+    pub fn get_work_index(
+        machine_id: &MachineId,
+    ) -> Option<(Vec<VerifySequence<T::AccountId>>, SelectionProof<T::AccountId, T::BlockNumber>)>
+    {
+        let committee = <committee::Pallet<T>>::available_committee()?;
+        // 可用委员会不足审核数量时不分派，机器等待之后重新分派
+        let verifier_count = T::ManageCommittee::verifier_count() as usize;
+        if committee.len() < verifier_count {
             return None
-        };
-
-        let mut verify_sequence = Vec::new();
-        for i in 0..3 {
-            let lucky_index =
-                <generic_func::Pallet<T>>::random_u32(committee.len() as u32) as usize;
-            verify_sequence.push(VerifySequence {
-                who: committee[lucky_index].clone(),
-                index: (i..DISTRIBUTION as usize).step_by(3).collect(),
-            });
-            committee.remove(lucky_index);
         }
-        Some(verify_sequence)
+
+        // 按 质押倍数 × 信誉分 加权抽取
+        let weights: Vec<_> = committee.iter().map(T::ManageCommittee::selection_weight).collect();
+        let proof = <generic_func::Pallet<T>>::random_select(
+            machine_id,
+            &committee,
+            &weights,
+            verifier_count,
+        );
+        let verify_sequence = proof
+            .selected
+            .iter()
+            .enumerate()
+            .map(|(i, who)| VerifySequence {
+                who: who.clone(),
+                // 提交原始值的时段轮流分给各委员会
+                index: (0..DISTRIBUTION as usize)
+                    .filter(|slot| slot % verifier_count == i)
+                    .collect(),
+            })
+            .collect();
+        Some((verify_sequence, proof))
     }
 
     // 一个委员会进行操作
@@ -410,6 +447,11 @@ impl<T: Config> Pallet<T> {
             committee_ops.machine_status = OCMachineStatus::Booked;
         });
 
+        Self::schedule_task_reminders(
+            work_index.who.clone(),
+            CommitteeTaskId::Machine(machine_id.clone()),
+            now,
+        );
         Self::deposit_event(Event::MachineDistributed(machine_id.to_vec(), work_index.who));
         Ok(())
     }
@@ -425,6 +467,58 @@ impl<T: Config> Pallet<T> {
         }
     }
 
+    // 分派任务时，按提交Hash及原始信息的时间窗口安排截止提醒
+    fn schedule_task_reminders(
+        committee: T::AccountId,
+        task_id: CommitteeTaskId,
+        now: T::BlockNumber,
+    ) {
+        let reminders = <T as Config>::ManageCommittee::deadline_reminders();
+        if reminders.is_empty() {
+            return
+        }
+        let task = match Self::get_committee_tasks(committee.clone())
+            .into_iter()
+            .find(|task| task.task_id == task_id)
+        {
+            Some(task) => task,
+            None => return,
+        };
+
+        for block in task.reminder_blocks(now, &reminders) {
+            DeadlineReminder::<T>::mutate(block, |reminder| {
+                ItemList::add_item(reminder, (committee.clone(), task_id.clone()));
+            });
+        }
+    }
+
+    fn deadline_reminder_weight(now: T::BlockNumber) -> Weight {
+        let count = DeadlineReminder::<T>::decode_len(now).unwrap_or_default() as u64;
+        // 每个提醒：读取委员会的任务列表及任务信息
+        T::DbWeight::get().reads_writes(1 + count * 2, 1)
+    }
+
+    // 委员会提交Hash/原始信息的截止时间临近时，发出提醒事件
+    fn remind_task_deadline(now: T::BlockNumber) {
+        for (committee, task_id) in DeadlineReminder::<T>::take(now) {
+            let task = match Self::get_committee_tasks(committee.clone())
+                .into_iter()
+                .find(|task| task.task_id == task_id)
+            {
+                Some(task) => task,
+                None => continue,
+            };
+            if let Some(deadline) = task.reminded_deadline(now) {
+                Self::deposit_event(Event::TaskDeadlineApproaching(
+                    committee,
+                    task.task_kind,
+                    task.task_id,
+                    deadline,
+                ));
+            }
+        }
+    }
+
     // 对已经提交完原始值的机器进行处理
     fn summary_raw(
         machine_id: MachineId,
@@ -479,6 +573,17 @@ impl<T: Config> Pallet<T> {
             },
         }
 
+        // 更新委员会信誉：无共识时无法判断委员会提交的对错，只扣除未完成工作的委员会信誉
+        if matches!(summary.verify_result, VerifyResult::NoConsensus) {
+            <T as Config>::ManageCommittee::update_reputation(&[], &[], &summary.unruly);
+        } else {
+            <T as Config>::ManageCommittee::update_reputation(
+                &summary.valid_vote,
+                &summary.invalid_vote,
+                &summary.unruly,
+            );
+        }
+
         // NOTE: 添加惩罚
         if stash_slash.is_some() || summary.should_slash_committee() {
             let (machine_stash, stash_slash_amount) = if let Some(tmp) = stash_slash {
@@ -638,11 +743,9 @@ impl<T: Config> Pallet<T> {
         }
         // 如果委员会应该被惩罚，则减少其total_stake和used_stake
         for a_committee in should_slash {
-            let _ = Self::change_committee_total_stake(
+            let _ = <T as Config>::ManageCommittee::slash_stake(
                 a_committee.clone(),
                 committee_order_stake,
-                false,
-                false,
             );
             let _ = Self::change_committee_used_stake(a_committee, committee_order_stake, false);
         }
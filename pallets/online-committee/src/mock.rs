@@ -4,7 +4,7 @@ use dbc_support::{
     machine_type::{
         CommitteeUploadInfo, Latitude, Longitude, StakerCustomizeInfo, StandardGpuPointPrice,
     },
-    ONE_DAY,
+    EraIndex, ONE_DAY,
 };
 use frame_support::{
     assert_ok,
@@ -201,10 +201,16 @@ impl pallet_timestamp::Config for TestRuntime {
     type WeightInfo = ();
 }
 
+parameter_types! {
+    pub CommitteeCurrentEra: EraIndex = OnlineProfile::current_era();
+}
+
 impl committee::Config for TestRuntime {
     type Currency = Balances;
     type RuntimeEvent = RuntimeEvent;
-    // type WeightInfo = ();
+    type SlashAndReward = GenericFunc;
+    type CurrentEra = CommitteeCurrentEra;
+    type WeightInfo = ();
 }
 
 impl online_committee::Config for TestRuntime {
@@ -214,7 +220,7 @@ impl online_committee::Config for TestRuntime {
     type ManageCommittee = Committee;
     type CancelSlashOrigin =
         pallet_collective::EnsureProportionAtLeast<Self::AccountId, TechnicalCollective, 2, 3>;
-    type SlashAndReward = GenericFunc;
+    type SlashAndReward = Committee;
 }
 
 parameter_types! {
@@ -231,7 +237,8 @@ impl online_profile::Config for TestRuntime {
     type Slash = Treasury;
     type CancelSlashOrigin =
         pallet_collective::EnsureProportionAtLeast<Self::AccountId, TechnicalCollective, 2, 3>;
-    type SlashAndReward = GenericFunc;
+    type SlashAndReward = Committee;
+    type WeightInfo = ();
 }
 
 #[allow(dead_code)]
@@ -1,5 +1,6 @@
 use crate::{BalanceOf, CommitteeUploadInfo, Config, Pallet};
 use dbc_support::{
+    committee_task::{CommitteeTask, CommitteeTaskId, CommitteeTaskKind},
     verify_online::{OCCommitteeMachineList, OCMachineCommitteeList, OCMachineStatus},
     MachineId,
 };
@@ -57,4 +58,26 @@ impl<T: Config> Pallet<T> {
             machine_info: oc_committee_ops.machine_info,
         })
     }
+
+    /// 获取委员会未完成的审核任务：未提交Hash及未提交原始信息的机器
+    pub fn get_committee_tasks(committee: T::AccountId) -> Vec<CommitteeTask<T::BlockNumber>> {
+        let committee_machine = Self::committee_machine(committee);
+
+        let booked = committee_machine.booked_machine.into_iter().map(|id| (id, false));
+        let hashed = committee_machine.hashed_machine.into_iter().map(|id| (id, true));
+
+        booked
+            .chain(hashed)
+            .map(|(machine_id, hash_submitted)| {
+                let machine_committee = Self::machine_committee(&machine_id);
+                CommitteeTask {
+                    task_kind: CommitteeTaskKind::OnlineVerify,
+                    task_id: CommitteeTaskId::Machine(machine_id),
+                    hash_submitted,
+                    hash_deadline: machine_committee.hash_deadline(),
+                    raw_deadline: machine_committee.raw_deadline(),
+                }
+            })
+            .collect()
+    }
 }
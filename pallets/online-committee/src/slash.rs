@@ -51,11 +51,20 @@ impl<T: Config> Pallet<T> {
             }
         } else {
             // applicant is slashed_committee
-            Self::change_committee_stake(
-                vec![review_info.applicant.clone()],
+            // 没收申请复审时的质押，由委员会自己承担，不分给委托人
+            Self::change_committee_total_stake(
+                review_info.applicant.clone(),
                 review_info.staked_amount,
-                true,
-            )?;
+                false,
+                false,
+            )
+            .map_err(|_| ())?;
+            Self::change_committee_used_stake(
+                review_info.applicant.clone(),
+                review_info.staked_amount,
+                false,
+            )
+            .map_err(|_| ())?;
         }
 
         // Slash applicant to treasury
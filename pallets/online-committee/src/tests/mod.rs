@@ -1,5 +1,7 @@
+pub mod test_deadline_reminder;
 pub mod test_fulfill_machine;
 pub mod test_generic_destroy;
+pub mod test_heartbeat_offline;
 pub mod test_online_failed;
 pub mod test_staker_report_offline;
 pub mod test_summary;
@@ -0,0 +1,73 @@
+use super::super::{mock::*, Event};
+use crate::tests::{committee1, committee3, committee4};
+use dbc_support::{
+    committee_task::{CommitteeTaskId, CommitteeTaskKind},
+    ONE_HOUR,
+};
+use frame_support::assert_ok;
+
+fn deadline_events() -> Vec<(sr25519::Public, CommitteeTaskId, BlockNumber)> {
+    System::events()
+        .into_iter()
+        .filter_map(|record| match record.event {
+            RuntimeEvent::OnlineCommittee(Event::TaskDeadlineApproaching(
+                committee,
+                CommitteeTaskKind::OnlineVerify,
+                task_id,
+                deadline,
+            )) => Some((committee, task_id, deadline)),
+            _ => None,
+        })
+        .collect()
+}
+
+// 默认在提交Hash/原始信息的剩余时间为各自时间窗口的50%及10%时发出提醒：
+// 提交Hash的时间窗口为36小时，提交原始信息的时间窗口为12小时
+#[test]
+fn remind_task_deadline_works() {
+    new_test_with_online_machine_distribution().execute_with(|| {
+        let machine_id = "8eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a48"
+            .as_bytes()
+            .to_vec();
+        let machine_committee = OnlineCommittee::machine_committee(&machine_id);
+        let hash_deadline = machine_committee.hash_deadline();
+        let raw_deadline = machine_committee.raw_deadline();
+        let task_id = CommitteeTaskId::Machine(machine_id.clone());
+
+        assert_ok!(OnlineCommittee::submit_confirm_hash(
+            RuntimeOrigin::signed(*committee1),
+            machine_id.clone(),
+            [0; 16]
+        ));
+        assert!(OnlineCommittee::get_committee_tasks(*committee1)[0].hash_submitted);
+
+        run_to_block(hash_deadline - 18 * ONE_HOUR - 1);
+        assert!(deadline_events().is_empty());
+
+        // 未提交Hash的委员会收到提醒，已提交Hash的委员会不再收到提交Hash的提醒
+        run_to_block(hash_deadline - 18 * ONE_HOUR);
+        let mut hash_reminders: Vec<_> = [*committee3, *committee4]
+            .into_iter()
+            .map(|committee| (committee, task_id.clone(), hash_deadline))
+            .collect();
+        let mut events = deadline_events();
+        hash_reminders.sort();
+        events.sort();
+        assert_eq!(events, hash_reminders);
+
+        run_to_block(hash_deadline - 36 * ONE_HOUR / 10);
+        assert_eq!(deadline_events().len(), 4);
+
+        // 已提交Hash的委员会在提交原始信息截止前收到提醒，每个提醒只发出一次
+        run_to_block(raw_deadline - 6 * ONE_HOUR);
+        let raw_reminder = (*committee1, task_id, raw_deadline);
+        assert_eq!(deadline_events().len(), 5);
+        assert_eq!(deadline_events()[4], raw_reminder);
+
+        run_to_block(raw_deadline - 12 * ONE_HOUR / 10);
+        let events = deadline_events();
+        assert_eq!(events.len(), 6);
+        assert_eq!(events.iter().filter(|event| **event == raw_reminder).count(), 2);
+        assert!(OnlineCommittee::deadline_reminder(raw_deadline - 12 * ONE_HOUR / 10).is_empty());
+    })
+}
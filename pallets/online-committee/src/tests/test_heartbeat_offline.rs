@@ -0,0 +1,82 @@
+use crate::{
+    mock::*,
+    tests::{controller, stash},
+};
+use dbc_support::{
+    traits::{HBOps, MTOps},
+    verify_slash::{OPPendingSlashInfo, OPSlashReason},
+    ONE_DAY, ONE_MINUTE,
+};
+use frame_support::assert_ok;
+use sp_runtime::Perbill;
+use std::collections::BTreeMap;
+
+// 错过心跳只扣除当时Era的得分，不影响之后的Era
+#[test]
+fn heartbeat_grade_penalty_only_affects_current_era() {
+    new_test_with_machine_online().execute_with(|| {
+        let machine_id = "8eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a48"
+            .as_bytes()
+            .to_vec();
+
+        run_to_block(ONE_DAY + 2);
+        assert_ok!(<OnlineProfile as HBOps>::hb_reduce_machine_grade(
+            &machine_id,
+            Perbill::from_percent(10)
+        ));
+
+        let mut grade_penalty = BTreeMap::new();
+        grade_penalty.insert(machine_id.clone(), Perbill::from_percent(10));
+        assert_eq!(OnlineProfile::eras_machine_grade_penalty(2), grade_penalty);
+        // 机器的得分快照不变
+        assert_eq!(OnlineProfile::eras_machine_points(2)[&machine_id].basic_grade, 59890);
+        assert_eq!(OnlineProfile::eras_machine_points(3)[&machine_id].basic_grade, 59890);
+
+        // 扣分的Era奖励减少，之后的Era不受影响；奖励发放后移除扣分记录
+        run_to_block(3 * ONE_DAY + 2);
+        assert!(
+            OnlineProfile::eras_machine_reward(2, &machine_id) <
+                OnlineProfile::eras_machine_reward(3, &machine_id)
+        );
+        assert_eq!(OnlineProfile::eras_machine_grade_penalty(2), BTreeMap::new());
+    })
+}
+
+// 心跳超时下线没有举报人，重新上线时与算工主动下线一样惩罚
+#[test]
+fn heartbeat_offline_has_no_reporter() {
+    new_test_with_machine_online().execute_with(|| {
+        let machine_id = "8eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a48"
+            .as_bytes()
+            .to_vec();
+
+        assert_ok!(<OnlineProfile as MTOps>::mt_machine_offline(
+            *stash,
+            vec![],
+            machine_id.clone(),
+            OPSlashReason::HeartbeatOffline(13),
+        ));
+
+        run_to_block(11 + 5 * ONE_MINUTE);
+        assert_ok!(OnlineProfile::controller_report_online(
+            RuntimeOrigin::signed(*controller),
+            machine_id.clone()
+        ));
+
+        // 4000*2%=80
+        assert_eq!(
+            OnlineProfile::pending_slash(0),
+            Some(OPPendingSlashInfo {
+                slash_who: *stash,
+                machine_id,
+                slash_time: 12 + 5 * ONE_MINUTE,
+                slash_amount: 80 * ONE_DBC,
+                slash_exec_time: 12 + 5 * ONE_MINUTE + 2 * ONE_DAY,
+                reporter: None,
+                renters: vec![],
+                reward_to_committee: None,
+                slash_reason: OPSlashReason::HeartbeatOffline(13),
+            })
+        );
+    })
+}
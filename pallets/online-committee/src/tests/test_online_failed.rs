@@ -61,7 +61,7 @@ fn test_machine_online_refused_after_some_online() {
         let machine_info_hash3: [u8; 16] =
             hex::decode("5425dd7deff26254321b6682a92254db").unwrap().try_into().unwrap();
         assert_ok!(OnlineCommittee::submit_confirm_hash(
-            RuntimeOrigin::signed(*committee3),
+            RuntimeOrigin::signed(*committee4),
             machine_id2.clone(),
             machine_info_hash1
         ));
@@ -71,7 +71,7 @@ fn test_machine_online_refused_after_some_online() {
             machine_info_hash2
         ));
         assert_ok!(OnlineCommittee::submit_confirm_hash(
-            RuntimeOrigin::signed(*committee4),
+            RuntimeOrigin::signed(*committee1),
             machine_id2.clone(),
             machine_info_hash3
         ));
@@ -96,7 +96,7 @@ fn test_machine_online_refused_after_some_online() {
 
         // 委员会提交原始信息
         assert_ok!(OnlineCommittee::submit_confirm_raw(
-            RuntimeOrigin::signed(*committee3),
+            RuntimeOrigin::signed(*committee4),
             committee_upload_info.clone()
         ));
         committee_upload_info.rand_str = "abcdefg2".as_bytes().to_vec();
@@ -106,7 +106,7 @@ fn test_machine_online_refused_after_some_online() {
         ));
         committee_upload_info.rand_str = "abcdefg3".as_bytes().to_vec();
         assert_ok!(OnlineCommittee::submit_confirm_raw(
-            RuntimeOrigin::signed(*committee4),
+            RuntimeOrigin::signed(*committee1),
             committee_upload_info
         ));
 
@@ -124,7 +124,7 @@ fn test_machine_online_refused_after_some_online() {
                 stash_slash_amount: 50 * ONE_DBC,
                 inconsistent_committee: vec![],
                 unruly_committee: vec![],
-                reward_committee: vec![*committee3, *committee2, *committee4],
+                reward_committee: vec![*committee2, *committee1, *committee4],
                 committee_stake: 1000 * ONE_DBC,
                 slash_time: 14,
                 slash_exec_time: 14 + 2 * ONE_DAY,
@@ -141,7 +141,7 @@ fn test_machine_online_refused_after_some_online() {
         assert!(<PendingSlash<TestRuntime>>::contains_key(0));
 
         assert_eq!(
-            Balances::free_balance(*committee3),
+            Balances::free_balance(*committee1),
             INIT_BALANCE - 20000 * ONE_DBC + Perbill::from_rational(1u32, 3u32) * (50 * ONE_DBC)
         );
     })
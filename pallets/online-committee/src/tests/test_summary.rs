@@ -1,6 +1,6 @@
 use super::super::{mock::*, *};
 use crate::tests::{committee1, committee2, committee3, committee4, stash};
-use committee::CommitteeStakeInfo;
+use committee::{CommitteeSlashInfo, CommitteeStakeInfo};
 use dbc_support::{
     live_machine::LiveMachine, machine_type::CommitteeUploadInfo, ONE_DAY, ONE_HOUR,
 };
@@ -35,8 +35,8 @@ fn get_base_machine_info() -> CommitteeUploadInfo {
 // NOTE: 测试summary函数
 // 当全部提交Hash+全部提交原始值时:
 //
-// case 1_1 只有1个提交信息，是支持
-// case 1_2 只有1个提交信息，是反对
+// case 1_1 只有1个提交信息，是支持，未过半 -> 重新分派
+// case 1_2 只有1个提交信息，是反对，未过半 -> 重新分派
 //
 // case 2_1 2个提交信息，都支持，但内容相同
 // case 2_2 2个提交信息，都支持，但内容不同
@@ -50,8 +50,12 @@ fn get_base_machine_info() -> CommitteeUploadInfo {
 // case 3_5: 2支持，1反对，支持信息不一致。重新分派
 // case 3_6: 1支持，2反对，拒绝上线
 // case 3_7: 3个反对，信息不一致 -> 拒绝上线
+//
+// case 5_1: 5个委员会，3个支持且内容一致，1个内容不一致，1个反对 -> 上线
+// case 5_2: 5个委员会，得票最多的信息有两种 -> 重新分派
+// case 5_3: 5个委员会，得票最多的信息唯一但只有2票，未过半 -> 重新分派
 
-// case 1_1 只有1个提交信息，是支持
+// case 1_1 只有1个提交信息，是支持，未过半 -> 重新分派
 #[test]
 fn test_summary_confirmation1_1() {
     new_test_with_init_params_ext().execute_with(|| {
@@ -60,10 +64,9 @@ fn test_summary_confirmation1_1() {
         let upload_info1 = get_base_machine_info();
 
         let summary_expect1 = Summary {
-            valid_vote: vec![*committee1],
+            invalid_vote: vec![*committee1],
             unruly: vec![*committee3, *committee2],
-            info: Some(upload_info1.clone()),
-            verify_result: VerifyResult::Confirmed,
+            verify_result: VerifyResult::NoConsensus,
             ..Default::default()
         };
 
@@ -83,7 +86,7 @@ fn test_summary_confirmation1_1() {
     })
 }
 
-// case 1_2 只有1个提交信息，是反对
+// case 1_2 只有1个提交信息，是反对，未过半 -> 重新分派
 #[test]
 fn test_summary_confirmation1_2() {
     new_test_with_init_params_ext().execute_with(|| {
@@ -92,9 +95,9 @@ fn test_summary_confirmation1_2() {
         let upload_info1 = CommitteeUploadInfo { is_support: false, ..get_base_machine_info() };
 
         let summary_expect1 = Summary {
-            valid_vote: vec![*committee1],
+            invalid_vote: vec![*committee1],
             unruly: vec![*committee3, *committee2],
-            verify_result: VerifyResult::Refused,
+            verify_result: VerifyResult::NoConsensus,
             ..Default::default()
         };
 
@@ -478,6 +481,108 @@ fn test_summary_confirmation3_7() {
     })
 }
 
+// 5个委员会审核时，按顺序排列的委员会: committee3, committee5, committee2, committee1, committee4
+fn get_five_committee() -> Vec<sp_core::sr25519::Public> {
+    let committee5 = sp_core::sr25519::Public::from(Sr25519Keyring::Bob);
+    vec![*committee3, committee5, *committee2, *committee1, *committee4]
+}
+
+fn get_five_machine_committee() -> OCMachineCommitteeList<sp_core::sr25519::Public, BlockNumber> {
+    OCMachineCommitteeList {
+        book_time: 9,
+        booked_committee: get_five_committee(),
+        hashed_committee: get_five_committee(),
+        confirm_start_time: 5432,
+        confirmed_committee: get_five_committee(),
+        onlined_committee: vec![],
+        status: OCVerifyStatus::Summarizing,
+    }
+}
+
+// case 5_1: 5个委员会，3个支持且内容一致，1个内容不一致，1个反对 -> 上线
+#[test]
+fn test_summary_confirmation5_1() {
+    new_test_with_init_params_ext().execute_with(|| {
+        run_to_block(10);
+
+        let committee = get_five_committee();
+        let upload_info = get_base_machine_info();
+        let upload_info2 = CommitteeUploadInfo { gpu_num: 3, ..upload_info.clone() };
+        let upload_info3 = CommitteeUploadInfo { is_support: false, ..upload_info.clone() };
+
+        let summary_expect = Summary {
+            valid_vote: vec![committee[0], committee[1], committee[4]],
+            invalid_vote: vec![committee[2], committee[3]],
+            info: Some(upload_info.clone()),
+            verify_result: VerifyResult::Confirmed,
+            ..Default::default()
+        };
+
+        let submit_info =
+            vec![upload_info.clone(), upload_info.clone(), upload_info2, upload_info3, upload_info];
+
+        let summary =
+            OnlineCommittee::summary_confirmation(get_five_machine_committee(), submit_info);
+        assert_eq!(summary_expect, summary);
+    })
+}
+
+// case 5_2: 5个委员会，得票最多的信息有两种 -> 重新分派
+#[test]
+fn test_summary_confirmation5_2() {
+    new_test_with_init_params_ext().execute_with(|| {
+        run_to_block(10);
+
+        let upload_info = get_base_machine_info();
+        let upload_info2 = CommitteeUploadInfo { gpu_num: 3, ..upload_info.clone() };
+        let upload_info3 = CommitteeUploadInfo { is_support: false, ..upload_info.clone() };
+
+        let summary_expect = Summary {
+            invalid_vote: get_five_committee(),
+            verify_result: VerifyResult::NoConsensus,
+            ..Default::default()
+        };
+
+        let submit_info = vec![
+            upload_info.clone(),
+            upload_info,
+            upload_info2.clone(),
+            upload_info2,
+            upload_info3,
+        ];
+
+        let summary =
+            OnlineCommittee::summary_confirmation(get_five_machine_committee(), submit_info);
+        assert_eq!(summary_expect, summary);
+    })
+}
+
+// case 5_3: 5个委员会，得票最多的信息唯一但只有2票，未过半 -> 重新分派
+#[test]
+fn test_summary_confirmation5_3() {
+    new_test_with_init_params_ext().execute_with(|| {
+        run_to_block(10);
+
+        let upload_info = get_base_machine_info();
+        let upload_info2 = CommitteeUploadInfo { gpu_num: 3, ..upload_info.clone() };
+        let upload_info3 = CommitteeUploadInfo { gpu_num: 5, ..upload_info.clone() };
+        let upload_info4 = CommitteeUploadInfo { is_support: false, ..upload_info.clone() };
+
+        let summary_expect = Summary {
+            invalid_vote: get_five_committee(),
+            verify_result: VerifyResult::NoConsensus,
+            ..Default::default()
+        };
+
+        let submit_info =
+            vec![upload_info.clone(), upload_info, upload_info2, upload_info3, upload_info4];
+
+        let summary =
+            OnlineCommittee::summary_confirmation(get_five_machine_committee(), submit_info);
+        assert_eq!(summary_expect, summary);
+    })
+}
+
 fn decode_box_pubkey<T: AsRef<[u8]>>(x: T) -> [u8; 32] {
     hex::decode(x).unwrap().try_into().unwrap()
 }
@@ -986,6 +1091,17 @@ fn test_machine_online_succeed_against_committee_apply_review() {
         assert_eq!(Balances::reserved_balance(&*committee4), (20000 + 1000) * ONE_DBC);
 
         assert_ok!(OnlineCommittee::do_cancel_slash(0));
+
+        // 惩罚被取消：申述的委员会没有被惩罚的记录，并获得原本获得奖励的委员会被惩罚的金额
+        assert_eq!(Committee::committee_slash(&*committee4), CommitteeSlashInfo::default());
+        assert_eq!(
+            Committee::committee_slash(&*committee1),
+            CommitteeSlashInfo { slash_count: 1, slashed_amount: 1000 * ONE_DBC }
+        );
+        assert_eq!(
+            Committee::committee_era_reward(&*committee4, OnlineProfile::current_era()),
+            2000 * ONE_DBC
+        );
     })
 }
 
@@ -1061,7 +1177,7 @@ fn test_machine_noconsensus_works() {
             OnlineCommittee::machine_committee(&machine_id),
             OCMachineCommitteeList {
                 book_time: 12 + ONE_DAY + 12 * ONE_HOUR,
-                booked_committee: vec![*committee3, *committee2, *committee4],
+                booked_committee: vec![*committee3, *committee1, *committee4],
                 hashed_committee: vec![],
                 confirm_start_time: 12 + ONE_DAY + 12 * ONE_HOUR + 36 * ONE_HOUR,
                 confirmed_committee: vec![],
@@ -1077,8 +1193,8 @@ fn test_machine_noconsensus_works() {
             },
             &committee::CommitteeStakeInfo {
                 staked_amount: 20000 * ONE_DBC,
-                used_stake: 0 * ONE_DBC, // 没有重新分派给committee1
-                can_claim_reward: 0,     // 1100000 * 0.25 * 0.01 / 2
+                used_stake: 1000 * ONE_DBC, // 重新分派给committee1
+                can_claim_reward: 0,        // 1100000 * 0.25 * 0.01 / 2
                 claimed_reward: 0,
                 ..Default::default()
             }
@@ -1091,8 +1207,8 @@ fn test_machine_noconsensus_works() {
             },
             &committee::CommitteeStakeInfo {
                 staked_amount: 20000 * ONE_DBC,
-                used_stake: 1000 * ONE_DBC, // 重新分派给committee2
-                can_claim_reward: 0,        // 1100000 * 0.25 * 0.01 / 2
+                used_stake: 0 * ONE_DBC, // 没有重新分派给committee2
+                can_claim_reward: 0,     // 1100000 * 0.25 * 0.01 / 2
                 claimed_reward: 0,
                 ..Default::default()
             }
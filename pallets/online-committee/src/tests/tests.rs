@@ -162,8 +162,17 @@ fn machine_online_works() {
                 ..Default::default()
             }
         );
-        // 获取可派单的委员会正常
-        assert_ok!(OnlineCommittee::get_work_index().ok_or(()));
+        // 可用委员会少于审核数量时不派单
+        assert_ok!(Committee::set_verifier_count(RawOrigin::Root.into(), 5));
+        assert!(OnlineCommittee::get_work_index(&machine_id).is_none());
+        assert_ok!(Committee::set_verifier_count(RawOrigin::Root.into(), 3));
+
+        // 获取可派单的委员会正常，每个委员会轮流分到提交原始值的时段
+        let (work_index, _) = OnlineCommittee::get_work_index(&machine_id).unwrap();
+        assert_eq!(work_index.len(), 3);
+        assert_eq!(work_index[0].index, vec![0, 3, 6]);
+        assert_eq!(work_index[1].index, vec![1, 4, 7]);
+        assert_eq!(work_index[2].index, vec![2, 5, 8]);
 
         run_to_block(5);
 
@@ -214,6 +223,18 @@ fn machine_online_works() {
             }
         );
 
+        // 分派过程可以由证明及分派时的候选委员会复现：权重 = 质押倍数(20) × 信誉分(100)
+        let proof = OnlineCommittee::machine_selection_proof(&machine_id).unwrap();
+        let candidates = vec![committee2, committee3, committee1];
+        let weights = vec![2000; 3];
+        assert_eq!(proof.selected_at, 4);
+        assert_eq!(proof.selected, vec![committee3, committee1, committee2]);
+        assert_eq!(proof.nonces.len(), 3);
+        assert!(GenericFunc::verify_selection(&proof, &machine_id, &candidates, &weights));
+        // 候选委员会或分派的机器不一致时无法通过校验
+        assert!(!GenericFunc::verify_selection(&proof, &machine_id, &candidates, &[2000, 2000]));
+        assert!(!GenericFunc::verify_selection(&proof, b"other_machine", &candidates, &weights));
+
         // 委员会提交机器Hash
         let machine_info_hash1: [u8; 16] =
             hex::decode("fd8885a22a9d9784adaa36effcd77522").unwrap().try_into().unwrap();
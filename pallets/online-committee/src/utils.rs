@@ -46,8 +46,7 @@ impl<T: Config> Pallet<T> {
     ) -> Result<(), ()> {
         for a_committee in committee_list {
             if is_slash {
-                Self::change_committee_total_stake(a_committee.clone(), amount, false, false)
-                    .map_err(|_| ())?;
+                <T as Config>::ManageCommittee::slash_stake(a_committee.clone(), amount)?;
             }
 
             Self::change_committee_used_stake(a_committee, amount, false).map_err(|_| ())?;
@@ -9,7 +9,10 @@ use dbc_support::{
     machine_type::{Latitude, Longitude},
     EraIndex, MachineId,
 };
-pub use online_profile::{rpc_types::StakerInfo, PosInfo, SysInfoDetail};
+pub use online_profile::{
+    rpc_types::{DayAvailability, MachineSearchFilter, StakerInfo},
+    PosInfo, SysInfoDetail,
+};
 use parity_scale_codec::Codec;
 use sp_runtime::traits::MaybeDisplay;
 use sp_std::prelude::Vec;
@@ -17,6 +20,8 @@ use sp_std::prelude::Vec;
 // Here we declare the runtime API. It is implemented it the `impl` block in
 // runtime amalgamator file (the `runtime/src/lib.rs`)
 sp_api::decl_runtime_apis! {
+    // Version 2 adds `search_machines` and `get_machine_availability`
+    #[api_version(2)]
     pub trait OpRpcApi<AccountId, Balance, BlockNumber> where
         AccountId: Codec + Ord,
         Balance: Codec + MaybeDisplay,
@@ -32,5 +37,10 @@ sp_api::decl_runtime_apis! {
         fn get_machine_era_released_reward(machine_id: MachineId, era_index: EraIndex) -> Balance;
         fn get_stash_era_reward(stash: AccountId, era_index: EraIndex) -> Balance;
         fn get_stash_era_released_reward(stash: AccountId, era_index: EraIndex) -> Balance;
+        /// `per_page` is capped at 100
+        #[api_version(2)]
+        fn search_machines(filter: MachineSearchFilter, page: u32, per_page: u32) -> Vec<MachineInfo<AccountId, BlockNumber, Balance>>;
+        #[api_version(2)]
+        fn get_machine_availability(machine_id: MachineId, from_day: u32, to_day: u32) -> Vec<DayAvailability>;
     }
 }
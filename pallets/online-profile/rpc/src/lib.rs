@@ -8,18 +8,22 @@ use jsonrpsee::{
     proc_macros::rpc,
     types::error::{CallError, ErrorCode, ErrorObject},
 };
-use sp_api::ProvideRuntimeApi;
+use sp_api::{ApiExt, ProvideRuntimeApi};
 use sp_blockchain::HeaderBackend;
 use sp_runtime::traits::{Block as BlockT, MaybeDisplay};
 use std::{fmt::Display, str::FromStr, sync::Arc};
 
 use dbc_support::{
+    machine_info::MachineInfo,
     machine_type::{Latitude, Longitude},
     rpc_types::RpcBalance,
     EraIndex,
 };
 use online_profile::{
-    rpc_types::{RpcLiveMachine, RpcMachineInfo, RpcStakerInfo, RpcStashMachine},
+    rpc_types::{
+        DayAvailability, MachineSearchFilter, RpcLiveMachine, RpcMachineInfo, RpcStakerInfo,
+        RpcStashMachine,
+    },
     PosInfo, SysInfoDetail,
 };
 pub use online_profile_runtime_api::OpRpcApi as OpStorageRuntimeApi;
@@ -89,6 +93,25 @@ where
         era_index: EraIndex,
         at: Option<BlockHash>,
     ) -> RpcResult<RpcBalance<Balance>>;
+
+    /// `per_page` is capped at 100
+    #[method(name = "onlineProfile_searchMachines")]
+    fn search_machines(
+        &self,
+        filter: MachineSearchFilter,
+        page: u32,
+        per_page: u32,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<RpcMachineInfo<AccountId, BlockNumber, RpcBalance<Balance>>>>;
+
+    #[method(name = "onlineProfile_getMachineAvailability")]
+    fn get_machine_availability(
+        &self,
+        machine_id: String,
+        from_day: u32,
+        to_day: u32,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Vec<DayAvailability>>;
 }
 
 pub struct OpStorage<C, M> {
@@ -225,25 +248,7 @@ where
 
         let runtime_api_result = api.get_machine_info(at_hash, machine_id);
         if let Ok(Some(machine_info)) = runtime_api_result {
-            return Ok(RpcMachineInfo {
-                controller: machine_info.controller,
-                machine_stash: machine_info.machine_stash,
-                renters: machine_info.renters,
-                last_machine_restake: machine_info.last_machine_restake,
-                bonding_height: machine_info.bonding_height,
-                online_height: machine_info.online_height,
-                last_online_height: machine_info.last_online_height,
-                init_stake_per_gpu: machine_info.init_stake_per_gpu.into(),
-                stake_amount: machine_info.stake_amount.into(),
-                machine_status: machine_info.machine_status,
-                total_rented_duration: machine_info.total_rented_duration,
-                total_rented_times: machine_info.total_rented_times,
-                total_rent_fee: machine_info.total_rent_fee.into(),
-                total_burn_fee: machine_info.total_burn_fee.into(),
-                machine_info_detail: machine_info.machine_info_detail.into(),
-                reward_committee: machine_info.reward_committee,
-                reward_deadline: machine_info.reward_deadline,
-            })
+            return Ok(to_rpc_machine_info(machine_info))
         };
         return Err(JsonRpseeError::Call(CallError::Custom(ErrorObject::owned(
             ErrorCode::InternalError.code(),
@@ -360,4 +365,107 @@ where
             })?;
         Ok(runtime_api_result)
     }
+
+    fn search_machines(
+        &self,
+        filter: MachineSearchFilter,
+        page: u32,
+        per_page: u32,
+        at: Option<Block::Hash>,
+    ) -> RpcResult<Vec<RpcMachineInfo<AccountId, BlockNumber, RpcBalance<Balance>>>> {
+        let api = self.client.runtime_api();
+        let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        // search_machines is only available since runtime api version 2
+        ensure_api_version::<_, Block, AccountId, Balance, BlockNumber>(&api, at_hash)?;
+
+        let runtime_api_result = api
+            .search_machines(at_hash, filter, page, per_page)
+            .map(|machines| machines.into_iter().map(to_rpc_machine_info).collect())
+            .map_err(|e| {
+                JsonRpseeError::Call(CallError::Custom(ErrorObject::owned(
+                    ErrorCode::InternalError.code(),
+                    "Something wrong",
+                    Some(e.to_string()),
+                )))
+            })?;
+        Ok(runtime_api_result)
+    }
+
+    fn get_machine_availability(
+        &self,
+        machine_id: String,
+        from_day: u32,
+        to_day: u32,
+        at: Option<Block::Hash>,
+    ) -> RpcResult<Vec<DayAvailability>> {
+        let api = self.client.runtime_api();
+        let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        // get_machine_availability is only available since runtime api version 2
+        ensure_api_version::<_, Block, AccountId, Balance, BlockNumber>(&api, at_hash)?;
+
+        let machine_id = machine_id.as_bytes().to_vec();
+
+        let runtime_api_result = api
+            .get_machine_availability(at_hash, machine_id, from_day, to_day)
+            .map_err(|e| {
+                JsonRpseeError::Call(CallError::Custom(ErrorObject::owned(
+                    ErrorCode::InternalError.code(),
+                    "Something wrong",
+                    Some(e.to_string()),
+                )))
+            })?;
+        Ok(runtime_api_result)
+    }
+}
+
+fn ensure_api_version<Api, Block, AccountId, Balance, BlockNumber>(
+    api: &Api,
+    at_hash: <Block as BlockT>::Hash,
+) -> RpcResult<()>
+where
+    Block: BlockT,
+    AccountId: Codec + Ord,
+    Balance: Codec + MaybeDisplay,
+    BlockNumber: Codec + MaybeDisplay,
+    Api: ApiExt<Block> + OpStorageRuntimeApi<Block, AccountId, Balance, BlockNumber>,
+{
+    let api_version = api
+        .api_version::<dyn OpStorageRuntimeApi<Block, AccountId, Balance, BlockNumber>>(at_hash)
+        .ok()
+        .flatten()
+        .unwrap_or_default();
+    if api_version < 2 {
+        return Err(JsonRpseeError::Call(CallError::Custom(ErrorObject::owned(
+            ErrorCode::MethodNotFound.code(),
+            "Not supported by the runtime at this block",
+            None::<String>,
+        ))))
+    }
+    Ok(())
+}
+
+fn to_rpc_machine_info<AccountId: Ord, BlockNumber, Balance: Display + FromStr>(
+    machine_info: MachineInfo<AccountId, BlockNumber, Balance>,
+) -> RpcMachineInfo<AccountId, BlockNumber, RpcBalance<Balance>> {
+    RpcMachineInfo {
+        controller: machine_info.controller,
+        machine_stash: machine_info.machine_stash,
+        renters: machine_info.renters,
+        last_machine_restake: machine_info.last_machine_restake,
+        bonding_height: machine_info.bonding_height,
+        online_height: machine_info.online_height,
+        last_online_height: machine_info.last_online_height,
+        init_stake_per_gpu: machine_info.init_stake_per_gpu.into(),
+        stake_amount: machine_info.stake_amount.into(),
+        machine_status: machine_info.machine_status,
+        total_rented_duration: machine_info.total_rented_duration,
+        total_rented_times: machine_info.total_rented_times,
+        total_rent_fee: machine_info.total_rent_fee.into(),
+        total_burn_fee: machine_info.total_burn_fee.into(),
+        machine_info_detail: machine_info.machine_info_detail.into(),
+        reward_committee: machine_info.reward_committee,
+        reward_deadline: machine_info.reward_deadline,
+    }
 }
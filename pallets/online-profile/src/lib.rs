@@ -6,9 +6,11 @@
 mod online_reward;
 mod rpc;
 mod slash;
+mod surge_pricing;
 mod traits;
 mod types;
 mod utils;
+pub mod weights;
 
 pub mod migration;
 use dbc_support::{
@@ -42,6 +44,7 @@ use sp_std::{
 
 pub use pallet::*;
 pub use types::*;
+pub use weights::WeightInfo;
 
 type BalanceOf<T> =
     <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
@@ -49,8 +52,12 @@ type NegativeImbalanceOf<T> = <<T as Config>::Currency as Currency<
     <T as frame_system::Config>::AccountId,
 >>::NegativeImbalance;
 
+// 分时段出租按毫秒时间戳计算
+const ONE_HOUR_MS: u64 = 3600 * 1000;
+const ONE_DAY_MS: u64 = 24 * ONE_HOUR_MS;
+
 use frame_support::traits::StorageVersion;
-const STORAGE_VERSION: StorageVersion = StorageVersion::new(0);
+const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
 #[frame_support::pallet]
 pub mod pallet {
     use super::*;
@@ -68,6 +75,7 @@ pub mod pallet {
         type Slash: OnUnbalanced<NegativeImbalanceOf<Self>>;
         type CancelSlashOrigin: EnsureOrigin<Self::RuntimeOrigin>;
         type SlashAndReward: GNOps<AccountId = Self::AccountId, Balance = BalanceOf<Self>>;
+        type WeightInfo: WeightInfo;
     }
 
     #[pallet::pallet]
@@ -122,15 +130,24 @@ pub mod pallet {
     pub type MachineRentalModeStorage<T: Config> =
         StorageMap<_, Blake2_128Concat, MachineId, MachineRentalMode, ValueQuery>;
 
+    /// 机器所在时区相对 UTC 的偏移（分钟，如 UTC+8 为 480；默认 0 即 UTC）
+    /// 时段表（每周循环及特定日期）均按机器本地时间解释
+    #[pallet::storage]
+    #[pallet::getter(fn machine_utc_offset)]
+    pub type MachineUtcOffset<T: Config> =
+        StorageMap<_, Blake2_128Concat, MachineId, i16, ValueQuery>;
+
     /// 每周循环时段表：MachineId → 7 天 × Vec<TimeRange>（索引 0=周日, 1=周一, ..., 6=周六）
-    /// UTC 时间
+    /// 机器本地时间，每天的时段按 start_hour 升序保存
+    /// 升级前的时段表均为 UTC 且不跨午夜，MachineUtcOffset 默认为 0，含义不变，无需迁移；
+    /// 校验租用时段时会重新排序，不依赖保存的顺序
     #[pallet::storage]
     #[pallet::getter(fn weekly_schedule)]
     pub type WeeklySchedule<T: Config> =
         StorageMap<_, Blake2_128Concat, MachineId, [Vec<TimeRange>; 7], ValueQuery>;
 
     /// 特定日期的时段表：优先级高于每周循环
-    /// key2: 自 UNIX epoch (1970-01-01) 起的天数
+    /// key2: 自 UNIX epoch (1970-01-01) 起的天数（机器本地日期）
     #[pallet::storage]
     #[pallet::getter(fn specific_date_schedule)]
     pub type SpecificDateSchedule<T: Config> = StorageDoubleMap<
@@ -161,6 +178,16 @@ pub mod pallet {
         ValueQuery,
     >;
 
+    /// Statistics of gpu of one gpu type
+    #[pallet::storage]
+    #[pallet::getter(fn gpu_type_info)]
+    pub type GPUTypeInfo<T: Config> = StorageMap<_, Blake2_128Concat, Vec<u8>, PosInfo, ValueQuery>;
+
+    /// 动态定价曲线，为空时不调整系统定价
+    #[pallet::storage]
+    #[pallet::getter(fn surge_pricing_curve)]
+    pub type SurgePricingCurveStorage<T: Config> = StorageValue<_, SurgePricingCurve>;
+
     #[pallet::storage]
     #[pallet::getter(fn stash_controller)]
     pub(super) type StashController<T: Config> =
@@ -187,6 +214,17 @@ pub mod pallet {
     pub type MachineRentedGPU<T: Config> =
         StorageMap<_, Blake2_128Concat, MachineId, u32, ValueQuery>;
 
+    /// 机器最近一天内已结束的下线时段 (下线时间, 重新上线时间)，用于按实际下线时间结算托管的租金
+    #[pallet::storage]
+    #[pallet::getter(fn machine_offline_records)]
+    pub type MachineOfflineRecords<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        MachineId,
+        Vec<(T::BlockNumber, T::BlockNumber)>,
+        ValueQuery,
+    >;
+
     /// Statistics of stash account
     #[pallet::storage]
     #[pallet::getter(fn stash_machines)]
@@ -232,6 +270,12 @@ pub mod pallet {
         ValueQuery,
     >;
 
+    /// 每个Era机器因错过心跳被扣除的得分比例，只影响该Era的奖励
+    #[pallet::storage]
+    #[pallet::getter(fn eras_machine_grade_penalty)]
+    pub(super) type ErasMachineGradePenalty<T: Config> =
+        StorageMap<_, Blake2_128Concat, EraIndex, BTreeMap<MachineId, Perbill>, ValueQuery>;
+
     #[pallet::storage]
     #[pallet::getter(fn phase_reward_info)]
     pub(super) type PhaseRewardInfo<T: Config> =
@@ -410,6 +454,7 @@ pub mod pallet {
             // decrement total_gpu_num without decrementing total_rented_gpu,
             // resulting in mainnet's totalRentedGpu=96 > totalGpuNum=93).
             crate::migration::rebuild_sys_info_from_machines_info::<T>()
+                .saturating_add(crate::migration::gpu_type_info::migrate::<T>())
         }
 
         // fn on_runtime_upgrade() -> Weight {
@@ -914,6 +959,8 @@ pub mod pallet {
                     OPSlashReason::RentedHardwareMalfunction(report_time) |
                     OPSlashReason::RentedHardwareCounterfeit(report_time) |
                     OPSlashReason::OnlineRentFailed(report_time) => report_time,
+                    // 心跳超时被下线
+                    OPSlashReason::HeartbeatOffline(offline_time) => offline_time,
                     _ => return Err(Error::<T>::MachineStatusNotAllowed.into()),
                 },
                 _ => return Err(Error::<T>::MachineStatusNotAllowed.into()),
@@ -984,6 +1031,8 @@ pub mod pallet {
                 slash_info.slash_amount = Zero::zero();
             }
 
+            Self::record_offline_interval(&machine_id, offline_time, now);
+
             // machine status before offline
             machine_info.last_online_height = now;
             machine_info.machine_status = if RentedFinished::<T>::contains_key(&machine_id) {
@@ -1362,7 +1411,7 @@ pub mod pallet {
         }
 
         /// 设置机器某一天的每周循环时段（weekday: 0=周日 .. 6=周六）
-        /// 时间为 UTC 小时值；start_hour < end_hour；end_hour 最大 24
+        /// 时间为机器本地小时值；end_hour 最大 24；start_hour > end_hour 表示跨午夜到次日
         /// 传入空 Vec 表示该天不出租
         /// ranges 上限 10 个；不允许时段重叠
         /// Weight: 1 read + ranges 校验 O(n²) n≤10 + 1 write [Vec×7 整数组] + 1 event ~ 200_000
@@ -1372,7 +1421,7 @@ pub mod pallet {
             origin: OriginFor<T>,
             machine_id: MachineId,
             weekday: u8,
-            ranges: Vec<TimeRange>,
+            mut ranges: Vec<TimeRange>,
         ) -> DispatchResultWithPostInfo {
             let who = ensure_signed(origin)?;
             let machine_info = Self::machines_info(&machine_id).ok_or(Error::<T>::Unknown)?;
@@ -1389,6 +1438,7 @@ pub mod pallet {
             ensure!(ranges.len() <= MAX_RANGES_PER_DAY, Error::<T>::InvalidScheduleArgs);
             // 校验 range 不重叠
             ensure!(Self::ranges_are_disjoint(&ranges), Error::<T>::InvalidScheduleArgs);
+            ranges.sort_by_key(|r| r.start_hour);
             WeeklySchedule::<T>::mutate(&machine_id, |schedule| {
                 schedule[weekday as usize] = ranges.clone();
             });
@@ -1407,7 +1457,7 @@ pub mod pallet {
             origin: OriginFor<T>,
             machine_id: MachineId,
             date_days: u32,
-            ranges: Vec<TimeRange>,
+            mut ranges: Vec<TimeRange>,
         ) -> DispatchResultWithPostInfo {
             let who = ensure_signed(origin)?;
             let machine_info = Self::machines_info(&machine_id).ok_or(Error::<T>::Unknown)?;
@@ -1440,6 +1490,7 @@ pub mod pallet {
                 Error::<T>::InvalidScheduleArgs
             );
 
+            ranges.sort_by_key(|r| r.start_hour);
             SpecificDateSchedule::<T>::insert(&machine_id, date_days, ranges);
             Self::deposit_event(Event::SpecificDateScheduleSet(machine_id, date_days));
             Ok(().into())
@@ -1488,6 +1539,49 @@ pub mod pallet {
             Self::deposit_event(Event::RentReceiverChanged(stash, receiver));
             Ok(().into())
         }
+
+        /// 设置机器所在时区相对 UTC 的偏移（分钟），时段表按该时区的本地时间解释
+        /// 范围 UTC-12:00 ~ UTC+14:00，且为 15 分钟的整数倍
+        #[pallet::call_index(28)]
+        #[pallet::weight(<T as Config>::WeightInfo::set_machine_utc_offset())]
+        pub fn set_machine_utc_offset(
+            origin: OriginFor<T>,
+            machine_id: MachineId,
+            utc_offset: i16,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+            let machine_info = Self::machines_info(&machine_id).ok_or(Error::<T>::Unknown)?;
+            ensure!(
+                machine_info.machine_stash == who || machine_info.controller == who,
+                Error::<T>::NotMachineController
+            );
+            ensure!(
+                (-12 * 60..=14 * 60).contains(&utc_offset) && utc_offset % 15 == 0,
+                Error::<T>::InvalidScheduleArgs
+            );
+            MachineUtcOffset::<T>::insert(&machine_id, utc_offset);
+            Self::deposit_event(Event::MachineUtcOffsetSet(machine_id, utc_offset));
+            Ok(().into())
+        }
+
+        /// 设置动态定价曲线，传 None 关闭动态定价
+        #[pallet::call_index(29)]
+        #[pallet::weight(frame_support::weights::Weight::from_parts(10000, 0))]
+        pub fn set_surge_pricing_curve(
+            origin: OriginFor<T>,
+            curve: Option<SurgePricingCurve>,
+        ) -> DispatchResultWithPostInfo {
+            ensure_root(origin)?;
+            match curve {
+                Some(curve) => {
+                    ensure!(curve.is_valid(), Error::<T>::InvalidSurgePricingCurve);
+                    SurgePricingCurveStorage::<T>::put(curve);
+                },
+                None => SurgePricingCurveStorage::<T>::kill(),
+            }
+            Self::deposit_event(Event::SurgePricingCurveChanged);
+            Ok(().into())
+        }
     }
 
     #[pallet::event]
@@ -1536,6 +1630,9 @@ pub mod pallet {
         SpecificDateCleared(MachineId, u32),
         // spec 410: 矿工设置独立收租钱包；Some(addr)=切换，None=恢复默认（stash 收）
         RentReceiverChanged(T::AccountId, Option<T::AccountId>),
+        // machine_id, utc_offset (minutes)
+        MachineUtcOffsetSet(MachineId, i16),
+        SurgePricingCurveChanged,
     }
 
     #[pallet::error]
@@ -1580,6 +1677,8 @@ pub mod pallet {
         OutOfRentalSchedule,
         /// spec 410: receiver 地址非法（如全零）
         InvalidRentReceiver,
+        /// 动态定价曲线不合法
+        InvalidSurgePricingCurve,
     }
 }
 
@@ -1595,40 +1694,47 @@ impl<T: Config> Pallet<T> {
         Self::stash_rent_receiver(stash).unwrap_or_else(|| stash.clone())
     }
 
+    // 机器重新上线时记录这次下线的时段，只保留最近一天内结束的记录
+    fn record_offline_interval(
+        machine_id: &MachineId,
+        offline_time: T::BlockNumber,
+        now: T::BlockNumber,
+    ) {
+        let keep_from = now.saturating_sub(ONE_DAY.into());
+        MachineOfflineRecords::<T>::mutate(machine_id, |records| {
+            records.retain(|(_, online_time)| *online_time > keep_from);
+            records.push((offline_time, now));
+        });
+    }
+
     // ═══════════════════════════════════════════════════════════════
     // 分时段出租：校验请求时段是否被机器允许
     // ═══════════════════════════════════════════════════════════════
 
-    /// 把毫秒时间戳拆成 (date_days, weekday, hour_of_day)
-    /// weekday: 0=周日, 1=周一 ... 6=周六（UNIX epoch 1970-01-01 是周四 = 4）
-    fn split_timestamp(ts_ms: u64) -> (u32, u8, u8) {
-        let secs = ts_ms / 1000;
-        let date_days = (secs / 86400) as u32;
-        let weekday = ((date_days + 4) % 7) as u8;
-        let hour_of_day = ((secs % 86400) / 3600) as u8;
-        (date_days, weekday, hour_of_day)
-    }
-
-    /// 返回机器在指定日期（UNIX 天数）的可用时段列表
-    /// 特定日期优先于每周循环
-    fn available_ranges_on_date(
-        machine_id: &MachineId,
-        date_days: u32,
-        weekday: u8,
-    ) -> Vec<TimeRange> {
+    /// 返回机器在指定日期（本地 UNIX 天数）的可用时段列表
+    /// 特定日期优先于每周循环；weekday: 0=周日, 1=周一 ... 6=周六（UNIX epoch 1970-01-01 是周四 = 4）
+    fn available_ranges_on_date(machine_id: &MachineId, date_days: u32) -> Vec<TimeRange> {
         if SpecificDateSchedule::<T>::contains_key(machine_id, date_days) {
             SpecificDateSchedule::<T>::get(machine_id, date_days)
         } else {
+            let weekday = (date_days + 4) % 7;
             let schedule = WeeklySchedule::<T>::get(machine_id);
             schedule.get(weekday as usize).cloned().unwrap_or_default()
         }
     }
 
+    /// 把 UTC 毫秒时间戳换算为机器本地时间的毫秒时间戳
+    pub fn to_local_time_ms(machine_id: &MachineId, ts_ms: u64) -> Option<u64> {
+        let offset_ms = Self::machine_utc_offset(machine_id) as i64 * 60 * 1000;
+        let local_ms = (ts_ms as i64).checked_add(offset_ms)?;
+        u64::try_from(local_ms).ok()
+    }
+
     /// 校验请求的租用时间段 [start_ts_ms, end_ts_ms) 是否在机器的可用时段内
     /// 仅对 TimeSlot 模式生效。FullTime 模式总是返回 true。
     /// 要求：
-    /// - 时长至少 2 小时
-    /// - 必须完全落在同一天的某个时段内（不支持跨日）
+    /// - 时长至少 2 小时，最多 31 天
+    /// - 按机器本地时间，由一个或多个首尾相接的可用时段完整覆盖（可跨日、跨午夜）
     pub fn is_rental_schedule_allowed(
         machine_id: &MachineId,
         start_ts_ms: u64,
@@ -1648,24 +1754,43 @@ impl<T: Config> Pallet<T> {
         if end_ts_ms - start_ts_ms < MIN_DURATION_MS {
             return false
         }
-
-        let (start_date, start_weekday, start_hour) = Self::split_timestamp(start_ts_ms);
-        let end_secs = end_ts_ms / 1000;
-        let end_date = (end_secs / 86400) as u32;
-        let end_hour_in_day = ((end_secs % 86400) / 3600) as u8;
-        // 如果租期跨越 UTC 日（例如 23:00 到次日 01:00），要求严格单日内
-        // 但允许结束正好是 00:00（即 end_hour = 24 当日视角）
-        let end_hour_normalized = if end_date == start_date {
-            end_hour_in_day
-        } else if end_date == start_date + 1 && end_secs % 86400 == 0 {
-            24
-        } else {
+        // 最长 31 天，限制读取的时段表数量
+        const MAX_DURATION_MS: u64 = 31 * ONE_DAY_MS;
+        if end_ts_ms - start_ts_ms > MAX_DURATION_MS {
             return false
+        }
+
+        let (start_ms, end_ms) = match (
+            Self::to_local_time_ms(machine_id, start_ts_ms),
+            Self::to_local_time_ms(machine_id, end_ts_ms),
+        ) {
+            (Some(start_ms), Some(end_ms)) => (start_ms, end_ms),
+            _ => return false,
         };
 
-        let ranges = Self::available_ranges_on_date(machine_id, start_date, start_weekday);
-        for range in ranges.iter() {
-            if range.covers(start_hour, end_hour_normalized) {
+        // 前一天跨午夜的时段可能覆盖租用开始的时间
+        let start_date = start_ms / ONE_DAY_MS;
+        let end_date = (end_ms - 1) / ONE_DAY_MS;
+        let mut intervals = Vec::new();
+        for date in start_date.saturating_sub(1)..=end_date {
+            let day_start = date * ONE_DAY_MS;
+            for range in Self::available_ranges_on_date(machine_id, date as u32) {
+                intervals.push((
+                    day_start + range.start_hour as u64 * ONE_HOUR_MS,
+                    day_start + range.end_hour_of_day() as u64 * ONE_HOUR_MS,
+                ));
+            }
+        }
+        intervals.sort_unstable();
+
+        // 从开始时间起依次衔接相邻的时段，出现空档则不允许
+        let mut covered_to = start_ms;
+        for (range_start, range_end) in intervals {
+            if range_start > covered_to {
+                break
+            }
+            covered_to = covered_to.max(range_end);
+            if covered_to >= end_ms {
                 return true
             }
         }
@@ -1683,8 +1808,8 @@ impl<T: Config> Pallet<T> {
             for j in (i + 1)..ranges.len() {
                 let a = ranges[i];
                 let b = ranges[j];
-                // 有交集：a.start < b.end && b.start < a.end
-                if a.start_hour < b.end_hour && b.start_hour < a.end_hour {
+                // 有交集：a.start < b.end && b.start < a.end（跨午夜的时段结束时间按次日计算）
+                if a.start_hour < b.end_hour_of_day() && b.start_hour < a.end_hour_of_day() {
                     return false
                 }
             }
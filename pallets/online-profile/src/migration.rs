@@ -227,6 +227,66 @@ pub mod v1 {
     }
 }
 
+/// 动态定价需要按GPU型号统计的使用率，与 PosGPUInfo 的统计方式一致，根据 MachinesInfo 生成 GPUTypeInfo。
+pub mod gpu_type_info {
+    use super::*;
+    use sp_std::collections::btree_map::BTreeMap;
+
+    pub struct Migration<T>(PhantomData<T>);
+    impl<T: Config> OnRuntimeUpgrade for Migration<T> {
+        fn on_runtime_upgrade() -> Weight {
+            migrate::<T>()
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(_state: Vec<u8>) -> Result<(), TryRuntimeError> {
+            let online_gpu: u64 = GPUTypeInfo::<T>::iter_values().map(|info| info.online_gpu).sum();
+            ensure!(
+                online_gpu == Pallet::<T>::sys_info().total_gpu_num,
+                "gpu type info should match sys info"
+            );
+            ensure!(
+                Pallet::<T>::on_chain_storage_version() == 2,
+                "storage version should be updated"
+            );
+            Ok(())
+        }
+    }
+
+    pub fn migrate<T: Config>() -> Weight {
+        let on_chain_version = Pallet::<T>::on_chain_storage_version();
+        if on_chain_version >= 2 {
+            return T::DbWeight::get().reads(1)
+        }
+
+        let mut count = 0u64;
+        let mut gpu_type_info: BTreeMap<Vec<u8>, PosInfo> = BTreeMap::new();
+        for (_, machine_info) in MachinesInfo::<T>::iter() {
+            count += 1;
+            let is_rented = match machine_info.machine_status {
+                MachineStatus::Online => false,
+                MachineStatus::Rented => true,
+                _ => continue,
+            };
+
+            let info = gpu_type_info.entry(machine_info.gpu_type_and_mem().0).or_default();
+            info.on_online_changed(true, machine_info.gpu_num(), machine_info.calc_point());
+            if is_rented {
+                info.on_rent_changed(true, machine_info.gpu_num());
+            }
+        }
+
+        let _ = GPUTypeInfo::<T>::clear(u32::MAX, None);
+        for (gpu_type, info) in gpu_type_info.iter() {
+            GPUTypeInfo::<T>::insert(gpu_type, info);
+        }
+
+        StorageVersion::new(2).put::<Pallet<T>>();
+        log::info!(target: TARGET, "migrate {} gpu types", gpu_type_info.len());
+        T::DbWeight::get().reads_writes(count + 1, gpu_type_info.len() as u64 + 1)
+    }
+}
+
 /// Recompute `SysInfo.total_gpu_num` and `SysInfo.total_rented_gpu` directly from
 /// `MachinesInfo` (the source of truth), repairing the drift caused by the
 /// `do_machine_exit` bug (force_machine_exit on a Rented machine decremented
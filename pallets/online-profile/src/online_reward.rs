@@ -1,8 +1,9 @@
 use crate::{
     types::{EraStashPoints, MachineGradeStatus, MachineRecentRewardInfo},
     AllMachineIdSnap, AllMachineIdSnapDetail, BalanceOf, Config, CurrentEra, EraReward,
-    ErasMachinePoints, ErasMachineReleasedReward, ErasMachineReward, ErasStashPoints,
-    ErasStashReleasedReward, ErasStashReward, MachineRecentReward, Pallet, StashMachines,
+    ErasMachineGradePenalty, ErasMachinePoints, ErasMachineReleasedReward, ErasMachineReward,
+    ErasStashPoints, ErasStashReleasedReward, ErasStashReward, MachineRecentReward, Pallet,
+    StashMachines,
 };
 use dbc_support::{
     traits::{DbcPrice, ManageCommittee, OPRPCQuery},
@@ -41,6 +42,8 @@ impl<T: Config> Pallet<T> {
             ErasStashPoints::<T>::insert(current_era + 1, current_era_stash_snapshot);
             let current_era_machine_snapshot = Self::eras_machine_points(current_era);
             ErasMachinePoints::<T>::insert(current_era + 1, current_era_machine_snapshot);
+            // 错过心跳的扣分只影响当时的Era，上上个Era的奖励已经发放完，移除其记录
+            ErasMachineGradePenalty::<T>::remove(current_era.saturating_sub(2));
         }
     }
 
@@ -140,6 +143,7 @@ impl<T: Config> Pallet<T> {
                 let era_total_reward = Self::era_reward(release_era);
                 let era_machine_points = Self::eras_machine_points(release_era);
                 let era_stash_points = Self::eras_stash_points(release_era);
+                let era_grade_penalty = Self::eras_machine_grade_penalty(release_era);
 
                 for _ in 0..=release_num {
                     if let Some(machine_id) = all_machine.all_machine_id.pop_front() {
@@ -149,6 +153,7 @@ impl<T: Config> Pallet<T> {
                             era_total_reward,
                             &era_machine_points,
                             &era_stash_points,
+                            &era_grade_penalty,
                         );
                     } else {
                         AllMachineIdSnap::<T>::put(all_machine);
@@ -169,6 +174,7 @@ impl<T: Config> Pallet<T> {
         era_total_reward: BalanceOf<T>,
         era_machine_points: &BTreeMap<MachineId, MachineGradeStatus>,
         era_stash_points: &EraStashPoints<T::AccountId>,
+        era_grade_penalty: &BTreeMap<MachineId, Perbill>,
     ) -> BalanceOf<T> {
         let machine_points = era_machine_points.get(machine_id);
         let stash_points = era_stash_points.staker_statistic.get(machine_stash);
@@ -177,6 +183,11 @@ impl<T: Config> Pallet<T> {
         } else {
             machine_points.unwrap().machine_actual_grade(stash_points.unwrap().inflation)
         };
+        // 错过心跳被扣除的得分不发放奖励
+        let machine_actual_grade = match era_grade_penalty.get(machine_id) {
+            Some(penalty) => penalty.left_from_one() * machine_actual_grade,
+            None => machine_actual_grade,
+        };
 
         // 该Era机器获得的总奖励 (reward_to_stash + reward_to_committee)
         if era_stash_points.total == 0 {
@@ -192,6 +203,7 @@ impl<T: Config> Pallet<T> {
         era_total_reward: BalanceOf<T>,
         era_machine_points: &BTreeMap<MachineId, MachineGradeStatus>,
         era_stash_points: &EraStashPoints<T::AccountId>,
+        era_grade_penalty: &BTreeMap<MachineId, Perbill>,
     ) -> Result<(), ()> {
         let mut machine_reward_info = Self::machine_recent_reward(&machine_id).ok_or(())?;
         let mut stash_machine = Self::stash_machines(&machine_reward_info.machine_stash);
@@ -202,6 +214,7 @@ impl<T: Config> Pallet<T> {
             era_total_reward,
             era_machine_points,
             era_stash_points,
+            era_grade_penalty,
         );
 
         MachineRecentRewardInfo::add_new_reward(&mut machine_reward_info, machine_total_reward);
@@ -239,7 +252,7 @@ impl<T: Config> Pallet<T> {
             Perbill::from_rational(1u32, machine_reward_info.reward_committee.len() as u32) *
                 reward_to_committee;
         for a_committee in machine_reward_info.reward_committee.clone() {
-            T::ManageCommittee::add_reward(a_committee, committee_each_get);
+            T::ManageCommittee::add_reward(a_committee, release_era, committee_each_get);
         }
 
         // NOTE: reward of actual get will change depend on how much days left
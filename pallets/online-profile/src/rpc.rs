@@ -3,20 +3,29 @@ use dbc_support::{
     live_machine::LiveMachine,
     machine_info::MachineInfo,
     machine_type::{Latitude, Longitude},
+    traits::{RTOps, SurgePricing},
     MachineId,
 };
 use frame_support::{IterableStorageDoubleMap, IterableStorageMap};
-use sp_std::vec::Vec;
+use sp_std::{vec, vec::Vec};
 
 use parity_scale_codec::EncodeLike;
 
 use crate::{
-    rpc_types::{MachineBriefInfo, StakerInfo},
-    BalanceOf, Config, Pallet, PosGPUInfo, StashMachines,
+    rpc_types::{
+        DayAvailability, MachineBriefInfo, MachineSearchFilter, RangeAvailability, StakerInfo,
+    },
+    BalanceOf, Config, MachineRentalMode, Pallet, PosGPUInfo, StashMachines, TimeRange, ONE_DAY_MS,
+    ONE_HOUR_MS,
 };
 
 type EraIndex = u32;
 
+/// 查询可用时段时，一次最多查询的天数
+pub const MAX_AVAILABILITY_DAYS: u32 = 90;
+/// 搜索机器时每页最多返回的机器数，超过时按该数量返回
+pub const MAX_SEARCH_PER_PAGE: u32 = 100;
+
 impl<T: Config> Pallet<T> {
     pub fn get_total_staker_num() -> u64 {
         <StashMachines<T> as IterableStorageMap<T::AccountId, _>>::iter().count() as u64
@@ -61,6 +70,215 @@ impl<T: Config> Pallet<T> {
         Self::machines_info(&machine_id)
     }
 
+    /// 按硬件、空闲GPU、租赁模式、价格及位置搜索可租用的机器，按每算力点价格从低到高排序
+    /// page从0开始，per_page最大为MAX_SEARCH_PER_PAGE
+    pub fn search_machines(
+        filter: MachineSearchFilter,
+        page: u32,
+        per_page: u32,
+    ) -> Vec<MachineInfo<T::AccountId, T::BlockNumber, BalanceOf<T>>> {
+        let per_page = per_page.min(MAX_SEARCH_PER_PAGE);
+        let live_machines = Self::live_machines();
+
+        let mut matched_machines = Vec::new();
+        for machine_id in
+            live_machines.online_machine.iter().chain(live_machines.rented_machine.iter())
+        {
+            let machine_info = match Self::machines_info(machine_id) {
+                Some(machine_info) => machine_info,
+                None => continue,
+            };
+            if !machine_info.can_rent() {
+                continue
+            }
+            let price = match Self::get_gpu_price_per_day(machine_id, &machine_info) {
+                Some(price) => price,
+                None => continue,
+            };
+            if Self::is_machine_matched(machine_id, &machine_info, price, &filter) {
+                matched_machines.push((price, machine_info));
+            }
+        }
+
+        // 按每算力点价格排序：整机价格 / 整机算力点，交叉相乘避免精度损失
+        matched_machines.sort_by(|(price_a, info_a), (price_b, info_b)| {
+            let price_a = *price_a as u128 * info_a.gpu_num() as u128;
+            let price_b = *price_b as u128 * info_b.gpu_num() as u128;
+            (price_a * info_b.calc_point() as u128).cmp(&(price_b * info_a.calc_point() as u128))
+        });
+
+        matched_machines
+            .into_iter()
+            .skip(page.saturating_mul(per_page) as usize)
+            .take(per_page as usize)
+            .map(|(_, machine_info)| machine_info)
+            .collect()
+    }
+
+    // 单卡每天的租金(USD*10^6) = 系统自动定价(按动态定价调整) + 卡主额外加价
+    fn get_gpu_price_per_day(
+        machine_id: &MachineId,
+        machine_info: &MachineInfo<T::AccountId, T::BlockNumber, BalanceOf<T>>,
+    ) -> Option<u64> {
+        let system_price = <Self as RTOps>::get_machine_price(
+            machine_info.calc_point(),
+            1,
+            machine_info.gpu_num(),
+        )?;
+        let system_price = <Self as SurgePricing>::adjust_machine_price(
+            &machine_info.machine_info_detail.committee_upload_info.gpu_type,
+            system_price,
+        )?;
+        system_price.checked_add(Self::machine_extra_price(machine_id))
+    }
+
+    fn is_machine_matched(
+        machine_id: &MachineId,
+        machine_info: &MachineInfo<T::AccountId, T::BlockNumber, BalanceOf<T>>,
+        price: u64,
+        filter: &MachineSearchFilter,
+    ) -> bool {
+        let upload_info = &machine_info.machine_info_detail.committee_upload_info;
+        let free_gpu = machine_info.gpu_num().saturating_sub(Self::machine_rented_gpu(machine_id));
+
+        if !filter.gpu_type.is_empty() && upload_info.gpu_type != filter.gpu_type {
+            return false
+        }
+        if filter.min_gpu_mem.map_or(false, |min| upload_info.gpu_mem < min) ||
+            filter.min_calc_point.map_or(false, |min| upload_info.calc_point < min) ||
+            filter.min_free_gpu.map_or(false, |min| free_gpu < min)
+        {
+            return false
+        }
+        if filter
+            .rental_mode
+            .map_or(false, |mode| Self::machine_rental_mode(machine_id) != mode)
+        {
+            return false
+        }
+        if filter.min_price.map_or(false, |min| price < min) ||
+            filter.max_price.map_or(false, |max| price > max)
+        {
+            return false
+        }
+        if let Some(((west, south), (east, north))) = &filter.bounding_box {
+            let longitude = machine_info.longitude().to_signed();
+            let latitude = machine_info.latitude().to_signed();
+            if longitude < west.to_signed() ||
+                longitude > east.to_signed() ||
+                latitude < south.to_signed() ||
+                latitude > north.to_signed()
+            {
+                return false
+            }
+        }
+        true
+    }
+
+    /// 获取机器在 [from_day, to_day] (UNIX 天数, UTC 日期) 每天实际可用的时段 (UTC 小时)，
+    /// 以及每个时段内同时被租用的GPU数的最大值。全天出租的机器每天可用时段为 [0, 24)
+    /// rented: 机器已有的租用/预约 (开始时间ms, 结束时间ms, GPU数)，由租用模块提供
+    pub fn get_machine_availability(
+        machine_id: &MachineId,
+        from_day: u32,
+        to_day: u32,
+        rented: &[(u64, u64, u32)],
+    ) -> Vec<DayAvailability> {
+        let to_day = to_day.min(from_day.saturating_add(MAX_AVAILABILITY_DAYS - 1));
+        let is_full_time = Self::machine_rental_mode(machine_id) == MachineRentalMode::FullTime;
+
+        let mut availability = Vec::new();
+        for day in from_day..=to_day {
+            let day_start = day as u64 * ONE_DAY_MS;
+            let intervals = if is_full_time {
+                vec![(day_start, day_start + ONE_DAY_MS)]
+            } else {
+                Self::utc_intervals_on_day(machine_id, day)
+            };
+
+            let ranges = intervals
+                .into_iter()
+                .filter_map(|(start, end)| {
+                    // 非整点时区的时段边界向内取整到整点
+                    let start_hour = (start - day_start + ONE_HOUR_MS - 1) / ONE_HOUR_MS;
+                    let end_hour = (end - day_start) / ONE_HOUR_MS;
+                    if start_hour >= end_hour {
+                        return None
+                    }
+                    let range =
+                        TimeRange { start_hour: start_hour as u8, end_hour: end_hour as u8 };
+                    Some(RangeAvailability {
+                        range,
+                        rented_gpu: Self::max_concurrent_rented_gpu(
+                            day_start + start_hour * ONE_HOUR_MS,
+                            day_start + end_hour * ONE_HOUR_MS,
+                            rented,
+                        ),
+                    })
+                })
+                .collect();
+            availability.push(DayAvailability { day, ranges });
+        }
+        availability
+    }
+
+    // 把机器本地时间的时段表换算为 UTC 时间，截取落在 UTC 某天内的部分，相接的时段合并
+    fn utc_intervals_on_day(machine_id: &MachineId, day: u32) -> Vec<(u64, u64)> {
+        let offset_ms = Self::machine_utc_offset(machine_id) as i64 * 60 * 1000;
+        let day_start = day as i64 * ONE_DAY_MS as i64;
+        let day_end = day_start + ONE_DAY_MS as i64;
+
+        // 时区偏移在 [-12h, +14h] 之间，加上跨午夜的时段，本地前两天到后一天的时段都可能落在当天
+        let mut intervals = Vec::new();
+        for local_day in day.saturating_sub(2)..=day.saturating_add(1) {
+            // 本地日期 00:00 对应的 UTC 时间
+            let local_day_start = local_day as i64 * ONE_DAY_MS as i64 - offset_ms;
+            for range in Self::available_ranges_on_date(machine_id, local_day) {
+                let start = local_day_start + range.start_hour as i64 * ONE_HOUR_MS as i64;
+                let end = local_day_start + range.end_hour_of_day() as i64 * ONE_HOUR_MS as i64;
+                let (start, end) = (start.max(day_start), end.min(day_end));
+                if start < end {
+                    intervals.push((start as u64, end as u64));
+                }
+            }
+        }
+        intervals.sort_unstable();
+
+        let mut merged: Vec<(u64, u64)> = Vec::new();
+        for (start, end) in intervals {
+            match merged.last_mut() {
+                Some(last) if start <= last.1 => last.1 = last.1.max(end),
+                _ => merged.push((start, end)),
+            }
+        }
+        merged
+    }
+
+    // 时段 [start, end) 内同时被租用的GPU数的最大值
+    fn max_concurrent_rented_gpu(start: u64, end: u64, rented: &[(u64, u64, u32)]) -> u32 {
+        let mut events = Vec::new();
+        for (rent_start, rent_end, gpu_num) in rented {
+            let (rent_start, rent_end) = ((*rent_start).max(start), (*rent_end).min(end));
+            if rent_start < rent_end {
+                events.push((rent_start, true, *gpu_num));
+                events.push((rent_end, false, *gpu_num));
+            }
+        }
+        // 同一时刻先处理结束再处理开始，首尾相接的订单不算同时租用
+        events.sort_unstable_by_key(|(time, is_start, _)| (*time, *is_start));
+
+        let (mut rented_gpu, mut max_rented_gpu) = (0u32, 0u32);
+        for (_, is_start, gpu_num) in events {
+            if is_start {
+                rented_gpu = rented_gpu.saturating_add(gpu_num);
+                max_rented_gpu = max_rented_gpu.max(rented_gpu);
+            } else {
+                rented_gpu = rented_gpu.saturating_sub(gpu_num);
+            }
+        }
+        max_rented_gpu
+    }
+
     /// 获得系统中所有位置列表
     pub fn get_pos_gpu_info() -> Vec<(Longitude, Latitude, PosInfo)> {
         <PosGPUInfo<T> as IterableStorageDoubleMap<Longitude, Latitude, PosInfo>>::iter()
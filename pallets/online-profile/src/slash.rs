@@ -123,6 +123,8 @@ impl<T: Config> Pallet<T> {
             },
             // 算工主动报告在线的机器，主动下线
             OPSlashReason::OnlineReportOffline(_) => (None, vec![], None),
+            // 机器错过心跳被下线，没有举报人，下线时的租用人获得补偿
+            OPSlashReason::HeartbeatOffline(_) => (None, renters, None),
             // 机器处于租用状态，无法访问，这种情况下，reporter == renter
             OPSlashReason::RentedInaccessible(_) => {
                 let reporter = match duration.saturated_into::<u32>() {
@@ -0,0 +1,39 @@
+use crate::{Config, GPUTypeInfo, Pallet, SURGE_MULTIPLIER_BASE};
+use dbc_support::traits::SurgePricing;
+use sp_runtime::Perbill;
+
+// 动态定价：按全网及该型号GPU的使用率，根据定价曲线调整系统定价
+impl<T: Config> Pallet<T> {
+    /// 当前该型号GPU的价格系数，以 SURGE_MULTIPLIER_BASE 为 1 倍；未设置定价曲线时为 1 倍
+    pub fn surge_multiplier(gpu_type: &[u8]) -> u32 {
+        match Self::surge_pricing_curve() {
+            Some(curve) => curve.multiplier(Self::gpu_utilization(gpu_type, curve.network_weight)),
+            None => SURGE_MULTIPLIER_BASE,
+        }
+    }
+
+    // 全网及该型号GPU使用率的加权；该型号没有在线GPU时只使用全网使用率
+    fn gpu_utilization(gpu_type: &[u8], network_weight: Perbill) -> Perbill {
+        let sys_info = Self::sys_info();
+        let network_utilization =
+            Perbill::from_rational(sys_info.total_rented_gpu, sys_info.total_gpu_num.max(1));
+
+        let gpu_type_info = GPUTypeInfo::<T>::get(gpu_type);
+        if gpu_type_info.online_gpu == 0 {
+            return network_utilization
+        }
+        let gpu_type_utilization =
+            Perbill::from_rational(gpu_type_info.rented_gpu, gpu_type_info.online_gpu);
+
+        (network_weight * network_utilization)
+            .saturating_add(network_weight.left_from_one() * gpu_type_utilization)
+    }
+}
+
+impl<T: Config> SurgePricing for Pallet<T> {
+    fn adjust_machine_price(gpu_type: &[u8], system_price: u64) -> Option<u64> {
+        system_price
+            .checked_mul(Self::surge_multiplier(gpu_type) as u64)?
+            .checked_div(SURGE_MULTIPLIER_BASE as u64)
+    }
+}
@@ -1,16 +1,16 @@
 use crate::{
-    types::*, BalanceOf, Config, ControllerMachines, LiveMachines, MachineRecentReward,
-    MachineRentedGPU, MachinesInfo, Pallet, RentedFinished, StashMachines, StashStake, SysInfo,
-    UserMutHardwareStake,
+    types::*, BalanceOf, Config, ControllerMachines, ErasMachineGradePenalty, LiveMachines,
+    MachineRecentReward, MachineRentedGPU, MachinesInfo, Pallet, RentedFinished, StashMachines,
+    StashStake, SysInfo, UserMutHardwareStake,
 };
 use dbc_support::{
     machine_type::{CommitteeUploadInfo, MachineStatus},
-    traits::{MTOps, OCOps, OPRPCQuery, RTOps},
+    traits::{HBOps, MTOps, OCOps, OPRPCQuery, RTOps},
     verify_online::StashMachine,
     verify_slash::OPSlashReason,
     ItemList, MachineId,
 };
-use frame_support::IterableStorageMap;
+use frame_support::{ensure, IterableStorageMap};
 use sp_runtime::{
     traits::{CheckedSub, Saturating, Zero},
     Perbill, SaturatedConversion,
@@ -408,6 +408,26 @@ impl<T: Config> RTOps for Pallet<T> {
         Ok::<(), ()>(())
     }
 
+    // 退还租金时，减少租金统计；已用于补足质押的租金不退回
+    fn reduce_machine_rent_fee(
+        machine_id: MachineId,
+        fee_to_destroy: BalanceOf<T>,
+        fee_to_stash: BalanceOf<T>,
+    ) -> Result<(), ()> {
+        let mut machine_info = Self::machines_info(&machine_id).ok_or(())?;
+        let mut staker_machine = Self::stash_machines(&machine_info.machine_stash);
+        let mut sys_info = Self::sys_info();
+
+        sys_info.reduce_rent_fee(fee_to_destroy, fee_to_stash);
+        staker_machine.reduce_rent_fee(fee_to_destroy, fee_to_stash);
+        machine_info.reduce_rent_fee(fee_to_destroy, fee_to_stash);
+
+        SysInfo::<T>::put(sys_info);
+        StashMachines::<T>::insert(&machine_info.machine_stash, staker_machine);
+        MachinesInfo::<T>::insert(&machine_id, machine_info);
+        Ok(())
+    }
+
     fn reset_machine_renters(machine_id: MachineId, renters: Vec<T::AccountId>) -> Result<(), ()> {
         MachinesInfo::<T>::mutate(machine_id, |machine_info| {
             let machine_info = machine_info.as_mut().ok_or(())?;
@@ -444,11 +464,19 @@ impl<T: Config> MTOps for Pallet<T> {
         machine_id: MachineId,
         fault_type: OPSlashReason<T::BlockNumber>,
     ) -> Result<(), ()> {
+        let machine_info = Self::machines_info(&machine_id).ok_or(())?;
+        // 心跳超时下线时，记录下线时的租用人，机器重新上线时租用人获得补偿
+        if matches!(fault_type, OPSlashReason::HeartbeatOffline(..)) &&
+            machine_info.machine_status == MachineStatus::Rented
+        {
+            Self::add_offline_machine_to_renters(machine_id.clone(), machine_info.renters);
+        }
+
         Self::machine_offline(
             machine_id.clone(),
             MachineStatus::ReporterReportOffline(
                 fault_type,
-                Box::new(Self::machines_info(&machine_id).ok_or(())?.machine_status),
+                Box::new(machine_info.machine_status),
                 reporter.clone(),
                 committee,
             ),
@@ -477,3 +505,38 @@ impl<T: Config> MTOps for Pallet<T> {
         Ok(())
     }
 }
+
+impl<T: Config> HBOps for Pallet<T> {
+    type AccountId = T::AccountId;
+    type BlockNumber = T::BlockNumber;
+    type MachineId = MachineId;
+
+    fn hb_online_machines() -> Vec<MachineId> {
+        let live_machines = Self::live_machines();
+        let mut online_machines = live_machines.online_machine;
+        online_machines.extend(live_machines.rented_machine);
+        online_machines
+    }
+
+    fn hb_online_machine(machine_id: &MachineId) -> Option<(T::AccountId, T::BlockNumber)> {
+        let machine_info = Self::machines_info(machine_id)?;
+        match machine_info.machine_status {
+            MachineStatus::Online | MachineStatus::Rented => Some((
+                machine_info.machine_stash,
+                machine_info.online_height.max(machine_info.last_online_height),
+            )),
+            _ => None,
+        }
+    }
+
+    // NOTE: 只记录当前Era的扣除比例，不改变机器及stash的得分，被扣除部分的奖励不再发放
+    fn hb_reduce_machine_grade(machine_id: &MachineId, percent: Perbill) -> Result<(), ()> {
+        ensure!(MachinesInfo::<T>::contains_key(machine_id), ());
+
+        ErasMachineGradePenalty::<T>::mutate(Self::current_era(), |grade_penalty| {
+            let penalty = grade_penalty.entry(machine_id.clone()).or_default();
+            *penalty = penalty.saturating_add(percent);
+        });
+        Ok(())
+    }
+}
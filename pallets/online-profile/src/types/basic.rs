@@ -4,7 +4,7 @@ use parity_scale_codec::{Decode, Encode};
 use scale_info::TypeInfo;
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
-use sp_runtime::{traits::Saturating, RuntimeDebug};
+use sp_runtime::{traits::Saturating, Perbill, RuntimeDebug};
 use sp_std::vec::Vec;
 
 pub type TelecomName = Vec<u8>;
@@ -68,8 +68,9 @@ impl Default for MachineRentalMode {
     }
 }
 
-/// 一天中的时段（小时级粒度，UTC 时间）
+/// 一天中的时段（小时级粒度，机器本地时间，见 MachineUtcOffset）
 /// start_hour ∈ [0, 23]，end_hour ∈ [1, 24]，end_hour == 24 表示到午夜 00:00
+/// start_hour > end_hour 表示跨午夜，如 22-6 表示当天 22:00 至次日 06:00
 #[derive(PartialEq, Eq, Clone, Copy, Encode, Decode, RuntimeDebug, TypeInfo)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct TimeRange {
@@ -81,20 +82,95 @@ impl TimeRange {
     /// 校验时段合法性
     pub fn is_valid(&self) -> bool {
         self.start_hour < 24 && self.end_hour >= 1 && self.end_hour <= 24
-            && self.start_hour < self.end_hour
+            && self.start_hour != self.end_hour
+    }
+
+    /// 是否跨午夜
+    pub fn is_overnight(&self) -> bool {
+        self.start_hour > self.end_hour
+    }
+
+    /// 以开始当天 00:00 为起点的结束小时，跨午夜时大于 24
+    pub fn end_hour_of_day(&self) -> u8 {
+        if self.is_overnight() {
+            self.end_hour + 24
+        } else {
+            self.end_hour
+        }
     }
 
     /// 判断给定小时是否在时段内 [start, end)
     /// 注意：hour 是 0-23，end_hour 可以是 24（表示到次日 00:00）
-    /// 当 hour 在范围 [start, end) 内返回 true
+    /// 跨午夜的时段包含 [start, 24) 及 [0, end)
     pub fn contains_hour(&self, hour: u8) -> bool {
-        hour >= self.start_hour && (hour as u16) < (self.end_hour as u16)
+        if self.is_overnight() {
+            hour >= self.start_hour || hour < self.end_hour
+        } else {
+            hour >= self.start_hour && (hour as u16) < (self.end_hour as u16)
+        }
     }
 
     /// 判断一段时间 [from_hour, to_hour] 是否完全落在本时段内
-    /// from/to 都是小时值，to_hour == 24 合法
+    /// from/to 都是以开始当天 00:00 为起点的小时值，跨午夜时 to_hour 可以大于 24
     pub fn covers(&self, from_hour: u8, to_hour: u8) -> bool {
-        from_hour >= self.start_hour && to_hour <= self.end_hour
+        from_hour >= self.start_hour && to_hour <= self.end_hour_of_day()
+    }
+}
+
+/// 动态定价的价格系数以 1_000_000 为 1 倍
+pub const SURGE_MULTIPLIER_BASE: u32 = 1_000_000;
+/// 价格系数范围：0.1 倍 ~ 10 倍
+pub const MIN_SURGE_MULTIPLIER: u32 = SURGE_MULTIPLIER_BASE / 10;
+pub const MAX_SURGE_MULTIPLIER: u32 = SURGE_MULTIPLIER_BASE * 10;
+/// 定价曲线最多的拐点数
+pub const MAX_SURGE_POINTS: usize = 10;
+
+/// 动态定价曲线（由治理设置）：根据GPU使用率(已租用GPU / 在线GPU)调整每算力点的价格
+#[derive(PartialEq, Eq, Clone, Encode, Decode, Default, RuntimeDebug, TypeInfo)]
+pub struct SurgePricingCurve {
+    /// 全网使用率所占的权重，其余为该型号GPU使用率的权重
+    pub network_weight: Perbill,
+    /// 曲线拐点 (使用率, 价格系数)，使用率严格递增；
+    /// 拐点之间线性插值，超出两端时取端点的价格系数
+    pub points: Vec<(Perbill, u32)>,
+}
+
+impl SurgePricingCurve {
+    pub fn is_valid(&self) -> bool {
+        !self.points.is_empty()
+            && self.points.len() <= MAX_SURGE_POINTS
+            && self.points.windows(2).all(|w| w[0].0 < w[1].0)
+            && self.points.iter().all(|(_, multiplier)| {
+                (MIN_SURGE_MULTIPLIER..=MAX_SURGE_MULTIPLIER).contains(multiplier)
+            })
+    }
+
+    /// 根据使用率计算价格系数
+    pub fn multiplier(&self, utilization: Perbill) -> u32 {
+        let (first, last) = match (self.points.first(), self.points.last()) {
+            (Some(first), Some(last)) => (first, last),
+            _ => return SURGE_MULTIPLIER_BASE,
+        };
+        if utilization <= first.0 {
+            return first.1
+        }
+        if utilization >= last.0 {
+            return last.1
+        }
+
+        for w in self.points.windows(2) {
+            let ((u0, m0), (u1, m1)) = (w[0], w[1]);
+            if utilization >= u1 {
+                continue
+            }
+            // m0 + (m1 - m0) * (u - u0) / (u1 - u0)
+            let progress = Perbill::from_rational(
+                utilization.deconstruct() - u0.deconstruct(),
+                u1.deconstruct() - u0.deconstruct(),
+            );
+            return if m1 >= m0 { m0 + progress * (m1 - m0) } else { m0 - progress * (m0 - m1) }
+        }
+        last.1
     }
 }
 
@@ -145,6 +221,12 @@ impl<Balance: Saturating + Copy> SysInfoDetail<Balance> {
         self.total_rent_fee = self.total_rent_fee.saturating_add(fee_to_stash);
     }
 
+    // 退还租金时，减少租金统计
+    pub fn reduce_rent_fee(&mut self, fee_to_destroy: Balance, fee_to_stash: Balance) {
+        self.total_burn_fee = self.total_burn_fee.saturating_sub(fee_to_destroy);
+        self.total_rent_fee = self.total_rent_fee.saturating_sub(fee_to_stash);
+    }
+
     pub fn on_rent_fee_changed(&mut self, rent_fee: Balance, burn_fee: Balance) {
         self.total_rent_fee = self.total_rent_fee.saturating_add(rent_fee);
         self.total_burn_fee = self.total_burn_fee.saturating_add(burn_fee);
@@ -4,7 +4,7 @@ use parity_scale_codec::{Decode, Encode};
 use scale_info::TypeInfo;
 use sp_runtime::RuntimeDebug;
 // use generic_func::rpc_types::RpcText;
-use crate::{LiveMachine, MachineId, StakerCustomizeInfo};
+use crate::{LiveMachine, MachineId, MachineRentalMode, StakerCustomizeInfo, TimeRange};
 use dbc_support::{
     machine_info::MachineInfo,
     machine_type::{CommitteeUploadInfo, Latitude, Longitude, MachineInfoDetail, MachineStatus},
@@ -188,6 +188,44 @@ pub struct MachineBriefInfo<BlockNumber, AccountId> {
     pub machine_status: MachineStatus<BlockNumber, AccountId>,
 }
 
+/// 搜索可租用机器的过滤条件，未设置的条件不过滤
+/// 价格为单卡每天的租金(USD*10^6)，包括卡主额外加价
+#[derive(PartialEq, Eq, Clone, Encode, Decode, Default, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase", default))]
+pub struct MachineSearchFilter {
+    /// GPU型号，为空时不过滤
+    #[cfg_attr(feature = "std", serde(with = "serde_text"))]
+    pub gpu_type: Vec<u8>,
+    pub min_gpu_mem: Option<u64>,
+    pub min_calc_point: Option<u64>,
+    /// 最少空闲的GPU数量
+    pub min_free_gpu: Option<u32>,
+    pub rental_mode: Option<MachineRentalMode>,
+    pub min_price: Option<u64>,
+    pub max_price: Option<u64>,
+    /// 经纬度范围：(西南角, 东北角)
+    pub bounding_box: Option<((Longitude, Latitude), (Longitude, Latitude))>,
+}
+
+/// 机器某一天(UTC, UNIX 天数)实际可用的时段：特定日期优先于每周循环
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct DayAvailability {
+    pub day: u32,
+    pub ranges: Vec<RangeAvailability>,
+}
+
+/// 可用时段(UTC 小时)及该时段内同时被租用(或预约)的GPU数(时段内的峰值)
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct RangeAvailability {
+    pub range: TimeRange,
+    pub rented_gpu: u32,
+}
+
 #[cfg(feature = "std")]
 #[derive(PartialEq, Eq, Clone, Encode, Decode, Default, RuntimeDebug, TypeInfo)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
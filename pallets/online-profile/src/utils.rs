@@ -1,4 +1,4 @@
-use crate::{BalanceOf, Config, Error, MachineId, Pallet, PosGPUInfo};
+use crate::{BalanceOf, Config, Error, GPUTypeInfo, MachineId, Pallet, PosGPUInfo};
 use dbc_support::{
     machine_info::MachineInfo, verify_slash::OPSlashReason, FIVE_DAYS, FOUR_HOURS, ONE_DAY,
     SEVEN_MINUTES, TEN_DAYS, TWO_DAYS,
@@ -39,7 +39,7 @@ impl<T: Config> Pallet<T> {
     }
 
     /// GPU online/offline
-    // - Writes: PosGPUInfo
+    // - Writes: PosGPUInfo, GPUTypeInfo
     // NOTE: pos_gpu_info only record actual machine grades(reward grade not included)
     pub fn update_region_on_online_changed(
         machine_info: &MachineInfo<T::AccountId, T::BlockNumber, BalanceOf<T>>,
@@ -53,6 +53,9 @@ impl<T: Config> Pallet<T> {
         PosGPUInfo::<T>::mutate(longitude, latitude, |region_mining_power| {
             region_mining_power.on_online_changed(is_online, gpu_num, calc_point);
         });
+        GPUTypeInfo::<T>::mutate(machine_info.gpu_type_and_mem().0, |gpu_type_info| {
+            gpu_type_info.on_online_changed(is_online, gpu_num, calc_point);
+        });
     }
 
     pub fn update_region_on_exit(
@@ -71,10 +74,18 @@ impl<T: Config> Pallet<T> {
         } else {
             PosGPUInfo::<T>::insert(longitude, latitude, region_mining_power);
         }
+
+        let gpu_type = machine_info.gpu_type_and_mem().0;
+        let mut gpu_type_info = Self::gpu_type_info(&gpu_type);
+        if gpu_type_info.on_machine_exit(gpu_num, calc_point) {
+            GPUTypeInfo::<T>::remove(gpu_type);
+        } else {
+            GPUTypeInfo::<T>::insert(gpu_type, gpu_type_info);
+        }
     }
 
     /// GPU rented/surrender
-    // - Writes: PosGPUInfo
+    // - Writes: PosGPUInfo, GPUTypeInfo
     pub fn update_region_on_rent_changed(
         machine_info: &MachineInfo<T::AccountId, T::BlockNumber, BalanceOf<T>>,
         is_rented: bool,
@@ -86,6 +97,9 @@ impl<T: Config> Pallet<T> {
         PosGPUInfo::<T>::mutate(longitude, latitude, |region_mining_power| {
             region_mining_power.on_rent_changed(is_rented, gpu_num);
         });
+        GPUTypeInfo::<T>::mutate(machine_info.gpu_type_and_mem().0, |gpu_type_info| {
+            gpu_type_info.on_rent_changed(is_rented, gpu_num);
+        });
     }
 }
 
@@ -122,11 +136,14 @@ impl<T: Config> Pallet<T> {
         let duration = duration.saturated_into::<u32>();
 
         match slash_reason {
-            OPSlashReason::RentedReportOffline(_) => match duration {
-                0..SEVEN_MINUTES => 2,        // <=7M扣除2%质押币。100%进入国库
-                SEVEN_MINUTES..TWO_DAYS => 4, // <=48H扣除4%质押币。100%进入国库
-                TWO_DAYS..FIVE_DAYS => 30,    // <=120H扣30%质押币，10%给用户，90%进入国库
-                _ => 50,                      // >120H扣除50%质押币。10%给用户，90%进入国库
+            // 心跳超时下线与算工主动下线租用中的机器惩罚相同
+            OPSlashReason::RentedReportOffline(_) | OPSlashReason::HeartbeatOffline(_) => {
+                match duration {
+                    0..SEVEN_MINUTES => 2,        // <=7M扣除2%质押币。100%进入国库
+                    SEVEN_MINUTES..TWO_DAYS => 4, // <=48H扣除4%质押币。100%进入国库
+                    TWO_DAYS..FIVE_DAYS => 30,    // <=120H扣30%质押币，10%给用户，90%进入国库
+                    _ => 50,                      // >120H扣除50%质押币。10%给用户，90%进入国库
+                }
             },
             OPSlashReason::OnlineReportOffline(_) => match duration {
                 // TODO: 如果机器从首次上线时间起超过365天，剩下20%押金可以申请退回。扣除80%质押币。
@@ -181,7 +198,9 @@ pub fn reach_max_slash<BlockNumber>(
     };
 
     match slash_reason {
-        OPSlashReason::RentedReportOffline(_) => max_slash(5 * ONE_DAY as u64),
+        OPSlashReason::RentedReportOffline(_) | OPSlashReason::HeartbeatOffline(_) => {
+            max_slash(5 * ONE_DAY as u64)
+        },
         OPSlashReason::OnlineReportOffline(_) => max_slash(10 * ONE_DAY as u64),
         OPSlashReason::RentedInaccessible(_) => max_slash(5 * ONE_DAY as u64),
         OPSlashReason::RentedHardwareMalfunction(_) => max_slash(5 * ONE_DAY as u64),
@@ -0,0 +1,37 @@
+//! Weights for online_profile
+//!
+//! 权重按调用时读写的存储项数量估算
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{
+    traits::Get,
+    weights::{constants::RocksDbWeight, Weight},
+};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for online_profile.
+pub trait WeightInfo {
+    fn set_machine_utc_offset() -> Weight;
+}
+
+/// Weights for online_profile using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+    // Storage: MachinesInfo, MachineUtcOffset
+    fn set_machine_utc_offset() -> Weight {
+        Weight::from_parts(10000, 0)
+            .saturating_add(T::DbWeight::get().reads(1))
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+    fn set_machine_utc_offset() -> Weight {
+        Weight::from_parts(10000, 0)
+            .saturating_add(RocksDbWeight::get().reads(1))
+            .saturating_add(RocksDbWeight::get().writes(1))
+    }
+}
@@ -8,13 +8,15 @@ use sp_runtime::traits::MaybeDisplay;
 use sp_std::prelude::Vec;
 
 use dbc_support::{
-    rental_type::{MachineGPUOrder, RentOrderDetail},
+    rental_type::{MachineDisputeStats, MachineGPUOrder, RentEscrowDetail, RentOrderDetail},
     MachineId, RentOrderId,
 };
 
 // Here we declare the runtime API. It is implemented it the `impl` block in
 // runtime amalgamator file (the `runtime/src/lib.rs`)
 sp_api::decl_runtime_apis! {
+    // Version 2 adds `get_order_escrow` and `get_machine_dispute_stats`
+    #[api_version(2)]
     pub trait RmRpcApi<AccountId, BlockNumber, Balance> where
         AccountId: Codec + Ord,
         BlockNumber: Codec + MaybeDisplay,
@@ -25,5 +27,11 @@ sp_api::decl_runtime_apis! {
 
         fn is_machine_renter(machine_id: MachineId, renter: AccountId) -> bool;
         fn get_machine_rent_id(machine_id: MachineId) -> MachineGPUOrder;
+
+        #[api_version(2)]
+        fn get_order_escrow(rent_id: RentOrderId) -> Option<RentEscrowDetail<AccountId, BlockNumber, Balance>>;
+
+        #[api_version(2)]
+        fn get_machine_dispute_stats(machine_id: MachineId) -> MachineDisputeStats;
     }
 }
@@ -6,13 +6,13 @@ use jsonrpsee::{
     types::error::{CallError, ErrorCode, ErrorObject},
 };
 use parity_scale_codec::Codec;
-use sp_api::ProvideRuntimeApi;
+use sp_api::{ApiExt, ProvideRuntimeApi};
 use sp_blockchain::HeaderBackend;
 use sp_runtime::traits::{Block as BlockT, MaybeDisplay};
 use std::{fmt::Display, str::FromStr, sync::Arc};
 
 use dbc_support::{
-    rental_type::{MachineGPUOrder, RentOrderDetail},
+    rental_type::{MachineDisputeStats, MachineGPUOrder, RentEscrowDetail, RentOrderDetail},
     rpc_types::RpcBalance,
     RentOrderId,
 };
@@ -51,6 +51,20 @@ where
         machine_id: String,
         at: Option<BlockHash>,
     ) -> RpcResult<MachineGPUOrder>;
+
+    #[method(name = "rentMachine_getOrderEscrow")]
+    fn get_order_escrow(
+        &self,
+        rent_id: RentOrderId,
+        at: Option<BlockHash>,
+    ) -> RpcResult<RentEscrowDetail<AccountId, BlockNumber, RpcBalance<Balance>>>;
+
+    #[method(name = "rentMachine_getMachineDisputeStats")]
+    fn get_machine_dispute_stats(
+        &self,
+        machine_id: String,
+        at: Option<BlockHash>,
+    ) -> RpcResult<MachineDisputeStats>;
 }
 
 pub struct RmStorage<C, M> {
@@ -164,4 +178,82 @@ where
 
         Ok(runtime_api_result)
     }
+
+    fn get_order_escrow(
+        &self,
+        rent_id: RentOrderId,
+        at: Option<Block::Hash>,
+    ) -> RpcResult<RentEscrowDetail<AccountId, BlockNumber, RpcBalance<Balance>>> {
+        let api = self.client.runtime_api();
+        let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        // get_order_escrow is only available since runtime api version 2
+        ensure_api_version::<_, Block, AccountId, BlockNumber, Balance>(&api, at_hash)?;
+
+        let runtime_api_result = api.get_order_escrow(at_hash, rent_id);
+        if let Ok(Some(escrow)) = runtime_api_result {
+            return Ok(RentEscrowDetail {
+                renter: escrow.renter,
+                total_amount: escrow.total_amount.into(),
+                released_amount: escrow.released_amount.into(),
+                refunded_amount: escrow.refunded_amount.into(),
+                last_settled: escrow.last_settled,
+                rent_end: escrow.rent_end,
+            })
+        }
+        return Err(JsonRpseeError::Call(CallError::Custom(ErrorObject::owned(
+            ErrorCode::InternalError.code(),
+            "Something wrong",
+            Some("NotFound"),
+        ))))
+    }
+
+    fn get_machine_dispute_stats(
+        &self,
+        machine_id: String,
+        at: Option<Block::Hash>,
+    ) -> RpcResult<MachineDisputeStats> {
+        let api = self.client.runtime_api();
+        let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        ensure_api_version::<_, Block, AccountId, BlockNumber, Balance>(&api, at_hash)?;
+
+        let machine_id = machine_id.as_bytes().to_vec();
+        let runtime_api_result =
+            api.get_machine_dispute_stats(at_hash, machine_id).map_err(|e| {
+                JsonRpseeError::Call(CallError::Custom(ErrorObject::owned(
+                    ErrorCode::InternalError.code(),
+                    "Something wrong",
+                    Some(e.to_string()),
+                )))
+            })?;
+
+        Ok(runtime_api_result)
+    }
+}
+
+fn ensure_api_version<Api, Block, AccountId, BlockNumber, Balance>(
+    api: &Api,
+    at_hash: <Block as BlockT>::Hash,
+) -> RpcResult<()>
+where
+    Block: BlockT,
+    AccountId: Codec + Ord,
+    BlockNumber: Codec + MaybeDisplay,
+    Balance: Codec + MaybeDisplay,
+    Api: ApiExt<Block> + RmStorageRuntimeApi<Block, AccountId, BlockNumber, Balance>,
+{
+    let api_version = api
+        .api_version::<dyn RmStorageRuntimeApi<Block, AccountId, BlockNumber, Balance>>(at_hash)
+        .ok()
+        .flatten()
+        .unwrap_or_default();
+    if api_version < 2 {
+        return Err(JsonRpseeError::Call(CallError::Custom(ErrorObject::owned(
+            ErrorCode::MethodNotFound.code(),
+            "Not supported by the runtime at this block",
+            None::<String>,
+        ))))
+    }
+    Ok(())
 }
@@ -0,0 +1,223 @@
+use crate::{
+    AssetIdOf, BalanceOf, Config, DlcBurnDetails, Error, Event, OwedAssetRentRefund, Pallet,
+    RentOrderAsset, RentOrderPaidFee, TotalBurnedDlc,
+};
+use dbc_support::{
+    rental_type::{DlcBurnDetail, RentOrderDetail},
+    traits::DlcPrice,
+    RentOrderId,
+};
+use frame_support::{
+    dispatch::{DispatchResult, DispatchResultWithPostInfo},
+    ensure,
+    traits::{
+        fungibles::{Inspect, Mutate},
+        tokens::{Fortitude, Precision, Preservation},
+        Get,
+    },
+};
+use sp_runtime::{
+    traits::{AccountIdConversion, Saturating, Zero},
+    Perbill,
+};
+
+// 使用资产(DLC)支付租金：租用时租金转入托管账户，确认租用后一次性支付，续租时直接支付，
+// 其中rent_fee_destroy_percent的部分直接销毁并记录
+impl<T: Config> Pallet<T> {
+    // 托管资产租金的账户
+    pub fn rent_fee_account() -> T::AccountId {
+        T::PalletId::get().into_account_truncating()
+    }
+
+    // 按DLC价格计算租金，并转入托管账户
+    pub(crate) fn lock_asset_rent_fee(
+        renter: &T::AccountId,
+        rent_id: RentOrderId,
+        asset_id: AssetIdOf<T>,
+        rent_fee_value: u64,
+    ) -> Result<BalanceOf<T>, Error<T>> {
+        ensure!(asset_id == T::DlcAssetId::get(), Error::<T>::AssetNotSupported);
+
+        let rent_fee = <T as Config>::DlcPrice::get_dlc_amount_by_value(rent_fee_value)
+            .ok_or(Error::<T>::GetMachinePriceFailed)?;
+        T::Assets::transfer(
+            asset_id,
+            renter,
+            &Self::rent_fee_account(),
+            rent_fee,
+            Preservation::Preserve,
+        )
+        .map_err(|_| Error::<T>::InsufficientValue)?;
+
+        // 续租时累加到订单的资产租金中
+        RentOrderAsset::<T>::mutate(rent_id, |order_asset| {
+            let total_fee = order_asset.map(|(_, fee)| fee).unwrap_or_default();
+            *order_asset = Some((asset_id, total_fee.saturating_add(rent_fee)));
+        });
+        Ok(rent_fee)
+    }
+
+    // 确认租用后支付租金：销毁部分直接销毁，其余支付给租金接收方
+    pub(crate) fn pay_asset_rent_fee(
+        renter: &T::AccountId,
+        rent_id: RentOrderId,
+        asset_id: AssetIdOf<T>,
+        rent_fee: BalanceOf<T>,
+        machine_stash: T::AccountId,
+    ) -> DispatchResult {
+        let rent_fee_account = Self::rent_fee_account();
+
+        let destroy_percent = <online_profile::Pallet<T>>::rent_fee_destroy_percent();
+        let fee_to_burn = destroy_percent * rent_fee;
+        let fee_to_stash = rent_fee.saturating_sub(fee_to_burn);
+
+        if !fee_to_burn.is_zero() {
+            T::Assets::burn_from(
+                asset_id,
+                &rent_fee_account,
+                fee_to_burn,
+                Precision::Exact,
+                Fortitude::Polite,
+            )
+            .map_err(|_| Error::<T>::AssetTransferFailed)?;
+
+            DlcBurnDetails::<T>::mutate(rent_id, |burn_details| {
+                burn_details.push(DlcBurnDetail {
+                    rent_id,
+                    renter: renter.clone(),
+                    burned_amount: fee_to_burn,
+                    at: <frame_system::Pallet<T>>::block_number(),
+                })
+            });
+            TotalBurnedDlc::<T>::mutate(|total_burned| {
+                *total_burned = total_burned.saturating_add(fee_to_burn)
+            });
+            Self::deposit_event(Event::BurnAssetRentFee(
+                rent_id,
+                renter.clone(),
+                asset_id,
+                fee_to_burn,
+            ));
+        }
+
+        if !fee_to_stash.is_zero() {
+            let rent_receiver = Self::rent_order_receiver(&rent_id).unwrap_or_else(|| {
+                <online_profile::Pallet<T>>::effective_rent_receiver(&machine_stash)
+            });
+            T::Assets::transfer(
+                asset_id,
+                &rent_fee_account,
+                &rent_receiver,
+                fee_to_stash,
+                Preservation::Expendable,
+            )
+            .map_err(|_| Error::<T>::AssetTransferFailed)?;
+        }
+        RentOrderPaidFee::<T>::mutate(rent_id, |(paid_to_stash, paid_to_burn)| {
+            *paid_to_stash = paid_to_stash.saturating_add(fee_to_stash);
+            *paid_to_burn = paid_to_burn.saturating_add(fee_to_burn);
+        });
+        Ok(())
+    }
+
+    // 提前终止时，按未使用的时长使用同一资产退还租金：
+    // 租金接收方退还收到的部分，已销毁的部分重新铸造给租用人
+    pub(crate) fn charge_back_asset_rent_fee(
+        rent_id: RentOrderId,
+        asset_id: AssetIdOf<T>,
+        rent_info: &RentOrderDetail<T::AccountId, T::BlockNumber, BalanceOf<T>>,
+        machine_stash: &T::AccountId,
+        renter: &T::AccountId,
+        now: T::BlockNumber,
+    ) -> Result<BalanceOf<T>, Error<T>> {
+        let unused_ratio = Perbill::from_rational(
+            rent_info.rent_end.saturating_sub(now),
+            rent_info.rent_end.saturating_sub(rent_info.rent_start),
+        );
+        let (paid_to_stash, paid_to_burn) = Self::rent_order_paid_fee(rent_id);
+        let refund_from_stash = unused_ratio * paid_to_stash;
+        let refund_burned = unused_ratio * paid_to_burn;
+
+        if !refund_from_stash.is_zero() {
+            let rent_receiver = Self::rent_order_receiver(&rent_id).unwrap_or_else(|| {
+                <online_profile::Pallet<T>>::effective_rent_receiver(machine_stash)
+            });
+            // 租金接收方余额不足时记录为欠款，不影响订单的终止
+            if T::Assets::transfer(
+                asset_id,
+                &rent_receiver,
+                renter,
+                refund_from_stash,
+                Preservation::Preserve,
+            )
+            .is_err()
+            {
+                OwedAssetRentRefund::<T>::mutate((&rent_receiver, renter, asset_id), |owed| {
+                    *owed = owed.saturating_add(refund_from_stash)
+                });
+                Self::deposit_event(Event::AssetRentRefundOwed(
+                    rent_id,
+                    rent_receiver,
+                    renter.clone(),
+                    asset_id,
+                    refund_from_stash,
+                ));
+            }
+        }
+        if !refund_burned.is_zero() {
+            T::Assets::mint_into(asset_id, renter, refund_burned)
+                .map_err(|_| Error::<T>::RefundFailed)?;
+            TotalBurnedDlc::<T>::mutate(|total_burned| {
+                *total_burned = total_burned.saturating_sub(refund_burned)
+            });
+        }
+        Ok(refund_from_stash.saturating_add(refund_burned))
+    }
+
+    pub(crate) fn do_claim_owed_asset_rent_refund(
+        renter: T::AccountId,
+        debtor: T::AccountId,
+        asset_id: AssetIdOf<T>,
+    ) -> DispatchResultWithPostInfo {
+        let owed = Self::owed_asset_rent_refund((&debtor, &renter, asset_id));
+        ensure!(!owed.is_zero(), Error::<T>::NoOwedRentRefund);
+
+        // 欠款方余额不足时领取可转出的部分
+        let transferable = T::Assets::reducible_balance(
+            asset_id,
+            &debtor,
+            Preservation::Preserve,
+            Fortitude::Polite,
+        );
+        let amount = owed.min(transferable);
+        ensure!(!amount.is_zero(), Error::<T>::RefundFailed);
+        T::Assets::transfer(asset_id, &debtor, &renter, amount, Preservation::Preserve)
+            .map_err(|_| Error::<T>::RefundFailed)?;
+
+        let owed = owed.saturating_sub(amount);
+        if owed.is_zero() {
+            OwedAssetRentRefund::<T>::remove((&debtor, &renter, asset_id));
+        } else {
+            OwedAssetRentRefund::<T>::insert((&debtor, &renter, asset_id), owed);
+        }
+        Self::deposit_event(Event::ClaimOwedAssetRentRefund(renter, debtor, asset_id, amount));
+        Ok(().into())
+    }
+
+    // 订单未确认时，托管的租金退还给租用人
+    pub(crate) fn refund_asset_rent_fee(
+        rent_id: RentOrderId,
+        renter: &T::AccountId,
+    ) -> Result<(), ()> {
+        let (asset_id, rent_fee) = RentOrderAsset::<T>::take(rent_id).ok_or(())?;
+        T::Assets::transfer(
+            asset_id,
+            &Self::rent_fee_account(),
+            renter,
+            rent_fee,
+            Preservation::Expendable,
+        )
+        .map_err(|_| ())?;
+        Ok(())
+    }
+}
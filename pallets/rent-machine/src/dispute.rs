@@ -0,0 +1,431 @@
+use crate::{
+    BalanceOf, Config, DisputeDeadline, Error, Event, MachineDispute, NextDisputeSlashId,
+    OrderEscrow, Pallet, PendingDisputeSlash, PendingDisputeSlashReview, RentDispute,
+    RentOrderPaidFee, UnhandledDisputeSlash, DISPUTE_RESOLVE_DURATION,
+};
+use dbc_support::{
+    rental_type::{
+        DisputeSlashInfo, DisputeSlashResult, DisputeSlashReviewInfo, RentDisputeDetail,
+        RentDisputeStatus, RentStatus,
+    },
+    traits::{GNOps, ManageCommittee, RTOps},
+    ItemList, RentOrderId, SlashId, TWO_DAYS,
+};
+use frame_support::{
+    dispatch::{DispatchResult, DispatchResultWithPostInfo},
+    ensure,
+    pallet_prelude::Weight,
+    traits::{
+        fungibles::Mutate, tokens::Preservation, Currency, ExistenceRequirement::KeepAlive, Get,
+    },
+};
+use sp_core::H256;
+use sp_runtime::{
+    traits::{Saturating, Zero},
+    Perbill,
+};
+use sp_std::{vec, vec::Vec};
+
+// 租用争议：租用者对部分服务不达标的订单提交争议，由一个委员会抢单处理，
+// 按比例退还租金：优先从托管的租金中退还，不足部分从租金接收方退还。每个订单只能提交一次争议。
+// 抢单的委员会需要在截止时间前处理，否则争议重新等待抢单，委员会在两天后被惩罚，期间可申请复审
+impl<T: Config> Pallet<T> {
+    pub(crate) fn do_dispute_rent(
+        renter: T::AccountId,
+        rent_id: RentOrderId,
+        evidence_hash: H256,
+    ) -> DispatchResultWithPostInfo {
+        let now = <frame_system::Pallet<T>>::block_number();
+        let rent_info = Self::rent_info(rent_id).ok_or(Error::<T>::NoOrderExist)?;
+        ensure!(rent_info.renter == renter, Error::<T>::NotMachineRenter);
+        ensure!(rent_info.rent_status == RentStatus::Renting, Error::<T>::StatusNotAllowed);
+        ensure!(!RentDispute::<T>::contains_key(rent_id), Error::<T>::DisputeAlreadyExist);
+
+        // 流式支付时为托管的租金总额，否则为已支付给租金接收方的租金，均扣除已退还的部分
+        let refundable = match Self::order_escrow(rent_id) {
+            Some(escrow) => escrow.total_amount.saturating_sub(escrow.refunded_amount),
+            None => Self::rent_order_paid_fee(rent_id).0,
+        };
+        ensure!(!refundable.is_zero(), Error::<T>::NoRefundableRentFee);
+
+        let machine_info = <online_profile::Pallet<T>>::machines_info(&rent_info.machine_id)
+            .ok_or(Error::<T>::MachineNotFound)?;
+        let rent_receiver = Self::rent_order_receiver(rent_id).unwrap_or_else(|| {
+            <online_profile::Pallet<T>>::effective_rent_receiver(&machine_info.machine_stash)
+        });
+
+        // NOTE: 提交争议需要扣除10个DBC
+        <generic_func::Pallet<T>>::pay_fixed_tx_fee(renter.clone())
+            .map_err(|_| Error::<T>::PayTxFeeFailed)?;
+
+        MachineDispute::<T>::mutate(&rent_info.machine_id, |stats| {
+            stats.dispute_count = stats.dispute_count.saturating_add(1)
+        });
+        RentDispute::<T>::insert(
+            rent_id,
+            RentDisputeDetail {
+                machine_id: rent_info.machine_id,
+                renter: renter.clone(),
+                rent_receiver,
+                evidence_hash,
+                refundable,
+                dispute_time: now,
+                committee: None,
+                book_time: Zero::zero(),
+                committee_stake: Zero::zero(),
+                dispute_status: RentDisputeStatus::WaitingBook,
+            },
+        );
+
+        Self::deposit_event(Event::RentDisputed(rent_id, renter, evidence_hash));
+        Ok(().into())
+    }
+
+    // 与委员会抢单处理故障报告一样，抢单时锁定一次抢单的质押
+    pub(crate) fn book_dispute(
+        committee: T::AccountId,
+        rent_id: RentOrderId,
+    ) -> DispatchResultWithPostInfo {
+        let now = <frame_system::Pallet<T>>::block_number();
+        ensure!(T::ManageCommittee::is_valid_committee(&committee), Error::<T>::NotCommittee);
+
+        let mut dispute = Self::rent_dispute(rent_id).ok_or(Error::<T>::DisputeNotFound)?;
+        ensure!(dispute.renter != committee, Error::<T>::DisputeStatusNotAllowed);
+        ensure!(
+            dispute.can_book(&committee, now, DISPUTE_RESOLVE_DURATION.into()),
+            Error::<T>::DisputeStatusNotAllowed
+        );
+
+        let order_stake = T::ManageCommittee::stake_per_order().ok_or(Error::<T>::StakeFailed)?;
+        T::ManageCommittee::change_used_stake(committee.clone(), order_stake, true)
+            .map_err(|_| Error::<T>::StakeFailed)?;
+
+        // 之前抢单的委员会超时未处理，且截止时间的检查尚未执行时，在此惩罚
+        if let Some(pre_committee) = dispute.committee.clone() {
+            Self::add_dispute_slash(rent_id, pre_committee, dispute.committee_stake, now);
+            Self::rm_dispute_deadline(rent_id, &dispute);
+        }
+
+        dispute.book(committee.clone(), now, order_stake);
+        DisputeDeadline::<T>::mutate(now.saturating_add(DISPUTE_RESOLVE_DURATION.into()), |ids| {
+            ItemList::add_item(ids, rent_id)
+        });
+        RentDispute::<T>::insert(rent_id, dispute);
+
+        Self::deposit_event(Event::CommitteeBookDispute(rent_id, committee));
+        Ok(().into())
+    }
+
+    pub(crate) fn resolve_dispute(
+        committee: T::AccountId,
+        rent_id: RentOrderId,
+        refund_percent: Perbill,
+    ) -> DispatchResultWithPostInfo {
+        let now = <frame_system::Pallet<T>>::block_number();
+        let mut dispute = Self::rent_dispute(rent_id).ok_or(Error::<T>::DisputeNotFound)?;
+        ensure!(
+            dispute.dispute_status == RentDisputeStatus::Verifying &&
+                dispute.committee.as_ref() == Some(&committee),
+            Error::<T>::DisputeStatusNotAllowed
+        );
+        ensure!(
+            now < dispute.book_time.saturating_add(DISPUTE_RESOLVE_DURATION.into()),
+            Error::<T>::DisputeStatusNotAllowed
+        );
+
+        let refund_amount = refund_percent * dispute.refundable;
+        Self::refund_dispute(rent_id, &dispute, refund_amount)?;
+
+        let _ = T::ManageCommittee::change_used_stake(
+            committee.clone(),
+            dispute.committee_stake,
+            false,
+        );
+        if !refund_amount.is_zero() {
+            MachineDispute::<T>::mutate(&dispute.machine_id, |stats| {
+                stats.refund_count = stats.refund_count.saturating_add(1)
+            });
+        }
+
+        // 处理完成后保留争议直到订单结束，拒绝租用者再次提交争议
+        Self::rm_dispute_deadline(rent_id, &dispute);
+        dispute.dispute_status = RentDisputeStatus::Resolved;
+        RentDispute::<T>::insert(rent_id, dispute);
+
+        Self::deposit_event(Event::DisputeResolved(rent_id, committee, refund_amount));
+        Ok(().into())
+    }
+
+    // 优先退还托管中尚未结算的租金，不足部分由租金接收方退还
+    fn refund_dispute(
+        rent_id: RentOrderId,
+        dispute: &RentDisputeDetail<T::AccountId, T::BlockNumber, BalanceOf<T>>,
+        amount: BalanceOf<T>,
+    ) -> DispatchResult {
+        let mut from_receiver = amount;
+
+        if let Some(mut escrow) = Self::order_escrow(rent_id) {
+            let from_escrow = amount.min(escrow.remaining());
+            if !from_escrow.is_zero() {
+                Self::refund_from_escrow(&dispute.renter, from_escrow)
+                    .map_err(|_| Error::<T>::DisputeRefundFailed)?;
+                let last_settled = escrow.last_settled;
+                escrow.refund(from_escrow, last_settled);
+                OrderEscrow::<T>::insert(rent_id, escrow);
+                from_receiver = from_receiver.saturating_sub(from_escrow);
+            }
+        }
+
+        if from_receiver.is_zero() {
+            return Ok(())
+        }
+        // 订单结束时按已支付的租金退款，需扣除争议已退还的部分，避免重复退款
+        RentOrderPaidFee::<T>::mutate(rent_id, |(paid_to_stash, _)| {
+            *paid_to_stash = paid_to_stash.saturating_sub(from_receiver)
+        });
+        if let Some((asset_id, _)) = Self::rent_order_asset(rent_id) {
+            // 资产支付的订单，使用同一资产退还
+            T::Assets::transfer(
+                asset_id,
+                &dispute.rent_receiver,
+                &dispute.renter,
+                from_receiver,
+                Preservation::Preserve,
+            )
+            .map_err(|_| Error::<T>::DisputeRefundFailed)?;
+        } else {
+            <T as Config>::Currency::transfer(
+                &dispute.rent_receiver,
+                &dispute.renter,
+                from_receiver,
+                KeepAlive,
+            )
+            .map_err(|_| Error::<T>::DisputeRefundFailed)?;
+            let _ = T::RTOps::reduce_machine_rent_fee(
+                dispute.machine_id.clone(),
+                Zero::zero(),
+                from_receiver,
+            );
+        }
+        Ok(())
+    }
+
+    pub(crate) fn dispute_deadline_weight(block_number: T::BlockNumber) -> Weight {
+        let count = DisputeDeadline::<T>::decode_len(block_number).unwrap_or_default() as u64;
+        // 每个到期的争议：读写争议、惩罚ID及待执行的惩罚
+        T::DbWeight::get().reads_writes(1 + count * 4, 1 + count * 4)
+    }
+
+    // 抢单的委员会在截止时间前未处理：记录对其抢单质押的惩罚，争议重新等待抢单
+    pub(crate) fn check_dispute_deadline(block_number: T::BlockNumber) {
+        let rent_ids = DisputeDeadline::<T>::take(block_number);
+        for rent_id in rent_ids {
+            let mut dispute = match Self::rent_dispute(rent_id) {
+                Some(dispute) => dispute,
+                None => continue,
+            };
+            // 已处理或已被重新抢单
+            if dispute.dispute_status != RentDisputeStatus::Verifying ||
+                dispute.book_time.saturating_add(DISPUTE_RESOLVE_DURATION.into()) != block_number
+            {
+                continue
+            }
+            let committee = match dispute.committee.clone() {
+                Some(committee) => committee,
+                None => continue,
+            };
+
+            Self::add_dispute_slash(rent_id, committee, dispute.committee_stake, block_number);
+            dispute.unbook(Zero::zero());
+            RentDispute::<T>::insert(rent_id, dispute);
+        }
+    }
+
+    // 订单结束时移除争议，未处理时释放抢单委员会的质押
+    pub(crate) fn expire_dispute(rent_id: RentOrderId) {
+        let dispute = match RentDispute::<T>::take(rent_id) {
+            Some(dispute) => dispute,
+            None => return,
+        };
+        if dispute.dispute_status == RentDisputeStatus::Resolved {
+            return
+        }
+
+        if dispute.dispute_status == RentDisputeStatus::Verifying {
+            if let Some(committee) = dispute.committee.clone() {
+                let _ = T::ManageCommittee::change_used_stake(
+                    committee,
+                    dispute.committee_stake,
+                    false,
+                );
+            }
+            Self::rm_dispute_deadline(rent_id, &dispute);
+        }
+        Self::deposit_event(Event::DisputeExpired(rent_id));
+    }
+
+    // 未按时处理争议的委员会：抢单质押保持锁定，两天后执行惩罚，期间可以申请复审
+    fn add_dispute_slash(
+        rent_id: RentOrderId,
+        committee: T::AccountId,
+        stake: BalanceOf<T>,
+        now: T::BlockNumber,
+    ) {
+        let slash_id = Self::next_dispute_slash_id();
+        NextDisputeSlashId::<T>::put(slash_id.saturating_add(1));
+
+        let slash_exec_time = now.saturating_add(TWO_DAYS.into());
+        PendingDisputeSlash::<T>::insert(
+            slash_id,
+            DisputeSlashInfo {
+                rent_id,
+                committee: committee.clone(),
+                committee_stake: stake,
+                slash_time: now,
+                slash_exec_time,
+                slash_result: DisputeSlashResult::Pending,
+            },
+        );
+        UnhandledDisputeSlash::<T>::mutate(slash_exec_time, |ids| {
+            ItemList::add_item(ids, slash_id)
+        });
+
+        Self::deposit_event(Event::DisputeSlashPending(slash_id, rent_id, committee, stake));
+    }
+
+    pub(crate) fn do_apply_dispute_slash_review(
+        applicant: T::AccountId,
+        slash_id: SlashId,
+        reason: Vec<u8>,
+    ) -> DispatchResultWithPostInfo {
+        let now = <frame_system::Pallet<T>>::block_number();
+        let slash_info =
+            Self::pending_dispute_slash(slash_id).ok_or(Error::<T>::DisputeSlashNotFound)?;
+
+        ensure!(slash_info.committee == applicant, Error::<T>::NotSlashed);
+        ensure!(
+            !PendingDisputeSlashReview::<T>::contains_key(slash_id),
+            Error::<T>::AlreadyApplied
+        );
+        ensure!(
+            slash_info.slash_result == DisputeSlashResult::Pending &&
+                now < slash_info.slash_exec_time,
+            Error::<T>::ExpiredApply
+        );
+
+        // 申请复审时锁定一次抢单的质押，复审失败时与抢单质押一同被惩罚
+        let stake_amount = T::ManageCommittee::stake_per_order().ok_or(Error::<T>::StakeFailed)?;
+        T::ManageCommittee::change_total_stake(applicant.clone(), stake_amount, true, true)
+            .map_err(|_| Error::<T>::StakeFailed)?;
+        T::ManageCommittee::change_used_stake(applicant.clone(), stake_amount, true)
+            .map_err(|_| Error::<T>::StakeFailed)?;
+
+        PendingDisputeSlashReview::<T>::insert(
+            slash_id,
+            DisputeSlashReviewInfo {
+                applicant: applicant.clone(),
+                staked_amount: stake_amount,
+                apply_time: now,
+                expire_time: slash_info.slash_exec_time,
+                reason,
+            },
+        );
+
+        Self::deposit_event(Event::ApplyDisputeSlashReview(slash_id, applicant));
+        Ok(().into())
+    }
+
+    // 复审通过：释放委员会的抢单质押及申请复审时锁定的质押
+    pub(crate) fn do_cancel_dispute_slash(slash_id: SlashId) -> DispatchResultWithPostInfo {
+        let now = <frame_system::Pallet<T>>::block_number();
+        let review_info = Self::pending_dispute_slash_review(slash_id)
+            .ok_or(Error::<T>::NotPendingReviewSlash)?;
+        let mut slash_info =
+            Self::pending_dispute_slash(slash_id).ok_or(Error::<T>::DisputeSlashNotFound)?;
+        ensure!(review_info.expire_time > now, Error::<T>::ExpiredApply);
+
+        let committee = slash_info.committee.clone();
+        let _ = T::ManageCommittee::change_used_stake(
+            committee.clone(),
+            slash_info.committee_stake,
+            false,
+        );
+        let _ = T::ManageCommittee::change_used_stake(
+            committee.clone(),
+            review_info.staked_amount,
+            false,
+        );
+        let _ = T::ManageCommittee::change_total_stake(
+            committee,
+            review_info.staked_amount,
+            false,
+            true,
+        );
+
+        UnhandledDisputeSlash::<T>::mutate(slash_info.slash_exec_time, |ids| {
+            ItemList::rm_item(ids, &slash_id)
+        });
+        slash_info.slash_result = DisputeSlashResult::Canceled;
+        PendingDisputeSlash::<T>::insert(slash_id, slash_info);
+        PendingDisputeSlashReview::<T>::remove(slash_id);
+
+        Self::deposit_event(Event::DisputeSlashCanceled(slash_id));
+        Ok(().into())
+    }
+
+    pub(crate) fn dispute_slash_weight(block_number: T::BlockNumber) -> Weight {
+        let count = UnhandledDisputeSlash::<T>::decode_len(block_number).unwrap_or_default() as u64;
+        // 每个到期的惩罚：读写惩罚、复审、委员会质押、信誉，及惩罚时的余额
+        T::DbWeight::get().reads_writes(1 + count * 6, 1 + count * 6)
+    }
+
+    // 执行到期的惩罚：扣除抢单质押并记录为未完成工作，复审未通过时同时扣除复审质押
+    pub(crate) fn exec_dispute_slash(block_number: T::BlockNumber) {
+        for slash_id in UnhandledDisputeSlash::<T>::take(block_number) {
+            let mut slash_info = match Self::pending_dispute_slash(slash_id) {
+                Some(slash_info) => slash_info,
+                None => continue,
+            };
+            if slash_info.slash_result != DisputeSlashResult::Pending {
+                continue
+            }
+
+            let committee = slash_info.committee.clone();
+            let mut slash_amount = slash_info.committee_stake;
+            let _ = T::ManageCommittee::change_used_stake(committee.clone(), slash_amount, false);
+            let _ = T::ManageCommittee::slash_stake(committee.clone(), slash_amount);
+
+            // 没收申请复审时的质押，由委员会自己承担，不分给委托人
+            if let Some(review_info) = PendingDisputeSlashReview::<T>::take(slash_id) {
+                let review_stake = review_info.staked_amount;
+                let _ =
+                    T::ManageCommittee::change_used_stake(committee.clone(), review_stake, false);
+                let _ = T::ManageCommittee::change_total_stake(
+                    committee.clone(),
+                    review_stake,
+                    false,
+                    false,
+                );
+                slash_amount = slash_amount.saturating_add(review_stake);
+            }
+
+            let _ =
+                T::SlashAndReward::slash_and_reward(vec![committee.clone()], slash_amount, vec![]);
+            T::ManageCommittee::update_reputation(&[], &[], &[committee.clone()]);
+
+            slash_info.slash_result = DisputeSlashResult::Executed;
+            let rent_id = slash_info.rent_id;
+            PendingDisputeSlash::<T>::insert(slash_id, slash_info);
+
+            Self::deposit_event(Event::DisputeCommitteeSlashed(rent_id, committee, slash_amount));
+        }
+    }
+
+    fn rm_dispute_deadline(
+        rent_id: RentOrderId,
+        dispute: &RentDisputeDetail<T::AccountId, T::BlockNumber, BalanceOf<T>>,
+    ) {
+        let deadline = dispute.book_time.saturating_add(DISPUTE_RESOLVE_DURATION.into());
+        DisputeDeadline::<T>::mutate(deadline, |ids| ItemList::rm_item(ids, &rent_id));
+    }
+}
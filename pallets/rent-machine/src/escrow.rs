@@ -0,0 +1,195 @@
+use crate::{BalanceOf, Config, Error, EscrowSettling, Event, OrderEscrow, Pallet};
+use dbc_support::{rental_type::RentEscrowDetail, ItemList, RentOrderId, ONE_HOUR};
+use frame_support::{
+    dispatch::DispatchResult,
+    storage::with_storage_layer,
+    traits::{
+        Currency,
+        ExistenceRequirement::{AllowDeath, KeepAlive},
+        Get,
+    },
+    weights::Weight,
+};
+use sp_runtime::{
+    traits::{AccountIdConversion, Saturating, Zero},
+    Perbill,
+};
+
+// 结算一个托管订单的读写次数
+const SETTLE_ESCROW_READS_WRITES: u64 = 8;
+
+// 流式支付：租金托管在模块账户中，每个订单从确认租用起每小时结算一次
+impl<T: Config> Pallet<T> {
+    // 托管流式支付租金(DBC)的账户
+    pub fn escrow_account() -> T::AccountId {
+        T::PalletId::get().into_sub_account_truncating(b"escrow")
+    }
+
+    // confirm_rent时创建托管：租用时锁定的租金转入托管账户，不再一次性支付
+    pub(crate) fn create_escrow(
+        rent_id: RentOrderId,
+        renter: T::AccountId,
+        amount: BalanceOf<T>,
+        now: T::BlockNumber,
+        rent_end: T::BlockNumber,
+    ) -> DispatchResult {
+        Self::change_renter_total_stake(&renter, amount, false)
+            .map_err(|_| Error::<T>::UnlockToPayFeeFailed)?;
+        <T as Config>::Currency::transfer(&renter, &Self::escrow_account(), amount, KeepAlive)?;
+
+        OrderEscrow::<T>::insert(
+            rent_id,
+            RentEscrowDetail {
+                renter,
+                total_amount: amount,
+                released_amount: Zero::zero(),
+                refunded_amount: Zero::zero(),
+                last_settled: now,
+                rent_end,
+            },
+        );
+        Self::schedule_escrow_settling(rent_id, now.saturating_add(ONE_HOUR.into()));
+        Ok(())
+    }
+
+    fn schedule_escrow_settling(rent_id: RentOrderId, at: T::BlockNumber) {
+        EscrowSettling::<T>::mutate(at, |settling| {
+            ItemList::add_item(settling, rent_id);
+        });
+    }
+
+    // 续租时续租的租金转入托管账户，并追加到托管中
+    pub(crate) fn add_escrow(
+        rent_id: RentOrderId,
+        amount: BalanceOf<T>,
+        rent_end: T::BlockNumber,
+    ) -> DispatchResult {
+        let mut escrow = Self::order_escrow(rent_id).ok_or(Error::<T>::NoOrderExist)?;
+        <T as Config>::Currency::transfer(
+            &escrow.renter,
+            &Self::escrow_account(),
+            amount,
+            KeepAlive,
+        )
+        .map_err(|_| Error::<T>::InsufficientValue)?;
+        escrow.add_amount(amount, rent_end);
+        OrderEscrow::<T>::insert(rent_id, escrow);
+        Ok(())
+    }
+
+    // 在on_initialize中计算当前块需要结算的托管订单的权重
+    pub(crate) fn escrow_settling_weight(block_number: T::BlockNumber) -> Weight {
+        let count = EscrowSettling::<T>::decode_len(block_number).unwrap_or_default() as u64;
+        T::DbWeight::get().reads_writes(
+            1 + count * SETTLE_ESCROW_READS_WRITES,
+            1 + count * SETTLE_ESCROW_READS_WRITES,
+        )
+    }
+
+    // 只结算当前块到期的托管订单，租用未结束时安排一小时后的下一次结算
+    pub(crate) fn settle_escrow_at(block_number: T::BlockNumber) {
+        let rent_ids = EscrowSettling::<T>::take(block_number);
+        for rent_id in rent_ids {
+            let _ = Self::settle_escrow(rent_id, block_number);
+
+            if let Some(escrow) = Self::order_escrow(rent_id) {
+                if block_number < escrow.rent_end {
+                    Self::schedule_escrow_settling(
+                        rent_id,
+                        block_number.saturating_add(ONE_HOUR.into()),
+                    );
+                }
+            }
+        }
+    }
+
+    // 结算 [last_settled, now) 时段的租金：
+    // 按机器实际的下线时段，在线时段的租金释放给矿工，下线时段的租金退还给租用人
+    pub(crate) fn settle_escrow(rent_id: RentOrderId, now: T::BlockNumber) -> DispatchResult {
+        let mut escrow = Self::order_escrow(rent_id).ok_or(Error::<T>::NoOrderExist)?;
+        let settle_to = now.min(escrow.rent_end);
+        if settle_to <= escrow.last_settled {
+            return Ok(())
+        }
+
+        let settle_duration = settle_to.saturating_sub(escrow.last_settled);
+        let due = Perbill::from_rational(
+            settle_duration,
+            escrow.rent_end.saturating_sub(escrow.last_settled),
+        ) * escrow.remaining();
+
+        let rent_info = Self::rent_info(rent_id).ok_or(Error::<T>::NoOrderExist)?;
+        let machine_info = <online_profile::Pallet<T>>::machines_info(&rent_info.machine_id)
+            .ok_or(Error::<T>::MachineNotFound)?;
+
+        let offline_records =
+            <online_profile::Pallet<T>>::machine_offline_records(&rent_info.machine_id);
+        let offline_duration =
+            machine_info.offline_duration(escrow.last_settled, settle_to, &offline_records);
+        let mut to_release = Perbill::from_rational(
+            settle_duration.saturating_sub(offline_duration),
+            settle_duration,
+        ) * due;
+
+        // 支付失败时视为退还给租用人
+        if !to_release.is_zero() &&
+            with_storage_layer(|| {
+                Self::pay_rent_fee(
+                    &Self::escrow_account(),
+                    rent_info.machine_id.clone(),
+                    machine_info.machine_stash.clone(),
+                    rent_id,
+                    to_release,
+                    AllowDeath,
+                )
+            })
+            .is_err()
+        {
+            to_release = Zero::zero();
+        }
+        let mut to_refund = due.saturating_sub(to_release);
+        if !to_refund.is_zero() && Self::refund_from_escrow(&escrow.renter, to_refund).is_err() {
+            to_refund = Zero::zero();
+        }
+
+        if !to_release.is_zero() {
+            escrow.release(to_release, settle_to);
+            Self::deposit_event(Event::EscrowReleased(rent_id, to_release));
+        }
+        if !to_refund.is_zero() {
+            escrow.refund(to_refund, settle_to);
+            Self::deposit_event(Event::EscrowRefunded(rent_id, escrow.renter.clone(), to_refund));
+        }
+        escrow.last_settled = settle_to;
+        OrderEscrow::<T>::insert(rent_id, escrow);
+        Ok(())
+    }
+
+    // 订单结束(到期或提前终止)时结算到当前时间，剩余的托管租金退还给租用人
+    pub(crate) fn close_escrow(rent_id: RentOrderId, now: T::BlockNumber) -> BalanceOf<T> {
+        let _ = Self::settle_escrow(rent_id, now);
+
+        let escrow = match OrderEscrow::<T>::take(rent_id) {
+            Some(escrow) => escrow,
+            None => return Zero::zero(),
+        };
+        let remaining = escrow.remaining();
+        if remaining.is_zero() {
+            return remaining
+        }
+
+        if Self::refund_from_escrow(&escrow.renter, remaining).is_err() {
+            return Zero::zero()
+        }
+        Self::deposit_event(Event::EscrowRefunded(rent_id, escrow.renter, remaining));
+        remaining
+    }
+
+    // 从托管账户退还租金给租用人
+    pub(crate) fn refund_from_escrow(
+        renter: &T::AccountId,
+        amount: BalanceOf<T>,
+    ) -> DispatchResult {
+        <T as Config>::Currency::transfer(&Self::escrow_account(), renter, amount, AllowDeath)
+    }
+}
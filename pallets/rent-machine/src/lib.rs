@@ -3,7 +3,13 @@
 #![warn(unused_crate_dependencies)]
 
 // pub mod migrations;
+mod asset_rent;
+mod dispute;
+mod escrow;
+mod rent_group;
+mod reservation;
 mod rpc;
+pub mod weights;
 
 #[cfg(test)]
 mod mock;
@@ -13,18 +19,27 @@ mod tests;
 
 pub use dbc_support::machine_type::MachineStatus;
 use dbc_support::{
-    rental_type::{MachineGPUOrder, MachineRenterRentedOrderDetail, RentOrderDetail, RentStatus},
-    traits::{DbcPrice, MachineInfoTrait, RTOps},
-    EraIndex, ItemList, MachineId, RentOrderId, HALF_HOUR, ONE_DAY, ONE_MINUTE,
+    rental_type::{
+        DisputeSlashInfo, DisputeSlashReviewInfo, DlcBurnDetail, MachineDisputeStats,
+        MachineGPUOrder, MachineRenterRentedOrderDetail, RentDisputeDetail, RentEndpoint,
+        RentEscrowDetail, RentGroupDetail, RentLivenessRound, RentOrderDetail,
+        RentReservationDetail, RentStatus,
+    },
+    traits::{DbcPrice, DlcPrice, GNOps, MachineInfoTrait, ManageCommittee, RTOps, SurgePricing},
+    EraIndex, ItemList, MachineId, RentOrderId, SlashId, HALF_HOUR, ONE_DAY, ONE_MINUTE,
 };
 use frame_support::{
     dispatch::DispatchResult,
     ensure,
     pallet_prelude::*,
-    traits::{Currency, ExistenceRequirement::KeepAlive, ReservableCurrency},
+    traits::{
+        fungibles, Currency, ExistenceRequirement, ExistenceRequirement::KeepAlive,
+        ReservableCurrency,
+    },
+    PalletId,
 };
 use frame_system::{ensure_root, ensure_signed, pallet_prelude::*};
-use sp_core::H160;
+use sp_core::{H160, H256};
 use sp_runtime::{
     traits::{CheckedAdd, CheckedSub, SaturatedConversion, Saturating, Zero},
     Perbill,
@@ -33,11 +48,22 @@ use sp_std::{prelude::*, str, vec::Vec};
 
 type BalanceOf<T> =
     <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+type AssetIdOf<T> =
+    <<T as Config>::Assets as fungibles::Inspect<<T as frame_system::Config>::AccountId>>::AssetId;
 
 /// 等待15min，用户确认是否租用成功
 pub const WAITING_CONFIRMING_DELAY: u32 = 15 * ONE_MINUTE;
+/// DBC 主网块时间 6 秒 = 6000ms（与 runtime MILLISECS_PER_BLOCK 一致）
+pub const MILLISECS_PER_BLOCK: u64 = 6_000;
+/// 批量租用时，一次最多租用的机器数量
+pub const MAX_MACHINES_PER_GROUP: u32 = 64;
+/// 委员会抢单后需要在2天内处理争议，超时后其他委员会可以抢单
+pub const DISPUTE_RESOLVE_DURATION: u32 = 2 * ONE_DAY;
+/// 租用者声明的访问地址中，域名的最大长度
+pub const MAX_ENDPOINT_HOST_LEN: usize = 253;
 
 pub use pallet::*;
+pub use weights::WeightInfo;
 
 #[frame_support::pallet]
 pub mod pallet {
@@ -55,6 +81,28 @@ pub mod pallet {
             BlockNumber = Self::BlockNumber,
         >;
         type DbcPrice: DbcPrice<Balance = BalanceOf<Self>>;
+        type DlcPrice: DlcPrice<Balance = BalanceOf<Self>>;
+        /// 根据GPU使用率调整系统定价
+        type SurgePricing: SurgePricing;
+        /// 处理租用争议的委员会
+        type ManageCommittee: ManageCommittee<
+            AccountId = Self::AccountId,
+            Balance = BalanceOf<Self>,
+        >;
+        /// 取消对未按时处理争议的委员会的惩罚
+        type CancelSlashOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+        /// 惩罚超时未处理争议的委员会
+        type SlashAndReward: GNOps<AccountId = Self::AccountId, Balance = BalanceOf<Self>>;
+        /// 用于支付租金的资产(pallet-assets)
+        type Assets: fungibles::Mutate<Self::AccountId, Balance = BalanceOf<Self>>;
+        /// 可以支付租金的DLC资产ID
+        #[pallet::constant]
+        type DlcAssetId: Get<AssetIdOf<Self>>;
+        /// 托管资产租金的账户
+        #[pallet::constant]
+        type PalletId: Get<PalletId>;
+        /// 租用、确认租用及续租的权重
+        type WeightInfo: WeightInfo;
     }
 
     #[pallet::pallet]
@@ -63,9 +111,19 @@ pub mod pallet {
 
     #[pallet::hooks]
     impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_initialize(block_number: T::BlockNumber) -> Weight {
+            Self::escrow_settling_weight(block_number)
+                .saturating_add(Self::dispute_deadline_weight(block_number))
+                .saturating_add(Self::dispute_slash_weight(block_number))
+        }
+
         fn on_finalize(block_number: T::BlockNumber) {
+            Self::check_reservation_starting(block_number);
             let _ = Self::check_machine_starting_status(block_number);
             let _ = Self::check_if_rent_finished(block_number);
+            Self::settle_escrow_at(block_number);
+            Self::check_dispute_deadline(block_number);
+            Self::exec_dispute_slash(block_number);
         }
 
         // fn on_runtime_upgrade() -> Weight {
@@ -150,6 +208,87 @@ pub mod pallet {
     pub(super) type RentOrderReceiver<T: Config> =
         StorageMap<_, Blake2_128Concat, RentOrderId, T::AccountId>;
 
+    /// 订单已支付的租金 (付给receiver的部分, 转入RentFeePot的部分)，提前终止时按比例退还
+    /// 资产支付的订单为 (付给receiver的部分, 销毁的部分)，单位为该资产
+    /// 升级前的旧订单无此记录，提前终止时不退款
+    #[pallet::storage]
+    #[pallet::getter(fn rent_order_paid_fee)]
+    pub(super) type RentOrderPaidFee<T: Config> =
+        StorageMap<_, Blake2_128Concat, RentOrderId, (BalanceOf<T>, BalanceOf<T>), ValueQuery>;
+
+    /// 是否开启流式支付：开启后confirm_rent不再一次性支付租金，
+    /// 而是托管在租用人账户中，按机器正常出租的时长逐小时释放给矿工
+    #[pallet::storage]
+    #[pallet::getter(fn rent_fee_streaming)]
+    pub(super) type RentFeeStreaming<T: Config> = StorageValue<_, bool, ValueQuery>;
+
+    /// 流式支付订单的租金托管情况
+    #[pallet::storage]
+    #[pallet::getter(fn order_escrow)]
+    pub type OrderEscrow<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        RentOrderId,
+        RentEscrowDetail<T::AccountId, T::BlockNumber, BalanceOf<T>>,
+    >;
+
+    /// 托管订单的下一次结算时间：块高 -> 在该块结算的订单
+    #[pallet::storage]
+    #[pallet::getter(fn escrow_settling)]
+    pub type EscrowSettling<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::BlockNumber, Vec<RentOrderId>, ValueQuery>;
+
+    /// 使用资产(DLC)支付租金的订单：(资产ID, 租金，包括续租的租金)
+    /// 租金在租用时转入托管账户，确认租用后支付给矿工
+    #[pallet::storage]
+    #[pallet::getter(fn rent_order_asset)]
+    pub type RentOrderAsset<T: Config> =
+        StorageMap<_, Blake2_128Concat, RentOrderId, (AssetIdOf<T>, BalanceOf<T>)>;
+
+    /// 提前终止时租金接收方或RentFeePot余额不足而未能退还的租金，由租用人之后领取
+    /// 欠款方 -> 租用人 -> 欠款
+    #[pallet::storage]
+    #[pallet::getter(fn owed_rent_refund)]
+    pub type OwedRentRefund<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Blake2_128Concat,
+        T::AccountId,
+        BalanceOf<T>,
+        ValueQuery,
+    >;
+
+    /// 资产支付的订单未能退还的租金：(欠款方, 租用人, 资产ID) -> 欠款
+    #[pallet::storage]
+    #[pallet::getter(fn owed_asset_rent_refund)]
+    pub type OwedAssetRentRefund<T: Config> = StorageNMap<
+        _,
+        (
+            NMapKey<Blake2_128Concat, T::AccountId>,
+            NMapKey<Blake2_128Concat, T::AccountId>,
+            NMapKey<Blake2_128Concat, AssetIdOf<T>>,
+        ),
+        BalanceOf<T>,
+        ValueQuery,
+    >;
+
+    /// 资产支付的租金中被销毁部分的记录
+    #[pallet::storage]
+    #[pallet::getter(fn dlc_burn_details)]
+    pub type DlcBurnDetails<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        RentOrderId,
+        Vec<DlcBurnDetail<T::AccountId, BalanceOf<T>, T::BlockNumber>>,
+        ValueQuery,
+    >;
+
+    /// 资产支付的租金中累计销毁的数量
+    #[pallet::storage]
+    #[pallet::getter(fn total_burned_dlc)]
+    pub type TotalBurnedDlc<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
+
     #[pallet::type_value]
     pub(super) fn MaximumRentalDurationDefault<T: Config>() -> EraIndex {
         60
@@ -161,6 +300,117 @@ pub mod pallet {
     pub(super) type MaximumRentalDuration<T: Config> =
         StorageValue<_, EraIndex, ValueQuery, MaximumRentalDurationDefault<T>>;
 
+    /// 分时段机器的预约订单，预约开始时转为租用订单（使用同一个RentOrderId）
+    #[pallet::storage]
+    #[pallet::getter(fn rent_reservation)]
+    pub type RentReservation<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        RentOrderId,
+        RentReservationDetail<T::AccountId, T::BlockNumber, BalanceOf<T>>,
+    >;
+
+    // 机器上尚未开始的预约
+    #[pallet::storage]
+    #[pallet::getter(fn machine_reservation)]
+    pub type MachineReservation<T: Config> =
+        StorageMap<_, Blake2_128Concat, MachineId, Vec<RentOrderId>, ValueQuery>;
+
+    // 用户尚未开始的预约
+    #[pallet::storage]
+    #[pallet::getter(fn user_reservation)]
+    pub type UserReservation<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, Vec<RentOrderId>, ValueQuery>;
+
+    // 记录每个区块将要开始的预约
+    #[pallet::storage]
+    #[pallet::getter(fn reservation_starting)]
+    pub type ReservationStarting<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::BlockNumber, Vec<RentOrderId>, ValueQuery>;
+
+    /// 批量租用多台机器的父订单
+    #[pallet::storage]
+    #[pallet::getter(fn rent_group)]
+    pub type RentGroup<T: Config> =
+        StorageMap<_, Blake2_128Concat, RentOrderId, RentGroupDetail<T::AccountId>>;
+
+    // 子订单所属的父订单
+    #[pallet::storage]
+    #[pallet::getter(fn rent_order_group)]
+    pub type RentOrderGroup<T: Config> = StorageMap<_, Blake2_128Concat, RentOrderId, RentOrderId>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn next_rent_group_id)]
+    pub(super) type NextRentGroupId<T: Config> = StorageValue<_, RentOrderId, ValueQuery>;
+
+    /// 租用者对订单提交的争议，处理完成后保留以拒绝再次提交，订单结束后移除
+    #[pallet::storage]
+    #[pallet::getter(fn rent_dispute)]
+    pub type RentDispute<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        RentOrderId,
+        RentDisputeDetail<T::AccountId, T::BlockNumber, BalanceOf<T>>,
+    >;
+
+    /// 机器收到的争议及退款次数
+    #[pallet::storage]
+    #[pallet::getter(fn machine_dispute_stats)]
+    pub type MachineDispute<T: Config> =
+        StorageMap<_, Blake2_128Concat, MachineId, MachineDisputeStats, ValueQuery>;
+
+    /// 抢单的委员会处理争议的截止时间：块高 -> 在该块到期的争议
+    #[pallet::storage]
+    #[pallet::getter(fn dispute_deadline)]
+    pub(super) type DisputeDeadline<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::BlockNumber, Vec<RentOrderId>, ValueQuery>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn next_dispute_slash_id)]
+    pub(super) type NextDisputeSlashId<T: Config> = StorageValue<_, SlashId, ValueQuery>;
+
+    /// 未按时处理争议的委员会的惩罚，在执行时间前可以申请复审
+    #[pallet::storage]
+    #[pallet::getter(fn pending_dispute_slash)]
+    pub type PendingDisputeSlash<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        SlashId,
+        DisputeSlashInfo<T::AccountId, T::BlockNumber, BalanceOf<T>>,
+    >;
+
+    #[pallet::storage]
+    #[pallet::getter(fn pending_dispute_slash_review)]
+    pub type PendingDisputeSlashReview<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        SlashId,
+        DisputeSlashReviewInfo<T::AccountId, BalanceOf<T>, T::BlockNumber>,
+    >;
+
+    /// 块高 -> 在该块执行的争议惩罚
+    #[pallet::storage]
+    #[pallet::getter(fn unhandled_dispute_slash)]
+    pub(super) type UnhandledDisputeSlash<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::BlockNumber, Vec<SlashId>, ValueQuery>;
+
+    /// 租用者声明的机器访问地址，委员会的链下工作机据此探测机器是否可以访问
+    #[pallet::storage]
+    #[pallet::getter(fn rent_order_endpoint)]
+    pub type RentOrderEndpoint<T: Config> =
+        StorageMap<_, Blake2_128Concat, RentOrderId, RentEndpoint>;
+
+    /// 委员会对租用订单访问地址的探测结果，与访问地址一同在订单结束时移除
+    #[pallet::storage]
+    #[pallet::getter(fn rent_liveness)]
+    pub type RentLiveness<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        RentOrderId,
+        RentLivenessRound<T::AccountId, T::BlockNumber>,
+        ValueQuery,
+    >;
+
     #[pallet::storage]
     #[pallet::getter(fn evm_address_to_account)]
     pub(super) type EvmAddress2Account<T: Config> =
@@ -187,7 +437,7 @@ pub mod pallet {
 
         /// 用户租用机器(按天租用)
         #[pallet::call_index(1)]
-        #[pallet::weight(frame_support::weights::Weight::from_parts(10000, 0))]
+        #[pallet::weight(<T as Config>::WeightInfo::rent_machine())]
         pub fn rent_machine(
             origin: OriginFor<T>,
             machine_id: MachineId,
@@ -195,125 +445,345 @@ pub mod pallet {
             duration: T::BlockNumber,
         ) -> DispatchResultWithPostInfo {
             let renter = ensure_signed(origin)?;
-            Self::rent_machine_by_block(renter, machine_id, rent_gpu_num, duration)
+            Self::rent_machine_by_block(renter, machine_id, rent_gpu_num, duration, None)?;
+            Ok(().into())
         }
 
         /// 用户在租用15min(30个块)内确认机器租用成功
         #[pallet::call_index(2)]
-        #[pallet::weight(frame_support::weights::Weight::from_parts(10000, 0))]
+        #[pallet::weight(<T as Config>::WeightInfo::confirm_rent())]
         pub fn confirm_rent(
             origin: OriginFor<T>,
             rent_id: RentOrderId,
         ) -> DispatchResultWithPostInfo {
             let renter = ensure_signed(origin)?;
-            let now = <frame_system::Pallet<T>>::block_number();
+            Self::confirm_rent_order(renter, rent_id)?;
+            Ok(().into())
+        }
 
-            let mut rent_info = Self::rent_info(&rent_id).ok_or(Error::<T>::Unknown)?;
-            let machine_id = rent_info.machine_id.clone();
-            let gpu_num = rent_info.gpu_num.clone();
-            ensure!(rent_info.renter == renter, Error::<T>::NoOrderExist);
-            ensure!(
-                rent_info.rent_status == RentStatus::WaitingVerifying,
-                Error::<T>::NoOrderExist
-            );
+        /// 用户续租(按天续租), 通过order_id来续租
+        #[pallet::call_index(3)]
+        #[pallet::weight(<T as Config>::WeightInfo::relet_machine())]
+        pub fn relet_machine(
+            origin: OriginFor<T>,
+            rent_id: RentOrderId,
+            relet_duration: T::BlockNumber,
+        ) -> DispatchResultWithPostInfo {
+            let renter = ensure_signed(origin)?;
+            Self::relet_machine_by_block(renter, rent_id, relet_duration)
+        }
 
-            // 不能超过15分钟
-            let machine_start_duration =
-                now.checked_sub(&rent_info.rent_start).ok_or(Error::<T>::Overflow)?;
-            ensure!(
-                machine_start_duration <= WAITING_CONFIRMING_DELAY.into(),
-                Error::<T>::ExpiredConfirm
-            );
+        #[pallet::call_index(4)]
+        #[pallet::weight(Weight::from_parts(10000, 0))]
+        pub fn bond_evm_address(
+            origin: OriginFor<T>,
+            machine_id: MachineId,
+            evm_address: H160,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
 
-            let machine_info = <online_profile::Pallet<T>>::machines_info(&machine_id)
-                .ok_or(Error::<T>::Unknown)?;
+            let machine_info = online_profile::Pallet::<T>::machines_info(machine_id)
+                .ok_or(Error::<T>::MachineNotFound.as_str())?;
             ensure!(
-                machine_info.machine_status == MachineStatus::Rented,
-                Error::<T>::StatusNotAllowed
+                machine_info.controller == who || machine_info.machine_stash == who,
+                Error::<T>::NotMachineOwner
             );
+            EvmAddress2Account::<T>::insert(evm_address, who.clone());
+            Self::deposit_event(Event::SetEvmAddress(evm_address, who));
+            Ok(().into())
+        }
 
-            // 质押转到特定账户
-            Self::change_renter_total_stake(&renter, rent_info.stake_amount, false)
-                .map_err(|_| Error::<T>::UnlockToPayFeeFailed)?;
-            Self::pay_rent_fee(
-                &renter,
-                machine_id.clone(),
-                machine_info.machine_stash,
-                rent_id,
-                rent_info.stake_amount,
-            )?;
-
-            // 在stake_amount设置0前记录，用作事件
-            let rent_fee = rent_info.stake_amount;
-            let rent_duration = rent_info.rent_end.saturating_sub(rent_info.rent_start);
+        /// 用户预约分时段机器的未来时段 [rent_start, rent_start + duration)
+        /// 预约时锁定租金，到达rent_start时自动生成租用订单并用锁定的租金支付，无需再确认
+        #[pallet::call_index(5)]
+        #[pallet::weight(<T as Config>::WeightInfo::reserve_machine())]
+        pub fn reserve_machine(
+            origin: OriginFor<T>,
+            machine_id: MachineId,
+            rent_gpu_num: u32,
+            rent_start: T::BlockNumber,
+            duration: T::BlockNumber,
+        ) -> DispatchResultWithPostInfo {
+            let renter = ensure_signed(origin)?;
+            Self::reserve_machine_by_block(renter, machine_id, rent_gpu_num, rent_start, duration)
+        }
 
-            rent_info.confirm_rent(now);
-            rent_info.stake_amount = Default::default();
+        /// 用户在预约开始前取消预约，退还锁定的租金
+        #[pallet::call_index(6)]
+        #[pallet::weight(frame_support::weights::Weight::from_parts(10000, 0))]
+        pub fn cancel_reservation(
+            origin: OriginFor<T>,
+            rent_id: RentOrderId,
+        ) -> DispatchResultWithPostInfo {
+            let renter = ensure_signed(origin)?;
+            let reservation =
+                Self::rent_reservation(rent_id).ok_or(Error::<T>::ReservationNotFound)?;
+            ensure!(reservation.renter == renter, Error::<T>::NotMachineRenter);
 
-            // 改变online_profile状态
-            T::RTOps::change_machine_status_on_confirmed(&machine_id, renter.clone())
+            Self::change_renter_total_stake(&renter, reservation.stake_amount, false)
                 .map_err(|_| Error::<T>::Unknown)?;
+            Self::clean_reservation(rent_id, &reservation);
 
-            let confirming_order_block = rent_info.rent_start + WAITING_CONFIRMING_DELAY.into();
-            let mut confirming_order = ConfirmingOrder::<T>::get(confirming_order_block);
-            ItemList::rm_item(&mut confirming_order, &rent_id);
-            if confirming_order.is_empty() {
-                ConfirmingOrder::<T>::remove(confirming_order_block);
-            } else {
-                ConfirmingOrder::<T>::insert(confirming_order_block, confirming_order);
-            }
-            RentInfo::<T>::insert(&rent_id, rent_info.clone());
+            Self::deposit_event(Event::ReservationCanceled(
+                rent_id,
+                renter,
+                reservation.machine_id,
+                reservation.stake_amount,
+            ));
+            Ok(().into())
+        }
 
+        /// 用户提前结束租用，按剩余时长比例退还已支付的租金
+        /// 退款由租金接收方(receiver/stash)及RentFeePot按支付时的比例承担；
+        /// 流式支付的订单直接退还尚未释放的托管租金；
+        /// 承担方余额不足时记为欠款，租用人之后通过claim_owed_rent_refund领取
+        #[pallet::call_index(7)]
+        #[pallet::weight(<T as Config>::WeightInfo::terminate_rent())]
+        pub fn terminate_rent(
+            origin: OriginFor<T>,
+            rent_id: RentOrderId,
+        ) -> DispatchResultWithPostInfo {
+            let renter = ensure_signed(origin)?;
+            let now = <frame_system::Pallet<T>>::block_number();
+
+            let rent_info = Self::rent_info(&rent_id).ok_or(Error::<T>::NoOrderExist)?;
+            let machine_id = rent_info.machine_id.clone();
+            ensure!(rent_info.renter == renter, Error::<T>::NotMachineRenter);
+            ensure!(rent_info.rent_status == RentStatus::Renting, Error::<T>::NoOrderExist);
+            ensure!(now < rent_info.rent_end, Error::<T>::NoOrderExist);
+
+            let machine_info = <online_profile::Pallet<T>>::machines_info(&machine_id)
+                .ok_or(Error::<T>::MachineNotFound)?;
+
+            let refund = if OrderEscrow::<T>::contains_key(rent_id) {
+                // 流式支付的订单，结算到当前时间后退还剩余的托管租金
+                Self::close_escrow(rent_id, now)
+            } else if let Some((asset_id, _)) = Self::rent_order_asset(rent_id) {
+                // 资产支付的订单，使用同一资产退还
+                Self::charge_back_asset_rent_fee(
+                    rent_id,
+                    asset_id,
+                    &rent_info,
+                    &machine_info.machine_stash,
+                    &renter,
+                    now,
+                )?
+            } else {
+                Self::charge_back_rent_fee(
+                    rent_id,
+                    &rent_info,
+                    &machine_info.machine_stash,
+                    &renter,
+                    now,
+                )?
+            };
+
+            let rent_duration = now.saturating_sub(rent_info.rent_start);
             MachineRenterRentedOrders::<T>::mutate(&machine_id, &renter, |details| {
-                details.push(MachineRenterRentedOrderDetail {
-                    rent_start: rent_info.rent_start,
-                    rent_end: rent_info.rent_end,
-                    rent_id: rent_id.clone(),
-                });
+                for detail in details.iter_mut().filter(|detail| detail.rent_id == rent_id) {
+                    detail.rent_end = now;
+                }
             });
-            RentInfo::<T>::insert(&rent_id, rent_info);
+            Self::finish_rent_order(rent_id, rent_info, rent_duration)
+                .map_err(|_| Error::<T>::Unknown)?;
 
-            Self::deposit_event(Event::ConfirmRent(
+            Self::deposit_event(Event::TerminateRent(
                 rent_id,
                 renter,
                 machine_id,
-                gpu_num,
                 rent_duration,
-                rent_fee,
+                refund,
             ));
             Ok(().into())
         }
 
-        /// 用户续租(按天续租), 通过order_id来续租
-        #[pallet::call_index(3)]
+        /// 设置是否开启流式支付，只影响之后确认的订单
+        #[pallet::call_index(8)]
         #[pallet::weight(frame_support::weights::Weight::from_parts(10000, 0))]
-        pub fn relet_machine(
+        pub fn set_rent_fee_streaming(
             origin: OriginFor<T>,
-            rent_id: RentOrderId,
+            enable: bool,
+        ) -> DispatchResultWithPostInfo {
+            ensure_root(origin)?;
+            RentFeeStreaming::<T>::put(enable);
+            Ok(().into())
+        }
+
+        /// 用户使用资产(DLC)支付租金租用机器(按块高租用)
+        #[pallet::call_index(9)]
+        #[pallet::weight(frame_support::weights::Weight::from_parts(10000, 0))]
+        pub fn rent_machine_with_asset(
+            origin: OriginFor<T>,
+            machine_id: MachineId,
+            rent_gpu_num: u32,
+            duration: T::BlockNumber,
+            asset_id: AssetIdOf<T>,
+        ) -> DispatchResultWithPostInfo {
+            let renter = ensure_signed(origin)?;
+            Self::rent_machine_by_block(
+                renter,
+                machine_id,
+                rent_gpu_num,
+                duration,
+                Some(asset_id),
+            )?;
+            Ok(().into())
+        }
+
+        /// 用户批量租用多台机器(按块高租用)：所有机器都有足够的GPU时才会租用，
+        /// 生成一个父订单关联所有子订单
+        #[pallet::call_index(10)]
+        #[pallet::weight(<T as Config>::WeightInfo::rent_machines(machines.len() as u32))]
+        pub fn rent_machines(
+            origin: OriginFor<T>,
+            machines: Vec<(MachineId, u32)>,
+            duration: T::BlockNumber,
+        ) -> DispatchResultWithPostInfo {
+            let renter = ensure_signed(origin)?;
+            Self::rent_machines_by_block(renter, machines, duration)
+        }
+
+        /// 用户在15min内一起确认父订单下的所有子订单
+        #[pallet::call_index(11)]
+        #[pallet::weight(frame_support::weights::Weight::from_parts(10000, 0))]
+        pub fn confirm_rent_group(
+            origin: OriginFor<T>,
+            group_id: RentOrderId,
+        ) -> DispatchResultWithPostInfo {
+            let renter = ensure_signed(origin)?;
+            Self::confirm_rent_group_orders(renter, group_id)
+        }
+
+        /// 用户一起续租父订单下的所有子订单
+        #[pallet::call_index(12)]
+        #[pallet::weight(frame_support::weights::Weight::from_parts(10000, 0))]
+        pub fn relet_rent_group(
+            origin: OriginFor<T>,
+            group_id: RentOrderId,
             relet_duration: T::BlockNumber,
         ) -> DispatchResultWithPostInfo {
             let renter = ensure_signed(origin)?;
-            Self::relet_machine_by_block(renter, rent_id, relet_duration)
+            Self::relet_rent_group_orders(renter, group_id, relet_duration)
         }
 
-        #[pallet::call_index(4)]
-        #[pallet::weight(Weight::from_parts(10000, 0))]
-        pub fn bond_evm_address(
+        /// 租用者对租用中的订单提交争议(如磁盘慢、GPU型号不符)，证据在链下提供给委员会
+        #[pallet::call_index(13)]
+        #[pallet::weight(<T as Config>::WeightInfo::dispute_rent())]
+        pub fn dispute_rent(
+            origin: OriginFor<T>,
+            rent_id: RentOrderId,
+            evidence_hash: H256,
+        ) -> DispatchResultWithPostInfo {
+            let renter = ensure_signed(origin)?;
+            Self::do_dispute_rent(renter, rent_id, evidence_hash)
+        }
+
+        /// 委员会抢单处理争议，需要锁定一次抢单的质押
+        #[pallet::call_index(14)]
+        #[pallet::weight(frame_support::weights::Weight::from_parts(10000, 0))]
+        pub fn committee_book_dispute(
+            origin: OriginFor<T>,
+            rent_id: RentOrderId,
+        ) -> DispatchResultWithPostInfo {
+            let committee = ensure_signed(origin)?;
+            Self::book_dispute(committee, rent_id)
+        }
+
+        /// 抢单的委员会处理争议，按比例退还租金，0表示驳回
+        #[pallet::call_index(15)]
+        #[pallet::weight(frame_support::weights::Weight::from_parts(10000, 0))]
+        pub fn committee_resolve_dispute(
+            origin: OriginFor<T>,
+            rent_id: RentOrderId,
+            refund_percent: Perbill,
+        ) -> DispatchResultWithPostInfo {
+            let committee = ensure_signed(origin)?;
+            Self::resolve_dispute(committee, rent_id, refund_percent)
+        }
+
+        /// 租用者设置机器的访问地址(host:port)，用于自动探测机器是否可以访问
+        #[pallet::call_index(16)]
+        #[pallet::weight(frame_support::weights::Weight::from_parts(10000, 0))]
+        pub fn set_rent_endpoint(
+            origin: OriginFor<T>,
+            rent_id: RentOrderId,
+            host: Vec<u8>,
+            port: u16,
+        ) -> DispatchResultWithPostInfo {
+            let renter = ensure_signed(origin)?;
+            let rent_info = Self::rent_info(rent_id).ok_or(Error::<T>::NoOrderExist)?;
+            ensure!(rent_info.renter == renter, Error::<T>::NotMachineRenter);
+            ensure!(rent_info.rent_status == RentStatus::Renting, Error::<T>::StatusNotAllowed);
+            ensure!(
+                !host.is_empty() && host.len() <= MAX_ENDPOINT_HOST_LEN && port != 0,
+                Error::<T>::InvalidRentEndpoint
+            );
+
+            RentOrderEndpoint::<T>::insert(rent_id, RentEndpoint { host, port });
+            Self::deposit_event(Event::SetRentEndpoint(rent_id, renter));
+            Ok(().into())
+        }
+
+        /// 被惩罚的委员会在惩罚执行前申请复审，需要锁定一次抢单的质押
+        #[pallet::call_index(17)]
+        #[pallet::weight(frame_support::weights::Weight::from_parts(10000, 0))]
+        pub fn apply_dispute_slash_review(
+            origin: OriginFor<T>,
+            slash_id: SlashId,
+            reason: Vec<u8>,
+        ) -> DispatchResultWithPostInfo {
+            let applicant = ensure_signed(origin)?;
+            Self::do_apply_dispute_slash_review(applicant, slash_id, reason)
+        }
+
+        #[pallet::call_index(18)]
+        #[pallet::weight(frame_support::weights::Weight::from_parts(10000, 0))]
+        pub fn cancel_dispute_slash(
+            origin: OriginFor<T>,
+            slash_id: SlashId,
+        ) -> DispatchResultWithPostInfo {
+            <T as Config>::CancelSlashOrigin::ensure_origin(origin)?;
+            Self::do_cancel_dispute_slash(slash_id)
+        }
+
+        /// 租用人领取提前终止时未能退还的租金，欠款方余额不足时领取可转出的部分
+        #[pallet::call_index(19)]
+        #[pallet::weight(frame_support::weights::Weight::from_parts(10000, 0))]
+        pub fn claim_owed_rent_refund(
+            origin: OriginFor<T>,
+            debtor: T::AccountId,
+        ) -> DispatchResultWithPostInfo {
+            let renter = ensure_signed(origin)?;
+            let owed = Self::owed_rent_refund(&debtor, &renter);
+            ensure!(!owed.is_zero(), Error::<T>::NoOwedRentRefund);
+
+            let transferable = <T as Config>::Currency::free_balance(&debtor)
+                .saturating_sub(<T as Config>::Currency::minimum_balance());
+            let amount = owed.min(transferable);
+            ensure!(!amount.is_zero(), Error::<T>::RefundFailed);
+            <T as Config>::Currency::transfer(&debtor, &renter, amount, KeepAlive)
+                .map_err(|_| Error::<T>::RefundFailed)?;
+
+            let owed = owed.saturating_sub(amount);
+            if owed.is_zero() {
+                OwedRentRefund::<T>::remove(&debtor, &renter);
+            } else {
+                OwedRentRefund::<T>::insert(&debtor, &renter, owed);
+            }
+            Self::deposit_event(Event::ClaimOwedRentRefund(renter, debtor, amount));
+            Ok(().into())
+        }
+
+        /// 租用人领取资产支付的订单未能退还的租金
+        #[pallet::call_index(20)]
+        #[pallet::weight(frame_support::weights::Weight::from_parts(10000, 0))]
+        pub fn claim_owed_asset_rent_refund(
             origin: OriginFor<T>,
-            machine_id: MachineId,
-            evm_address: H160,
+            debtor: T::AccountId,
+            asset_id: AssetIdOf<T>,
         ) -> DispatchResultWithPostInfo {
-            let who = ensure_signed(origin)?;
-
-            let machine_info = online_profile::Pallet::<T>::machines_info(machine_id)
-                .ok_or(Error::<T>::MachineNotFound.as_str())?;
-            ensure!(
-                machine_info.controller == who || machine_info.machine_stash == who,
-                Error::<T>::NotMachineOwner
-            );
-            EvmAddress2Account::<T>::insert(evm_address, who.clone());
-            Self::deposit_event(Event::SetEvmAddress(evm_address, who));
-            Ok(().into())
+            let renter = ensure_signed(origin)?;
+            Self::do_claim_owed_asset_rent_refund(renter, debtor, asset_id)
         }
     }
 
@@ -330,6 +800,72 @@ pub mod pallet {
         Relet(RentOrderId, T::AccountId, MachineId, u32, T::BlockNumber, BalanceOf<T>),
 
         SetEvmAddress(H160, T::AccountId),
+        // rent_id, renter, MachineId, gpu_num, rent_start, duration, balance
+        Reserve(
+            RentOrderId,
+            T::AccountId,
+            MachineId,
+            u32,
+            T::BlockNumber,
+            T::BlockNumber,
+            BalanceOf<T>,
+        ),
+        // rent_id, renter, MachineId
+        ReservationActivated(RentOrderId, T::AccountId, MachineId),
+        // rent_id, renter, MachineId, refunded balance
+        ReservationCanceled(RentOrderId, T::AccountId, MachineId, BalanceOf<T>),
+        // rent_id, renter, MachineId, refunded balance
+        ReservationFailed(RentOrderId, T::AccountId, MachineId, BalanceOf<T>),
+        // rent_id, renter, MachineId, rented duration, refunded balance
+        TerminateRent(RentOrderId, T::AccountId, MachineId, T::BlockNumber, BalanceOf<T>),
+        // rent_id, released balance
+        EscrowReleased(RentOrderId, BalanceOf<T>),
+        // rent_id, renter, refunded balance
+        EscrowRefunded(RentOrderId, T::AccountId, BalanceOf<T>),
+        // rent_id, renter, MachineId, gpu_num, duration, asset_id, asset amount
+        RentWithAsset(
+            RentOrderId,
+            T::AccountId,
+            MachineId,
+            u32,
+            T::BlockNumber,
+            AssetIdOf<T>,
+            BalanceOf<T>,
+        ),
+        // rent_id, renter, asset_id, burned amount
+        BurnAssetRentFee(RentOrderId, T::AccountId, AssetIdOf<T>, BalanceOf<T>),
+        // group_id, renter, rent_ids
+        RentGroup(RentOrderId, T::AccountId, Vec<RentOrderId>),
+        // group_id, renter
+        ConfirmRentGroup(RentOrderId, T::AccountId),
+        // group_id, renter, duration
+        ReletRentGroup(RentOrderId, T::AccountId, T::BlockNumber),
+        // rent_id, renter, evidence_hash
+        RentDisputed(RentOrderId, T::AccountId, H256),
+        // rent_id, committee
+        CommitteeBookDispute(RentOrderId, T::AccountId),
+        // rent_id, committee, refunded balance
+        DisputeResolved(RentOrderId, T::AccountId, BalanceOf<T>),
+        // slash_id, rent_id, committee, stake to slash
+        DisputeSlashPending(SlashId, RentOrderId, T::AccountId, BalanceOf<T>),
+        // rent_id, committee, slashed stake
+        DisputeCommitteeSlashed(RentOrderId, T::AccountId, BalanceOf<T>),
+        // slash_id, applicant
+        ApplyDisputeSlashReview(SlashId, T::AccountId),
+        // slash_id
+        DisputeSlashCanceled(SlashId),
+        // rent_id
+        DisputeExpired(RentOrderId),
+        // rent_id, renter
+        SetRentEndpoint(RentOrderId, T::AccountId),
+        // rent_id, debtor, renter, owed balance
+        RentRefundOwed(RentOrderId, T::AccountId, T::AccountId, BalanceOf<T>),
+        // rent_id, debtor, renter, asset_id, owed amount
+        AssetRentRefundOwed(RentOrderId, T::AccountId, T::AccountId, AssetIdOf<T>, BalanceOf<T>),
+        // renter, debtor, claimed balance
+        ClaimOwedRentRefund(T::AccountId, T::AccountId, BalanceOf<T>),
+        // renter, debtor, asset_id, claimed amount
+        ClaimOwedAssetRentRefund(T::AccountId, T::AccountId, AssetIdOf<T>, BalanceOf<T>),
     }
 
     #[pallet::error]
@@ -358,26 +894,85 @@ pub mod pallet {
         MoreThanOneRenter,
         /// 请求时段不在机器允许出租的时段内，或时长不足 2 小时
         OutOfRentalSchedule,
+        /// 只有分时段出租的机器可以预约
+        ReservationNotAllowed,
+        /// 预约开始时间必须晚于当前块高，且不超过最大租用时间
+        InvalidReservationTime,
+        ReservationNotFound,
+        /// 租金接收方或RentFeePot余额不足，无法退款
+        RefundFailed,
+        /// 没有未能退还的租金
+        NoOwedRentRefund,
+        /// 该资产不能用于支付租金
+        AssetNotSupported,
+        AssetTransferFailed,
+        /// 批量租用的机器列表为空，或超过MAX_MACHINES_PER_GROUP
+        InvalidRentGroupSize,
+        /// 批量租用的机器列表中有重复的机器
+        DuplicateMachineInGroup,
+        RentGroupNotFound,
+        NotCommittee,
+        StakeFailed,
+        /// 订单已经提交过争议
+        DisputeAlreadyExist,
+        DisputeNotFound,
+        /// 争议当前状态不允许该操作
+        DisputeStatusNotAllowed,
+        /// 订单没有可退还的租金(租金均已退还)
+        NoRefundableRentFee,
+        DisputeRefundFailed,
+        InvalidRentEndpoint,
+        /// 预约时长超过最大租用时间
+        ExceedMaxRentalDuration,
+        DisputeSlashNotFound,
+        /// 只有被惩罚的委员会可以申请复审
+        NotSlashed,
+        AlreadyApplied,
+        /// 惩罚已执行或已取消
+        ExpiredApply,
+        NotPendingReviewSlash,
     }
 }
 
 impl<T: Config> Pallet<T> {
-    fn rent_machine_by_block(
+    // 返回生成的订单ID；机器没有GPU或租用时长为0时不生成订单，返回None
+    pub fn rent_machine_by_block(
         renter: T::AccountId,
         machine_id: MachineId,
         rent_gpu_num: u32,
         duration: T::BlockNumber,
-    ) -> DispatchResultWithPostInfo {
-        let now = <frame_system::Pallet<T>>::block_number();
+        rent_asset: Option<AssetIdOf<T>>,
+    ) -> Result<Option<RentOrderId>, DispatchError> {
         let machine_info =
             <online_profile::Pallet<T>>::machines_info(&machine_id).ok_or(Error::<T>::Unknown)?;
-        let machine_rented_gpu = <online_profile::Pallet<T>>::machine_rented_gpu(&machine_id);
-        let gpu_num = machine_info.gpu_num();
 
-        if gpu_num == 0 || duration == Zero::zero() {
-            return Ok(().into())
+        if machine_info.gpu_num() == 0 || duration == Zero::zero() {
+            return Ok(None)
         }
 
+        let duration = Self::check_rent_capacity(&machine_id, rent_gpu_num, duration)?;
+
+        // NOTE: 用户提交订单，需要扣除10个DBC
+        <generic_func::Pallet<T>>::pay_fixed_tx_fee(renter.clone())
+            .map_err(|_| Error::<T>::PayTxFeeFailed)?;
+
+        let rent_id =
+            Self::create_rent_order(renter, machine_id, rent_gpu_num, duration, rent_asset)?;
+        Ok(Some(rent_id))
+    }
+
+    // 检查机器是否有足够的GPU可以租用，返回受MaximumRentalDuration限制后的租用时长
+    fn check_rent_capacity(
+        machine_id: &MachineId,
+        rent_gpu_num: u32,
+        duration: T::BlockNumber,
+    ) -> Result<T::BlockNumber, Error<T>> {
+        let now = <frame_system::Pallet<T>>::block_number();
+        let machine_info =
+            <online_profile::Pallet<T>>::machines_info(machine_id).ok_or(Error::<T>::Unknown)?;
+        let machine_rented_gpu = <online_profile::Pallet<T>>::machine_rented_gpu(machine_id);
+        let gpu_num = machine_info.gpu_num();
+
         // 检查还有空闲的GPU
         ensure!(rent_gpu_num + machine_rented_gpu <= gpu_num, Error::<T>::GPUNotEnough);
 
@@ -396,52 +991,74 @@ impl<T: Config> Pallet<T> {
             duration.min((Self::maximum_rental_duration().saturating_mul(ONE_DAY)).into());
 
         // 分时段出租校验（TimeSlot 模式下必须落在允许时段内，且 ≥ 2 小时）
-        // DBC 主网块时间 6 秒 = 6000ms（与 runtime MILLISECS_PER_BLOCK 一致）
-        const MILLISECS_PER_BLOCK: u64 = 6_000;
         let start_ts_ms = <online_profile::Pallet<T>>::current_time_ms();
         let duration_ms: u64 = duration.saturated_into::<u64>()
             .checked_mul(MILLISECS_PER_BLOCK).ok_or(Error::<T>::Overflow)?;
         let end_ts_ms = start_ts_ms.checked_add(duration_ms).ok_or(Error::<T>::Overflow)?;
         ensure!(
             <online_profile::Pallet<T>>::is_rental_schedule_allowed(
-                &machine_id, start_ts_ms, end_ts_ms
+                machine_id, start_ts_ms, end_ts_ms
             ),
             Error::<T>::OutOfRentalSchedule
         );
 
-        // NOTE: 用户提交订单，需要扣除10个DBC
-        <generic_func::Pallet<T>>::pay_fixed_tx_fee(renter.clone())
-            .map_err(|_| Error::<T>::PayTxFeeFailed)?;
+        // 获取用户租用的结束时间(块高)
+        let rent_end = duration.checked_add(&now).ok_or(Error::<T>::Overflow)?;
 
-        // 获得machine_price(每天的价格) = 系统自动定价 + 卡主额外加价
-        // 根据租用GPU数量计算价格
-        let system_price =
-            T::RTOps::get_machine_price(machine_info.calc_point(), rent_gpu_num, gpu_num)
-                .ok_or(Error::<T>::GetMachinePriceFailed)?;
-        let extra_price_per_gpu = <online_profile::Pallet<T>>::machine_extra_price(&machine_id);
-        let extra_price = extra_price_per_gpu
-            .checked_mul(rent_gpu_num as u64)
-            .ok_or(Error::<T>::Overflow)?;
-        let machine_price = system_price.checked_add(extra_price).ok_or(Error::<T>::Overflow)?;
+        // 已被预约的GPU在预约时段内不能被租用
+        let reserved_gpu = Self::reserved_gpu_in_window(machine_id, now, rent_end);
+        ensure!(
+            rent_gpu_num + machine_rented_gpu + reserved_gpu <= gpu_num,
+            Error::<T>::GPUNotEnough
+        );
 
-        // 根据租用时长计算rent_fee
-        let rent_fee_value = machine_price
-            .checked_mul(duration.saturated_into::<u64>())
-            .ok_or(Error::<T>::Overflow)?
-            .checked_div(ONE_DAY.into())
-            .ok_or(Error::<T>::Overflow)?;
-        let rent_fee = <T as Config>::DbcPrice::get_dbc_amount_by_value(rent_fee_value)
-            .ok_or(Error::<T>::Overflow)?;
+        Ok(duration)
+    }
 
-        // 获取用户租用的结束时间(块高)
+    // 生成租用订单：质押租金，修改机器状态，等待用户确认
+    // NOTE: 调用前需要通过check_rent_capacity检查
+    fn create_rent_order(
+        renter: T::AccountId,
+        machine_id: MachineId,
+        rent_gpu_num: u32,
+        duration: T::BlockNumber,
+        rent_asset: Option<AssetIdOf<T>>,
+    ) -> Result<RentOrderId, DispatchError> {
+        let now = <frame_system::Pallet<T>>::block_number();
+        let machine_info =
+            <online_profile::Pallet<T>>::machines_info(&machine_id).ok_or(Error::<T>::Unknown)?;
+        let gpu_num = machine_info.gpu_num();
         let rent_end = duration.checked_add(&now).ok_or(Error::<T>::Overflow)?;
 
-        // 质押用户的资金，并修改机器状态
-        Self::change_renter_total_stake(&renter, rent_fee, true)
-            .map_err(|_| Error::<T>::InsufficientValue)?;
+        // 获得machine_price(每天的价格) = 系统自动定价 + 卡主额外加价
+        // 根据租用GPU数量及租用时长计算rent_fee
+        let rent_fee_value = Self::calc_rent_fee_value(
+            &machine_id,
+            machine_info.calc_point(),
+            rent_gpu_num,
+            gpu_num,
+            duration,
+        )?;
 
         let rent_id = Self::get_new_rent_id();
 
+        // 质押用户的资金，并修改机器状态
+        // 使用资产支付时，租金转入托管账户，订单中不再质押DBC
+        let (rent_fee, stake_amount) = match rent_asset {
+            Some(asset_id) => {
+                let rent_fee =
+                    Self::lock_asset_rent_fee(&renter, rent_id, asset_id, rent_fee_value)?;
+                (rent_fee, Zero::zero())
+            },
+            None => {
+                let rent_fee = <T as Config>::DbcPrice::get_dbc_amount_by_value(rent_fee_value)
+                    .ok_or(Error::<T>::Overflow)?;
+                Self::change_renter_total_stake(&renter, rent_fee, true)
+                    .map_err(|_| Error::<T>::InsufficientValue)?;
+                (rent_fee, rent_fee)
+            },
+        };
+
         // spec 410: 快照矿工当前的独立收租钱包，防止 confirm 窗口内 bait-and-switch
         if let Some(snapshot) =
             <online_profile::Pallet<T>>::stash_rent_receiver(&machine_info.machine_stash)
@@ -464,7 +1081,7 @@ impl<T: Config> Pallet<T> {
                 renter.clone(),
                 now,
                 rent_end,
-                rent_fee,
+                stake_amount,
                 rent_gpu_num,
                 rentable_gpu_index,
             ),
@@ -484,18 +1101,124 @@ impl<T: Config> Pallet<T> {
 
         MachineRentOrder::<T>::insert(&machine_id, machine_rent_order);
 
-        Self::deposit_event(Event::Rent(
+        match rent_asset {
+            Some(asset_id) => Self::deposit_event(Event::RentWithAsset(
+                rent_id,
+                renter,
+                machine_id,
+                rent_gpu_num,
+                duration,
+                asset_id,
+                rent_fee,
+            )),
+            None => Self::deposit_event(Event::Rent(
+                rent_id,
+                renter,
+                machine_id,
+                rent_gpu_num,
+                duration.into(),
+                rent_fee,
+            )),
+        }
+        Ok(rent_id)
+    }
+
+    // 确认租用成功：支付租金，并修改机器状态
+    pub fn confirm_rent_order(renter: T::AccountId, rent_id: RentOrderId) -> DispatchResult {
+        let now = <frame_system::Pallet<T>>::block_number();
+
+        let mut rent_info = Self::rent_info(&rent_id).ok_or(Error::<T>::Unknown)?;
+        let machine_id = rent_info.machine_id.clone();
+        let gpu_num = rent_info.gpu_num.clone();
+        ensure!(rent_info.renter == renter, Error::<T>::NoOrderExist);
+        ensure!(rent_info.rent_status == RentStatus::WaitingVerifying, Error::<T>::NoOrderExist);
+
+        // 不能超过15分钟
+        let machine_start_duration =
+            now.checked_sub(&rent_info.rent_start).ok_or(Error::<T>::Overflow)?;
+        ensure!(
+            machine_start_duration <= WAITING_CONFIRMING_DELAY.into(),
+            Error::<T>::ExpiredConfirm
+        );
+
+        let machine_info =
+            <online_profile::Pallet<T>>::machines_info(&machine_id).ok_or(Error::<T>::Unknown)?;
+        ensure!(machine_info.machine_status == MachineStatus::Rented, Error::<T>::StatusNotAllowed);
+
+        if let Some((asset_id, asset_rent_fee)) = Self::rent_order_asset(rent_id) {
+            // 资产支付：租金从托管账户一次性支付，不参与流式支付
+            Self::pay_asset_rent_fee(
+                &renter,
+                rent_id,
+                asset_id,
+                asset_rent_fee,
+                machine_info.machine_stash,
+            )?;
+        } else if Self::rent_fee_streaming() {
+            // 流式支付：租金转入托管账户，按小时结算
+            Self::create_escrow(
+                rent_id,
+                renter.clone(),
+                rent_info.stake_amount,
+                now,
+                rent_info.rent_end,
+            )?;
+        } else {
+            // 质押转到特定账户
+            Self::change_renter_total_stake(&renter, rent_info.stake_amount, false)
+                .map_err(|_| Error::<T>::UnlockToPayFeeFailed)?;
+            Self::pay_rent_fee(
+                &renter,
+                machine_id.clone(),
+                machine_info.machine_stash,
+                rent_id,
+                rent_info.stake_amount,
+                KeepAlive,
+            )?;
+        }
+
+        // 在stake_amount设置0前记录，用作事件
+        let rent_fee = rent_info.stake_amount;
+        let rent_duration = rent_info.rent_end.saturating_sub(rent_info.rent_start);
+
+        rent_info.confirm_rent(now);
+        rent_info.stake_amount = Default::default();
+
+        // 改变online_profile状态
+        T::RTOps::change_machine_status_on_confirmed(&machine_id, renter.clone())
+            .map_err(|_| Error::<T>::Unknown)?;
+
+        let confirming_order_block = rent_info.rent_start + WAITING_CONFIRMING_DELAY.into();
+        let mut confirming_order = ConfirmingOrder::<T>::get(confirming_order_block);
+        ItemList::rm_item(&mut confirming_order, &rent_id);
+        if confirming_order.is_empty() {
+            ConfirmingOrder::<T>::remove(confirming_order_block);
+        } else {
+            ConfirmingOrder::<T>::insert(confirming_order_block, confirming_order);
+        }
+        RentInfo::<T>::insert(&rent_id, rent_info.clone());
+
+        MachineRenterRentedOrders::<T>::mutate(&machine_id, &renter, |details| {
+            details.push(MachineRenterRentedOrderDetail {
+                rent_start: rent_info.rent_start,
+                rent_end: rent_info.rent_end,
+                rent_id: rent_id.clone(),
+            });
+        });
+        RentInfo::<T>::insert(&rent_id, rent_info);
+
+        Self::deposit_event(Event::ConfirmRent(
             rent_id,
             renter,
             machine_id,
-            rent_gpu_num,
-            duration.into(),
+            gpu_num,
+            rent_duration,
             rent_fee,
         ));
-        Ok(().into())
+        Ok(())
     }
 
-    fn relet_machine_by_block(
+    pub fn relet_machine_by_block(
         renter: T::AccountId,
         rent_id: RentOrderId,
         duration: T::BlockNumber,
@@ -532,31 +1255,71 @@ impl<T: Config> Pallet<T> {
             return Ok(().into())
         }
 
-        // 计算rent_fee = 系统自动定价 + 卡主额外加价
-        let system_price =
-            T::RTOps::get_machine_price(calc_point, gpu_num, machine_info.gpu_num())
-                .ok_or(Error::<T>::GetMachinePriceFailed)?;
-        let extra_price = <online_profile::Pallet<T>>::machine_extra_price(&machine_id)
-            .checked_mul(gpu_num as u64).ok_or(Error::<T>::Overflow)?;
-        let machine_price = system_price.checked_add(extra_price).ok_or(Error::<T>::Overflow)?;
-        let rent_fee_value = machine_price
-            .checked_mul(add_duration.saturated_into::<u64>())
-            .ok_or(Error::<T>::Overflow)?
-            .checked_div(ONE_DAY.into())
-            .ok_or(Error::<T>::Overflow)?;
-        let rent_fee = <T as Config>::DbcPrice::get_dbc_amount_by_value(rent_fee_value)
-            .ok_or(Error::<T>::Overflow)?;
-
-        // 检查用户是否有足够的资金，来租用机器
-        let user_balance = <T as Config>::Currency::free_balance(&renter);
-        ensure!(rent_fee < user_balance, Error::<T>::InsufficientValue);
-
-        Self::pay_rent_fee(&renter, machine_id.clone(), machine_info.machine_stash, rent_id, rent_fee)?;
+        // 续租时段内已被预约的GPU不能被占用
+        let reserved_gpu =
+            Self::reserved_gpu_in_window(&machine_id, old_rent_end, old_rent_end + add_duration);
+        ensure!(
+            <online_profile::Pallet<T>>::machine_rented_gpu(&machine_id) + reserved_gpu <=
+                machine_info.gpu_num(),
+            Error::<T>::GPUNotEnough
+        );
 
         // 获取用户租用的结束时间
         rent_info.rent_end =
             rent_info.rent_end.checked_add(&add_duration).ok_or(Error::<T>::Overflow)?;
 
+        let rent_fee = match Self::rent_order_asset(rent_id) {
+            // 资产支付的订单，续租的租金同样使用该资产支付
+            Some((asset_id, _)) => {
+                let rent_fee_value = Self::calc_rent_fee_value(
+                    &machine_id,
+                    calc_point,
+                    gpu_num,
+                    machine_info.gpu_num(),
+                    add_duration,
+                )?;
+                let rent_fee =
+                    Self::lock_asset_rent_fee(&renter, rent_id, asset_id, rent_fee_value)?;
+                Self::pay_asset_rent_fee(
+                    &renter,
+                    rent_id,
+                    asset_id,
+                    rent_fee,
+                    machine_info.machine_stash,
+                )?;
+                rent_fee
+            },
+            None => {
+                // 计算rent_fee = 系统自动定价 + 卡主额外加价
+                let rent_fee = Self::calc_rent_fee(
+                    &machine_id,
+                    calc_point,
+                    gpu_num,
+                    machine_info.gpu_num(),
+                    add_duration,
+                )?;
+
+                // 检查用户是否有足够的资金，来租用机器
+                let user_balance = <T as Config>::Currency::free_balance(&renter);
+                ensure!(rent_fee < user_balance, Error::<T>::InsufficientValue);
+
+                if OrderEscrow::<T>::contains_key(rent_id) {
+                    // 流式支付的订单，续租的租金追加到托管中
+                    Self::add_escrow(rent_id, rent_fee, rent_info.rent_end)?;
+                } else {
+                    Self::pay_rent_fee(
+                        &renter,
+                        machine_id.clone(),
+                        machine_info.machine_stash,
+                        rent_id,
+                        rent_fee,
+                        KeepAlive,
+                    )?;
+                }
+                rent_fee
+            },
+        };
+
         let mut old_rent_ending = RentEnding::<T>::get(old_rent_end);
         ItemList::rm_item(&mut old_rent_ending, &rent_id);
         if old_rent_ending.is_empty() {
@@ -589,6 +1352,110 @@ impl<T: Config> Pallet<T> {
         Ok(().into())
     }
 
+    // 提前终止时，按未使用的时长从租金接收方及RentFeePot退还已支付的租金
+    fn charge_back_rent_fee(
+        rent_id: RentOrderId,
+        rent_info: &RentOrderDetail<T::AccountId, T::BlockNumber, BalanceOf<T>>,
+        machine_stash: &T::AccountId,
+        renter: &T::AccountId,
+        now: T::BlockNumber,
+    ) -> Result<BalanceOf<T>, Error<T>> {
+        let unused_ratio = Perbill::from_rational(
+            rent_info.rent_end.saturating_sub(now),
+            rent_info.rent_end.saturating_sub(rent_info.rent_start),
+        );
+        let (paid_to_stash, paid_to_destroy) = Self::rent_order_paid_fee(rent_id);
+        let refund_from_stash = unused_ratio * paid_to_stash;
+        let refund_from_pot = unused_ratio * paid_to_destroy;
+
+        if !refund_from_stash.is_zero() {
+            let rent_receiver = Self::rent_order_receiver(&rent_id).unwrap_or_else(|| {
+                <online_profile::Pallet<T>>::effective_rent_receiver(machine_stash)
+            });
+            Self::refund_or_owe(rent_id, &rent_receiver, renter, refund_from_stash);
+        }
+        if !refund_from_pot.is_zero() {
+            let rent_fee_pot = Self::rent_fee_pot().ok_or(Error::<T>::UndefinedRentPot)?;
+            Self::refund_or_owe(rent_id, &rent_fee_pot, renter, refund_from_pot);
+        }
+        let _ = T::RTOps::reduce_machine_rent_fee(
+            rent_info.machine_id.clone(),
+            refund_from_pot,
+            refund_from_stash,
+        );
+        Ok(refund_from_stash.saturating_add(refund_from_pot))
+    }
+
+    // 欠款方余额不足时记录为欠款，由租用人之后领取，不影响订单的终止
+    fn refund_or_owe(
+        rent_id: RentOrderId,
+        debtor: &T::AccountId,
+        renter: &T::AccountId,
+        amount: BalanceOf<T>,
+    ) {
+        if <T as pallet::Config>::Currency::transfer(debtor, renter, amount, KeepAlive).is_ok() {
+            return
+        }
+        OwedRentRefund::<T>::mutate(debtor, renter, |owed| *owed = owed.saturating_add(amount));
+        Self::deposit_event(Event::RentRefundOwed(rent_id, debtor.clone(), renter.clone(), amount));
+    }
+
+    // 获得machine_price(每天的价格) = 系统自动定价 + 卡主额外加价
+    // 根据租用GPU数量及租用时长(块高)计算应付的DBC
+    fn calc_rent_fee(
+        machine_id: &MachineId,
+        calc_point: u64,
+        rent_gpu_num: u32,
+        total_gpu_num: u32,
+        duration: T::BlockNumber,
+    ) -> Result<BalanceOf<T>, Error<T>> {
+        let rent_fee_value = Self::calc_rent_fee_value(
+            machine_id,
+            calc_point,
+            rent_gpu_num,
+            total_gpu_num,
+            duration,
+        )?;
+        <T as Config>::DbcPrice::get_dbc_amount_by_value(rent_fee_value).ok_or(Error::<T>::Overflow)
+    }
+
+    // 根据租用GPU数量及租用时长(块高)计算租金的价值(USD)
+    fn calc_rent_fee_value(
+        machine_id: &MachineId,
+        calc_point: u64,
+        rent_gpu_num: u32,
+        total_gpu_num: u32,
+        duration: T::BlockNumber,
+    ) -> Result<u64, Error<T>> {
+        let gpu_type = <online_profile::Pallet<T>>::machines_info(machine_id)
+            .map(|machine_info| machine_info.gpu_type_and_mem().0)
+            .unwrap_or_default();
+        let system_price =
+            Self::get_system_price(&gpu_type, calc_point, rent_gpu_num, total_gpu_num)?;
+        let extra_price = <online_profile::Pallet<T>>::machine_extra_price(machine_id)
+            .checked_mul(rent_gpu_num as u64)
+            .ok_or(Error::<T>::Overflow)?;
+        let machine_price = system_price.checked_add(extra_price).ok_or(Error::<T>::Overflow)?;
+
+        machine_price
+            .checked_mul(duration.saturated_into::<u64>())
+            .ok_or(Error::<T>::Overflow)?
+            .checked_div(ONE_DAY.into())
+            .ok_or(Error::<T>::Overflow)
+    }
+
+    // 系统自动定价，按动态定价曲线调整
+    fn get_system_price(
+        gpu_type: &[u8],
+        calc_point: u64,
+        rent_gpu_num: u32,
+        total_gpu_num: u32,
+    ) -> Result<u64, Error<T>> {
+        let system_price = T::RTOps::get_machine_price(calc_point, rent_gpu_num, total_gpu_num)
+            .ok_or(Error::<T>::GetMachinePriceFailed)?;
+        T::SurgePricing::adjust_machine_price(gpu_type, system_price).ok_or(Error::<T>::Overflow)
+    }
+
     // 获取一个新的租用订单的ID
     pub fn get_new_rent_id() -> RentOrderId {
         let rent_id = Self::next_rent_id();
@@ -608,11 +1475,12 @@ impl<T: Config> Pallet<T> {
     // NOTE: 银河竞赛开启前，租金付给stash账户；开启后租金转到销毁账户
     // NOTE: 租金付给stash账户时，检查是否满足单卡10w/$300的质押条件，不满足，先质押.
     fn pay_rent_fee(
-        renter: &T::AccountId,
+        payer: &T::AccountId,
         machine_id: MachineId,
         machine_stash: T::AccountId,
         rent_id: RentOrderId,
         fee_amount: BalanceOf<T>,
+        existence: ExistenceRequirement,
     ) -> DispatchResult {
         let rent_fee_pot = Self::rent_fee_pot().ok_or(Error::<T>::UndefinedRentPot)?;
 
@@ -625,13 +1493,12 @@ impl<T: Config> Pallet<T> {
         // 则回退到矿工当前设置；仍未设置则回退到 stash
         let rent_receiver = Self::rent_order_receiver(&rent_id)
             .unwrap_or_else(|| <online_profile::Pallet<T>>::effective_rent_receiver(&machine_stash));
-        <T as pallet::Config>::Currency::transfer(renter, &rent_receiver, fee_to_stash, KeepAlive)?;
-        <T as pallet::Config>::Currency::transfer(
-            renter,
-            &rent_fee_pot,
-            fee_to_destroy,
-            KeepAlive,
-        )?;
+        <T as pallet::Config>::Currency::transfer(payer, &rent_receiver, fee_to_stash, existence)?;
+        <T as pallet::Config>::Currency::transfer(payer, &rent_fee_pot, fee_to_destroy, existence)?;
+        RentOrderPaidFee::<T>::mutate(rent_id, |(paid_to_stash, paid_to_destroy)| {
+            *paid_to_stash = paid_to_stash.saturating_add(fee_to_stash);
+            *paid_to_destroy = paid_to_destroy.saturating_add(fee_to_destroy);
+        });
         let _ = T::RTOps::change_machine_rent_fee(
             machine_stash,
             machine_id,
@@ -659,6 +1526,7 @@ impl<T: Config> Pallet<T> {
                     false,
                 );
             }
+            let _ = Self::refund_asset_rent_fee(rent_id, &rent_info.renter);
 
             let mut user_order = Self::user_order(&rent_info.renter);
             ItemList::rm_item(&mut user_order, &rent_id);
@@ -690,6 +1558,9 @@ impl<T: Config> Pallet<T> {
 
             RentInfo::<T>::remove(rent_id);
             RentOrderReceiver::<T>::remove(rent_id);
+            RentOrderEndpoint::<T>::remove(rent_id);
+            RentLiveness::<T>::remove(rent_id);
+            Self::rm_from_rent_group(rent_id);
 
             T::RTOps::change_machine_status_on_confirm_expired(
                 &rent_info.machine_id,
@@ -731,52 +1602,66 @@ impl<T: Config> Pallet<T> {
         let pending_ending = Self::rent_ending(block_number);
         for rent_id in pending_ending {
             let rent_info = Self::rent_info(&rent_id).ok_or(())?;
-            let machine_id = rent_info.machine_id.clone();
             let rent_duration = rent_info.rent_end.saturating_sub(rent_info.rent_start);
+            Self::finish_rent_order(rent_id, rent_info, rent_duration)?;
+        }
+        Ok(())
+    }
 
-            // NOTE: 只要机器还有租用订单(租用订单>1)，就不修改成online状态。
-            let is_last_rent = Self::is_last_rent(&machine_id, &rent_info.renter)?;
-            let _ = T::RTOps::change_machine_status_on_rent_end(
-                &machine_id,
-                rent_info.gpu_num,
-                rent_duration,
-                is_last_rent.0,
-                is_last_rent.1,
-                rent_info.renter.clone(),
-            );
-
-            // return back staked money!
-            if !rent_info.stake_amount.is_zero() {
-                let _ = Self::change_renter_total_stake(
-                    &rent_info.renter,
-                    rent_info.stake_amount,
-                    false,
-                );
-            }
+    // 租用结束(到期或提前终止)：通知onlineProfile，退还剩余质押，并清理订单记录
+    fn finish_rent_order(
+        rent_id: RentOrderId,
+        rent_info: RentOrderDetail<T::AccountId, T::BlockNumber, BalanceOf<T>>,
+        rent_duration: T::BlockNumber,
+    ) -> Result<(), ()> {
+        let machine_id = rent_info.machine_id.clone();
 
-            let mut user_order = Self::user_order(&rent_info.renter);
-            ItemList::rm_item(&mut user_order, &rent_id);
-            if user_order.is_empty() {
-                UserOrder::<T>::remove(&rent_info.renter);
-            } else {
-                UserOrder::<T>::insert(&rent_info.renter, user_order);
-            }
+        // NOTE: 只要机器还有租用订单(租用订单>1)，就不修改成online状态。
+        let is_last_rent = Self::is_last_rent(&machine_id, &rent_info.renter)?;
+        let _ = T::RTOps::change_machine_status_on_rent_end(
+            &machine_id,
+            rent_info.gpu_num,
+            rent_duration,
+            is_last_rent.0,
+            is_last_rent.1,
+            rent_info.renter.clone(),
+        );
 
-            let mut rent_ending = Self::rent_ending(block_number);
-            ItemList::rm_item(&mut rent_ending, &rent_id);
-            if rent_ending.is_empty() {
-                RentEnding::<T>::remove(block_number);
-            } else {
-                RentEnding::<T>::insert(block_number, rent_ending);
-            }
+        // return back staked money!
+        if !rent_info.stake_amount.is_zero() {
+            let _ =
+                Self::change_renter_total_stake(&rent_info.renter, rent_info.stake_amount, false);
+        }
+        let _ = Self::close_escrow(rent_id, <frame_system::Pallet<T>>::block_number());
 
-            let mut machine_rent_order = Self::machine_rent_order(&rent_info.machine_id);
-            machine_rent_order.clean_expired_order(rent_id, rent_info.gpu_index);
-            MachineRentOrder::<T>::insert(&rent_info.machine_id, machine_rent_order);
+        let mut user_order = Self::user_order(&rent_info.renter);
+        ItemList::rm_item(&mut user_order, &rent_id);
+        if user_order.is_empty() {
+            UserOrder::<T>::remove(&rent_info.renter);
+        } else {
+            UserOrder::<T>::insert(&rent_info.renter, user_order);
+        }
 
-            RentInfo::<T>::remove(rent_id);
-            RentOrderReceiver::<T>::remove(rent_id);
+        let mut rent_ending = Self::rent_ending(rent_info.rent_end);
+        ItemList::rm_item(&mut rent_ending, &rent_id);
+        if rent_ending.is_empty() {
+            RentEnding::<T>::remove(rent_info.rent_end);
+        } else {
+            RentEnding::<T>::insert(rent_info.rent_end, rent_ending);
         }
+
+        let mut machine_rent_order = Self::machine_rent_order(&machine_id);
+        machine_rent_order.clean_expired_order(rent_id, rent_info.gpu_index);
+        MachineRentOrder::<T>::insert(&machine_id, machine_rent_order);
+
+        RentInfo::<T>::remove(rent_id);
+        RentOrderReceiver::<T>::remove(rent_id);
+        RentOrderPaidFee::<T>::remove(rent_id);
+        RentOrderAsset::<T>::remove(rent_id);
+        RentOrderEndpoint::<T>::remove(rent_id);
+        RentLiveness::<T>::remove(rent_id);
+        Self::expire_dispute(rent_id);
+        Self::rm_from_rent_group(rent_id);
         Ok(())
     }
 
@@ -911,12 +1796,12 @@ impl<T: Config> MachineInfoTrait for Pallet<T> {
         let machine_info = <online_profile::Pallet<T>>::machines_info(&machine_id)
             .ok_or(Error::<T>::Unknown.as_str())?;
 
-        let system_price = T::RTOps::get_machine_price(
+        let system_price = Self::get_system_price(
+            &machine_info.gpu_type_and_mem().0,
             machine_info.calc_point(),
             rent_gpu_num,
             machine_info.gpu_num(),
-        )
-        .ok_or(Error::<T>::GetMachinePriceFailed)?;
+        )?;
         let extra_price = <online_profile::Pallet<T>>::machine_extra_price(&machine_id)
             .checked_mul(rent_gpu_num as u64).ok_or(Error::<T>::Overflow)?;
         let machine_price = system_price.checked_add(extra_price).ok_or(Error::<T>::Overflow)?;
@@ -937,12 +1822,12 @@ impl<T: Config> MachineInfoTrait for Pallet<T> {
         let machine_info = <online_profile::Pallet<T>>::machines_info(&machine_id)
             .ok_or(Error::<T>::Unknown.as_str())?;
 
-        let system_price = T::RTOps::get_machine_price(
+        let system_price = Self::get_system_price(
+            &machine_info.gpu_type_and_mem().0,
             machine_info.calc_point(),
             rent_gpu_num,
             machine_info.gpu_num(),
-        )
-        .ok_or(Error::<T>::GetMachinePriceFailed)?;
+        )?;
         let extra_price = <online_profile::Pallet<T>>::machine_extra_price(&machine_id)
             .checked_mul(rent_gpu_num as u64).ok_or(Error::<T>::Overflow)?;
         let machine_price = system_price.checked_add(extra_price).ok_or(Error::<T>::Overflow)?;
@@ -967,8 +1852,8 @@ impl<T: Config> MachineInfoTrait for Pallet<T> {
         rent_gpu_num: u32,
         total_gpu_num: u32,
     ) -> Result<u64, &'static str> {
-        let machine_price = T::RTOps::get_machine_price(calc_point, rent_gpu_num, total_gpu_num)
-            .ok_or(Error::<T>::GetMachinePriceFailed)?;
+        // 未指定GPU型号，只按全网使用率调整
+        let machine_price = Self::get_system_price(&[], calc_point, rent_gpu_num, total_gpu_num)?;
 
         let rent_fee_value = machine_price
             .checked_mul(duration.saturated_into::<u64>())
@@ -989,12 +1874,12 @@ impl<T: Config> MachineInfoTrait for Pallet<T> {
         let machine_info = <online_profile::Pallet<T>>::machines_info(&machine_id)
             .ok_or(Error::<T>::Unknown.as_str())?;
 
-        let system_price = T::RTOps::get_machine_price(
+        let system_price = Self::get_system_price(
+            &machine_info.gpu_type_and_mem().0,
             machine_info.calc_point(),
             rent_gpu_num,
             machine_info.gpu_num(),
-        )
-        .ok_or(Error::<T>::GetMachinePriceFailed)?;
+        )?;
         let extra_price = <online_profile::Pallet<T>>::machine_extra_price(&machine_id)
             .checked_mul(rent_gpu_num as u64).ok_or(Error::<T>::Overflow)?;
         let machine_price = system_price.checked_add(extra_price).ok_or(Error::<T>::Overflow)?;
@@ -4,17 +4,17 @@ use dbc_support::{
     machine_type::{
         CommitteeUploadInfo, Latitude, Longitude, StakerCustomizeInfo, StandardGpuPointPrice,
     },
-    ONE_DAY,
+    EraIndex, ONE_DAY,
 };
 use frame_support::{
     assert_ok,
     pallet_prelude::Weight,
     parameter_types,
-    traits::{ConstU32, OnFinalize, OnInitialize},
+    traits::{AsEnsureOriginWithArg, ConstU128, ConstU32, OnFinalize, OnInitialize},
     PalletId,
 };
 pub use frame_system::{self as system, RawOrigin};
-use frame_system::{EnsureRoot, EnsureWithSuccess};
+use frame_system::{EnsureRoot, EnsureSigned, EnsureWithSuccess};
 pub use sp_core::{
     sr25519::{self, Signature},
     H256,
@@ -39,6 +39,9 @@ pub const INIT_BALANCE: u128 = 10_000_000 * ONE_DBC;
 pub const INIT_TIMESTAMP: u64 = 90_000;
 pub const BLOCK_TIME: u64 = 30_000;
 pub type BlockNumber = u32;
+// 用于支付租金的DLC资产
+pub const DLC_ASSET_ID: u32 = 88;
+pub const ONE_DLC: u128 = 1_000_000_000_000_000;
 
 parameter_types! {
     pub const BlockHashCount: BlockNumber = 250;
@@ -172,10 +175,16 @@ parameter_types! {
     pub const ProfitReleaseDuration: u64 = 150;
 }
 
+parameter_types! {
+    pub CommitteeCurrentEra: EraIndex = OnlineProfile::current_era();
+}
+
 impl committee::Config for TestRuntime {
     type Currency = Balances;
     type RuntimeEvent = RuntimeEvent;
-    // type WeightInfo = ();
+    type SlashAndReward = GenericFunc;
+    type CurrentEra = CommitteeCurrentEra;
+    type WeightInfo = ();
 }
 
 impl online_profile::Config for TestRuntime {
@@ -188,7 +197,8 @@ impl online_profile::Config for TestRuntime {
     type Slash = Treasury;
     type CancelSlashOrigin =
         pallet_collective::EnsureProportionAtLeast<Self::AccountId, TechnicalCollective, 2, 3>;
-    type SlashAndReward = GenericFunc;
+    type SlashAndReward = Committee;
+    type WeightInfo = ();
 }
 
 impl dbc_price_ocw::Config for TestRuntime {
@@ -197,6 +207,40 @@ impl dbc_price_ocw::Config for TestRuntime {
     type RandomnessSource = RandomnessCollectiveFlip;
 }
 
+impl dlc_price_ocw::Config for TestRuntime {
+    type Currency = Balances;
+    type RuntimeEvent = RuntimeEvent;
+    type RandomnessSource = RandomnessCollectiveFlip;
+}
+
+parameter_types! {
+    pub const MinLockAmount: Balance = 100 * ONE_DLC;
+    pub const MaxLockDuration: BlockNumber = 1800 * ONE_DAY;
+}
+
+impl pallet_assets::Config for TestRuntime {
+    type RuntimeEvent = RuntimeEvent;
+    type Balance = Balance;
+    type AssetId = u32;
+    type AssetIdParameter = parity_scale_codec::Compact<u32>;
+    type Currency = Balances;
+    type CreateOrigin = AsEnsureOriginWithArg<EnsureSigned<Self::AccountId>>;
+    type ForceOrigin = EnsureRoot<Self::AccountId>;
+    type AssetDeposit = ConstU128<1>;
+    type AssetAccountDeposit = ConstU128<1>;
+    type MetadataDepositBase = ConstU128<1>;
+    type MetadataDepositPerByte = ConstU128<1>;
+    type ApprovalDeposit = ConstU128<1>;
+    type StringLimit = ConstU32<50>;
+    type Freezer = ();
+    type Extra = ();
+    type CallbackHandle = ();
+    type WeightInfo = ();
+    type RemoveItemsLimit = ConstU32<1000>;
+    type MinLockAmount = MinLockAmount;
+    type MaxLockDuration = MaxLockDuration;
+}
+
 impl online_committee::Config for TestRuntime {
     type RuntimeEvent = RuntimeEvent;
     type Currency = Balances;
@@ -204,7 +248,12 @@ impl online_committee::Config for TestRuntime {
     type ManageCommittee = Committee;
     type CancelSlashOrigin =
         pallet_collective::EnsureProportionAtLeast<Self::AccountId, TechnicalCollective, 2, 3>;
-    type SlashAndReward = GenericFunc;
+    type SlashAndReward = Committee;
+}
+
+parameter_types! {
+    pub const DlcAssetId: u32 = DLC_ASSET_ID;
+    pub const RentMachinePalletId: PalletId = PalletId(*b"dbc/rent");
 }
 
 impl rent_machine::Config for TestRuntime {
@@ -212,6 +261,16 @@ impl rent_machine::Config for TestRuntime {
     type RuntimeEvent = RuntimeEvent;
     type RTOps = OnlineProfile;
     type DbcPrice = DBCPriceOCW;
+    type DlcPrice = DLCPriceOCW;
+    type SurgePricing = OnlineProfile;
+    type ManageCommittee = Committee;
+    type CancelSlashOrigin =
+        pallet_collective::EnsureProportionAtLeast<Self::AccountId, TechnicalCollective, 2, 3>;
+    type SlashAndReward = Committee;
+    type Assets = Assets;
+    type DlcAssetId = DlcAssetId;
+    type PalletId = RentMachinePalletId;
+    type WeightInfo = ();
 }
 
 type TestExtrinsic = TestXt<RuntimeCall, ()>;
@@ -258,6 +317,8 @@ frame_support::construct_runtime!(
         Balances: pallet_balances,
         Committee: committee,
         DBCPriceOCW: dbc_price_ocw,
+        DLCPriceOCW: dlc_price_ocw,
+        Assets: pallet_assets,
         Treasury: pallet_treasury,
         GenericFunc: generic_func,
         RentMachine: rent_machine,
@@ -285,6 +346,14 @@ pub fn new_test_ext_after_machine_online() -> sp_io::TestExternalities {
     .assimilate_storage(&mut storage)
     .unwrap();
 
+    pallet_assets::GenesisConfig::<TestRuntime> {
+        assets: vec![(DLC_ASSET_ID, sr25519::Public::from(Sr25519Keyring::Alice), true, 1)],
+        metadata: vec![],
+        accounts: vec![(DLC_ASSET_ID, sr25519::Public::from(Sr25519Keyring::Dave), INIT_BALANCE)],
+    }
+    .assimilate_storage(&mut storage)
+    .unwrap();
+
     let mut ext = sp_io::TestExternalities::from(storage);
 
     let controller = sr25519::Public::from(Sr25519Keyring::Eve);
@@ -313,6 +382,12 @@ pub fn new_test_ext_after_machine_online() -> sp_io::TestExternalities {
         DBCPriceOCW::add_avg_price();
         assert_eq!(DBCPriceOCW::avg_price(), Some(12_000u64));
 
+        // 初始化DLC价格 (0.003$)
+        for _ in 0..dlc_price_ocw::MAX_LEN {
+            DLCPriceOCW::add_price(3_000u64);
+        }
+        DLCPriceOCW::add_avg_price();
+
         // 初始化设置参数
         // 委员会每次抢单质押数量 (15$)
         // 委员会每次抢单质押数量 (15$)
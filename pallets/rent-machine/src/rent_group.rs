@@ -0,0 +1,132 @@
+use crate::{
+    Config, Error, Event, NextRentGroupId, Pallet, RentGroup, RentOrderGroup,
+    MAX_MACHINES_PER_GROUP,
+};
+use dbc_support::{
+    rental_type::{RentGroupDetail, RentStatus},
+    ItemList, MachineId, RentOrderId,
+};
+use frame_support::{dispatch::DispatchResultWithPostInfo, ensure};
+use sp_runtime::traits::Zero;
+use sp_std::{vec, vec::Vec};
+
+// 批量租用：所有机器检查通过后才生成订单，任何一台失败则整体失败
+impl<T: Config> Pallet<T> {
+    pub(crate) fn rent_machines_by_block(
+        renter: T::AccountId,
+        machines: Vec<(MachineId, u32)>,
+        duration: T::BlockNumber,
+    ) -> DispatchResultWithPostInfo {
+        ensure!(
+            !machines.is_empty() && machines.len() <= MAX_MACHINES_PER_GROUP as usize,
+            Error::<T>::InvalidRentGroupSize
+        );
+        if duration == Zero::zero() {
+            return Ok(().into())
+        }
+
+        // 先检查所有机器都有足够的GPU，再扣费及生成订单
+        let mut checked_machine: Vec<MachineId> = vec![];
+        let mut rent_duration = duration;
+        for (machine_id, rent_gpu_num) in machines.iter() {
+            ensure!(!checked_machine.contains(machine_id), Error::<T>::DuplicateMachineInGroup);
+            let machine_info = <online_profile::Pallet<T>>::machines_info(machine_id)
+                .ok_or(Error::<T>::MachineNotFound)?;
+            ensure!(machine_info.gpu_num() != 0, Error::<T>::MachineNotRentable);
+
+            rent_duration = Self::check_rent_capacity(machine_id, *rent_gpu_num, duration)?;
+            checked_machine.push(machine_id.clone());
+        }
+
+        // NOTE: 批量租用只扣除一次10个DBC
+        <generic_func::Pallet<T>>::pay_fixed_tx_fee(renter.clone())
+            .map_err(|_| Error::<T>::PayTxFeeFailed)?;
+
+        let group_id = Self::get_new_rent_group_id();
+        let mut rent_ids = vec![];
+        for (machine_id, rent_gpu_num) in machines {
+            let rent_id = Self::create_rent_order(
+                renter.clone(),
+                machine_id,
+                rent_gpu_num,
+                rent_duration,
+                None,
+            )?;
+            RentOrderGroup::<T>::insert(rent_id, group_id);
+            rent_ids.push(rent_id);
+        }
+
+        RentGroup::<T>::insert(
+            group_id,
+            RentGroupDetail { renter: renter.clone(), rent_ids: rent_ids.clone() },
+        );
+
+        Self::deposit_event(Event::RentGroup(group_id, renter, rent_ids));
+        Ok(().into())
+    }
+
+    // 确认父订单下所有等待确认的子订单
+    pub(crate) fn confirm_rent_group_orders(
+        renter: T::AccountId,
+        group_id: RentOrderId,
+    ) -> DispatchResultWithPostInfo {
+        let rent_group = Self::rent_group(group_id).ok_or(Error::<T>::RentGroupNotFound)?;
+        ensure!(rent_group.renter == renter, Error::<T>::NotMachineRenter);
+
+        let mut confirmed = false;
+        for rent_id in rent_group.rent_ids {
+            let rent_info = Self::rent_info(rent_id).ok_or(Error::<T>::NoOrderExist)?;
+            if rent_info.rent_status != RentStatus::WaitingVerifying {
+                continue
+            }
+            Self::confirm_rent_order(renter.clone(), rent_id)?;
+            confirmed = true;
+        }
+        ensure!(confirmed, Error::<T>::NoOrderExist);
+
+        Self::deposit_event(Event::ConfirmRentGroup(group_id, renter));
+        Ok(().into())
+    }
+
+    // 续租父订单下所有的子订单
+    pub(crate) fn relet_rent_group_orders(
+        renter: T::AccountId,
+        group_id: RentOrderId,
+        duration: T::BlockNumber,
+    ) -> DispatchResultWithPostInfo {
+        let rent_group = Self::rent_group(group_id).ok_or(Error::<T>::RentGroupNotFound)?;
+        ensure!(rent_group.renter == renter, Error::<T>::NotMachineRenter);
+
+        for rent_id in rent_group.rent_ids {
+            Self::relet_machine_by_block(renter.clone(), rent_id, duration)?;
+        }
+
+        Self::deposit_event(Event::ReletRentGroup(group_id, renter, duration));
+        Ok(().into())
+    }
+
+    // 子订单结束后从父订单中移除，所有子订单都结束后移除父订单
+    pub(crate) fn rm_from_rent_group(rent_id: RentOrderId) {
+        let group_id = match RentOrderGroup::<T>::take(rent_id) {
+            Some(group_id) => group_id,
+            None => return,
+        };
+        let mut rent_group = match Self::rent_group(group_id) {
+            Some(rent_group) => rent_group,
+            None => return,
+        };
+
+        ItemList::rm_item(&mut rent_group.rent_ids, &rent_id);
+        if rent_group.rent_ids.is_empty() {
+            RentGroup::<T>::remove(group_id);
+        } else {
+            RentGroup::<T>::insert(group_id, rent_group);
+        }
+    }
+
+    fn get_new_rent_group_id() -> RentOrderId {
+        let group_id = Self::next_rent_group_id();
+        NextRentGroupId::<T>::put(group_id.wrapping_add(1));
+        group_id
+    }
+}
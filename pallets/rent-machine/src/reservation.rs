@@ -0,0 +1,294 @@
+use crate::{
+    BalanceOf, Config, Error, Event, MachineRentOrder, MachineReservation, Pallet, RentEnding,
+    RentInfo, RentOrderReceiver, RentReservation, ReservationStarting, UserOrder, UserReservation,
+    MILLISECS_PER_BLOCK,
+};
+use dbc_support::{
+    machine_type::MachineStatus,
+    rental_type::{RentOrderDetail, RentReservationDetail},
+    traits::RTOps,
+    ItemList, MachineId, RentOrderId, HALF_HOUR, ONE_DAY,
+};
+use frame_support::{dispatch::DispatchResultWithPostInfo, ensure, storage::with_storage_layer};
+use online_profile::MachineRentalMode;
+use sp_runtime::{
+    traits::{CheckedAdd, SaturatedConversion, Saturating, Zero},
+    DispatchError,
+};
+
+type ReservationOf<T> = RentReservationDetail<
+    <T as frame_system::Config>::AccountId,
+    <T as frame_system::Config>::BlockNumber,
+    BalanceOf<T>,
+>;
+
+// 分时段机器的预约
+impl<T: Config> Pallet<T> {
+    pub(crate) fn reserve_machine_by_block(
+        renter: T::AccountId,
+        machine_id: MachineId,
+        rent_gpu_num: u32,
+        rent_start: T::BlockNumber,
+        duration: T::BlockNumber,
+    ) -> DispatchResultWithPostInfo {
+        let now = <frame_system::Pallet<T>>::block_number();
+        let machine_info = <online_profile::Pallet<T>>::machines_info(&machine_id)
+            .ok_or(Error::<T>::Unknown)?;
+        let gpu_num = machine_info.gpu_num();
+
+        if gpu_num == 0 || duration == Zero::zero() {
+            return Ok(().into())
+        }
+
+        // 只有分时段出租的机器支持预约
+        ensure!(
+            <online_profile::Pallet<T>>::machine_rental_mode(&machine_id) ==
+                MachineRentalMode::TimeSlot,
+            Error::<T>::ReservationNotAllowed
+        );
+        ensure!(duration % HALF_HOUR.into() == Zero::zero(), Error::<T>::OnlyHalfHourAllowed);
+        ensure!(
+            machine_info.machine_status == MachineStatus::Online ||
+                machine_info.machine_status == MachineStatus::Rented,
+            Error::<T>::MachineNotRentable
+        );
+
+        // 预约开始时间必须在未来，且不超过MaximumRentalDuration
+        let max_duration: T::BlockNumber =
+            (Self::maximum_rental_duration().saturating_mul(ONE_DAY)).into();
+        ensure!(
+            rent_start > now && rent_start.saturating_sub(now) <= max_duration,
+            Error::<T>::InvalidReservationTime
+        );
+        ensure!(duration <= max_duration, Error::<T>::ExceedMaxRentalDuration);
+        let rent_end = rent_start.checked_add(&duration).ok_or(Error::<T>::Overflow)?;
+
+        // 预约时段必须落在机器允许出租的时段内
+        let start_ts_ms = Self::estimate_time_ms(rent_start).ok_or(Error::<T>::Overflow)?;
+        let end_ts_ms = Self::estimate_time_ms(rent_end).ok_or(Error::<T>::Overflow)?;
+        ensure!(
+            <online_profile::Pallet<T>>::is_rental_schedule_allowed(
+                &machine_id,
+                start_ts_ms,
+                end_ts_ms
+            ),
+            Error::<T>::OutOfRentalSchedule
+        );
+
+        // 预约开始时仍在租用的GPU + 重叠时段已被预约的GPU
+        let rented_gpu = Self::rented_gpu_after(&machine_id, rent_start);
+        let reserved_gpu = Self::reserved_gpu_in_window(&machine_id, rent_start, rent_end);
+        ensure!(rent_gpu_num + rented_gpu + reserved_gpu <= gpu_num, Error::<T>::GPUNotEnough);
+
+        // NOTE: 用户提交订单，需要扣除10个DBC
+        <generic_func::Pallet<T>>::pay_fixed_tx_fee(renter.clone())
+            .map_err(|_| Error::<T>::PayTxFeeFailed)?;
+
+        let rent_fee = Self::calc_rent_fee(
+            &machine_id,
+            machine_info.calc_point(),
+            rent_gpu_num,
+            gpu_num,
+            duration,
+        )?;
+
+        // 预约时即锁定租金
+        Self::change_renter_total_stake(&renter, rent_fee, true)
+            .map_err(|_| Error::<T>::InsufficientValue)?;
+
+        let rent_id = Self::get_new_rent_id();
+
+        RentReservation::<T>::insert(
+            rent_id,
+            RentReservationDetail {
+                machine_id: machine_id.clone(),
+                renter: renter.clone(),
+                rent_start,
+                rent_end,
+                stake_amount: rent_fee,
+                gpu_num: rent_gpu_num,
+            },
+        );
+        MachineReservation::<T>::mutate(&machine_id, |reservation| {
+            ItemList::add_item(reservation, rent_id);
+        });
+        UserReservation::<T>::mutate(&renter, |reservation| {
+            ItemList::add_item(reservation, rent_id);
+        });
+        ReservationStarting::<T>::mutate(rent_start, |starting| {
+            ItemList::add_item(starting, rent_id);
+        });
+
+        Self::deposit_event(Event::Reserve(
+            rent_id,
+            renter,
+            machine_id,
+            rent_gpu_num,
+            rent_start,
+            duration,
+            rent_fee,
+        ));
+        Ok(().into())
+    }
+
+    // 预约开始时，将预约转为租用中的订单并用锁定的租金支付；失败则退还锁定的租金
+    pub(crate) fn check_reservation_starting(block_number: T::BlockNumber) {
+        if !ReservationStarting::<T>::contains_key(block_number) {
+            return
+        }
+
+        let starting = ReservationStarting::<T>::take(block_number);
+        for rent_id in starting {
+            let reservation = match Self::rent_reservation(rent_id) {
+                Some(reservation) => reservation,
+                None => continue,
+            };
+            Self::clean_reservation(rent_id, &reservation);
+
+            // on_finalize中执行，不在交易中：激活失败时回滚已写入的存储
+            let activated = with_storage_layer(|| {
+                Self::activate_reservation(rent_id, &reservation, block_number)
+            });
+            if activated.is_ok() {
+                Self::deposit_event(Event::ReservationActivated(
+                    rent_id,
+                    reservation.renter,
+                    reservation.machine_id,
+                ));
+            } else {
+                let _ = Self::change_renter_total_stake(
+                    &reservation.renter,
+                    reservation.stake_amount,
+                    false,
+                );
+                Self::deposit_event(Event::ReservationFailed(
+                    rent_id,
+                    reservation.renter,
+                    reservation.machine_id,
+                    reservation.stake_amount,
+                ));
+            }
+        }
+    }
+
+    fn activate_reservation(
+        rent_id: RentOrderId,
+        reservation: &ReservationOf<T>,
+        now: T::BlockNumber,
+    ) -> Result<(), DispatchError> {
+        let machine_id = reservation.machine_id.clone();
+        let machine_info = <online_profile::Pallet<T>>::machines_info(&machine_id)
+            .ok_or(Error::<T>::MachineNotFound)?;
+        let gpu_num = machine_info.gpu_num();
+        let machine_rented_gpu = <online_profile::Pallet<T>>::machine_rented_gpu(&machine_id);
+
+        ensure!(
+            machine_info.machine_status == MachineStatus::Online ||
+                machine_info.machine_status == MachineStatus::Rented,
+            Error::<T>::MachineNotRentable
+        );
+        ensure!(reservation.gpu_num + machine_rented_gpu <= gpu_num, Error::<T>::GPUNotEnough);
+
+        let mut machine_rent_order = Self::machine_rent_order(&machine_id);
+        let rentable_gpu_index = machine_rent_order.gen_rentable_gpu(reservation.gpu_num, gpu_num);
+        ItemList::add_item(&mut machine_rent_order.rent_order, rent_id);
+
+        T::RTOps::change_machine_status_on_rent_start(&machine_id, reservation.gpu_num)
+            .map_err(|_| Error::<T>::Unknown)?;
+
+        // 可能失败的操作均在此之前完成
+        if let Some(snapshot) =
+            <online_profile::Pallet<T>>::stash_rent_receiver(&machine_info.machine_stash)
+        {
+            RentOrderReceiver::<T>::insert(&rent_id, snapshot);
+        }
+
+        // 锁定的租金作为订单质押，随后与confirm_rent一样支付，用户无需再确认
+        RentInfo::<T>::insert(
+            &rent_id,
+            RentOrderDetail::new(
+                machine_id.clone(),
+                reservation.renter.clone(),
+                now,
+                reservation.rent_end,
+                reservation.stake_amount,
+                reservation.gpu_num,
+                rentable_gpu_index,
+            ),
+        );
+        UserOrder::<T>::mutate(&reservation.renter, |user_order| {
+            ItemList::add_item(user_order, rent_id);
+        });
+        RentEnding::<T>::mutate(reservation.rent_end, |rent_ending| {
+            ItemList::add_item(rent_ending, rent_id);
+        });
+        MachineRentOrder::<T>::insert(&machine_id, machine_rent_order);
+
+        Self::confirm_rent_order(reservation.renter.clone(), rent_id)
+    }
+
+    // 移除预约的索引，不处理锁定的租金
+    pub(crate) fn clean_reservation(rent_id: RentOrderId, reservation: &ReservationOf<T>) {
+        let mut machine_reservation = Self::machine_reservation(&reservation.machine_id);
+        ItemList::rm_item(&mut machine_reservation, &rent_id);
+        if machine_reservation.is_empty() {
+            MachineReservation::<T>::remove(&reservation.machine_id);
+        } else {
+            MachineReservation::<T>::insert(&reservation.machine_id, machine_reservation);
+        }
+
+        let mut user_reservation = Self::user_reservation(&reservation.renter);
+        ItemList::rm_item(&mut user_reservation, &rent_id);
+        if user_reservation.is_empty() {
+            UserReservation::<T>::remove(&reservation.renter);
+        } else {
+            UserReservation::<T>::insert(&reservation.renter, user_reservation);
+        }
+
+        let mut starting = Self::reservation_starting(reservation.rent_start);
+        ItemList::rm_item(&mut starting, &rent_id);
+        if starting.is_empty() {
+            ReservationStarting::<T>::remove(reservation.rent_start);
+        } else {
+            ReservationStarting::<T>::insert(reservation.rent_start, starting);
+        }
+
+        RentReservation::<T>::remove(rent_id);
+    }
+
+    /// 与 [start, end) 有交集的预约所占用的GPU数（保守估计：所有重叠预约之和）
+    pub fn reserved_gpu_in_window(
+        machine_id: &MachineId,
+        start: T::BlockNumber,
+        end: T::BlockNumber,
+    ) -> u32 {
+        Self::machine_reservation(machine_id)
+            .into_iter()
+            .filter_map(|rent_id| Self::rent_reservation(rent_id))
+            .filter(|reservation| reservation.overlaps(start, end))
+            .fold(0u32, |acc, reservation| acc.saturating_add(reservation.gpu_num))
+    }
+
+    // 在at块高之后仍在租用中的GPU数
+    fn rented_gpu_after(machine_id: &MachineId, at: T::BlockNumber) -> u32 {
+        Self::machine_rent_order(machine_id)
+            .rent_order
+            .into_iter()
+            .filter_map(|rent_id| Self::rent_info(rent_id))
+            .filter(|rent_info| rent_info.rent_end > at)
+            .fold(0u32, |acc, rent_info| acc.saturating_add(rent_info.gpu_num))
+    }
+
+    // 根据块高估算链上时间（毫秒）
+    pub(crate) fn estimate_time_ms(at: T::BlockNumber) -> Option<u64> {
+        let now = <frame_system::Pallet<T>>::block_number();
+        let now_ms = <online_profile::Pallet<T>>::current_time_ms();
+        if at >= now {
+            let blocks: u64 = at.saturating_sub(now).saturated_into::<u64>();
+            now_ms.checked_add(blocks.checked_mul(MILLISECS_PER_BLOCK)?)
+        } else {
+            let blocks: u64 = now.saturating_sub(at).saturated_into::<u64>();
+            now_ms.checked_sub(blocks.checked_mul(MILLISECS_PER_BLOCK)?)
+        }
+    }
+}
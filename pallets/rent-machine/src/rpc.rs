@@ -1,7 +1,11 @@
 use sp_std::vec::Vec;
 
 use crate::{BalanceOf, Config, MachineGPUOrder, Pallet, RentOrderId};
-use dbc_support::{rental_type::RentOrderDetail, MachineId};
+use dbc_support::{
+    rental_type::{MachineDisputeStats, RentEscrowDetail, RentOrderDetail},
+    MachineId,
+};
+use online_profile::rpc_types::DayAvailability;
 
 // RPC
 impl<T: Config> Pallet<T> {
@@ -32,4 +36,51 @@ impl<T: Config> Pallet<T> {
     pub fn get_machine_rent_id(machine_id: MachineId) -> MachineGPUOrder {
         Self::machine_rent_order(machine_id)
     }
+
+    pub fn get_order_escrow(
+        rent_id: RentOrderId,
+    ) -> Option<RentEscrowDetail<T::AccountId, T::BlockNumber, BalanceOf<T>>> {
+        Self::order_escrow(rent_id)
+    }
+
+    pub fn get_machine_dispute_stats(machine_id: MachineId) -> MachineDisputeStats {
+        Self::machine_dispute_stats(machine_id)
+    }
+
+    /// 分时段机器在 [from_day, to_day] (UTC 日期) 每天的可用时段，及各时段同时被租用/预约的GPU数
+    pub fn get_machine_availability(
+        machine_id: MachineId,
+        from_day: u32,
+        to_day: u32,
+    ) -> Vec<DayAvailability> {
+        let mut rented = Vec::new();
+
+        for rent_id in Self::machine_rent_order(&machine_id).rent_order {
+            if let Some(rent_info) = Self::rent_info(rent_id) {
+                if let (Some(start), Some(end)) = (
+                    Self::estimate_time_ms(rent_info.rent_start),
+                    Self::estimate_time_ms(rent_info.rent_end),
+                ) {
+                    rented.push((start, end, rent_info.gpu_num));
+                }
+            }
+        }
+        for rent_id in Self::machine_reservation(&machine_id) {
+            if let Some(reservation) = Self::rent_reservation(rent_id) {
+                if let (Some(start), Some(end)) = (
+                    Self::estimate_time_ms(reservation.rent_start),
+                    Self::estimate_time_ms(reservation.rent_end),
+                ) {
+                    rented.push((start, end, reservation.gpu_num));
+                }
+            }
+        }
+
+        <online_profile::Pallet<T>>::get_machine_availability(
+            &machine_id,
+            from_day,
+            to_day,
+            &rented,
+        )
+    }
 }
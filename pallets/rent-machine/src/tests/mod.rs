@@ -1,7 +1,14 @@
 pub mod test_gpu_rental_rules;
 pub mod test_online_profile;
+pub mod test_rent_dispute;
+pub mod test_rent_escrow;
+pub mod test_rent_group;
 pub mod test_rent_individual_gpu;
 pub mod test_rent_receiver;
+pub mod test_rent_with_asset;
 pub mod test_renters;
+pub mod test_reservation;
+pub mod test_surge_pricing;
+pub mod test_terminate_rent;
 pub mod test_time_slot_rental;
 pub mod tests;
@@ -10,7 +10,7 @@ use dbc_support::{
 pub use sp_keyring::sr25519::Keyring as Sr25519Keyring;
 
 use frame_support::{assert_err, assert_ok, traits::ReservableCurrency};
-use online_profile::{Error as OnlineProfileErr, MachinesInfo};
+use online_profile::{Error as OnlineProfileErr, MachineSearchFilter, MachinesInfo};
 use pallet_balances::AccountData;
 use std::convert::TryInto;
 use system::AccountInfo;
@@ -443,3 +443,91 @@ fn restake_online_machine_works() {
 }
 #[test]
 fn cancel_online_profile_slash_works() {}
+
+// 按硬件、空闲GPU、价格及位置搜索可租用的机器
+#[test]
+fn search_machines_works() {
+    new_test_ext_after_machine_online().execute_with(|| {
+        let machine_id = "8eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a48"
+            .as_bytes()
+            .to_vec();
+        let controller = sr25519::Public::from(Sr25519Keyring::Eve);
+        let renter = sr25519::Public::from(Sr25519Keyring::Dave);
+
+        let search = |filter: MachineSearchFilter| -> Vec<MachineId> {
+            OnlineProfile::search_machines(filter, 0, 10)
+                .iter()
+                .map(|machine_info| machine_info.machine_id())
+                .collect()
+        };
+
+        assert_eq!(search(MachineSearchFilter::default()), vec![machine_id.clone()]);
+        assert_eq!(OnlineProfile::search_machines(MachineSearchFilter::default(), 1, 10), vec![]);
+
+        // 硬件条件
+        let filter = MachineSearchFilter {
+            gpu_type: "GeForceRTX3080".as_bytes().to_vec(),
+            min_gpu_mem: Some(10),
+            min_calc_point: Some(59890),
+            ..Default::default()
+        };
+        assert_eq!(search(filter), vec![machine_id.clone()]);
+        let filter = MachineSearchFilter {
+            gpu_type: "GeForceRTX4090".as_bytes().to_vec(),
+            ..Default::default()
+        };
+        assert_eq!(search(filter), vec![]);
+        let filter = MachineSearchFilter { min_gpu_mem: Some(11), ..Default::default() };
+        assert_eq!(search(filter), vec![]);
+        let filter = MachineSearchFilter {
+            rental_mode: Some(online_profile::MachineRentalMode::TimeSlot),
+            ..Default::default()
+        };
+        assert_eq!(search(filter), vec![]);
+
+        // 单卡每天价格 = 系统定价 74_862_500 + 卡主额外加价
+        assert_ok!(OnlineProfile::set_machine_extra_price(
+            RuntimeOrigin::signed(controller),
+            machine_id.clone(),
+            1_000_000
+        ));
+        let filter = MachineSearchFilter {
+            min_price: Some(75_862_500),
+            max_price: Some(75_862_500),
+            ..Default::default()
+        };
+        assert_eq!(search(filter), vec![machine_id.clone()]);
+        let filter = MachineSearchFilter { max_price: Some(75_000_000), ..Default::default() };
+        assert_eq!(search(filter), vec![]);
+
+        // 位置范围
+        let filter = MachineSearchFilter {
+            bounding_box: Some((
+                (Longitude::East(1000000), Latitude::South(100)),
+                (Longitude::East(1200000), Latitude::North(300000)),
+            )),
+            ..Default::default()
+        };
+        assert_eq!(search(filter), vec![machine_id.clone()]);
+        let filter = MachineSearchFilter {
+            bounding_box: Some((
+                (Longitude::West(1200000), Latitude::South(300000)),
+                (Longitude::West(1000000), Latitude::North(300000)),
+            )),
+            ..Default::default()
+        };
+        assert_eq!(search(filter), vec![]);
+
+        // 空闲GPU
+        assert_ok!(RentMachine::rent_machine(
+            RuntimeOrigin::signed(renter),
+            machine_id.clone(),
+            3,
+            ONE_DAY
+        ));
+        let filter = MachineSearchFilter { min_free_gpu: Some(1), ..Default::default() };
+        assert_eq!(search(filter), vec![machine_id.clone()]);
+        let filter = MachineSearchFilter { min_free_gpu: Some(2), ..Default::default() };
+        assert_eq!(search(filter), vec![]);
+    });
+}
@@ -0,0 +1,324 @@
+/// Unit tests for renter disputes.
+/// A renter disputes a renting order, one committee books it by locking the
+/// per-order stake and decides a partial refund, paid from the escrowed rent fee
+/// first and then from the rent receiver. An order can only be disputed once.
+/// A committee that does not resolve the dispute in time gets a pending slash it
+/// can apply to review, and the dispute waits to be booked again.
+use crate::{mock::*, Error, DISPUTE_RESOLVE_DURATION};
+use dbc_support::{
+    rental_type::{DisputeSlashResult, MachineDisputeStats, RentDisputeStatus},
+    ONE_DAY, TWO_DAYS,
+};
+use frame_support::{assert_noop, assert_ok};
+use once_cell::sync::Lazy;
+use sp_core::H256;
+use sp_runtime::Perbill;
+
+const renter_dave: Lazy<sp_core::sr25519::Public> =
+    Lazy::new(|| sr25519::Public::from(Sr25519Keyring::Dave));
+const stash: Lazy<sp_core::sr25519::Public> =
+    Lazy::new(|| sr25519::Public::from(Sr25519Keyring::Ferdie));
+const committee1: Lazy<sp_core::sr25519::Public> =
+    Lazy::new(|| sr25519::Public::from(Sr25519Keyring::One));
+const committee2: Lazy<sp_core::sr25519::Public> =
+    Lazy::new(|| sr25519::Public::from(Sr25519Keyring::Two));
+const machine_id: Lazy<Vec<u8>> = Lazy::new(|| {
+    "8eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a48"
+        .as_bytes()
+        .to_vec()
+});
+
+fn rent_and_confirm(duration: u32) {
+    assert_ok!(RentMachine::rent_machine(
+        RuntimeOrigin::signed(*renter_dave),
+        machine_id.clone(),
+        4,
+        duration
+    ));
+    assert_ok!(RentMachine::confirm_rent(RuntimeOrigin::signed(*renter_dave), 0));
+}
+
+#[test]
+fn dispute_refund_from_rent_receiver_works() {
+    new_test_ext_after_machine_online().execute_with(|| {
+        rent_and_confirm(ONE_DAY);
+        let evidence_hash = H256::repeat_byte(1);
+
+        assert_noop!(
+            RentMachine::dispute_rent(RuntimeOrigin::signed(*stash), 0, evidence_hash),
+            Error::<TestRuntime>::NotMachineRenter
+        );
+        assert_ok!(RentMachine::dispute_rent(
+            RuntimeOrigin::signed(*renter_dave),
+            0,
+            evidence_hash
+        ));
+        assert_noop!(
+            RentMachine::dispute_rent(RuntimeOrigin::signed(*renter_dave), 0, evidence_hash),
+            Error::<TestRuntime>::DisputeAlreadyExist
+        );
+        assert_eq!(
+            RentMachine::machine_dispute_stats(&*machine_id),
+            MachineDisputeStats { dispute_count: 1, refund_count: 0 }
+        );
+
+        // 只有委员会可以抢单，抢单后才能处理
+        assert_noop!(
+            RentMachine::committee_book_dispute(RuntimeOrigin::signed(*stash), 0),
+            Error::<TestRuntime>::NotCommittee
+        );
+        assert_noop!(
+            RentMachine::committee_resolve_dispute(
+                RuntimeOrigin::signed(*committee1),
+                0,
+                Perbill::from_percent(50)
+            ),
+            Error::<TestRuntime>::DisputeStatusNotAllowed
+        );
+
+        let used_stake = Committee::committee_stake(&*committee1).used_stake;
+        assert_ok!(RentMachine::committee_book_dispute(RuntimeOrigin::signed(*committee1), 0));
+        assert_eq!(
+            Committee::committee_stake(&*committee1).used_stake,
+            used_stake + 1000 * ONE_DBC
+        );
+        assert_noop!(
+            RentMachine::committee_book_dispute(RuntimeOrigin::signed(*committee2), 0),
+            Error::<TestRuntime>::DisputeStatusNotAllowed
+        );
+
+        // 非流式支付，从租金接收方(stash)退还
+        let refundable = RentMachine::rent_order_paid_fee(0).0;
+        let renter_balance = Balances::free_balance(&*renter_dave);
+        let stash_balance = Balances::free_balance(&*stash);
+        assert_ok!(RentMachine::committee_resolve_dispute(
+            RuntimeOrigin::signed(*committee1),
+            0,
+            Perbill::from_percent(50)
+        ));
+
+        // 处理完成后保留争议，已退还的租金从订单已支付的租金中扣除
+        let refund_amount = Perbill::from_percent(50) * refundable;
+        assert_eq!(
+            RentMachine::rent_dispute(0).unwrap().dispute_status,
+            RentDisputeStatus::Resolved
+        );
+        assert_eq!(RentMachine::rent_order_paid_fee(0).0, refundable - refund_amount);
+        assert_eq!(Balances::free_balance(&*renter_dave), renter_balance + refund_amount);
+        assert_eq!(Balances::free_balance(&*stash), stash_balance - refund_amount);
+        assert_eq!(Committee::committee_stake(&*committee1).used_stake, used_stake);
+        assert_eq!(
+            RentMachine::machine_dispute_stats(&*machine_id),
+            MachineDisputeStats { dispute_count: 1, refund_count: 1 }
+        );
+
+        // 每个订单只能提交一次争议
+        assert_noop!(
+            RentMachine::dispute_rent(RuntimeOrigin::signed(*renter_dave), 0, H256::repeat_byte(2)),
+            Error::<TestRuntime>::DisputeAlreadyExist
+        );
+    });
+}
+
+#[test]
+fn dispute_refund_from_escrow_works() {
+    new_test_ext_after_machine_online().execute_with(|| {
+        assert_ok!(RentMachine::set_rent_fee_streaming(RuntimeOrigin::root(), true));
+        rent_and_confirm(ONE_DAY);
+
+        assert_ok!(RentMachine::dispute_rent(
+            RuntimeOrigin::signed(*renter_dave),
+            0,
+            H256::repeat_byte(1)
+        ));
+        assert_ok!(RentMachine::committee_book_dispute(RuntimeOrigin::signed(*committee1), 0));
+
+        // 流式支付，从托管中尚未结算的租金退还
+        let escrow = RentMachine::order_escrow(0).unwrap();
+        let escrow_balance = Balances::free_balance(&RentMachine::escrow_account());
+        let stash_balance = Balances::free_balance(&*stash);
+        assert_ok!(RentMachine::committee_resolve_dispute(
+            RuntimeOrigin::signed(*committee1),
+            0,
+            Perbill::from_percent(10)
+        ));
+
+        let refund_amount = Perbill::from_percent(10) * escrow.total_amount;
+        assert_eq!(
+            RentMachine::order_escrow(0).unwrap().refunded_amount,
+            escrow.refunded_amount + refund_amount
+        );
+        assert_eq!(
+            Balances::free_balance(&RentMachine::escrow_account()),
+            escrow_balance - refund_amount
+        );
+        assert_eq!(Balances::free_balance(&*stash), stash_balance);
+    });
+}
+
+#[test]
+fn timeout_dispute_can_be_rebooked() {
+    new_test_ext_after_machine_online().execute_with(|| {
+        rent_and_confirm(3 * ONE_DAY);
+        assert_ok!(RentMachine::dispute_rent(
+            RuntimeOrigin::signed(*renter_dave),
+            0,
+            H256::repeat_byte(1)
+        ));
+
+        let committee1_stake = Committee::committee_stake(&*committee1);
+        let committee1_reserved = Balances::reserved_balance(&*committee1);
+        assert_ok!(RentMachine::committee_book_dispute(RuntimeOrigin::signed(*committee1), 0));
+
+        // 委员会超时未处理，争议重新等待抢单，抢单质押保持锁定直到惩罚执行
+        run_to_block(11 + DISPUTE_RESOLVE_DURATION);
+        let dispute = RentMachine::rent_dispute(0).unwrap();
+        assert_eq!(dispute.dispute_status, RentDisputeStatus::WaitingBook);
+        assert_eq!(dispute.committee, None);
+        let slash_info = RentMachine::pending_dispute_slash(0).unwrap();
+        assert_eq!(slash_info.committee, *committee1);
+        assert_eq!(slash_info.slash_exec_time, 11 + DISPUTE_RESOLVE_DURATION + TWO_DAYS);
+        assert_eq!(
+            Committee::committee_stake(&*committee1).used_stake,
+            committee1_stake.used_stake + 1000 * ONE_DBC
+        );
+        assert_eq!(Balances::reserved_balance(&*committee1), committee1_reserved);
+
+        assert_ok!(RentMachine::committee_book_dispute(RuntimeOrigin::signed(*committee2), 0));
+        assert_eq!(RentMachine::rent_dispute(0).unwrap().committee, Some(*committee2));
+
+        assert_noop!(
+            RentMachine::committee_resolve_dispute(
+                RuntimeOrigin::signed(*committee1),
+                0,
+                Perbill::from_percent(50)
+            ),
+            Error::<TestRuntime>::DisputeStatusNotAllowed
+        );
+        // 驳回争议，不退还租金
+        assert_ok!(RentMachine::committee_resolve_dispute(
+            RuntimeOrigin::signed(*committee2),
+            0,
+            Perbill::zero()
+        ));
+        assert_eq!(
+            RentMachine::machine_dispute_stats(&*machine_id),
+            MachineDisputeStats { dispute_count: 1, refund_count: 0 }
+        );
+
+        // 未申请复审，到期后扣除抢单质押
+        run_to_block(11 + DISPUTE_RESOLVE_DURATION + TWO_DAYS);
+        assert_eq!(
+            RentMachine::pending_dispute_slash(0).unwrap().slash_result,
+            DisputeSlashResult::Executed
+        );
+        assert_eq!(
+            Committee::committee_stake(&*committee1).used_stake,
+            committee1_stake.used_stake
+        );
+        assert_eq!(
+            Committee::committee_stake(&*committee1).staked_amount,
+            committee1_stake.staked_amount - 1000 * ONE_DBC
+        );
+        assert_eq!(Balances::reserved_balance(&*committee1), committee1_reserved - 1000 * ONE_DBC);
+    });
+}
+
+#[test]
+fn dispute_slash_can_be_reviewed() {
+    new_test_ext_after_machine_online().execute_with(|| {
+        rent_and_confirm(3 * ONE_DAY);
+        assert_ok!(RentMachine::dispute_rent(
+            RuntimeOrigin::signed(*renter_dave),
+            0,
+            H256::repeat_byte(1)
+        ));
+
+        let committee1_stake = Committee::committee_stake(&*committee1);
+        let committee1_reserved = Balances::reserved_balance(&*committee1);
+        assert_ok!(RentMachine::committee_book_dispute(RuntimeOrigin::signed(*committee1), 0));
+        run_to_block(11 + DISPUTE_RESOLVE_DURATION);
+
+        // 只有被惩罚的委员会可以申请复审，申请时锁定一次抢单的质押
+        assert_noop!(
+            RentMachine::apply_dispute_slash_review(RuntimeOrigin::signed(*committee2), 0, vec![]),
+            Error::<TestRuntime>::NotSlashed
+        );
+        assert_ok!(RentMachine::apply_dispute_slash_review(
+            RuntimeOrigin::signed(*committee1),
+            0,
+            vec![]
+        ));
+        assert_noop!(
+            RentMachine::apply_dispute_slash_review(RuntimeOrigin::signed(*committee1), 0, vec![]),
+            Error::<TestRuntime>::AlreadyApplied
+        );
+        assert_eq!(
+            Committee::committee_stake(&*committee1).used_stake,
+            committee1_stake.used_stake + 2000 * ONE_DBC
+        );
+
+        // 复审通过，释放抢单质押及复审质押
+        assert_ok!(RentMachine::do_cancel_dispute_slash(0));
+        assert_eq!(
+            RentMachine::pending_dispute_slash(0).unwrap().slash_result,
+            DisputeSlashResult::Canceled
+        );
+        assert_eq!(RentMachine::pending_dispute_slash_review(0), None);
+        assert_eq!(
+            RentMachine::unhandled_dispute_slash(11 + DISPUTE_RESOLVE_DURATION + TWO_DAYS),
+            vec![]
+        );
+        assert_eq!(Committee::committee_stake(&*committee1), committee1_stake);
+        assert_eq!(Balances::reserved_balance(&*committee1), committee1_reserved);
+    });
+}
+
+#[test]
+fn dispute_refund_then_terminate_not_exceed_paid_fee() {
+    new_test_ext_after_machine_online().execute_with(|| {
+        rent_and_confirm(10 * ONE_DAY);
+        let (paid_to_stash, paid_to_pot) = RentMachine::rent_order_paid_fee(0);
+
+        assert_ok!(RentMachine::dispute_rent(
+            RuntimeOrigin::signed(*renter_dave),
+            0,
+            H256::repeat_byte(1)
+        ));
+        let renter_balance = Balances::free_balance(&*renter_dave);
+        assert_ok!(RentMachine::committee_book_dispute(RuntimeOrigin::signed(*committee1), 0));
+        assert_ok!(RentMachine::committee_resolve_dispute(
+            RuntimeOrigin::signed(*committee1),
+            0,
+            Perbill::from_percent(90)
+        ));
+
+        // 争议退款后立即终止租用，剩余租金按未使用时长退还，退还总额不超过已支付的租金
+        run_to_block(20);
+        assert_ok!(RentMachine::terminate_rent(RuntimeOrigin::signed(*renter_dave), 0));
+
+        let total_refund = Balances::free_balance(&*renter_dave) - renter_balance;
+        assert!(total_refund > Perbill::from_percent(90) * paid_to_stash);
+        assert!(total_refund <= paid_to_stash + paid_to_pot);
+    });
+}
+
+#[test]
+fn dispute_removed_when_rent_end() {
+    new_test_ext_after_machine_online().execute_with(|| {
+        rent_and_confirm(ONE_DAY);
+        assert_ok!(RentMachine::dispute_rent(
+            RuntimeOrigin::signed(*renter_dave),
+            0,
+            H256::repeat_byte(1)
+        ));
+        let used_stake = Committee::committee_stake(&*committee1).used_stake;
+        assert_ok!(RentMachine::committee_book_dispute(RuntimeOrigin::signed(*committee1), 0));
+
+        // 订单结束时移除争议，并释放处理中的委员会的质押
+        assert_ok!(RentMachine::terminate_rent(RuntimeOrigin::signed(*renter_dave), 0));
+        assert_eq!(RentMachine::rent_dispute(0), None);
+        assert_eq!(RentMachine::dispute_deadline(11 + DISPUTE_RESOLVE_DURATION), vec![]);
+        assert_eq!(Committee::committee_stake(&*committee1).used_stake, used_stake);
+    });
+}
@@ -0,0 +1,159 @@
+/// Unit tests for streaming rent payments: the rent fee is moved into the pallet's
+/// escrow account and is settled every hour after the order is confirmed:
+/// the part for the hours the machine is online is released to the rent receiver, the
+/// part for the blocks the machine is offline is returned to the renter.
+use crate::{mock::*, MachineStatus};
+use dbc_support::{ONE_DAY, ONE_HOUR};
+use frame_support::assert_ok;
+use once_cell::sync::Lazy;
+use online_profile::{MachineOfflineRecords, MachinesInfo};
+use sp_runtime::Perbill;
+
+const renter_dave: Lazy<sp_core::sr25519::Public> =
+    Lazy::new(|| sr25519::Public::from(Sr25519Keyring::Dave));
+const stash: Lazy<sp_core::sr25519::Public> =
+    Lazy::new(|| sr25519::Public::from(Sr25519Keyring::Ferdie));
+const receiver_alice: Lazy<sp_core::sr25519::Public> =
+    Lazy::new(|| sr25519::Public::from(Sr25519Keyring::Alice));
+const machine_id: Lazy<Vec<u8>> = Lazy::new(|| {
+    "8eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a48"
+        .as_bytes()
+        .to_vec()
+});
+
+// 开启流式支付，在块高 11 租用全部 4 张GPU 1 天并确认
+fn streaming_rent_and_confirm() {
+    assert_ok!(RentMachine::set_rent_fee_streaming(RuntimeOrigin::root(), true));
+    assert_ok!(OnlineProfile::set_rent_receiver(
+        RuntimeOrigin::signed(*stash),
+        Some(*receiver_alice),
+    ));
+    assert_ok!(RentMachine::rent_machine(
+        RuntimeOrigin::signed(*renter_dave),
+        machine_id.clone(),
+        4,
+        ONE_DAY
+    ));
+    assert_ok!(RentMachine::confirm_rent(RuntimeOrigin::signed(*renter_dave), 0));
+}
+
+fn set_machine_status(status: MachineStatus<BlockNumber, sp_core::sr25519::Public>) {
+    let mut machine_info = OnlineProfile::machines_info(&*machine_id).unwrap();
+    machine_info.machine_status = status;
+    MachinesInfo::<TestRuntime>::insert(&*machine_id, &machine_info);
+}
+
+#[test]
+fn streaming_rent_fee_released_hourly_and_refunded_on_fault() {
+    new_test_ext_after_machine_online().execute_with(|| {
+        let receiver_before = Balances::free_balance(&*receiver_alice);
+        streaming_rent_and_confirm();
+
+        // 确认时不支付租金，租金转入托管账户
+        let escrow = RentMachine::order_escrow(0).unwrap();
+        let rent_fee = escrow.total_amount;
+        assert_eq!(escrow.last_settled, 11);
+        assert_eq!(escrow.rent_end, 11 + ONE_DAY);
+        assert_eq!(RentMachine::user_total_stake(&*renter_dave), 0);
+        assert_eq!(Balances::free_balance(&RentMachine::escrow_account()), rent_fee);
+        assert_eq!(RentMachine::rent_order_paid_fee(0), (0, 0));
+        assert_eq!(Balances::free_balance(&*receiver_alice), receiver_before);
+
+        // 确认租用一小时后结算 [11, 11 + ONE_HOUR) 的租金给矿工，并安排下一次结算
+        assert_eq!(RentMachine::escrow_settling(11 + ONE_HOUR), vec![0]);
+        run_to_block(11 + ONE_HOUR);
+        let released = Perbill::from_rational(ONE_HOUR, ONE_DAY) * rent_fee;
+        let escrow = RentMachine::order_escrow(0).unwrap();
+        assert_eq!(escrow.released_amount, released);
+        assert_eq!(escrow.last_settled, 11 + ONE_HOUR);
+        assert!(RentMachine::escrow_settling(11 + ONE_HOUR).is_empty());
+        assert_eq!(RentMachine::escrow_settling(11 + 2 * ONE_HOUR), vec![0]);
+        let (paid_to_receiver, paid_to_pot) = RentMachine::rent_order_paid_fee(0);
+        assert_eq!(paid_to_receiver + paid_to_pot, released);
+        assert_eq!(Balances::free_balance(&*receiver_alice), receiver_before + paid_to_receiver);
+        assert_eq!(Balances::free_balance(&RentMachine::escrow_account()), rent_fee - released);
+
+        // 机器在下一小时的后半段下线：在线时段的租金支付给矿工，下线时段的租金退还给租用人
+        set_machine_status(MachineStatus::StakerReportOffline(
+            11 + ONE_HOUR + ONE_HOUR / 2,
+            Box::new(MachineStatus::Rented),
+        ));
+        let renter_free = Balances::free_balance(&*renter_dave);
+        run_to_block(11 + 2 * ONE_HOUR);
+        let due = Perbill::from_rational(ONE_HOUR, ONE_DAY - ONE_HOUR) * (rent_fee - released);
+        let half_released = Perbill::from_rational(ONE_HOUR / 2, ONE_HOUR) * due;
+        let refunded = due - half_released;
+        let escrow = RentMachine::order_escrow(0).unwrap();
+        assert_eq!(escrow.refunded_amount, refunded);
+        assert_eq!(escrow.released_amount, released + half_released);
+        assert_eq!(escrow.last_settled, 11 + 2 * ONE_HOUR);
+        assert_eq!(Balances::free_balance(&*renter_dave), renter_free + refunded);
+        assert_eq!(
+            Balances::free_balance(&RentMachine::escrow_account()),
+            rent_fee - released - due
+        );
+
+        // 整个小时都处于下线状态时，全部退还给租用人
+        run_to_block(11 + 3 * ONE_HOUR);
+        let escrow = RentMachine::order_escrow(0).unwrap();
+        assert_eq!(escrow.released_amount, released + half_released);
+        assert!(escrow.refunded_amount > refunded);
+
+        // 机器恢复后继续结算，已结束的下线时段不影响之后的结算
+        let mut machine_info = OnlineProfile::machines_info(&*machine_id).unwrap();
+        machine_info.machine_status = MachineStatus::Rented;
+        machine_info.last_online_height = 11 + 3 * ONE_HOUR;
+        MachinesInfo::<TestRuntime>::insert(&*machine_id, &machine_info);
+        MachineOfflineRecords::<TestRuntime>::insert(
+            &*machine_id,
+            vec![(11 + ONE_HOUR + ONE_HOUR / 2, 11 + 3 * ONE_HOUR)],
+        );
+        run_to_block(11 + 4 * ONE_HOUR);
+        let escrow_before = escrow;
+        let escrow = RentMachine::order_escrow(0).unwrap();
+        assert_eq!(escrow.refunded_amount, escrow_before.refunded_amount);
+        assert!(escrow.released_amount > escrow_before.released_amount);
+
+        // 两次结算之间下线又恢复：按实际的下线时段退还
+        MachineOfflineRecords::<TestRuntime>::mutate(&*machine_id, |records| {
+            records.push((11 + 4 * ONE_HOUR + ONE_HOUR / 4, 11 + 4 * ONE_HOUR + ONE_HOUR * 3 / 4))
+        });
+        run_to_block(11 + 5 * ONE_HOUR);
+        let due = Perbill::from_rational(ONE_HOUR, ONE_DAY - 4 * ONE_HOUR) * escrow.remaining();
+        let to_release = Perbill::from_rational(ONE_HOUR / 2, ONE_HOUR) * due;
+        let escrow_before = escrow;
+        let escrow = RentMachine::order_escrow(0).unwrap();
+        assert_eq!(escrow.released_amount, escrow_before.released_amount + to_release);
+        assert_eq!(escrow.refunded_amount, escrow_before.refunded_amount + due - to_release);
+
+        // 租用结束时托管被清空
+        run_to_block(11 + ONE_DAY);
+        assert_eq!(RentMachine::order_escrow(0), None);
+        assert_eq!(RentMachine::rent_info(0), None);
+        assert_eq!(RentMachine::user_total_stake(&*renter_dave), 0);
+        assert_eq!(Balances::free_balance(&RentMachine::escrow_account()), 0);
+    });
+}
+
+#[test]
+fn terminate_streaming_rent_refunds_escrow() {
+    new_test_ext_after_machine_online().execute_with(|| {
+        streaming_rent_and_confirm();
+        run_to_block(11 + ONE_HOUR);
+
+        let escrow = RentMachine::order_escrow(0).unwrap();
+        let receiver_before = Balances::free_balance(&*receiver_alice);
+        let renter_before = Balances::free_balance(&*renter_dave);
+
+        assert_ok!(RentMachine::terminate_rent(RuntimeOrigin::signed(*renter_dave), 0));
+
+        // 托管的租金全部结清：结算到终止时间的部分支付给矿工，其余从托管账户退还给租用人
+        assert_eq!(RentMachine::order_escrow(0), None);
+        assert_eq!(RentMachine::user_total_stake(&*renter_dave), 0);
+        assert_eq!(Balances::free_balance(&*renter_dave), renter_before + escrow.remaining());
+        assert_eq!(Balances::free_balance(&RentMachine::escrow_account()), 0);
+        // 不会从租金接收方扣回租金
+        assert!(Balances::free_balance(&*receiver_alice) >= receiver_before);
+        assert_eq!(RentMachine::rent_info(0), None);
+    });
+}
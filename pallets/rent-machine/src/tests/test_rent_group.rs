@@ -0,0 +1,117 @@
+/// Unit tests for renting several machines in one call.
+/// Every machine is checked before any order is created, so the call either
+/// creates all child orders under one parent order or fails as a whole.
+use crate::{mock::*, Error, WAITING_CONFIRMING_DELAY};
+use dbc_support::{rental_type::RentStatus, ONE_DAY};
+use frame_support::{assert_noop, assert_ok};
+use once_cell::sync::Lazy;
+
+const renter_dave: Lazy<sp_core::sr25519::Public> =
+    Lazy::new(|| sr25519::Public::from(Sr25519Keyring::Dave));
+const machine_id: Lazy<Vec<u8>> = Lazy::new(|| {
+    "8eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a48"
+        .as_bytes()
+        .to_vec()
+});
+const unknown_machine_id: Lazy<Vec<u8>> = Lazy::new(|| {
+    "306f6a0a3e8f7f4e7c5e1d7c0b7a8a3e7d2a2fa6a54c4a8a0e3e4b0a9d1c2b3a"
+        .as_bytes()
+        .to_vec()
+});
+
+#[test]
+fn rent_machines_confirm_and_relet_group_works() {
+    new_test_ext_after_machine_online().execute_with(|| {
+        let balance_before = Balances::free_balance(&*renter_dave);
+        assert_ok!(RentMachine::rent_machines(
+            RuntimeOrigin::signed(*renter_dave),
+            vec![(machine_id.clone(), 4)],
+            ONE_DAY
+        ));
+
+        // 生成父订单，关联子订单
+        let rent_group = RentMachine::rent_group(0).unwrap();
+        assert_eq!(rent_group.renter, *renter_dave);
+        assert_eq!(rent_group.rent_ids, vec![0]);
+        assert_eq!(RentMachine::rent_order_group(0), Some(0));
+        assert_eq!(OnlineProfile::machine_rented_gpu(&*machine_id), 4);
+
+        // 只扣除一次交易费
+        let rent_info = RentMachine::rent_info(0).unwrap();
+        assert_eq!(
+            Balances::free_balance(&*renter_dave),
+            balance_before - 10 * ONE_DBC - rent_info.stake_amount
+        );
+
+        assert_ok!(RentMachine::confirm_rent_group(RuntimeOrigin::signed(*renter_dave), 0));
+        assert_eq!(RentMachine::rent_info(0).unwrap().rent_status, RentStatus::Renting);
+        // 已全部确认，不能再次确认
+        assert_noop!(
+            RentMachine::confirm_rent_group(RuntimeOrigin::signed(*renter_dave), 0),
+            Error::<TestRuntime>::NoOrderExist
+        );
+
+        assert_ok!(RentMachine::relet_rent_group(RuntimeOrigin::signed(*renter_dave), 0, ONE_DAY));
+        assert_eq!(RentMachine::rent_info(0).unwrap().rent_end, 11 + 2 * ONE_DAY);
+
+        // 子订单结束后，父订单被清理
+        run_to_block(11 + 2 * ONE_DAY);
+        assert_eq!(RentMachine::rent_info(0), None);
+        assert_eq!(RentMachine::rent_group(0), None);
+        assert_eq!(RentMachine::rent_order_group(0), None);
+    });
+}
+
+#[test]
+fn rent_machines_is_all_or_nothing() {
+    new_test_ext_after_machine_online().execute_with(|| {
+        // 任何一台机器不可租用，都不会生成订单
+        assert_noop!(
+            RentMachine::rent_machines(
+                RuntimeOrigin::signed(*renter_dave),
+                vec![(machine_id.clone(), 2), (unknown_machine_id.clone(), 1)],
+                ONE_DAY
+            ),
+            Error::<TestRuntime>::MachineNotFound
+        );
+        assert_noop!(
+            RentMachine::rent_machines(
+                RuntimeOrigin::signed(*renter_dave),
+                vec![(machine_id.clone(), 5)],
+                ONE_DAY
+            ),
+            Error::<TestRuntime>::GPUNotEnough
+        );
+        assert_noop!(
+            RentMachine::rent_machines(
+                RuntimeOrigin::signed(*renter_dave),
+                vec![(machine_id.clone(), 1), (machine_id.clone(), 1)],
+                ONE_DAY
+            ),
+            Error::<TestRuntime>::DuplicateMachineInGroup
+        );
+        assert_noop!(
+            RentMachine::rent_machines(RuntimeOrigin::signed(*renter_dave), vec![], ONE_DAY),
+            Error::<TestRuntime>::InvalidRentGroupSize
+        );
+    });
+}
+
+#[test]
+fn unconfirmed_rent_group_expires() {
+    new_test_ext_after_machine_online().execute_with(|| {
+        assert_ok!(RentMachine::rent_machines(
+            RuntimeOrigin::signed(*renter_dave),
+            vec![(machine_id.clone(), 2)],
+            ONE_DAY
+        ));
+
+        run_to_block(11 + WAITING_CONFIRMING_DELAY);
+        assert_eq!(RentMachine::rent_info(0), None);
+        assert_eq!(RentMachine::rent_group(0), None);
+        assert_noop!(
+            RentMachine::confirm_rent_group(RuntimeOrigin::signed(*renter_dave), 0),
+            Error::<TestRuntime>::RentGroupNotFound
+        );
+    });
+}
@@ -0,0 +1,171 @@
+/// Unit tests for paying the rent fee in DLC (pallet-assets).
+/// The fee is priced by the DLC price and held by the pallet account until the
+/// order is confirmed; the burn share is destroyed and recorded in DlcBurnDetails.
+use crate::{mock::*, Error, WAITING_CONFIRMING_DELAY};
+use dbc_support::{rental_type::DlcBurnDetail, ONE_DAY};
+use frame_support::{assert_noop, assert_ok};
+use once_cell::sync::Lazy;
+
+const renter_dave: Lazy<sp_core::sr25519::Public> =
+    Lazy::new(|| sr25519::Public::from(Sr25519Keyring::Dave));
+const stash: Lazy<sp_core::sr25519::Public> =
+    Lazy::new(|| sr25519::Public::from(Sr25519Keyring::Ferdie));
+const machine_id: Lazy<Vec<u8>> = Lazy::new(|| {
+    "8eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a48"
+        .as_bytes()
+        .to_vec()
+});
+
+#[test]
+fn rent_machine_with_dlc_works() {
+    new_test_ext_after_machine_online().execute_with(|| {
+        let dbc_stake_before = RentMachine::user_total_stake(&*renter_dave);
+        assert_ok!(RentMachine::rent_machine_with_asset(
+            RuntimeOrigin::signed(*renter_dave),
+            machine_id.clone(),
+            4,
+            ONE_DAY,
+            DLC_ASSET_ID
+        ));
+
+        // 租金转入托管账户，不再质押DBC
+        let (asset_id, rent_fee) = RentMachine::rent_order_asset(0).unwrap();
+        assert_eq!(asset_id, DLC_ASSET_ID);
+        assert!(rent_fee > 0);
+        let rent_fee_account = RentMachine::rent_fee_account();
+        assert_eq!(Assets::balance(DLC_ASSET_ID, &*renter_dave), INIT_BALANCE - rent_fee);
+        assert_eq!(Assets::balance(DLC_ASSET_ID, rent_fee_account), rent_fee);
+        assert_eq!(RentMachine::user_total_stake(&*renter_dave), dbc_stake_before);
+        assert_eq!(RentMachine::rent_info(0).unwrap().stake_amount, 0);
+
+        assert_ok!(RentMachine::confirm_rent(RuntimeOrigin::signed(*renter_dave), 0));
+
+        // 销毁部分被销毁并记录，其余支付给stash
+        let fee_to_burn = OnlineProfile::rent_fee_destroy_percent() * rent_fee;
+        assert_eq!(Assets::balance(DLC_ASSET_ID, &*stash), rent_fee - fee_to_burn);
+        assert_eq!(Assets::balance(DLC_ASSET_ID, rent_fee_account), 0);
+        assert_eq!(Assets::total_supply(DLC_ASSET_ID), INIT_BALANCE - fee_to_burn);
+        assert_eq!(RentMachine::total_burned_dlc(), fee_to_burn);
+        assert_eq!(
+            RentMachine::dlc_burn_details(0),
+            vec![DlcBurnDetail {
+                rent_id: 0,
+                renter: *renter_dave,
+                burned_amount: fee_to_burn,
+                at: 11
+            }]
+        );
+
+        // 租用结束后清理订单记录
+        run_to_block(11 + ONE_DAY);
+        assert_eq!(RentMachine::rent_info(0), None);
+        assert_eq!(RentMachine::rent_order_asset(0), None);
+    });
+}
+
+#[test]
+fn rent_machine_with_unsupported_asset_fails() {
+    new_test_ext_after_machine_online().execute_with(|| {
+        assert_noop!(
+            RentMachine::rent_machine_with_asset(
+                RuntimeOrigin::signed(*renter_dave),
+                machine_id.clone(),
+                4,
+                ONE_DAY,
+                DLC_ASSET_ID + 1
+            ),
+            Error::<TestRuntime>::AssetNotSupported
+        );
+    });
+}
+
+#[test]
+fn unconfirmed_asset_rent_refunds_dlc() {
+    new_test_ext_after_machine_online().execute_with(|| {
+        assert_ok!(RentMachine::rent_machine_with_asset(
+            RuntimeOrigin::signed(*renter_dave),
+            machine_id.clone(),
+            4,
+            ONE_DAY,
+            DLC_ASSET_ID
+        ));
+
+        // 超过确认时间未确认，托管的DLC退还给租用人
+        run_to_block(11 + WAITING_CONFIRMING_DELAY);
+        assert_eq!(RentMachine::rent_info(0), None);
+        assert_eq!(RentMachine::rent_order_asset(0), None);
+        assert_eq!(Assets::balance(DLC_ASSET_ID, &*renter_dave), INIT_BALANCE);
+        assert_eq!(Assets::balance(DLC_ASSET_ID, RentMachine::rent_fee_account()), 0);
+        assert_eq!(RentMachine::total_burned_dlc(), 0);
+    });
+}
+
+#[test]
+fn relet_asset_rent_pays_in_dlc() {
+    new_test_ext_after_machine_online().execute_with(|| {
+        assert_ok!(RentMachine::rent_machine_with_asset(
+            RuntimeOrigin::signed(*renter_dave),
+            machine_id.clone(),
+            4,
+            ONE_DAY,
+            DLC_ASSET_ID
+        ));
+        assert_ok!(RentMachine::confirm_rent(RuntimeOrigin::signed(*renter_dave), 0));
+        let (_, rent_fee) = RentMachine::rent_order_asset(0).unwrap();
+        let dbc_before = Balances::free_balance(&*renter_dave);
+        let dlc_before = Assets::balance(DLC_ASSET_ID, &*renter_dave);
+
+        // 续租的租金同样使用DLC支付，不扣除DBC
+        assert_ok!(RentMachine::relet_machine(RuntimeOrigin::signed(*renter_dave), 0, ONE_DAY));
+        let (_, total_fee) = RentMachine::rent_order_asset(0).unwrap();
+        let relet_fee = total_fee - rent_fee;
+        assert!(relet_fee > 0);
+        assert_eq!(Balances::free_balance(&*renter_dave), dbc_before);
+        assert_eq!(Assets::balance(DLC_ASSET_ID, &*renter_dave), dlc_before - relet_fee);
+
+        let fee_to_burn = OnlineProfile::rent_fee_destroy_percent() * rent_fee;
+        let relet_fee_to_burn = OnlineProfile::rent_fee_destroy_percent() * relet_fee;
+        assert_eq!(RentMachine::total_burned_dlc(), fee_to_burn + relet_fee_to_burn);
+        assert_eq!(
+            RentMachine::rent_order_paid_fee(0),
+            (total_fee - fee_to_burn - relet_fee_to_burn, fee_to_burn + relet_fee_to_burn)
+        );
+        assert_eq!(Assets::balance(DLC_ASSET_ID, RentMachine::rent_fee_account()), 0);
+    });
+}
+
+#[test]
+fn terminate_asset_rent_refunds_dlc() {
+    new_test_ext_after_machine_online().execute_with(|| {
+        assert_ok!(RentMachine::rent_machine_with_asset(
+            RuntimeOrigin::signed(*renter_dave),
+            machine_id.clone(),
+            4,
+            10 * ONE_DAY,
+            DLC_ASSET_ID
+        ));
+        assert_ok!(RentMachine::confirm_rent(RuntimeOrigin::signed(*renter_dave), 0));
+        let (paid_to_stash, paid_to_burn) = RentMachine::rent_order_paid_fee(0);
+        assert!(paid_to_stash > 0 && paid_to_burn > 0);
+
+        // 使用一半时长后终止，按剩余时长退还DLC，不退还DBC
+        run_to_block(11 + 5 * ONE_DAY);
+        let dbc_before = Balances::free_balance(&*renter_dave);
+        let dlc_before = Assets::balance(DLC_ASSET_ID, &*renter_dave);
+        let stash_dlc_before = Assets::balance(DLC_ASSET_ID, &*stash);
+
+        assert_ok!(RentMachine::terminate_rent(RuntimeOrigin::signed(*renter_dave), 0));
+
+        let refund_from_stash = sp_runtime::Perbill::from_percent(50) * paid_to_stash;
+        let refund_burned = sp_runtime::Perbill::from_percent(50) * paid_to_burn;
+        assert_eq!(
+            Assets::balance(DLC_ASSET_ID, &*renter_dave),
+            dlc_before + refund_from_stash + refund_burned
+        );
+        assert_eq!(Assets::balance(DLC_ASSET_ID, &*stash), stash_dlc_before - refund_from_stash);
+        assert_eq!(RentMachine::total_burned_dlc(), paid_to_burn - refund_burned);
+        assert_eq!(Balances::free_balance(&*renter_dave), dbc_before);
+        assert_eq!(RentMachine::rent_info(0), None);
+        assert_eq!(RentMachine::rent_order_asset(0), None);
+    });
+}
@@ -0,0 +1,238 @@
+/// Unit tests for advance reservations of time-slot machines (分时段机器预约)
+use crate::mock::*;
+use dbc_support::{rental_type::RentStatus, ONE_DAY, ONE_HOUR};
+use frame_support::{assert_noop, assert_ok};
+use once_cell::sync::Lazy;
+use online_profile::{MachineRentalMode, TimeRange};
+
+const renter_dave: Lazy<sp_core::sr25519::Public> =
+    Lazy::new(|| sr25519::Public::from(Sr25519Keyring::Dave));
+const renter_alice: Lazy<sp_core::sr25519::Public> =
+    Lazy::new(|| sr25519::Public::from(Sr25519Keyring::Alice));
+const stash: Lazy<sp_core::sr25519::Public> =
+    Lazy::new(|| sr25519::Public::from(Sr25519Keyring::Ferdie));
+const machine_id: Lazy<Vec<u8>> = Lazy::new(|| {
+    "8eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a48"
+        .as_bytes()
+        .to_vec()
+});
+
+// 切到 TimeSlot 模式并设置每天全天可租
+fn set_all_day_time_slot() {
+    assert_ok!(OnlineProfile::set_machine_rental_mode(
+        RuntimeOrigin::signed(*stash),
+        machine_id.clone(),
+        MachineRentalMode::TimeSlot
+    ));
+    let all_day = vec![TimeRange { start_hour: 0, end_hour: 24 }];
+    for wd in 0..7u8 {
+        assert_ok!(OnlineProfile::set_weekly_schedule(
+            RuntimeOrigin::signed(*stash),
+            machine_id.clone(),
+            wd,
+            all_day.clone()
+        ));
+    }
+}
+
+#[test]
+fn reserve_machine_activates_when_window_opens() {
+    new_test_ext_after_machine_online().execute_with(|| {
+        set_all_day_time_slot();
+
+        // 当前块高 11，预约 1 小时后开始的 2 小时
+        let rent_start = 11 + ONE_HOUR;
+        let reserved_before = Balances::reserved_balance(&*renter_dave);
+        assert_ok!(RentMachine::reserve_machine(
+            RuntimeOrigin::signed(*renter_dave),
+            machine_id.clone(),
+            4,
+            rent_start,
+            2 * ONE_HOUR
+        ));
+
+        // 预约时锁定租金，但还没有租用订单
+        let reservation = RentMachine::rent_reservation(0).unwrap();
+        assert_eq!(reservation.rent_start, rent_start);
+        assert_eq!(reservation.rent_end, rent_start + 2 * ONE_HOUR);
+        assert_eq!(
+            Balances::reserved_balance(&*renter_dave),
+            reserved_before + reservation.stake_amount
+        );
+        assert_eq!(RentMachine::user_total_stake(&*renter_dave), reservation.stake_amount);
+        assert_eq!(RentMachine::rent_info(0), None);
+        assert_eq!(RentMachine::machine_reservation(&*machine_id), vec![0]);
+        assert_eq!(OnlineProfile::machine_rented_gpu(&*machine_id), 0);
+
+        // 到达预约开始时间，自动生成租用订单，并用锁定的租金支付
+        run_to_block(rent_start);
+        assert_eq!(RentMachine::rent_reservation(0), None);
+        assert!(RentMachine::machine_reservation(&*machine_id).is_empty());
+
+        let rent_info = RentMachine::rent_info(0).unwrap();
+        assert_eq!(rent_info.rent_status, RentStatus::Renting);
+        assert_eq!(rent_info.rent_start, rent_start);
+        assert_eq!(rent_info.confirm_rent, rent_start);
+        assert_eq!(rent_info.rent_end, rent_start + 2 * ONE_HOUR);
+        assert_eq!(rent_info.stake_amount, 0);
+        assert_eq!(rent_info.gpu_index, vec![0, 1, 2, 3]);
+        assert_eq!(OnlineProfile::machine_rented_gpu(&*machine_id), 4);
+        assert_eq!(RentMachine::user_order(&*renter_dave), vec![0]);
+        assert_eq!(RentMachine::user_total_stake(&*renter_dave), 0);
+        assert_eq!(Balances::reserved_balance(&*renter_dave), reserved_before);
+
+        // 不需要再确认，也不会因为没有确认而过期
+        assert_noop!(
+            RentMachine::confirm_rent(RuntimeOrigin::signed(*renter_dave), 0),
+            crate::Error::<TestRuntime>::NoOrderExist
+        );
+        run_to_block(rent_start + ONE_HOUR);
+        assert_eq!(RentMachine::rent_info(0).unwrap().rent_status, RentStatus::Renting);
+    });
+}
+
+#[test]
+fn reserve_machine_rejects_fulltime_machine() {
+    new_test_ext_after_machine_online().execute_with(|| {
+        assert_noop!(
+            RentMachine::reserve_machine(
+                RuntimeOrigin::signed(*renter_dave),
+                machine_id.clone(),
+                4,
+                11 + ONE_HOUR,
+                2 * ONE_HOUR
+            ),
+            crate::Error::<TestRuntime>::ReservationNotAllowed
+        );
+    });
+}
+
+#[test]
+fn reserve_machine_rejects_invalid_start() {
+    new_test_ext_after_machine_online().execute_with(|| {
+        set_all_day_time_slot();
+
+        // 开始时间不能是当前或过去
+        assert_noop!(
+            RentMachine::reserve_machine(
+                RuntimeOrigin::signed(*renter_dave),
+                machine_id.clone(),
+                4,
+                11,
+                2 * ONE_HOUR
+            ),
+            crate::Error::<TestRuntime>::InvalidReservationTime
+        );
+        // 开始时间不能超过最大租用时间 (60 天)
+        assert_noop!(
+            RentMachine::reserve_machine(
+                RuntimeOrigin::signed(*renter_dave),
+                machine_id.clone(),
+                4,
+                11 + 61 * ONE_DAY,
+                2 * ONE_HOUR
+            ),
+            crate::Error::<TestRuntime>::InvalidReservationTime
+        );
+        // 预约时长不能超过最大租用时间，不会被截断
+        assert_noop!(
+            RentMachine::reserve_machine(
+                RuntimeOrigin::signed(*renter_dave),
+                machine_id.clone(),
+                4,
+                11 + ONE_HOUR,
+                61 * ONE_DAY
+            ),
+            crate::Error::<TestRuntime>::ExceedMaxRentalDuration
+        );
+    });
+}
+
+#[test]
+fn reserved_gpu_cannot_be_rented_or_double_booked() {
+    new_test_ext_after_machine_online().execute_with(|| {
+        set_all_day_time_slot();
+
+        let rent_start = 11 + ONE_HOUR;
+        assert_ok!(RentMachine::reserve_machine(
+            RuntimeOrigin::signed(*renter_dave),
+            machine_id.clone(),
+            3,
+            rent_start,
+            2 * ONE_HOUR
+        ));
+
+        // 与预约重叠的立即租用，GPU不足
+        assert_noop!(
+            RentMachine::rent_machine(
+                RuntimeOrigin::signed(*renter_alice),
+                machine_id.clone(),
+                2,
+                2 * ONE_HOUR
+            ),
+            crate::Error::<TestRuntime>::GPUNotEnough
+        );
+        // 与预约重叠的预约，GPU不足
+        assert_noop!(
+            RentMachine::reserve_machine(
+                RuntimeOrigin::signed(*renter_alice),
+                machine_id.clone(),
+                2,
+                rent_start + ONE_HOUR,
+                2 * ONE_HOUR
+            ),
+            crate::Error::<TestRuntime>::GPUNotEnough
+        );
+
+        // 剩余的 1 张GPU可以租用
+        assert_ok!(RentMachine::rent_machine(
+            RuntimeOrigin::signed(*renter_alice),
+            machine_id.clone(),
+            1,
+            2 * ONE_HOUR
+        ));
+        // 预约结束之后的时段不受影响
+        assert_ok!(RentMachine::reserve_machine(
+            RuntimeOrigin::signed(*renter_alice),
+            machine_id.clone(),
+            3,
+            rent_start + 2 * ONE_HOUR,
+            2 * ONE_HOUR
+        ));
+    });
+}
+
+#[test]
+fn cancel_reservation_refunds_locked_fee() {
+    new_test_ext_after_machine_online().execute_with(|| {
+        set_all_day_time_slot();
+
+        let rent_start = 11 + ONE_HOUR;
+        let reserved_before = Balances::reserved_balance(&*renter_dave);
+        assert_ok!(RentMachine::reserve_machine(
+            RuntimeOrigin::signed(*renter_dave),
+            machine_id.clone(),
+            4,
+            rent_start,
+            2 * ONE_HOUR
+        ));
+
+        // 只有预约者可以取消
+        assert_noop!(
+            RentMachine::cancel_reservation(RuntimeOrigin::signed(*renter_alice), 0),
+            crate::Error::<TestRuntime>::NotMachineRenter
+        );
+
+        assert_ok!(RentMachine::cancel_reservation(RuntimeOrigin::signed(*renter_dave), 0));
+        assert_eq!(Balances::reserved_balance(&*renter_dave), reserved_before);
+        assert_eq!(RentMachine::user_total_stake(&*renter_dave), 0);
+        assert_eq!(RentMachine::rent_reservation(0), None);
+        assert!(RentMachine::user_reservation(&*renter_dave).is_empty());
+        assert!(RentMachine::reservation_starting(rent_start).is_empty());
+
+        // 取消后不会再生成租用订单
+        run_to_block(rent_start);
+        assert_eq!(RentMachine::rent_info(0), None);
+        assert_eq!(OnlineProfile::machine_rented_gpu(&*machine_id), 0);
+    });
+}
@@ -0,0 +1,112 @@
+/// Unit tests for surge pricing.
+/// The system price is scaled by a governance curve over the weighted mix of the
+/// network and the machine's GPU type utilisation; without a curve it is unchanged.
+use crate::mock::*;
+use dbc_support::{traits::MachineInfoTrait, ONE_DAY};
+use frame_support::{assert_noop, assert_ok};
+use once_cell::sync::Lazy;
+use online_profile::SurgePricingCurve;
+use sp_runtime::{DispatchError, Perbill};
+
+const renter_dave: Lazy<sp_core::sr25519::Public> =
+    Lazy::new(|| sr25519::Public::from(Sr25519Keyring::Dave));
+const machine_id: Lazy<Vec<u8>> = Lazy::new(|| {
+    "8eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a48"
+        .as_bytes()
+        .to_vec()
+});
+
+fn surge_pricing_curve() -> SurgePricingCurve {
+    SurgePricingCurve {
+        network_weight: Perbill::from_percent(50),
+        points: vec![(Perbill::zero(), 800_000), (Perbill::one(), 2_000_000)],
+    }
+}
+
+#[test]
+fn set_surge_pricing_curve_works() {
+    new_test_ext_after_machine_online().execute_with(|| {
+        assert_noop!(
+            OnlineProfile::set_surge_pricing_curve(
+                RuntimeOrigin::signed(*renter_dave),
+                Some(surge_pricing_curve())
+            ),
+            DispatchError::BadOrigin
+        );
+
+        // 使用率需严格递增，系数需在允许范围内
+        let invalid_curves = vec![
+            SurgePricingCurve { network_weight: Perbill::zero(), points: vec![] },
+            SurgePricingCurve {
+                network_weight: Perbill::zero(),
+                points: vec![(Perbill::one(), 1_000_000), (Perbill::zero(), 1_000_000)],
+            },
+            SurgePricingCurve {
+                network_weight: Perbill::zero(),
+                points: vec![(Perbill::zero(), 1_000_000), (Perbill::one(), 20_000_000)],
+            },
+        ];
+        for curve in invalid_curves {
+            assert_noop!(
+                OnlineProfile::set_surge_pricing_curve(RuntimeOrigin::root(), Some(curve)),
+                online_profile::Error::<TestRuntime>::InvalidSurgePricingCurve
+            );
+        }
+
+        assert_ok!(OnlineProfile::set_surge_pricing_curve(
+            RuntimeOrigin::root(),
+            Some(surge_pricing_curve())
+        ));
+        assert_eq!(OnlineProfile::surge_pricing_curve(), Some(surge_pricing_curve()));
+
+        assert_ok!(OnlineProfile::set_surge_pricing_curve(RuntimeOrigin::root(), None));
+        assert_eq!(OnlineProfile::surge_pricing_curve(), None);
+    });
+}
+
+#[test]
+fn surge_pricing_follows_gpu_utilization() {
+    new_test_ext_after_machine_online().execute_with(|| {
+        let gpu_type = OnlineProfile::machines_info(&*machine_id).unwrap().gpu_type_and_mem().0;
+        assert_eq!(OnlineProfile::gpu_type_info(&gpu_type).online_gpu, 4);
+
+        // 未设置定价曲线时，价格不变
+        assert_eq!(
+            RentMachine::get_usdt_machine_rent_fee(machine_id.clone(), ONE_DAY, 4),
+            Ok(299_450_000)
+        );
+
+        assert_ok!(OnlineProfile::set_surge_pricing_curve(
+            RuntimeOrigin::root(),
+            Some(surge_pricing_curve())
+        ));
+
+        // 空闲时按0.8倍定价
+        assert_eq!(OnlineProfile::surge_multiplier(&gpu_type), 800_000);
+        assert_eq!(
+            RentMachine::get_usdt_machine_rent_fee(machine_id.clone(), ONE_DAY, 4),
+            Ok(239_560_000)
+        );
+
+        assert_ok!(RentMachine::rent_machine(
+            RuntimeOrigin::signed(*renter_dave),
+            machine_id.clone(),
+            4,
+            ONE_DAY
+        ));
+        assert_ok!(RentMachine::confirm_rent(RuntimeOrigin::signed(*renter_dave), 0));
+        assert_eq!(OnlineProfile::gpu_type_info(&gpu_type).rented_gpu, 4);
+
+        // 全部租出时按2倍定价
+        assert_eq!(OnlineProfile::surge_multiplier(&gpu_type), 2_000_000);
+        assert_eq!(
+            RentMachine::get_usdt_machine_rent_fee(machine_id.clone(), ONE_DAY, 4),
+            Ok(598_900_000)
+        );
+
+        // 租用结束后恢复
+        run_to_block(11 + ONE_DAY);
+        assert_eq!(OnlineProfile::gpu_type_info(&gpu_type).rented_gpu, 0);
+        assert_eq!(OnlineProfile::surge_multiplier(&gpu_type), 800_000);
+    });
+}
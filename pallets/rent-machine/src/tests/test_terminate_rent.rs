@@ -0,0 +1,216 @@
+/// Unit tests for terminating a rent order early with a pro-rated refund.
+/// The unused part of the paid rent fee is charged back from the rent receiver
+/// and the rent fee pot, in the same ratio it was paid.
+use crate::{mock::*, MachineStatus};
+use dbc_support::ONE_DAY;
+use frame_support::{assert_noop, assert_ok, traits::Currency};
+use once_cell::sync::Lazy;
+use sp_runtime::Perbill;
+
+const renter_dave: Lazy<sp_core::sr25519::Public> =
+    Lazy::new(|| sr25519::Public::from(Sr25519Keyring::Dave));
+const stash: Lazy<sp_core::sr25519::Public> =
+    Lazy::new(|| sr25519::Public::from(Sr25519Keyring::Ferdie));
+const receiver_alice: Lazy<sp_core::sr25519::Public> =
+    Lazy::new(|| sr25519::Public::from(Sr25519Keyring::Alice));
+const pot_two: Lazy<sp_core::sr25519::Public> =
+    Lazy::new(|| sr25519::Public::from(Sr25519Keyring::Two));
+const machine_id: Lazy<Vec<u8>> = Lazy::new(|| {
+    "8eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a48"
+        .as_bytes()
+        .to_vec()
+});
+
+// 在块高 11 租用全部 4 张GPU 10 天并确认
+fn rent_and_confirm() {
+    assert_ok!(OnlineProfile::set_rent_receiver(
+        RuntimeOrigin::signed(*stash),
+        Some(*receiver_alice),
+    ));
+    assert_ok!(RentMachine::rent_machine(
+        RuntimeOrigin::signed(*renter_dave),
+        machine_id.clone(),
+        4,
+        10 * ONE_DAY
+    ));
+    run_to_block(30);
+    assert_ok!(RentMachine::confirm_rent(RuntimeOrigin::signed(*renter_dave), 0));
+}
+
+#[test]
+fn terminate_rent_refunds_unused_fee() {
+    new_test_ext_after_machine_online().execute_with(|| {
+        rent_and_confirm();
+
+        let (paid_to_receiver, paid_to_pot) = RentMachine::rent_order_paid_fee(0);
+        assert!(paid_to_receiver > 0 && paid_to_pot > 0);
+
+        // 使用一半时长后终止
+        run_to_block(10 + 5 * ONE_DAY);
+
+        let renter_before = Balances::free_balance(&*renter_dave);
+        let receiver_before = Balances::free_balance(&*receiver_alice);
+        let pot_before = Balances::free_balance(&*pot_two);
+        let machine_info_before = OnlineProfile::machines_info(&*machine_id).unwrap();
+        let stash_machine_before = OnlineProfile::stash_machines(&*stash);
+        let sys_info_before = OnlineProfile::sys_info();
+
+        assert_ok!(RentMachine::terminate_rent(RuntimeOrigin::signed(*renter_dave), 0));
+
+        let refund_from_receiver = Perbill::from_percent(50) * paid_to_receiver;
+        let refund_from_pot = Perbill::from_percent(50) * paid_to_pot;
+        assert_eq!(
+            Balances::free_balance(&*renter_dave),
+            renter_before + refund_from_receiver + refund_from_pot
+        );
+        assert_eq!(
+            Balances::free_balance(&*receiver_alice),
+            receiver_before - refund_from_receiver
+        );
+        assert_eq!(Balances::free_balance(&*pot_two), pot_before - refund_from_pot);
+
+        // 退还的租金从租金统计中扣除
+        let machine_info = OnlineProfile::machines_info(&*machine_id).unwrap();
+        assert_eq!(
+            machine_info.total_rent_fee,
+            machine_info_before.total_rent_fee - refund_from_receiver
+        );
+        assert_eq!(
+            machine_info.total_burn_fee,
+            machine_info_before.total_burn_fee - refund_from_pot
+        );
+        let stash_machine = OnlineProfile::stash_machines(&*stash);
+        assert_eq!(
+            stash_machine.total_rent_fee,
+            stash_machine_before.total_rent_fee - refund_from_receiver
+        );
+        let sys_info = OnlineProfile::sys_info();
+        assert_eq!(sys_info.total_rent_fee, sys_info_before.total_rent_fee - refund_from_receiver);
+        assert_eq!(sys_info.total_burn_fee, sys_info_before.total_burn_fee - refund_from_pot);
+
+        // 订单被清理，GPU被释放
+        assert_eq!(RentMachine::rent_info(0), None);
+        assert_eq!(RentMachine::rent_order_paid_fee(0), (0, 0));
+        assert_eq!(RentMachine::rent_order_receiver(0), None);
+        assert!(RentMachine::user_order(&*renter_dave).is_empty());
+        assert!(RentMachine::rent_ending(11 + 10 * ONE_DAY).is_empty());
+        let machine_rent_order = RentMachine::machine_rent_order(&*machine_id);
+        assert!(machine_rent_order.rent_order.is_empty());
+        assert!(machine_rent_order.used_gpu.is_empty());
+        assert_eq!(OnlineProfile::machine_rented_gpu(&*machine_id), 0);
+
+        let machine_info = OnlineProfile::machines_info(&*machine_id).unwrap();
+        assert_eq!(machine_info.machine_status, MachineStatus::Online);
+        assert!(machine_info.renters.is_empty());
+
+        // 租用历史记录的结束时间改为终止时间
+        let rented_orders = RentMachine::machine_renter_rented_orders(&*machine_id, &*renter_dave);
+        assert_eq!(rented_orders[0].rent_end, 11 + 5 * ONE_DAY);
+
+        // 释放的GPU可以再次被租用
+        assert_ok!(RentMachine::rent_machine(
+            RuntimeOrigin::signed(*renter_dave),
+            machine_id.clone(),
+            4,
+            ONE_DAY
+        ));
+    });
+}
+
+#[test]
+fn terminate_rent_records_owed_refund() {
+    new_test_ext_after_machine_online().execute_with(|| {
+        rent_and_confirm();
+
+        let (paid_to_receiver, paid_to_pot) = RentMachine::rent_order_paid_fee(0);
+        run_to_block(10 + 5 * ONE_DAY);
+
+        // 租金接收方已转走收到的租金，余额不足以退款
+        let _ = Balances::make_free_balance_be(&*receiver_alice, 1);
+        let renter_before = Balances::free_balance(&*renter_dave);
+
+        assert_ok!(RentMachine::terminate_rent(RuntimeOrigin::signed(*renter_dave), 0));
+
+        // RentFeePot承担的部分正常退还，接收方承担的部分记为欠款，订单正常结束
+        let refund_from_receiver = Perbill::from_percent(50) * paid_to_receiver;
+        let refund_from_pot = Perbill::from_percent(50) * paid_to_pot;
+        assert_eq!(Balances::free_balance(&*renter_dave), renter_before + refund_from_pot);
+        assert_eq!(
+            RentMachine::owed_rent_refund(&*receiver_alice, &*renter_dave),
+            refund_from_receiver
+        );
+        assert_eq!(RentMachine::rent_info(0), None);
+
+        assert_noop!(
+            RentMachine::claim_owed_rent_refund(
+                RuntimeOrigin::signed(*renter_dave),
+                *receiver_alice
+            ),
+            crate::Error::<TestRuntime>::RefundFailed
+        );
+
+        // 接收方余额不足时，先领取可转出的部分
+        let first_claim = refund_from_receiver / 2;
+        let _ = Balances::make_free_balance_be(&*receiver_alice, first_claim + 1);
+        assert_ok!(RentMachine::claim_owed_rent_refund(
+            RuntimeOrigin::signed(*renter_dave),
+            *receiver_alice
+        ));
+        assert_eq!(
+            Balances::free_balance(&*renter_dave),
+            renter_before + refund_from_pot + first_claim
+        );
+        assert_eq!(
+            RentMachine::owed_rent_refund(&*receiver_alice, &*renter_dave),
+            refund_from_receiver - first_claim
+        );
+
+        let _ = Balances::make_free_balance_be(&*receiver_alice, refund_from_receiver);
+        assert_ok!(RentMachine::claim_owed_rent_refund(
+            RuntimeOrigin::signed(*renter_dave),
+            *receiver_alice
+        ));
+        assert_eq!(
+            Balances::free_balance(&*renter_dave),
+            renter_before + refund_from_pot + refund_from_receiver
+        );
+        assert_eq!(RentMachine::owed_rent_refund(&*receiver_alice, &*renter_dave), 0);
+        assert_noop!(
+            RentMachine::claim_owed_rent_refund(
+                RuntimeOrigin::signed(*renter_dave),
+                *receiver_alice
+            ),
+            crate::Error::<TestRuntime>::NoOwedRentRefund
+        );
+    });
+}
+
+#[test]
+fn terminate_rent_only_by_renter() {
+    new_test_ext_after_machine_online().execute_with(|| {
+        rent_and_confirm();
+
+        assert_noop!(
+            RentMachine::terminate_rent(RuntimeOrigin::signed(*receiver_alice), 0),
+            crate::Error::<TestRuntime>::NotMachineRenter
+        );
+    });
+}
+
+#[test]
+fn terminate_rent_requires_confirmed_order() {
+    new_test_ext_after_machine_online().execute_with(|| {
+        assert_ok!(RentMachine::rent_machine(
+            RuntimeOrigin::signed(*renter_dave),
+            machine_id.clone(),
+            4,
+            10 * ONE_DAY
+        ));
+
+        // 未确认的订单，到期后会自动退还质押
+        assert_noop!(
+            RentMachine::terminate_rent(RuntimeOrigin::signed(*renter_dave), 0),
+            crate::Error::<TestRuntime>::NoOrderExist
+        );
+    });
+}
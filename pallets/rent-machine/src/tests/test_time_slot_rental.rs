@@ -3,7 +3,7 @@ use crate::mock::*;
 use dbc_support::ONE_DAY;
 use frame_support::{assert_noop, assert_ok};
 use once_cell::sync::Lazy;
-use online_profile::{MachineRentalMode, TimeRange};
+use online_profile::{DayAvailability, MachineRentalMode, RangeAvailability, TimeRange};
 use sp_runtime::Perbill;
 
 const renter_dave: Lazy<sp_core::sr25519::Public> =
@@ -225,8 +225,18 @@ fn time_range_is_valid() {
     assert!(!invalid1.is_valid());
     let invalid2 = TimeRange { start_hour: 10, end_hour: 10 }; // start == end
     assert!(!invalid2.is_valid());
-    let invalid3 = TimeRange { start_hour: 10, end_hour: 5 }; // start > end
+    let invalid3 = TimeRange { start_hour: 22, end_hour: 0 }; // 跨午夜应写为 22-24
     assert!(!invalid3.is_valid());
+
+    // start > end 表示跨午夜
+    let overnight = TimeRange { start_hour: 22, end_hour: 6 };
+    assert!(overnight.is_valid());
+    assert!(overnight.is_overnight());
+    assert_eq!(overnight.end_hour_of_day(), 30);
+    assert!(overnight.contains_hour(23) && overnight.contains_hour(5));
+    assert!(!overnight.contains_hour(6) && !overnight.contains_hour(21));
+    assert!(overnight.covers(23, 27));
+    assert!(!overnight.covers(23, 31));
 }
 
 #[test]
@@ -689,3 +699,247 @@ fn timeslot_rental_must_fit_in_range() {
         assert!(!OnlineProfile::is_rental_schedule_allowed(&*machine_id, start_bad, end_bad));
     });
 }
+
+// ═══════════════════════════════════════════════════════════════
+// Cross-midnight and timezone
+// ═══════════════════════════════════════════════════════════════
+
+#[test]
+fn overnight_and_adjacent_ranges_allow_cross_midnight_rental() {
+    new_test_ext_after_machine_online().execute_with(|| {
+        assert_ok!(OnlineProfile::set_machine_rental_mode(
+            RuntimeOrigin::signed(*stash),
+            machine_id.clone(),
+            MachineRentalMode::TimeSlot
+        ));
+        // 周四 (Day 0) 22:00 - 周五 06:00
+        assert_ok!(OnlineProfile::set_weekly_schedule(
+            RuntimeOrigin::signed(*stash),
+            machine_id.clone(),
+            4,
+            vec![TimeRange { start_hour: 22, end_hour: 6 }]
+        ));
+
+        let hour = 3600 * 1000u64;
+        assert!(OnlineProfile::is_rental_schedule_allowed(&*machine_id, 23 * hour, 27 * hour));
+        assert!(!OnlineProfile::is_rental_schedule_allowed(&*machine_id, 23 * hour, 31 * hour));
+
+        // 周五 0-4, 20-24；周六 0-4，周五 20-24 与周六 0-4 首尾相接
+        assert_ok!(OnlineProfile::set_weekly_schedule(
+            RuntimeOrigin::signed(*stash),
+            machine_id.clone(),
+            5,
+            vec![
+                TimeRange { start_hour: 20, end_hour: 24 },
+                TimeRange { start_hour: 0, end_hour: 4 }
+            ]
+        ));
+        assert_ok!(OnlineProfile::set_weekly_schedule(
+            RuntimeOrigin::signed(*stash),
+            machine_id.clone(),
+            6,
+            vec![TimeRange { start_hour: 0, end_hour: 4 }]
+        ));
+        // 按 start_hour 升序保存
+        assert_eq!(
+            OnlineProfile::weekly_schedule(&*machine_id)[5],
+            vec![
+                TimeRange { start_hour: 0, end_hour: 4 },
+                TimeRange { start_hour: 20, end_hour: 24 }
+            ]
+        );
+        // 周四 23:00 - 周五 03:00 由周四跨午夜的时段覆盖
+        assert!(OnlineProfile::is_rental_schedule_allowed(&*machine_id, 23 * hour, 27 * hour));
+        // 周五 22:00 - 周六 02:00 跨越相邻两天的时段
+        assert!(OnlineProfile::is_rental_schedule_allowed(&*machine_id, 46 * hour, 50 * hour));
+        // 周五 02:00 - 07:00 超出周四跨午夜时段的 06:00
+        assert!(!OnlineProfile::is_rental_schedule_allowed(&*machine_id, 26 * hour, 31 * hour));
+    });
+}
+
+#[test]
+fn utc_offset_shifts_schedule_to_local_time() {
+    new_test_ext_after_machine_online().execute_with(|| {
+        assert_ok!(OnlineProfile::set_machine_rental_mode(
+            RuntimeOrigin::signed(*stash),
+            machine_id.clone(),
+            MachineRentalMode::TimeSlot
+        ));
+        for wd in 0..7u8 {
+            assert_ok!(OnlineProfile::set_weekly_schedule(
+                RuntimeOrigin::signed(*stash),
+                machine_id.clone(),
+                wd,
+                vec![TimeRange { start_hour: 9, end_hour: 17 }]
+            ));
+        }
+
+        // 只有 stash/controller 可以设置，且必须为 15 分钟整数倍，范围 UTC-12 ~ UTC+14
+        assert_noop!(
+            OnlineProfile::set_machine_utc_offset(
+                RuntimeOrigin::signed(*renter_dave),
+                machine_id.clone(),
+                480
+            ),
+            online_profile::Error::<TestRuntime>::NotMachineController
+        );
+        for invalid in [470, 15 * 60, -13 * 60] {
+            assert_noop!(
+                OnlineProfile::set_machine_utc_offset(
+                    RuntimeOrigin::signed(*stash),
+                    machine_id.clone(),
+                    invalid
+                ),
+                online_profile::Error::<TestRuntime>::InvalidScheduleArgs
+            );
+        }
+        assert_ok!(OnlineProfile::set_machine_utc_offset(
+            RuntimeOrigin::signed(*controller),
+            machine_id.clone(),
+            480
+        ));
+        assert_eq!(OnlineProfile::machine_utc_offset(&*machine_id), 480);
+
+        // UTC+8: Day 1 01:00-03:00 UTC 即本地 09:00-11:00
+        let day1 = 24 * 3600 * 1000u64;
+        let hour = 3600 * 1000u64;
+        assert!(OnlineProfile::is_rental_schedule_allowed(
+            &*machine_id,
+            day1 + hour,
+            day1 + 3 * hour
+        ));
+        // Day 1 09:00-11:00 UTC 即本地 17:00-19:00，不在时段内
+        assert!(!OnlineProfile::is_rental_schedule_allowed(
+            &*machine_id,
+            day1 + 9 * hour,
+            day1 + 11 * hour
+        ));
+    });
+}
+
+// ═══════════════════════════════════════════════════════════════
+// Availability calendar (RPC)
+// ═══════════════════════════════════════════════════════════════
+
+#[test]
+fn get_machine_availability_works() {
+    new_test_ext_after_machine_online().execute_with(|| {
+        // 全天出租的机器每天都是 0-24
+        assert_eq!(
+            RentMachine::get_machine_availability(machine_id.clone(), 0, 0),
+            vec![DayAvailability {
+                day: 0,
+                ranges: vec![RangeAvailability {
+                    range: TimeRange { start_hour: 0, end_hour: 24 },
+                    rented_gpu: 0
+                }]
+            }]
+        );
+
+        assert_ok!(OnlineProfile::set_machine_rental_mode(
+            RuntimeOrigin::signed(*stash),
+            machine_id.clone(),
+            MachineRentalMode::TimeSlot
+        ));
+        // Day 0 为周四：0-6, 8-12；周五全天，但 Day 1 单独设置为 10-14
+        let thursday = vec![
+            TimeRange { start_hour: 0, end_hour: 6 },
+            TimeRange { start_hour: 8, end_hour: 12 },
+        ];
+        assert_ok!(OnlineProfile::set_weekly_schedule(
+            RuntimeOrigin::signed(*stash),
+            machine_id.clone(),
+            4,
+            thursday
+        ));
+        assert_ok!(OnlineProfile::set_weekly_schedule(
+            RuntimeOrigin::signed(*stash),
+            machine_id.clone(),
+            5,
+            vec![TimeRange { start_hour: 0, end_hour: 24 }]
+        ));
+        assert_ok!(OnlineProfile::set_specific_date_schedule(
+            RuntimeOrigin::signed(*stash),
+            machine_id.clone(),
+            1,
+            vec![TimeRange { start_hour: 10, end_hour: 14 }]
+        ));
+
+        // 租用 2 个GPU 2小时，落在 Day 0 的 0-6 时段内
+        assert_ok!(RentMachine::rent_machine(
+            RuntimeOrigin::signed(*renter_dave),
+            machine_id.clone(),
+            2,
+            1200u32.into()
+        ));
+
+        assert_eq!(
+            RentMachine::get_machine_availability(machine_id.clone(), 0, 1),
+            vec![
+                DayAvailability {
+                    day: 0,
+                    ranges: vec![
+                        RangeAvailability {
+                            range: TimeRange { start_hour: 0, end_hour: 6 },
+                            rented_gpu: 2
+                        },
+                        RangeAvailability {
+                            range: TimeRange { start_hour: 8, end_hour: 12 },
+                            rented_gpu: 0
+                        },
+                    ]
+                },
+                DayAvailability {
+                    day: 1,
+                    ranges: vec![RangeAvailability {
+                        range: TimeRange { start_hour: 10, end_hour: 14 },
+                        rented_gpu: 0
+                    }]
+                },
+            ]
+        );
+
+        // 同一时段内先后租用、没有重叠的订单不累加
+        let hour = 3600 * 1000u64;
+        assert_eq!(
+            OnlineProfile::get_machine_availability(
+                &*machine_id,
+                0,
+                0,
+                &[(0, hour + hour / 2, 2), (hour * 3 / 2, 3 * hour, 2), (2 * hour, 4 * hour, 1)]
+            )[0]
+            .ranges[0],
+            RangeAvailability { range: TimeRange { start_hour: 0, end_hour: 6 }, rented_gpu: 3 }
+        );
+
+        // 一次最多查询 90 天
+        assert_eq!(RentMachine::get_machine_availability(machine_id.clone(), 0, 365).len(), 90);
+
+        // UTC+8：时段表按本地时间解释，返回的是 UTC 日期及 UTC 小时
+        // 本地 Day 0 的 8-12 即 UTC Day 0 的 0-4，本地 Day 1 的 10-14 即 UTC Day 1 的 2-6
+        assert_ok!(OnlineProfile::set_machine_utc_offset(
+            RuntimeOrigin::signed(*controller),
+            machine_id.clone(),
+            480
+        ));
+        assert_eq!(
+            RentMachine::get_machine_availability(machine_id.clone(), 0, 1),
+            vec![
+                DayAvailability {
+                    day: 0,
+                    ranges: vec![RangeAvailability {
+                        range: TimeRange { start_hour: 0, end_hour: 4 },
+                        rented_gpu: 2
+                    }]
+                },
+                DayAvailability {
+                    day: 1,
+                    ranges: vec![RangeAvailability {
+                        range: TimeRange { start_hour: 2, end_hour: 6 },
+                        rented_gpu: 0
+                    }]
+                },
+            ]
+        );
+    });
+}
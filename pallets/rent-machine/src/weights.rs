@@ -0,0 +1,135 @@
+//! Weights for rent_machine
+//!
+//! 权重按调用时读写的存储项数量估算(租用类调用按以DBC支付租金估算)，
+//! EVM预编译合约也按这些权重收取gas
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{
+    traits::Get,
+    weights::{constants::RocksDbWeight, Weight},
+};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for rent_machine.
+pub trait WeightInfo {
+    fn rent_machine() -> Weight;
+    fn confirm_rent() -> Weight;
+    fn relet_machine() -> Weight;
+    fn reserve_machine() -> Weight;
+    fn terminate_rent() -> Weight;
+    fn rent_machines(n: u32) -> Weight;
+    fn dispute_rent() -> Weight;
+}
+
+/// Weights for rent_machine using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+    // Storage: MachinesInfo(4), MachineRentedGPU(2), MaximumRentalDuration, Timestamp,
+    //   RentalSchedule, MachineReservation, FixedTxFee, System.Account(2), StandardGPUPointPrice,
+    //   PriceCurve, MachineExtraPrice, NextRentId, RentInfo, DBC price, UserTotalStake,
+    //   StashRentReceiver, MachineRentOrder, UserOrder, RentEnding, ConfirmingOrder, Treasury,
+    //   RentOrderReceiver
+    fn rent_machine() -> Weight {
+        Weight::from_parts(10000, 0)
+            .saturating_add(T::DbWeight::get().reads(25))
+            .saturating_add(T::DbWeight::get().writes(13))
+    }
+    // Storage: RentInfo(2), MachinesInfo(2), RentOrderAsset, RentFeeStreaming, UserTotalStake,
+    //   System.Account(3), RentFeePot, RentFeeDestroyPercent, RentOrderReceiver, RentReceiver,
+    //   RentOrderPaidFee, LiveMachines, snapshots of stash & machine(4), region info(2),
+    //   ConfirmingOrder, MachineRenterRentedOrders, Galaxy competition status(3)
+    fn confirm_rent() -> Weight {
+        Weight::from_parts(10000, 0)
+            .saturating_add(T::DbWeight::get().reads(28))
+            .saturating_add(T::DbWeight::get().writes(17))
+    }
+    // Storage: RentInfo, MachinesInfo(2), MachineReservation, MachineRentedGPU,
+    //   StandardGPUPointPrice, PriceCurve, MachineExtraPrice, DBC price, System.Account(3),
+    //   OrderEscrow, RentFeePot, RentFeeDestroyPercent, RentOrderReceiver, RentOrderPaidFee,
+    //   RentEnding(2), MachineRenterRentedOrders
+    fn relet_machine() -> Weight {
+        Weight::from_parts(10000, 0)
+            .saturating_add(T::DbWeight::get().reads(21))
+            .saturating_add(T::DbWeight::get().writes(8))
+    }
+    // Storage: MachinesInfo, MachineRentalMode, MaximumRentalDuration, Timestamp,
+    //   MachineUtcOffset, RentalSchedule(2), MachineRentOrder, RentInfo(4), MachineReservation,
+    //   RentReservation(4), FixedTxFee, System.Account(3), StandardGPUPointPrice, PriceCurve,
+    //   MachineExtraPrice, DBC price, UserTotalStake, NextRentId, UserReservation,
+    //   ReservationStarting
+    fn reserve_machine() -> Weight {
+        Weight::from_parts(10000, 0)
+            .saturating_add(T::DbWeight::get().reads(31))
+            .saturating_add(T::DbWeight::get().writes(10))
+    }
+    // Storage: RentInfo, MachinesInfo(2), OrderEscrow, RentOrderAsset, RentOrderPaidFee,
+    //   RentOrderReceiver, StashRentReceiver, System.Account(3), RentFeePot, OwedRentRefund(2),
+    //   rent fee statistics of machine, stash & system(3), MachineRenterRentedOrders,
+    //   MachineRentOrder, MachineRentedGPU, LiveMachines, StashMachines, UserOrder, RentEnding,
+    //   RentOrderEndpoint, RentLiveness, UserTotalStake
+    fn terminate_rent() -> Weight {
+        Weight::from_parts(10000, 0)
+            .saturating_add(T::DbWeight::get().reads(27))
+            .saturating_add(T::DbWeight::get().writes(24))
+    }
+    // Storage: FixedTxFee, System.Account(2), Treasury, NextRentGroupId, RentGroup
+    // Storage per machine: the checks and order written by rent_machine, RentOrderGroup
+    fn rent_machines(n: u32) -> Weight {
+        Weight::from_parts(10000, 0)
+            .saturating_add(T::DbWeight::get().reads(5))
+            .saturating_add(T::DbWeight::get().writes(4))
+            .saturating_add(T::DbWeight::get().reads(21).saturating_mul(n as u64))
+            .saturating_add(T::DbWeight::get().writes(11).saturating_mul(n as u64))
+    }
+    // Storage: RentInfo, RentDispute, OrderEscrow, RentOrderPaidFee, MachinesInfo,
+    //   RentOrderReceiver, StashRentReceiver, FixedTxFee, System.Account(2), Treasury,
+    //   MachineDispute
+    fn dispute_rent() -> Weight {
+        Weight::from_parts(10000, 0)
+            .saturating_add(T::DbWeight::get().reads(12))
+            .saturating_add(T::DbWeight::get().writes(4))
+    }
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+    fn rent_machine() -> Weight {
+        Weight::from_parts(10000, 0)
+            .saturating_add(RocksDbWeight::get().reads(25))
+            .saturating_add(RocksDbWeight::get().writes(13))
+    }
+    fn confirm_rent() -> Weight {
+        Weight::from_parts(10000, 0)
+            .saturating_add(RocksDbWeight::get().reads(28))
+            .saturating_add(RocksDbWeight::get().writes(17))
+    }
+    fn relet_machine() -> Weight {
+        Weight::from_parts(10000, 0)
+            .saturating_add(RocksDbWeight::get().reads(21))
+            .saturating_add(RocksDbWeight::get().writes(8))
+    }
+    fn reserve_machine() -> Weight {
+        Weight::from_parts(10000, 0)
+            .saturating_add(RocksDbWeight::get().reads(31))
+            .saturating_add(RocksDbWeight::get().writes(10))
+    }
+    fn terminate_rent() -> Weight {
+        Weight::from_parts(10000, 0)
+            .saturating_add(RocksDbWeight::get().reads(27))
+            .saturating_add(RocksDbWeight::get().writes(24))
+    }
+    fn rent_machines(n: u32) -> Weight {
+        Weight::from_parts(10000, 0)
+            .saturating_add(RocksDbWeight::get().reads(5))
+            .saturating_add(RocksDbWeight::get().writes(4))
+            .saturating_add(RocksDbWeight::get().reads(21).saturating_mul(n as u64))
+            .saturating_add(RocksDbWeight::get().writes(11).saturating_mul(n as u64))
+    }
+    fn dispute_rent() -> Weight {
+        Weight::from_parts(10000, 0)
+            .saturating_add(RocksDbWeight::get().reads(12))
+            .saturating_add(RocksDbWeight::get().writes(4))
+    }
+}
@@ -0,0 +1,96 @@
+#[cfg(feature = "std")]
+use crate::rpc_types::serde_text;
+use crate::{ItemList, MachineId, ReportId};
+use parity_scale_codec::{Decode, Encode};
+use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_runtime::{traits::AtLeast32BitUnsigned, Perbill, RuntimeDebug};
+use sp_std::{vec, vec::Vec};
+
+/// 委员会需要完成的任务类型
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub enum CommitteeTaskKind {
+    /// 审核机器上线 (online-committee)
+    OnlineVerify,
+    /// 处理机器故障报告 (maintain-committee)
+    FaultReport,
+    /// 审核机器上线 (terminating-rental)
+    IROnlineVerify,
+    /// 处理机器故障报告 (terminating-rental)
+    IRFaultReport,
+}
+
+/// 审核上线的任务为机器ID，处理故障报告的任务为报告ID
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub enum CommitteeTaskId {
+    Machine(#[cfg_attr(feature = "std", serde(with = "serde_text"))] MachineId),
+    Report(ReportId),
+}
+
+/// 委员会未完成的任务及提交信息的截止时间
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct CommitteeTask<BlockNumber> {
+    pub task_kind: CommitteeTaskKind,
+    pub task_id: CommitteeTaskId,
+    /// 是否已经提交了Hash
+    pub hash_submitted: bool,
+    /// 提交Hash的截止时间
+    pub hash_deadline: BlockNumber,
+    /// 提交原始信息的截止时间
+    pub raw_deadline: BlockNumber,
+}
+
+impl<BlockNumber: AtLeast32BitUnsigned + Copy> CommitteeTask<BlockNumber> {
+    /// 当前需要完成的步骤的截止时间：未提交Hash时为提交Hash的截止时间，否则为提交原始信息的截止时间
+    pub fn next_deadline(&self) -> BlockNumber {
+        if self.hash_submitted {
+            self.raw_deadline
+        } else {
+            self.hash_deadline
+        }
+    }
+
+    /// 抢单时计算发出提醒的块高：提交Hash的时间窗口从抢单开始，提交原始信息的时间窗口从提交Hash截止开始，
+    /// 剩余时间占各自时间窗口的比例为reminders中的一个时提醒
+    pub fn reminder_blocks(
+        &self,
+        booked_time: BlockNumber,
+        reminders: &[Perbill],
+    ) -> Vec<BlockNumber> {
+        let mut blocks = vec![];
+        let windows = [
+            (booked_time, self.hash_deadline),
+            (self.hash_deadline.max(booked_time), self.raw_deadline),
+        ];
+        for (start, deadline) in windows {
+            if deadline <= start {
+                continue
+            }
+            let window = deadline - start;
+            for reminder in reminders {
+                let block = deadline - *reminder * window;
+                if block > booked_time && block < deadline {
+                    ItemList::add_item(&mut blocks, block);
+                }
+            }
+        }
+        blocks
+    }
+
+    /// 到达提醒的块高时，返回当前步骤需要提醒的截止时间；
+    /// 已经提交Hash时，不再发出提交Hash时间窗口内的提醒
+    pub fn reminded_deadline(&self, now: BlockNumber) -> Option<BlockNumber> {
+        let deadline = self.next_deadline();
+        if deadline <= now || (self.hash_submitted && now < self.hash_deadline) {
+            return None
+        }
+        Some(deadline)
+    }
+}
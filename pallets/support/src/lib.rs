@@ -2,6 +2,7 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 #![warn(unused_crate_dependencies)]
 
+pub mod committee_task;
 pub mod custom_err;
 pub mod live_machine;
 pub mod machine_info;
@@ -73,6 +73,12 @@ where
         self.total_rent_fee = self.total_rent_fee.saturating_add(fee_to_stash);
     }
 
+    // 退还租金时，减少租金统计
+    pub fn reduce_rent_fee(&mut self, fee_to_destroy: Balance, fee_to_stash: Balance) {
+        self.total_burn_fee = self.total_burn_fee.saturating_sub(fee_to_destroy);
+        self.total_rent_fee = self.total_rent_fee.saturating_sub(fee_to_stash);
+    }
+
     pub fn new_bonding(
         controller: AccountId,
         stash: AccountId,
@@ -168,6 +174,35 @@ where
     }
 }
 
+impl<AccountId, BlockNumber, Balance> MachineInfo<AccountId, BlockNumber, Balance>
+where
+    BlockNumber: Copy + Ord + Saturating + Zero,
+{
+    /// [from, to) 时段内机器下线的块数：
+    /// 已重新上线的下线时段按 offline_records (下线时间, 重新上线时间) 累加；
+    /// 当前仍下线时，再加上从下线(被举报)的时间起算的部分
+    pub fn offline_duration(
+        &self,
+        from: BlockNumber,
+        to: BlockNumber,
+        offline_records: &[(BlockNumber, BlockNumber)],
+    ) -> BlockNumber {
+        let recorded =
+            offline_records.iter().fold(Zero::zero(), |acc: BlockNumber, (start, end)| {
+                acc.saturating_add((*end).min(to).saturating_sub((*start).max(from)))
+            });
+
+        let offline_time = match &self.machine_status {
+            MachineStatus::Online | MachineStatus::Rented => return recorded,
+            MachineStatus::StakerReportOffline(offline_time, _) => *offline_time,
+            MachineStatus::ReporterReportOffline(slash_reason, ..) =>
+                slash_reason.offline_time().unwrap_or(from),
+            _ => from,
+        };
+        recorded.saturating_add(to.saturating_sub(offline_time.max(from)))
+    }
+}
+
 // For Terminating Renting
 impl<AccountId, BlockNumber, Balance> MachineInfo<AccountId, BlockNumber, Balance>
 where
@@ -36,6 +36,26 @@ impl Default for Latitude {
     }
 }
 
+impl Longitude {
+    /// 东经为正，西经为负
+    pub fn to_signed(&self) -> i128 {
+        match self {
+            Longitude::East(value) => *value as i128,
+            Longitude::West(value) => -(*value as i128),
+        }
+    }
+}
+
+impl Latitude {
+    /// 北纬为正，南纬为负
+    pub fn to_signed(&self) -> i128 {
+        match self {
+            Latitude::North(value) => *value as i128,
+            Latitude::South(value) => -(*value as i128),
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Encode, Decode, Default, PartialOrd, Ord, TypeInfo)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub struct CommitteeUploadInfo {
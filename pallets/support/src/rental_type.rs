@@ -5,7 +5,8 @@ use parity_scale_codec::{Decode, Encode};
 use scale_info::TypeInfo;
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
-use sp_runtime::RuntimeDebug;
+use sp_core::H256;
+use sp_runtime::{traits::Saturating, RuntimeDebug};
 use sp_std::{vec, vec::Vec};
 
 #[derive(Debug, PartialEq, Eq, Clone, Encode, Decode, TypeInfo)]
@@ -135,3 +136,266 @@ pub struct DlcBurnDetail<AccountId, Balance, BlockNumber> {
     pub burned_amount: Balance,
     pub at: BlockNumber,
 }
+
+/// 分时段机器的预约订单：提前锁定未来 [rent_start, rent_end) 的GPU，到期自动生成租用订单
+#[derive(Debug, PartialEq, Eq, Clone, Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct RentReservationDetail<AccountId, BlockNumber, Balance> {
+    /// 预约的机器ID
+    #[cfg_attr(feature = "std", serde(with = "serde_text"))]
+    pub machine_id: MachineId,
+    /// 预约者
+    pub renter: AccountId,
+    /// 预约开始时间(块高)
+    pub rent_start: BlockNumber,
+    /// 预约结束时间(块高)
+    pub rent_end: BlockNumber,
+    /// 预约时锁定的租金
+    pub stake_amount: Balance,
+    /// 预约的GPU数量
+    pub gpu_num: u32,
+}
+
+impl<A, B: PartialOrd + Copy, C> RentReservationDetail<A, B, C> {
+    /// 预约时段是否与 [start, end) 有交集
+    pub fn overlaps(&self, start: B, end: B) -> bool {
+        self.rent_start < end && start < self.rent_end
+    }
+}
+
+/// 批量租用多台机器时的父订单：所有子订单同时创建，可一起确认、续租
+#[derive(Debug, PartialEq, Eq, Clone, Encode, Decode, Default, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct RentGroupDetail<AccountId> {
+    /// 租用者
+    pub renter: AccountId,
+    /// 子订单
+    pub rent_ids: Vec<RentOrderId>,
+}
+
+/// 流式支付的租金托管：租金转入租用模块的托管账户，按机器正常在线的时长逐小时释放给矿工，
+/// 机器故障期间对应的租金退还给租用人
+#[derive(Debug, PartialEq, Eq, Clone, Encode, Decode, Default, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct RentEscrowDetail<AccountId, BlockNumber, Balance> {
+    /// 租用者
+    pub renter: AccountId,
+    /// 托管的租金总额(包括续租)
+    pub total_amount: Balance,
+    /// 已释放给矿工的租金
+    pub released_amount: Balance,
+    /// 已退还给租用者的租金
+    pub refunded_amount: Balance,
+    /// 上次结算的时间
+    pub last_settled: BlockNumber,
+    /// 租用结束时间
+    pub rent_end: BlockNumber,
+}
+
+impl<A, B, C: Saturating + Copy> RentEscrowDetail<A, B, C> {
+    /// 尚未结算的托管租金
+    pub fn remaining(&self) -> C {
+        self.total_amount.saturating_sub(self.released_amount).saturating_sub(self.refunded_amount)
+    }
+
+    pub fn add_amount(&mut self, amount: C, rent_end: B) {
+        self.total_amount = self.total_amount.saturating_add(amount);
+        self.rent_end = rent_end;
+    }
+
+    pub fn release(&mut self, amount: C, now: B) {
+        self.released_amount = self.released_amount.saturating_add(amount);
+        self.last_settled = now;
+    }
+
+    pub fn refund(&mut self, amount: C, now: B) {
+        self.refunded_amount = self.refunded_amount.saturating_add(amount);
+        self.last_settled = now;
+    }
+}
+
+/// 租用人对部分服务不达标(如磁盘慢、GPU型号不符)的争议，由一个委员会抢单处理并决定退还部分租金，
+/// 处理完成后移除
+#[derive(Debug, PartialEq, Eq, Clone, Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct RentDisputeDetail<AccountId, BlockNumber, Balance> {
+    #[cfg_attr(feature = "std", serde(with = "serde_text"))]
+    pub machine_id: MachineId,
+    /// 租用者
+    pub renter: AccountId,
+    /// 租金接收方，托管的租金不足以退款时从该账户退还
+    pub rent_receiver: AccountId,
+    /// 证据的Hash，证据由租用者在链下提供给委员会
+    pub evidence_hash: H256,
+    /// 可退还的租金上限：已支付给租金接收方的租金，流式支付时为托管的租金总额
+    pub refundable: Balance,
+    /// 提交争议的时间
+    pub dispute_time: BlockNumber,
+    /// 抢单的委员会
+    pub committee: Option<AccountId>,
+    /// 委员会抢单的时间
+    pub book_time: BlockNumber,
+    /// 委员会抢单时锁定的质押
+    pub committee_stake: Balance,
+    pub dispute_status: RentDisputeStatus,
+}
+
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub enum RentDisputeStatus {
+    /// 等待委员会抢单
+    WaitingBook,
+    /// 委员会处理中
+    Verifying,
+    /// 委员会已处理，每个订单只能提交一次争议
+    Resolved,
+}
+
+impl<A: PartialEq, B: Copy + PartialOrd + Saturating, C: Copy> RentDisputeDetail<A, B, C> {
+    /// 等待抢单，或抢单的委员会超时未处理时，可以被其他委员会抢单
+    pub fn can_book(&self, committee: &A, now: B, resolve_duration: B) -> bool {
+        match self.dispute_status {
+            RentDisputeStatus::WaitingBook => true,
+            RentDisputeStatus::Verifying =>
+                self.committee.as_ref() != Some(committee) &&
+                    now >= self.book_time.saturating_add(resolve_duration),
+            RentDisputeStatus::Resolved => false,
+        }
+    }
+
+    pub fn book(&mut self, committee: A, now: B, stake: C) {
+        self.committee = Some(committee);
+        self.book_time = now;
+        self.committee_stake = stake;
+        self.dispute_status = RentDisputeStatus::Verifying;
+    }
+
+    /// 抢单的委员会超时未处理，重新等待抢单
+    pub fn unbook(&mut self, stake: C) {
+        self.committee = None;
+        self.committee_stake = stake;
+        self.dispute_status = RentDisputeStatus::WaitingBook;
+    }
+}
+
+/// 未按时处理争议的委员会的惩罚，执行前委员会可以申请复审
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct DisputeSlashInfo<AccountId, BlockNumber, Balance> {
+    pub rent_id: RentOrderId,
+    /// 被惩罚的委员会
+    pub committee: AccountId,
+    /// 被惩罚的抢单质押
+    pub committee_stake: Balance,
+    pub slash_time: BlockNumber,
+    pub slash_exec_time: BlockNumber,
+    pub slash_result: DisputeSlashResult,
+}
+
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub enum DisputeSlashResult {
+    Pending,
+    Canceled,
+    Executed,
+}
+
+impl Default for DisputeSlashResult {
+    fn default() -> Self {
+        Self::Pending
+    }
+}
+
+/// 被惩罚的委员会申请复审时锁定的质押
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct DisputeSlashReviewInfo<AccountId, Balance, BlockNumber> {
+    pub applicant: AccountId,
+    pub staked_amount: Balance,
+    pub apply_time: BlockNumber,
+    pub expire_time: BlockNumber,
+    #[cfg_attr(feature = "std", serde(with = "serde_text"))]
+    pub reason: Vec<u8>,
+}
+
+/// 机器的争议统计，供租用者评估矿工
+#[derive(PartialEq, Eq, Clone, Encode, Decode, Default, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct MachineDisputeStats {
+    /// 收到的争议次数
+    pub dispute_count: u32,
+    /// 委员会判定退还租金的次数
+    pub refund_count: u32,
+}
+
+/// 租用者声明的机器访问地址，供委员会的链下工作机探测机器是否可以访问
+#[derive(PartialEq, Eq, Clone, Encode, Decode, Default, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct RentEndpoint {
+    /// 域名或IP
+    #[cfg_attr(feature = "std", serde(with = "serde_text"))]
+    pub host: Vec<u8>,
+    pub port: u16,
+}
+
+/// 委员会的链下工作机对租用订单访问地址的探测结果，每轮重新统计
+#[derive(PartialEq, Eq, Clone, Encode, Decode, Default, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct RentLivenessRound<AccountId, BlockNumber> {
+    /// 探测轮次的开始时间
+    pub round: BlockNumber,
+    /// 探测到可以访问的委员会
+    pub online_committee: Vec<AccountId>,
+    /// 探测到无法访问的委员会
+    pub offline_committee: Vec<AccountId>,
+    /// 本轮开始时的审核委员会数量，本轮内不受set_verifier_count影响
+    pub quorum: u32,
+}
+
+impl<AccountId: Ord, BlockNumber: PartialEq> RentLivenessRound<AccountId, BlockNumber> {
+    pub fn attest(
+        &mut self,
+        round: BlockNumber,
+        committee: AccountId,
+        is_online: bool,
+        quorum: u32,
+    ) -> Result<(), ()> {
+        // 进入新的一轮，清空上一轮的结果
+        if self.round != round {
+            self.round = round;
+            self.online_committee = Vec::new();
+            self.offline_committee = Vec::new();
+            self.quorum = quorum;
+        }
+
+        if self.online_committee.binary_search(&committee).is_ok() ||
+            self.offline_committee.binary_search(&committee).is_ok()
+        {
+            return Err(())
+        }
+
+        if is_online {
+            ItemList::add_item(&mut self.online_committee, committee);
+        } else {
+            ItemList::add_item(&mut self.offline_committee, committee);
+        }
+        Ok(())
+    }
+
+    /// 足够多的委员会探测到无法访问，且多于探测到可以访问的委员会
+    pub fn is_offline_quorum(&self) -> bool {
+        self.offline_committee.len() >= self.quorum as usize &&
+            self.offline_committee.len() > self.online_committee.len()
+    }
+}
@@ -1,12 +1,18 @@
+#[cfg(feature = "std")]
+use crate::rpc_types::serde_text;
 use crate::{custom_err::ReportErr, report::MachineFaultType, ItemList, ReportHash, ReportId};
 use frame_support::ensure;
 use parity_scale_codec::{Decode, Encode};
 use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
 use sp_runtime::RuntimeDebug;
 use sp_std::{cmp::PartialEq, vec::Vec};
 
 /// 委员会抢到的报告的列表
 #[derive(PartialEq, Eq, Clone, Encode, Decode, Default, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
 pub struct MTCommitteeOrderList {
     /// 委员会预订的报告
     pub booked_report: Vec<ReportId>,
@@ -54,6 +60,8 @@ impl MTCommitteeOrderList {
 
 /// 委员会抢单之后，对应订单的状态
 #[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
 pub enum MTOrderStatus {
     /// 预订报告，状态将等待加密信息
     WaitingEncrypt,
@@ -73,6 +81,8 @@ impl Default for MTOrderStatus {
 
 /// 委员会对报告的操作信息
 #[derive(PartialEq, Eq, Clone, Encode, Decode, Default, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
 pub struct MTCommitteeOpsDetail<BlockNumber, Balance> {
     pub booked_time: BlockNumber,
     /// reporter 提交的加密后的信息
@@ -81,6 +91,7 @@ pub struct MTCommitteeOpsDetail<BlockNumber, Balance> {
     pub confirm_hash: ReportHash,
     pub hash_time: BlockNumber,
     /// 委员会可以补充额外的信息
+    #[cfg_attr(feature = "std", serde(with = "serde_text"))]
     pub extra_err_info: Vec<u8>,
     /// 委员会提交raw信息的时间
     pub confirm_time: BlockNumber,
@@ -1,12 +1,16 @@
 use crate::{report::MachineFaultType, ItemList, ReportId};
 use parity_scale_codec::{Decode, Encode};
 use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
 use sp_runtime::RuntimeDebug;
 use sp_std::{cmp::PartialEq, vec::Vec};
 
 /// 机器故障的报告列表
 /// 记录该模块中所有活跃的报告, 根据ReportStatus来区分
 #[derive(PartialEq, Eq, Clone, Encode, Decode, Default, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
 pub struct MTLiveReportList {
     /// 委员会可以抢单的报告
     pub bookable_report: Vec<ReportId>,
@@ -32,8 +36,9 @@ impl MTLiveReportList {
         report_id: ReportId,
         report_type: MachineFaultType,
         booked_committee_count: usize,
+        book_limit: usize,
     ) {
-        if booked_committee_count == 3 ||
+        if booked_committee_count >= book_limit ||
             !matches!(report_type, MachineFaultType::RentedInaccessible(..))
         {
             ItemList::rm_item(&mut self.bookable_report, &report_id);
@@ -46,8 +51,9 @@ impl MTLiveReportList {
         report_id: ReportId,
         report_type: MachineFaultType,
         hashed_committee_count: usize,
+        book_limit: usize,
     ) {
-        if hashed_committee_count == 3 {
+        if hashed_committee_count >= book_limit {
             // 全都提交了hash后，进入提交raw的阶段
             ItemList::rm_item(&mut self.verifying_report, &report_id);
             ItemList::add_item(&mut self.waiting_raw_report, report_id);
@@ -59,8 +65,8 @@ impl MTLiveReportList {
     }
 
     pub fn time_to_submit_raw(&mut self, report_id: ReportId) {
-        ItemList::rm_item(&mut self.bookable_report, &report_id); // 小于3人时处于bookable
-        ItemList::rm_item(&mut self.verifying_report, &report_id); // 等于3人时处于verifying
+        ItemList::rm_item(&mut self.bookable_report, &report_id); // 未达到抢单人数时处于bookable
+        ItemList::rm_item(&mut self.verifying_report, &report_id); // 达到抢单人数时处于verifying
         ItemList::add_item(&mut self.waiting_raw_report, report_id);
     }
 
@@ -1,10 +1,14 @@
+#[cfg(feature = "std")]
+use crate::rpc_types::serde_text;
 use crate::{
     custom_err::ReportErr, report::ReportConfirmStatus, BoxPubkey, ItemList, MachineId,
-    RentOrderId, ReportHash, FIVE_MINUTES, FOUR_HOURS, ONE_MINUTE, THREE_HOURS,
+    RentOrderId, ReportHash, FIVE_MINUTES, FOUR_HOURS, ONE_HOUR, ONE_MINUTE, THREE_HOURS,
 };
 use frame_support::ensure;
 use parity_scale_codec::{Decode, Encode};
 use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
 use sp_runtime::{
     traits::{Saturating, Zero},
     Perbill, RuntimeDebug,
@@ -13,6 +17,8 @@ use sp_std::{cmp::PartialEq, ops::Sub, vec, vec::Vec};
 
 // 报告的详细信息
 #[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
 pub struct MTReportInfoDetail<AccountId, BlockNumber, Balance> {
     ///报告人
     pub reporter: AccountId,
@@ -23,10 +29,12 @@ pub struct MTReportInfoDetail<AccountId, BlockNumber, Balance> {
     /// 第一个委员会抢单时间
     pub first_book_time: BlockNumber,
     /// 出问题的机器，只有委员会提交原始信息时才存入
+    #[cfg_attr(feature = "std", serde(with = "serde_text"))]
     pub machine_id: MachineId,
     /// 出问题的机器的租用ID
     pub rent_order_id: RentOrderId,
     /// 机器的故障原因
+    #[cfg_attr(feature = "std", serde(with = "serde_text"))]
     pub err_info: Vec<u8>,
     /// 当前正在验证机器的委员会
     pub verifying_committee: Option<AccountId>,
@@ -48,6 +56,8 @@ pub struct MTReportInfoDetail<AccountId, BlockNumber, Balance> {
     pub report_status: ReportStatus,
     /// 机器的故障类型
     pub machine_fault_type: MachineFaultType,
+    /// 创建报告时的审核委员会数量，审核过程中不受set_verifier_count影响
+    pub verifier_count: u32,
 }
 
 impl<Account, BlockNumber, Balance> MTReportInfoDetail<Account, BlockNumber, Balance>
@@ -68,12 +78,14 @@ where
         report_time: BlockNumber,
         machine_fault_type: MachineFaultType,
         reporter_stake: Balance,
+        verifier_count: u32,
     ) -> Self {
         let mut report_info = MTReportInfoDetail {
             reporter,
             report_time,
             machine_fault_type: machine_fault_type.clone(),
             reporter_stake,
+            verifier_count,
 
             first_book_time: BlockNumber::default(),
             machine_id: MachineId::default(),
@@ -101,6 +113,11 @@ where
         report_info
     }
 
+    // 一个报告最多可被抢单的委员会数量
+    pub fn book_limit(&self) -> usize {
+        self.verifier_count as usize
+    }
+
     pub fn can_book(&self, committee: &Account) -> Result<(), ReportErr> {
         // 检查订单是否可以抢定
         ensure!(self.report_time != Zero::zero(), ReportErr::OrderNotAllowBook);
@@ -108,7 +125,7 @@ where
             matches!(self.report_status, ReportStatus::Reported | ReportStatus::WaitingBook),
             ReportErr::OrderNotAllowBook
         );
-        ensure!(self.booked_committee.len() < 3, ReportErr::OrderNotAllowBook);
+        ensure!(self.booked_committee.len() < self.book_limit(), ReportErr::OrderNotAllowBook);
         ensure!(self.booked_committee.binary_search(committee).is_err(), ReportErr::AlreadyBooked);
         Ok(())
     }
@@ -144,6 +161,28 @@ where
         Ok(())
     }
 
+    // 委员会提交Hash的截止时间：
+    // 无法访问的报告在第一个委员会抢单5分钟内，其他报告在该委员会抢单1小时内
+    pub fn hash_deadline(&self, booked_time: BlockNumber) -> BlockNumber {
+        match self.machine_fault_type {
+            MachineFaultType::RentedInaccessible(..) => {
+                self.first_book_time.saturating_add(FIVE_MINUTES.into())
+            },
+            _ => booked_time.saturating_add(ONE_HOUR.into()),
+        }
+    }
+
+    // 委员会提交原始信息的截止时间：
+    // 无法访问的报告在第一个委员会抢单10分钟内，其他报告在4小时内
+    pub fn raw_deadline(&self) -> BlockNumber {
+        match self.machine_fault_type {
+            MachineFaultType::RentedInaccessible(..) => {
+                self.first_book_time.saturating_add((10 * ONE_MINUTE).into())
+            },
+            _ => self.first_book_time.saturating_add(FOUR_HOURS.into()),
+        }
+    }
+
     // 获取链上已经记录的报告人提交的Hash
     pub fn get_reporter_hash(&self) -> Result<ReportHash, ReportErr> {
         self.machine_fault_type.clone().get_hash().ok_or(ReportErr::OrderStatusNotFeat)
@@ -229,7 +268,7 @@ where
 
         self.report_status = match self.machine_fault_type {
             MachineFaultType::RentedInaccessible(..) => {
-                if self.booked_committee.len() == 3 {
+                if self.booked_committee.len() >= self.book_limit() {
                     ReportStatus::Verifying
                 } else {
                     ReportStatus::WaitingBook
@@ -250,7 +289,7 @@ where
         self.verifying_committee = None;
 
         // 达到book_limit，则允许提交Raw
-        if self.hashed_committee.len() == 3 {
+        if self.hashed_committee.len() >= self.book_limit() {
             self.report_status = ReportStatus::SubmittingRaw;
         } else if !matches!(self.machine_fault_type, MachineFaultType::RentedInaccessible(..)) {
             // 否则，是普通错误时，继续允许预订
@@ -364,6 +403,8 @@ where
 }
 
 #[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
 pub enum MachineFaultType {
     /// 机器被租用，但无法访问的故障 (机器离线)
     RentedInaccessible(MachineId, RentOrderId),
@@ -405,6 +446,8 @@ impl MachineFaultType {
 }
 
 #[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
 pub enum ReportStatus {
     /// 没有委员会预订过的报告, 允许报告人取消
     Reported,
@@ -1,9 +1,13 @@
+#[cfg(feature = "std")]
+use crate::rpc_types::serde_text;
 use crate::{
     report::{MTReportInfoDetail, ReportConfirmStatus},
     ItemList, MachineId, ReportId, TWO_DAYS,
 };
 use parity_scale_codec::{Decode, Encode};
 use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
 use sp_runtime::{
     traits::{Saturating, Zero},
     RuntimeDebug,
@@ -17,6 +21,8 @@ use sp_std::{
 
 /// 报告的处理结果
 #[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
 pub struct MTReportResultInfo<AccountId, BlockNumber, Balance> {
     pub report_id: ReportId,
     pub reporter: AccountId,
@@ -28,6 +34,7 @@ pub struct MTReportResultInfo<AccountId, BlockNumber, Balance> {
     pub committee_stake: Balance,
 
     pub machine_stash: Option<AccountId>,
+    #[cfg_attr(feature = "std", serde(with = "serde_text"))]
     pub machine_id: MachineId,
 
     pub slash_time: BlockNumber,
@@ -37,6 +44,8 @@ pub struct MTReportResultInfo<AccountId, BlockNumber, Balance> {
 }
 
 #[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
 pub enum MCSlashResult {
     Pending,
     Canceled,
@@ -50,6 +59,8 @@ impl Default for MCSlashResult {
 }
 
 #[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
 pub enum ReportResultType {
     ReportSucceed,
     ReportRefused,
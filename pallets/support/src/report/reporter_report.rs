@@ -1,11 +1,15 @@
 use crate::{ItemList, ReportId};
 use parity_scale_codec::{Decode, Encode};
 use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
 use sp_runtime::{traits::Saturating, RuntimeDebug};
 use sp_std::{cmp::PartialEq, vec::Vec};
 
 /// 报告人的报告记录
 #[derive(PartialEq, Eq, Clone, Encode, Decode, Default, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
 pub struct ReporterReportList {
     pub processing_report: Vec<ReportId>,
     pub canceled_report: Vec<ReportId>,
@@ -1,9 +1,10 @@
-use crate::{MachineId, RentOrderId};
+use crate::{EraIndex, MachineId, RentOrderId};
 use frame_support::{
     dispatch::{Decode, Encode, TypeInfo},
     RuntimeDebug,
 };
 use sp_core::H160;
+use sp_runtime::Perbill;
 use sp_std::vec::Vec;
 
 pub trait PhaseReward {
@@ -75,6 +76,11 @@ pub trait RTOps {
         fee_to_destroy: Self::Balance,
         fee_to_stash: Self::Balance,
     ) -> Result<(), ()>;
+    fn reduce_machine_rent_fee(
+        machine_id: Self::MachineId,
+        fee_to_destroy: Self::Balance,
+        fee_to_stash: Self::Balance,
+    ) -> Result<(), ()>;
     fn reset_machine_renters(
         machine_id: Self::MachineId,
         renters: Vec<Self::AccountId>,
@@ -109,8 +115,27 @@ pub trait ManageCommittee {
         is_add: bool,
         change_reserve: bool,
     ) -> Result<(), ()>;
+    // Slash staked amount of committee, delegators share the slash by their delegation
+    fn slash_stake(committee: Self::AccountId, amount: Self::Balance) -> Result<(), ()>;
     fn stake_per_order() -> Option<Self::Balance>;
-    fn add_reward(committee: Self::AccountId, reward: Self::Balance);
+    // Reward of committee is recorded by the era it is released
+    fn add_reward(committee: Self::AccountId, era: EraIndex, reward: Self::Balance);
+    // Number of committees to verify a machine or a fault report
+    fn verifier_count() -> u32;
+    // Committees are reminded when the time left is this part of the submitting window
+    fn deadline_reminders() -> Vec<Perbill>;
+    // Whether the box_pubkey of this version can still be used to encrypt info for the committee
+    fn box_key_valid(committee: &Self::AccountId, version: u32) -> bool;
+    // Version of the box_pubkey the committee currently uses
+    fn box_key_version(committee: &Self::AccountId) -> u32;
+    // Weight when distributing orders: stake multiple * reputation
+    fn selection_weight(who: &Self::AccountId) -> u64;
+    // Update reputation by the summary of an order
+    fn update_reputation(
+        valid: &[Self::AccountId],
+        invalid: &[Self::AccountId],
+        unruly: &[Self::AccountId],
+    );
 }
 
 pub trait DbcPrice {
@@ -122,6 +147,18 @@ pub trait DbcPrice {
     fn get_dlc_amount_by_value(value: u64) -> Option<Self::Balance>;
 }
 
+/// 动态定价：根据全网及该型号GPU的使用率调整系统定价
+pub trait SurgePricing {
+    /// 返回调整后的系统定价(USD*10^6)；未配置定价曲线时原样返回
+    fn adjust_machine_price(gpu_type: &[u8], system_price: u64) -> Option<u64>;
+}
+
+impl SurgePricing for () {
+    fn adjust_machine_price(_gpu_type: &[u8], system_price: u64) -> Option<u64> {
+        Some(system_price)
+    }
+}
+
 pub trait DlcPrice {
     type Balance;
 
@@ -240,6 +277,22 @@ pub trait MTOps {
     fn mt_rm_stash_total_stake(stash: Self::AccountId, amount: Self::Balance) -> Result<(), ()>;
 }
 
+// machine-heartbeat_ops
+pub trait HBOps {
+    type AccountId;
+    type BlockNumber;
+    type MachineId;
+
+    /// 所有处于在线或租用状态的机器
+    fn hb_online_machines() -> Vec<Self::MachineId>;
+    /// 机器处于在线或租用状态时，返回机器的stash账户及最近一次上线的高度
+    fn hb_online_machine(
+        machine_id: &Self::MachineId,
+    ) -> Option<(Self::AccountId, Self::BlockNumber)>;
+    /// 按比例扣除机器在当前Era的得分，不影响之后的Era
+    fn hb_reduce_machine_grade(machine_id: &Self::MachineId, percent: Perbill) -> Result<(), ()>;
+}
+
 pub trait GNOps {
     type AccountId;
     type Balance;
@@ -248,5 +301,14 @@ pub trait GNOps {
         slash_who: Vec<Self::AccountId>,
         each_slash: Self::Balance,
         reward_who: Vec<Self::AccountId>,
-    ) -> Result<(), ()>;
+    ) -> Result<(), ()> {
+        Self::slash_and_pay(slash_who, each_slash, reward_who).map(|_| ())
+    }
+
+    // Same as `slash_and_reward`, returns the amount each of `reward_who` actually received
+    fn slash_and_pay(
+        slash_who: Vec<Self::AccountId>,
+        each_slash: Self::Balance,
+        reward_who: Vec<Self::AccountId>,
+    ) -> Result<Vec<(Self::AccountId, Self::Balance)>, ()>;
 }
@@ -67,13 +67,14 @@ use crate::{
     verify_online::{OCMachineCommitteeList, Summary, VerifyResult},
     ItemList,
 };
-use sp_std::{collections::btree_set::BTreeSet, ops};
+use sp_std::{collections::btree_map::BTreeMap, ops};
 pub trait OnlineCommitteeSummary {
     //<AccountId, BlockNumber> {
     type AccountId;
     type BlockNumber;
 
     // 总结机器的确认情况: 检查机器是否被确认，并检查提交的信息是否一致
+    // 以得票超过审核委员会总数一半的信息为准，否则无共识
     // 返回三种状态：
     // 1. 无共识：处理办法：退还委员会质押，机器重新派单。
     // 2. 支持上线: 处理办法：扣除所有反对上线，支持上线但提交无效信息的委员会的质押。
@@ -99,52 +100,36 @@ pub trait OnlineCommitteeSummary {
 
         let mut summary = Summary::default();
         summary.unruly = machine_committee.summary_unruly();
-        let uniq_len = submit_info.iter().collect::<BTreeSet<_>>().len();
 
-        if machine_committee.confirmed_committee.is_empty() {
-            // Case: Zero info
-            summary.verify_result = VerifyResult::NoConsensus;
-        } else if submit_info.iter().min().unwrap() == submit_info.iter().max().unwrap() {
-            // Cases: One submit info; Two same info; Three same info
-            let info = submit_info[0].clone();
-            if info.is_support {
-                summary.info = Some(info);
-                summary.valid_vote = machine_committee.confirmed_committee;
-                summary.verify_result = VerifyResult::Confirmed;
-            } else {
-                summary.valid_vote = machine_committee.confirmed_committee;
-                summary.verify_result = VerifyResult::Refused;
-            }
-        } else if uniq_len == submit_info.len() {
-            // Cases: Two different info; Three different info
+        // 统计每种提交信息的委员会数量，得票超过派单委员会数量一半的信息为多数结果
+        let mut vote_count: BTreeMap<&CommitteeUploadInfo, usize> = BTreeMap::new();
+        submit_info.iter().for_each(|info| *vote_count.entry(info).or_default() += 1);
+        let max_vote = vote_count.values().max().copied().unwrap_or_default();
+        let majority: Vec<_> =
+            vote_count.into_iter().filter(|(_, count)| *count == max_vote).collect();
+
+        if majority.len() != 1 || max_vote * 2 <= machine_committee.booked_committee.len() {
+            // Cases: Zero info; 得票最多的信息不止一种(如各不相同); 得票未过半(如5人中仅2人一致)，
+            // 无共识
             summary.invalid_vote = machine_committee.confirmed_committee;
             summary.verify_result = VerifyResult::NoConsensus;
         } else {
-            // Cases: Three info: two is same.
-            let (valid_1, valid_2, invalid) = if submit_info[0] == submit_info[1] {
-                (0, 1, 2)
-            } else if submit_info[0] == submit_info[2] {
-                (0, 2, 1)
-            } else {
-                (1, 2, 0)
-            };
-
-            summary.invalid_vote = vec![machine_committee.confirmed_committee[invalid].clone()];
-            if submit_info[valid_1].is_support {
-                summary.info = Some(submit_info[valid_1].clone());
-                summary.valid_vote = vec![
-                    machine_committee.confirmed_committee[valid_1].clone(),
-                    machine_committee.confirmed_committee[valid_2].clone(),
-                ];
+            // Cases: 所有信息一致; 多数委员会信息一致，其余委员会提交的信息无效
+            let info = majority[0].0.clone();
+            for (a_committee, a_info) in
+                machine_committee.confirmed_committee.into_iter().zip(submit_info.iter())
+            {
+                if *a_info == info {
+                    ItemList::add_item(&mut summary.valid_vote, a_committee);
+                } else {
+                    ItemList::add_item(&mut summary.invalid_vote, a_committee);
+                }
+            }
+
+            if info.is_support {
+                summary.info = Some(info);
                 summary.verify_result = VerifyResult::Confirmed;
             } else {
-                ItemList::expand_to_order(
-                    &mut summary.valid_vote,
-                    vec![
-                        machine_committee.confirmed_committee[valid_1].clone(),
-                        machine_committee.confirmed_committee[valid_2].clone(),
-                    ],
-                );
                 summary.verify_result = VerifyResult::Refused;
             }
         };
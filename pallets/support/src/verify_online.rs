@@ -6,6 +6,7 @@ use parity_scale_codec::{Decode, Encode};
 use scale_info::TypeInfo;
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
+use sp_core::H256;
 use sp_runtime::{
     traits::{CheckedAdd, Saturating, Zero},
     RuntimeDebug,
@@ -258,6 +259,14 @@ where
         now >= self.book_time + SUBMIT_HASH_END.into()
     }
 
+    pub fn hash_deadline(&self) -> BlockNumber {
+        self.book_time + SUBMIT_HASH_END.into()
+    }
+
+    pub fn raw_deadline(&self) -> BlockNumber {
+        self.book_time + SUBMIT_RAW_END.into()
+    }
+
     pub fn submit_raw_end(&self, now: BlockNumber) -> bool {
         now >= self.book_time + SUBMIT_RAW_END.into()
     }
@@ -335,6 +344,25 @@ pub struct VerifySequence<AccountId> {
     pub index: Vec<usize>,
 }
 
+/// 随机分派委员会的证明，任何人都可以根据证明复现分派过程：
+/// 1. random_seeds[i] = Hash(epoch_random ++ 机器ID ++ nonces[i])
+/// 2. 第i次抽取：以 random_seeds[i] 为种子的 ChaChaRng 按权重从剩余的候选委员会中取出一个
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct SelectionProof<AccountId, BlockNumber> {
+    /// 分派时的块高
+    pub selected_at: BlockNumber,
+    /// 分派时使用的BABE epoch随机数，随证明一起保存和删除
+    pub epoch_random: H256,
+    /// 每次抽取使用的随机数 nonce
+    pub nonces: Vec<u64>,
+    /// 分派时可用的委员会及其抽取权重(质押倍数 × 信誉分)的哈希
+    pub candidates_hash: H256,
+    /// 按抽取顺序被选中的委员会
+    pub selected: Vec<AccountId>,
+}
+
 /// stash account overview self-status
 #[derive(PartialEq, Eq, Clone, Encode, Decode, Default, RuntimeDebug, TypeInfo)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
@@ -388,6 +416,12 @@ impl<B: Saturating + Copy + CheckedAdd + Zero> StashMachine<B> {
         self.total_burn_fee = self.total_burn_fee.saturating_add(fee_to_destroy);
         self.total_rent_fee = self.total_rent_fee.saturating_add(fee_to_stash);
     }
+
+    // 退还租金时，减少租金统计
+    pub fn reduce_rent_fee(&mut self, fee_to_destroy: B, fee_to_stash: B) {
+        self.total_burn_fee = self.total_burn_fee.saturating_sub(fee_to_destroy);
+        self.total_rent_fee = self.total_rent_fee.saturating_sub(fee_to_stash);
+    }
 }
 
 // In terminating pallet:
@@ -60,6 +60,8 @@ pub enum OPSlashReason<BlockNumber> {
     CommitteeRefusedMutHardware,
     /// Machine change hardware is passed, so should reward committee
     ReonlineShouldReward,
+    /// Machine missed heartbeat too many times, no reporter
+    HeartbeatOffline(BlockNumber),
 }
 
 impl<BlockNumber> Default for OPSlashReason<BlockNumber> {
@@ -67,3 +69,19 @@ impl<BlockNumber> Default for OPSlashReason<BlockNumber> {
         Self::CommitteeRefusedOnline
     }
 }
+
+impl<BlockNumber: Copy> OPSlashReason<BlockNumber> {
+    /// 机器因该原因下线的时间
+    pub fn offline_time(&self) -> Option<BlockNumber> {
+        match self {
+            Self::RentedReportOffline(time) |
+            Self::OnlineReportOffline(time) |
+            Self::RentedInaccessible(time) |
+            Self::RentedHardwareMalfunction(time) |
+            Self::RentedHardwareCounterfeit(time) |
+            Self::OnlineRentFailed(time) |
+            Self::HeartbeatOffline(time) => Some(*time),
+            _ => None,
+        }
+    }
+}
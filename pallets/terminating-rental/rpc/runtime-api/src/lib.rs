@@ -10,7 +10,7 @@ use sp_std::prelude::Vec;
 use dbc_support::{
     live_machine::LiveMachine,
     machine_info::MachineInfo,
-    rental_type::{MachineGPUOrder, RentOrderDetail},
+    rental_type::{MachineGPUOrder, RentEscrowDetail, RentOrderDetail},
     verify_online::{OCCommitteeMachineList, OCMachineCommitteeList},
     MachineId, RentOrderId,
 };
@@ -19,6 +19,8 @@ use terminating_rental::rpc_types::{RpcIRCommitteeOps, StakerInfo};
 // Here we declare the runtime API. It is implemented it the `impl` block in
 // runtime amalgamator file (the `runtime/src/lib.rs`)
 sp_api::decl_runtime_apis! {
+    // Version 2 adds `get_order_escrow`
+    #[api_version(2)]
     pub trait IrRpcApi<AccountId, Balance, BlockNumber> where
         AccountId: Codec + Ord,
         Balance: Codec + MaybeDisplay,
@@ -38,5 +40,7 @@ sp_api::decl_runtime_apis! {
         fn get_rent_list(renter: AccountId) -> Vec<RentOrderId>;
         fn is_machine_renter(machine_id: MachineId, renter: AccountId) -> bool;
         fn get_machine_rent_id(machine_id: MachineId) -> MachineGPUOrder;
+        #[api_version(2)]
+        fn get_order_escrow(rent_id: RentOrderId) -> Option<RentEscrowDetail<AccountId, BlockNumber, Balance>>;
     }
 }
@@ -6,13 +6,13 @@ use jsonrpsee::{
     types::error::{CallError, ErrorCode, ErrorObject},
 };
 use parity_scale_codec::Codec;
-use sp_api::ProvideRuntimeApi;
+use sp_api::{ApiExt, ProvideRuntimeApi};
 use sp_blockchain::HeaderBackend;
 use sp_runtime::traits::{Block as BlockT, MaybeDisplay};
 use std::{fmt::Display, str::FromStr, sync::Arc};
 
 use dbc_support::{
-    rental_type::{MachineGPUOrder, RentOrderDetail},
+    rental_type::{MachineGPUOrder, RentEscrowDetail, RentOrderDetail},
     rpc_types::RpcBalance,
     verify_online::OCMachineCommitteeList,
     RentOrderId,
@@ -100,6 +100,13 @@ where
         machine_id: String,
         at: Option<BlockHash>,
     ) -> RpcResult<MachineGPUOrder>;
+
+    #[method(name = "terminatingRental_getOrderEscrow")]
+    fn get_order_escrow(
+        &self,
+        rent_id: RentOrderId,
+        at: Option<BlockHash>,
+    ) -> RpcResult<RentEscrowDetail<AccountId, BlockNumber, RpcBalance<Balance>>>;
 }
 
 pub struct IrStorage<C, M> {
@@ -379,4 +386,59 @@ where
         })?;
         Ok(runtime_api_result)
     }
+
+    fn get_order_escrow(
+        &self,
+        rent_id: RentOrderId,
+        at: Option<Block::Hash>,
+    ) -> RpcResult<RentEscrowDetail<AccountId, BlockNumber, RpcBalance<Balance>>> {
+        let api = self.client.runtime_api();
+        let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+
+        // get_order_escrow is only available since runtime api version 2
+        ensure_api_version::<_, Block, AccountId, BlockNumber, Balance>(&api, at_hash)?;
+
+        let runtime_api_result = api.get_order_escrow(at_hash, rent_id);
+        if let Ok(Some(escrow)) = runtime_api_result {
+            return Ok(RentEscrowDetail {
+                renter: escrow.renter,
+                total_amount: escrow.total_amount.into(),
+                released_amount: escrow.released_amount.into(),
+                refunded_amount: escrow.refunded_amount.into(),
+                last_settled: escrow.last_settled,
+                rent_end: escrow.rent_end,
+            })
+        }
+        return Err(JsonRpseeError::Call(CallError::Custom(ErrorObject::owned(
+            ErrorCode::InternalError.code(),
+            "Something wrong",
+            Some("NotFound"),
+        ))))
+    }
+}
+
+fn ensure_api_version<Api, Block, AccountId, BlockNumber, Balance>(
+    api: &Api,
+    at_hash: <Block as BlockT>::Hash,
+) -> RpcResult<()>
+where
+    Block: BlockT,
+    AccountId: Codec + Ord,
+    BlockNumber: Codec + MaybeDisplay,
+    Balance: Codec + MaybeDisplay,
+    Api: ApiExt<Block> + IrStorageRuntimeApi<Block, AccountId, Balance, BlockNumber>,
+{
+    let api_version = api
+        .api_version::<dyn IrStorageRuntimeApi<Block, AccountId, Balance, BlockNumber>>(at_hash)
+        .ok()
+        .flatten()
+        .unwrap_or_default();
+    if api_version < 2 {
+        return Err(JsonRpseeError::Call(CallError::Custom(ErrorObject::owned(
+            ErrorCode::MethodNotFound.code(),
+            "Not supported by the runtime at this block",
+            None::<String>,
+        ))))
+    }
+    Ok(())
 }
@@ -0,0 +1,135 @@
+use crate::{
+    AssetIdOf, BalanceOf, Config, DlcBurnDetails, Error, Event, Pallet, RentOrderAsset,
+    TotalBurnedDlc,
+};
+use dbc_support::{
+    rental_type::{DlcBurnDetail, RentOrderDetail},
+    traits::DlcPrice,
+    MachineId, RentOrderId,
+};
+use frame_support::{
+    dispatch::DispatchResult,
+    ensure,
+    traits::{
+        fungibles::Mutate,
+        tokens::{Fortitude, Precision, Preservation},
+        Get,
+    },
+};
+use sp_runtime::traits::{AccountIdConversion, Saturating, Zero};
+
+// 使用资产(DLC)支付租金：租用时租金转入托管账户，租用结束时按实际租用时长结算，
+// 其中rent_fee_destroy_percent的部分直接销毁并记录，未使用的部分退还给租用人
+impl<T: Config> Pallet<T> {
+    // 托管资产租金的账户
+    pub fn rent_fee_account() -> T::AccountId {
+        T::PalletId::get().into_account_truncating()
+    }
+
+    // 按DLC价格计算租金，并转入托管账户
+    pub(crate) fn lock_asset_rent_fee(
+        renter: &T::AccountId,
+        rent_id: RentOrderId,
+        asset_id: AssetIdOf<T>,
+        rent_fee_value: u64,
+    ) -> Result<BalanceOf<T>, Error<T>> {
+        let rent_fee = Self::add_asset_rent_fee(renter, asset_id, rent_fee_value)?;
+        RentOrderAsset::<T>::insert(rent_id, asset_id);
+        Ok(rent_fee)
+    }
+
+    // 续租时，按DLC价格计算续租的租金，并转入托管账户
+    pub(crate) fn add_asset_rent_fee(
+        renter: &T::AccountId,
+        asset_id: AssetIdOf<T>,
+        rent_fee_value: u64,
+    ) -> Result<BalanceOf<T>, Error<T>> {
+        ensure!(asset_id == T::DlcAssetId::get(), Error::<T>::AssetNotSupported);
+
+        let rent_fee = <T as Config>::DlcPrice::get_dlc_amount_by_value(rent_fee_value)
+            .ok_or(Error::<T>::GetMachinePriceFailed)?;
+        T::Assets::transfer(
+            asset_id,
+            renter,
+            &Self::rent_fee_account(),
+            rent_fee,
+            Preservation::Preserve,
+        )
+        .map_err(|_| Error::<T>::InsufficientValue)?;
+        Ok(rent_fee)
+    }
+
+    // 租用结束或被终止时结算：rent_fee中销毁部分直接销毁，其余支付给租金接收方，
+    // 托管的剩余部分退还给租用人
+    pub(crate) fn pay_asset_rent_fee(
+        rent_id: RentOrderId,
+        rent_order: &RentOrderDetail<T::AccountId, T::BlockNumber, BalanceOf<T>>,
+        asset_id: AssetIdOf<T>,
+        rent_fee: BalanceOf<T>,
+        machine_id: MachineId,
+    ) -> DispatchResult {
+        let machine_info = Self::machines_info(&machine_id).ok_or(Error::<T>::Unknown)?;
+        let rent_fee_account = Self::rent_fee_account();
+
+        let rent_fee = rent_fee.min(rent_order.stake_amount);
+        let fee_to_burn = Self::rent_fee_destroy_percent() * rent_fee;
+        let fee_to_stash = rent_fee.saturating_sub(fee_to_burn);
+        let refund = rent_order.stake_amount.saturating_sub(rent_fee);
+
+        if !fee_to_burn.is_zero() {
+            T::Assets::burn_from(
+                asset_id,
+                &rent_fee_account,
+                fee_to_burn,
+                Precision::Exact,
+                Fortitude::Polite,
+            )
+            .map_err(|_| Error::<T>::AssetTransferFailed)?;
+
+            DlcBurnDetails::<T>::mutate(rent_id, |burn_details| {
+                burn_details.push(DlcBurnDetail {
+                    rent_id,
+                    renter: rent_order.renter.clone(),
+                    burned_amount: fee_to_burn,
+                    at: <frame_system::Pallet<T>>::block_number(),
+                })
+            });
+            TotalBurnedDlc::<T>::mutate(|total_burned| {
+                *total_burned = total_burned.saturating_add(fee_to_burn)
+            });
+            Self::deposit_event(Event::BurnAssetRentFee(
+                rent_id,
+                rent_order.renter.clone(),
+                asset_id,
+                fee_to_burn,
+            ));
+        }
+
+        if !fee_to_stash.is_zero() {
+            let rent_receiver = Self::stash_rent_receiver(&machine_info.machine_stash)
+                .unwrap_or_else(|| machine_info.machine_stash.clone());
+            T::Assets::transfer(
+                asset_id,
+                &rent_fee_account,
+                &rent_receiver,
+                fee_to_stash,
+                Preservation::Expendable,
+            )
+            .map_err(|_| Error::<T>::AssetTransferFailed)?;
+        }
+
+        if !refund.is_zero() {
+            T::Assets::transfer(
+                asset_id,
+                &rent_fee_account,
+                &rent_order.renter,
+                refund,
+                Preservation::Expendable,
+            )
+            .map_err(|_| Error::<T>::AssetTransferFailed)?;
+        }
+
+        RentOrderAsset::<T>::remove(rent_id);
+        Ok(())
+    }
+}
@@ -7,6 +7,8 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
+mod asset_rent;
+mod escrow;
 pub mod migrations;
 mod online_verify_slash;
 mod report_machine_fault;
@@ -16,22 +18,23 @@ mod types;
 use frame_support::log;
 
 use dbc_support::{
+    committee_task::{CommitteeTaskId, CommitteeTaskKind},
     live_machine::LiveMachine,
     machine_info::MachineInfo,
     machine_type::{CommitteeUploadInfo, MachineStatus, StakerCustomizeInfo},
-    rental_type::{MachineGPUOrder, RentOrderDetail, RentStatus},
+    rental_type::{DlcBurnDetail, MachineGPUOrder, RentEscrowDetail, RentOrderDetail, RentStatus},
     report::{
         MTCommitteeOpsDetail, MTCommitteeOrderList, MTLiveReportList, MTOrderStatus,
         MTReportInfoDetail, MTReportResultInfo, MachineFaultType, ReportStatus, ReporterReportList,
         ReporterStakeInfo, ReporterStakeParamsInfo,
     },
-    traits::{DbcPrice, GNOps, ManageCommittee},
+    traits::{DbcPrice, DlcPrice, GNOps, ManageCommittee, SurgePricing},
     utils::{get_hash, OnlineCommitteeSummary},
     verify_committee_slash::{OCPendingSlashInfo as PendingOnlineSlashInfo, OCSlashResult},
     verify_online::{
         OCCommitteeMachineList, OCCommitteeOps as IRCommitteeOnlineOps, OCMachineCommitteeList,
-        OCMachineStatus as VerifyMachineStatus, OCVerifyStatus, StashMachine, Summary,
-        VerifyResult, VerifySequence,
+        OCMachineStatus as VerifyMachineStatus, OCVerifyStatus, SelectionProof, StashMachine,
+        Summary, VerifyResult, VerifySequence,
     },
     BoxPubkey, EraIndex, ItemList, MachineId, RentOrderId, ReportHash, ReportId, SlashId,
     HALF_HOUR, ONE_DAY, ONE_HOUR, ONE_MINUTE, TWO_DAYS,
@@ -39,7 +42,11 @@ use dbc_support::{
 use frame_support::{
     dispatch::{DispatchResult, DispatchResultWithPostInfo},
     pallet_prelude::*,
-    traits::{Currency, ExistenceRequirement::KeepAlive, OnUnbalanced, ReservableCurrency},
+    traits::{
+        fungibles, Currency, ExistenceRequirement, ExistenceRequirement::KeepAlive, OnUnbalanced,
+        ReservableCurrency,
+    },
+    PalletId,
 };
 use parity_scale_codec::alloc::string::ToString;
 use sp_runtime::{
@@ -64,10 +71,15 @@ type BalanceOf<T> =
 type NegativeImbalanceOf<T> = <<T as Config>::Currency as Currency<
     <T as frame_system::Config>::AccountId,
 >>::NegativeImbalance;
+type AssetIdOf<T> =
+    <<T as Config>::Assets as fungibles::Inspect<<T as frame_system::Config>::AccountId>>::AssetId;
 
 pub use pallet::*;
 pub use types::*;
 
+const STORAGE_VERSION: frame_support::traits::StorageVersion =
+    frame_support::traits::StorageVersion::new(2);
+
 #[frame_support::pallet]
 pub mod pallet {
     use super::*;
@@ -87,9 +99,21 @@ pub mod pallet {
         >;
         type DbcPrice: DbcPrice<Balance = BalanceOf<Self>>;
         type SlashAndReward: GNOps<AccountId = Self::AccountId, Balance = BalanceOf<Self>>;
+        type DlcPrice: DlcPrice<Balance = BalanceOf<Self>>;
+        /// 根据GPU使用率调整系统定价
+        type SurgePricing: SurgePricing;
+        /// 用于支付租金的资产(pallet-assets)
+        type Assets: fungibles::Mutate<Self::AccountId, Balance = BalanceOf<Self>>;
+        /// 可以支付租金的DLC资产ID
+        #[pallet::constant]
+        type DlcAssetId: Get<AssetIdOf<Self>>;
+        /// 托管资产租金的账户
+        #[pallet::constant]
+        type PalletId: Get<PalletId>;
     }
 
     #[pallet::pallet]
+    #[pallet::storage_version(STORAGE_VERSION)]
     #[pallet::without_storage_info]
     pub struct Pallet<T>(_);
 
@@ -156,6 +180,17 @@ pub mod pallet {
     pub type MachineRentedGPU<T: Config> =
         StorageMap<_, Blake2_128Concat, MachineId, u32, ValueQuery>;
 
+    /// 机器最近一天内已结束的下线时段 (下线时间, 重新上线时间)，用于按实际下线时间结算托管的租金
+    #[pallet::storage]
+    #[pallet::getter(fn machine_offline_records)]
+    pub type MachineOfflineRecords<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        MachineId,
+        Vec<(T::BlockNumber, T::BlockNumber)>,
+        ValueQuery,
+    >;
+
     // 存储用户订阅的不同确认阶段的机器
     #[pallet::storage]
     #[pallet::getter(fn committee_machine)]
@@ -172,6 +207,12 @@ pub mod pallet {
         ValueQuery,
     >;
 
+    /// 机器最近一次分派委员会的随机抽取证明
+    #[pallet::storage]
+    #[pallet::getter(fn machine_selection_proof)]
+    pub type MachineSelectionProof<T: Config> =
+        StorageMap<_, Blake2_128Concat, MachineId, SelectionProof<T::AccountId, T::BlockNumber>>;
+
     #[pallet::storage]
     #[pallet::getter(fn machine_submited_hash)]
     pub(super) type MachineSubmitedHash<T> =
@@ -257,6 +298,49 @@ pub mod pallet {
     pub(super) type StashRentReceiver<T: Config> =
         StorageMap<_, Blake2_128Concat, T::AccountId, T::AccountId>;
 
+    /// 是否开启流式支付：开启后租金不再在租用结束时一次性支付，
+    /// 而是按机器正常出租的时长逐小时释放给矿工
+    #[pallet::storage]
+    #[pallet::getter(fn rent_fee_streaming)]
+    pub(super) type RentFeeStreaming<T: Config> = StorageValue<_, bool, ValueQuery>;
+
+    /// 流式支付订单的租金托管情况
+    #[pallet::storage]
+    #[pallet::getter(fn order_escrow)]
+    pub type OrderEscrow<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        RentOrderId,
+        RentEscrowDetail<T::AccountId, T::BlockNumber, BalanceOf<T>>,
+    >;
+
+    /// 托管订单的下一次结算时间：块高 -> 在该块结算的订单
+    #[pallet::storage]
+    #[pallet::getter(fn escrow_settling)]
+    pub type EscrowSettling<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::BlockNumber, Vec<RentOrderId>, ValueQuery>;
+
+    /// 使用资产(DLC)支付租金的订单，订单的stake_amount为转入托管账户的资产数量
+    #[pallet::storage]
+    #[pallet::getter(fn rent_order_asset)]
+    pub type RentOrderAsset<T: Config> = StorageMap<_, Blake2_128Concat, RentOrderId, AssetIdOf<T>>;
+
+    /// 资产支付的租金中被销毁部分的记录
+    #[pallet::storage]
+    #[pallet::getter(fn dlc_burn_details)]
+    pub type DlcBurnDetails<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        RentOrderId,
+        Vec<DlcBurnDetail<T::AccountId, BalanceOf<T>, T::BlockNumber>>,
+        ValueQuery,
+    >;
+
+    /// 资产支付的租金中累计销毁的数量
+    #[pallet::storage]
+    #[pallet::getter(fn total_burned_dlc)]
+    pub type TotalBurnedDlc<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
+
     #[pallet::type_value]
     pub(super) fn MaximumRentalDurationDefault<T: Config>() -> EraIndex {
         60
@@ -313,6 +397,17 @@ pub mod pallet {
     #[pallet::getter(fn unhandled_online_slash)]
     pub(super) type UnhandledOnlineSlash<T: Config> = StorageValue<_, Vec<SlashId>, ValueQuery>;
 
+    /// 委员会任务(审核上线及故障报告)的截止提醒：块高 -> 在该块需要提醒的委员会及任务
+    #[pallet::storage]
+    #[pallet::getter(fn deadline_reminder)]
+    pub(super) type DeadlineReminder<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::BlockNumber,
+        Vec<(T::AccountId, CommitteeTaskId)>,
+        ValueQuery,
+    >;
+
     /// 系统中还未完成的举报订单
     #[pallet::storage]
     #[pallet::getter(fn live_report)]
@@ -368,6 +463,19 @@ pub mod pallet {
         ValueQuery,
     >;
 
+    /// 报告人加密故障信息时使用的委员会box_pubkey版本
+    #[pallet::storage]
+    #[pallet::getter(fn encrypted_info_key_version)]
+    pub(super) type EncryptedInfoKeyVersion<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Blake2_128Concat,
+        ReportId,
+        u32,
+        ValueQuery,
+    >;
+
     #[pallet::storage]
     #[pallet::getter(fn report_result)]
     pub(super) type ReportResult<T: Config> = StorageMap<
@@ -389,25 +497,31 @@ pub mod pallet {
 
     #[pallet::hooks]
     impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
-        fn on_initialize(_n: BlockNumberFor<T>) -> frame_support::weights::Weight {
+        fn on_initialize(n: BlockNumberFor<T>) -> frame_support::weights::Weight {
             Self::check_and_exec_pending_slash();
 
             Self::summary_fault_report_hook();
-            Weight::zero()
+            Self::escrow_settling_weight(n).saturating_add(Self::deadline_reminder_weight(n))
         }
 
-        fn on_finalize(_block_number: T::BlockNumber) {
+        fn on_finalize(block_number: T::BlockNumber) {
             Self::statistic_online_verify();
             Self::distribute_machines();
+            Self::remind_task_deadline(block_number);
 
             // Self::check_machine_starting_status();
             let _ = Self::check_if_rent_finished();
+            Self::settle_escrow_at(block_number);
             // 检查OfflineMachines是否到达了10天
             let _ = Self::check_if_offline_timeout();
 
             let _ = Self::exec_report_slash();
         }
 
+        fn on_runtime_upgrade() -> frame_support::weights::Weight {
+            migrations::v2::migrate::<T>()
+        }
+
         // fn on_runtime_upgrade() -> frame_support::weights::Weight {
         //     frame_support::log::info!("🔍 TerminatingRental Storage Migration start");
         //     migrations::migrate::<T>();
@@ -665,104 +779,7 @@ pub mod pallet {
             duration: T::BlockNumber,
         ) -> DispatchResultWithPostInfo {
             let renter = ensure_signed(origin)?;
-            let now = <frame_system::Pallet<T>>::block_number();
-            let machine_info = Self::machines_info(&machine_id).ok_or(Error::<T>::Unknown)?;
-            let machine_rented_gpu = Self::machine_rented_gpu(&machine_id);
-            let gpu_num = machine_info.gpu_num();
-
-            if gpu_num == 0 || duration == Zero::zero() {
-                return Ok(().into())
-            }
-
-            // 检查还有空闲的GPU
-            ensure!(rent_gpu_num + machine_rented_gpu <= gpu_num, Error::<T>::GPUNotEnough);
-            // 只允许半小时整数倍的租用
-            ensure!(
-                duration % HALF_HOUR.into() == Zero::zero(),
-                Error::<T>::OnlyAllowIntegerMultipleOfHour
-            );
-
-            // 检查machine_id状态是否可以租用
-            ensure!(machine_info.can_rent(), Error::<T>::MachineNotRentable);
-
-            // 最大租用时间限制MaximumRentalDuration
-            let duration =
-                duration.min((Self::maximum_rental_duration().saturating_mul(ONE_DAY)).into());
-
-            // NOTE: 用户提交订单，需要扣除10个DBC
-            Self::pay_fixed_tx_fee(renter.clone())?;
-
-            // 获得machine_price(每天的价格) = 系统自动定价 + 卡主额外加价
-            // 根据租用GPU数量计算价格
-            let system_price =
-                Self::get_machine_price(machine_info.calc_point(), rent_gpu_num, gpu_num)
-                    .ok_or(Error::<T>::GetMachinePriceFailed)?;
-            let extra_price = Self::machine_extra_price(&machine_id)
-                .checked_mul(rent_gpu_num as u64).ok_or(Error::<T>::Overflow)?;
-            let machine_price = system_price.checked_add(extra_price).ok_or(Error::<T>::Overflow)?;
-
-            // 根据租用时长计算rent_fee
-            let rent_fee_value = machine_price
-                .checked_mul(duration.saturated_into::<u64>())
-                .ok_or(Error::<T>::Overflow)?
-                .checked_div(ONE_DAY.into())
-                .ok_or(Error::<T>::Overflow)?;
-            let rent_fee = <T as Config>::DbcPrice::get_dbc_amount_by_value(rent_fee_value)
-                .ok_or(Error::<T>::Overflow)?;
-
-            // 获取用户租用的结束时间(块高)
-            let rent_end = now.checked_add(&duration).ok_or(Error::<T>::Overflow)?;
-
-            // 质押用户的资金，并修改机器状态
-            Self::change_renter_total_stake(&renter, rent_fee, true)
-                .map_err(|_| Error::<T>::InsufficientValue)?;
-
-            let rent_id = Self::get_new_rent_id();
-
-            let mut machine_rent_order = Self::machine_rent_order(&machine_id);
-            let rentable_gpu_index = machine_rent_order.gen_rentable_gpu(rent_gpu_num, gpu_num);
-            ItemList::add_item(&mut machine_rent_order.rent_order, rent_id);
-            MachineRentOrder::<T>::insert(&machine_id, machine_rent_order);
-
-            RentOrder::<T>::insert(
-                &rent_id,
-                RentOrderDetail::new(
-                    machine_id.clone(),
-                    renter.clone(),
-                    now,
-                    rent_end,
-                    rent_fee,
-                    rent_gpu_num,
-                    rentable_gpu_index,
-                ),
-            );
-
-            // 改变online_profile状态，影响机器佣金
-            Self::change_machine_status_on_rent_start(&machine_id, rent_gpu_num)
-                .map_err(|_| Error::<T>::Unknown)?;
-
-            UserRented::<T>::mutate(&renter, |user_rented| {
-                ItemList::add_item(user_rented, rent_id);
-            });
-            PendingRentEnding::<T>::mutate(rent_end, |pending_rent_ending| {
-                ItemList::add_item(pending_rent_ending, rent_id);
-            });
-            PendingConfirming::<T>::mutate(
-                now + WAITING_CONFIRMING_DELAY.into(),
-                |pending_confirming| {
-                    ItemList::add_item(pending_confirming, rent_id);
-                },
-            );
-
-            Self::deposit_event(Event::RentBlockNum(
-                rent_id,
-                renter,
-                machine_id,
-                rent_fee,
-                duration.into(),
-                gpu_num,
-            ));
-            Ok(().into())
+            Self::rent_machine_by_block(renter, machine_id, rent_gpu_num, duration, None)
         }
 
         /// 用户在租用15min内确认机器租用成功
@@ -803,6 +820,19 @@ pub mod pallet {
 
             order_info.confirm_rent(now);
 
+            // 资产支付的订单在租用结束时结算，不参与流式支付
+            if Self::rent_fee_streaming() && !RentOrderAsset::<T>::contains_key(rent_id) {
+                // 流式支付：锁定的租金转入托管账户，按小时结算
+                Self::create_escrow(
+                    rent_id,
+                    renter.clone(),
+                    order_info.stake_amount,
+                    now,
+                    order_info.rent_end,
+                )?;
+                order_info.stake_amount = Zero::zero();
+            }
+
             // 改变online_profile状态
             Self::change_machine_status_on_confirmed(&machine_id, renter.clone())
                 .map_err(|_| Error::<T>::Unknown)?;
@@ -863,9 +893,13 @@ pub mod pallet {
             }
 
             // 计算rent_fee = 系统自动定价 + 卡主额外加价
-            let system_price =
-                Self::get_machine_price(calc_point, gpu_num, machine_info.gpu_num())
-                    .ok_or(Error::<T>::GetMachinePriceFailed)?;
+            let system_price = Self::get_machine_price(
+                &machine_info.gpu_type_and_mem().0,
+                calc_point,
+                gpu_num,
+                machine_info.gpu_num(),
+            )
+            .ok_or(Error::<T>::GetMachinePriceFailed)?;
             let extra_price = Self::machine_extra_price(&machine_id)
                 .checked_mul(gpu_num as u64).ok_or(Error::<T>::Overflow)?;
             let machine_price = system_price.checked_add(extra_price).ok_or(Error::<T>::Overflow)?;
@@ -874,22 +908,49 @@ pub mod pallet {
                 .ok_or(Error::<T>::Overflow)?
                 .checked_div(ONE_DAY.into())
                 .ok_or(Error::<T>::Overflow)?;
-            let rent_fee = <T as Config>::DbcPrice::get_dbc_amount_by_value(rent_fee_value)
-                .ok_or(Error::<T>::Overflow)?;
-
-            // 检查用户是否有足够的资金，来租用机器
-            let user_balance = <T as Config>::Currency::free_balance(&renter);
-            ensure!(rent_fee < user_balance, Error::<T>::InsufficientValue);
-
-            // 质押用户的资金，并修改机器状态
-            Self::change_renter_total_stake(&renter, rent_fee, true)
-                .map_err(|_| Error::<T>::InsufficientValue)?;
+            let is_escrow = OrderEscrow::<T>::contains_key(rent_id);
+            let rent_fee = match Self::rent_order_asset(rent_id) {
+                // 资产支付的订单，续租的租金同样使用该资产支付
+                Some(asset_id) => Self::add_asset_rent_fee(&renter, asset_id, rent_fee_value)?,
+                None => {
+                    let rent_fee = <T as Config>::DbcPrice::get_dbc_amount_by_value(rent_fee_value)
+                        .ok_or(Error::<T>::Overflow)?;
+
+                    // 检查用户是否有足够的资金，来租用机器
+                    let user_balance = <T as Config>::Currency::free_balance(&renter);
+                    ensure!(rent_fee < user_balance, Error::<T>::InsufficientValue);
+
+                    if is_escrow {
+                        // 流式支付的订单，续租的租金转入托管账户
+                        <T as Config>::Currency::transfer(
+                            &renter,
+                            &Self::escrow_account(),
+                            rent_fee,
+                            KeepAlive,
+                        )
+                        .map_err(|_| Error::<T>::InsufficientValue)?;
+                    } else {
+                        // 质押用户的资金，并修改机器状态
+                        Self::change_renter_total_stake(&renter, rent_fee, true)
+                            .map_err(|_| Error::<T>::InsufficientValue)?;
+                    }
+                    rent_fee
+                },
+            };
 
             // 获取用户租用的结束时间
             order_info.rent_end =
                 order_info.rent_end.checked_add(&add_duration).ok_or(Error::<T>::Overflow)?;
-            order_info.stake_amount =
-                order_info.stake_amount.checked_add(&rent_fee).ok_or(Error::<T>::Overflow)?;
+            if is_escrow {
+                OrderEscrow::<T>::mutate(rent_id, |escrow| {
+                    if let Some(escrow) = escrow.as_mut() {
+                        escrow.add_amount(rent_fee, order_info.rent_end);
+                    }
+                });
+            } else {
+                order_info.stake_amount =
+                    order_info.stake_amount.checked_add(&rent_fee).ok_or(Error::<T>::Overflow)?;
+            }
 
             PendingRentEnding::<T>::mutate(pre_rent_end, |pre_pending_rent_ending| {
                 ItemList::rm_item(pre_pending_rent_ending, &rent_id);
@@ -949,7 +1010,12 @@ pub mod pallet {
             MachineRentOrder::<T>::insert(&rent_order.machine_id, machine_rent_order);
 
             // NOTE: Here will change machine_info.
-            Self::pay_rent_fee(&rent_order, rent_fee, rent_order.machine_id.clone())?;
+            if OrderEscrow::<T>::contains_key(rent_id) {
+                // 流式支付的订单，结算到当前时间后退还剩余的托管租金
+                Self::close_escrow(rent_id, now);
+            } else {
+                Self::pay_rent_fee(rent_id, &rent_order, rent_fee, rent_order.machine_id.clone())?;
+            }
             RentOrder::<T>::remove(rent_id);
 
             Ok(().into())
@@ -992,7 +1058,11 @@ pub mod pallet {
                 ) * rent_order.stake_amount;
 
                 // NOTE: Here will change machine_info again.
-                Self::pay_rent_fee(&rent_order, rent_fee, machine_id.clone())?;
+                if OrderEscrow::<T>::contains_key(rent_id) {
+                    Self::close_escrow(rent_id, now);
+                } else {
+                    Self::pay_rent_fee(rent_id, &rent_order, rent_fee, machine_id.clone())?;
+                }
 
                 RentOrder::<T>::remove(rent_id);
             }
@@ -1030,8 +1100,11 @@ pub mod pallet {
                     OfflineMachines::<T>::insert(offline_expire_time, offline_machines);
                 }
 
+                // StakerReportOffline 中保存的是机器下线的时间
+                let now = <frame_system::Pallet<T>>::block_number();
+                Self::record_offline_interval(&machine_id, offline_expire_time, now);
                 machine_info.machine_status = MachineStatus::Online;
-                machine_info.last_online_height = <frame_system::Pallet<T>>::block_number();
+                machine_info.last_online_height = now;
                 MachinesInfo::<T>::insert(machine_id, machine_info);
                 Ok(().into())
             } else {
@@ -1072,7 +1145,11 @@ pub mod pallet {
                     rent_order.rent_end.saturating_sub(rent_order.rent_start),
                 ) * rent_order.stake_amount;
 
-                Self::pay_rent_fee(&rent_order, rent_fee, machine_id.clone())?;
+                if OrderEscrow::<T>::contains_key(rent_id) {
+                    Self::close_escrow(rent_id, now);
+                } else {
+                    Self::pay_rent_fee(rent_id, &rent_order, rent_fee, machine_id.clone())?;
+                }
 
                 RentOrder::<T>::remove(rent_id);
             }
@@ -1229,30 +1306,15 @@ pub mod pallet {
             encrypted_err_info: Vec<u8>,
         ) -> DispatchResultWithPostInfo {
             let reporter = ensure_signed(origin)?;
-            let now = <frame_system::Pallet<T>>::block_number();
-
-            let mut report_info = Self::report_info(&report_id).ok_or(Error::<T>::Unknown)?;
-            let mut committee_ops = Self::committee_report_ops(&to_committee, &report_id);
-
-            // 检查报告可以提供加密信息
-            // 该orde处于验证中, 且还没有提交过加密信息
-            report_info
-                .can_submit_encrypted_info(&reporter, &to_committee)
-                .map_err::<Error<T>, _>(Into::into)?;
-            ensure!(
-                committee_ops.order_status == MTOrderStatus::WaitingEncrypt,
-                Error::<T>::OrderStatusNotFeat
-            );
-
-            // report_info中插入已经收到了加密信息的委员会
-            ItemList::add_item(&mut report_info.get_encrypted_info_committee, to_committee.clone());
-            ReportInfo::<T>::insert(&report_id, report_info);
-
-            committee_ops.add_encry_info(encrypted_err_info, now);
-            CommitteeReportOps::<T>::insert(&to_committee, &report_id, committee_ops);
-
-            Self::deposit_event(Event::EncryptedInfoSent(reporter, to_committee, report_id));
-            Ok(().into())
+            // 使用委员会当前的公钥加密
+            let key_version = <T as Config>::ManageCommittee::box_key_version(&to_committee);
+            Self::do_add_encrypted_error_info(
+                reporter,
+                report_id,
+                to_committee,
+                key_version,
+                encrypted_err_info,
+            )
         }
 
         // 委员会提交验证之后的Hash
@@ -1288,6 +1350,7 @@ pub mod pallet {
                     report_id,
                     report_info.machine_fault_type.clone(),
                     report_info.hashed_committee.len(),
+                    report_info.book_limit(),
                 )
             });
             ReportInfo::<T>::insert(&report_id, report_info);
@@ -1424,6 +1487,52 @@ pub mod pallet {
             Self::deposit_event(Event::RentReceiverChanged(stash, receiver));
             Ok(().into())
         }
+
+        /// 设置是否开启流式支付，只影响之后确认的订单
+        #[pallet::call_index(28)]
+        #[pallet::weight(frame_support::weights::Weight::from_parts(10000, 0))]
+        pub fn set_rent_fee_streaming(
+            origin: OriginFor<T>,
+            enable: bool,
+        ) -> DispatchResultWithPostInfo {
+            ensure_root(origin)?;
+            RentFeeStreaming::<T>::put(enable);
+            Ok(().into())
+        }
+
+        /// 用户使用资产(DLC)支付租金租用机器(按分钟租用)
+        #[pallet::call_index(29)]
+        #[pallet::weight(frame_support::weights::Weight::from_parts(10000, 0))]
+        pub fn rent_machine_with_asset(
+            origin: OriginFor<T>,
+            machine_id: MachineId,
+            rent_gpu_num: u32,
+            duration: T::BlockNumber,
+            asset_id: AssetIdOf<T>,
+        ) -> DispatchResultWithPostInfo {
+            let renter = ensure_signed(origin)?;
+            Self::rent_machine_by_block(renter, machine_id, rent_gpu_num, duration, Some(asset_id))
+        }
+
+        // 报告人使用委员会指定版本的公钥(当前公钥或宽限期内的旧公钥)，提交加密后的故障信息
+        #[pallet::call_index(30)]
+        #[pallet::weight(frame_support::weights::Weight::from_parts(10000, 0))]
+        pub fn reporter_add_versioned_encrypted_error_info(
+            origin: OriginFor<T>,
+            report_id: ReportId,
+            to_committee: T::AccountId,
+            key_version: u32,
+            encrypted_err_info: Vec<u8>,
+        ) -> DispatchResultWithPostInfo {
+            let reporter = ensure_signed(origin)?;
+            Self::do_add_encrypted_error_info(
+                reporter,
+                report_id,
+                to_committee,
+                key_version,
+                encrypted_err_info,
+            )
+        }
     }
 
     #[pallet::event]
@@ -1464,6 +1573,15 @@ pub mod pallet {
         // S2 修复：on_finalize 租金结算彻底失败（即便回退到 stash 也失败）
         // 不再静默吞错，事件上链方便监控。(rent_id,)
         RentFeePayoutFailed(RentOrderId),
+        // rent_id, released balance
+        EscrowReleased(RentOrderId, BalanceOf<T>),
+        // rent_id, renter, refunded balance
+        EscrowRefunded(RentOrderId, T::AccountId, BalanceOf<T>),
+        // rent_id, renter, asset_id, locked asset amount
+        RentWithAsset(RentOrderId, T::AccountId, AssetIdOf<T>, BalanceOf<T>),
+        // rent_id, renter, asset_id, burned amount
+        BurnAssetRentFee(RentOrderId, T::AccountId, AssetIdOf<T>, BalanceOf<T>),
+        TaskDeadlineApproaching(T::AccountId, CommitteeTaskKind, CommitteeTaskId, T::BlockNumber),
     }
 
     #[pallet::error]
@@ -1532,6 +1650,11 @@ pub mod pallet {
         UndefinedRentPot,
         /// spec 410: receiver 地址非法（如全零）
         InvalidRentReceiver,
+        /// 该资产不能用于支付租金
+        AssetNotSupported,
+        AssetTransferFailed,
+        /// 加密信息使用的委员会公钥版本已失效
+        InvalidBoxKeyVersion,
     }
 }
 
@@ -1638,7 +1761,8 @@ impl<T: Config> Pallet<T> {
                 MachineCommittee::<T>::remove(&machine_id);
             }
 
-            if let Some(committee_work_index) = Self::get_work_index() {
+            if let Some((committee_work_index, proof)) = Self::get_work_index(&machine_id) {
+                MachineSelectionProof::<T>::insert(&machine_id, proof);
                 for work_index in committee_work_index {
                     if let Err(e) =
                         Self::book_one(machine_id.to_vec(), confirm_start, now, work_index)
@@ -1654,25 +1778,40 @@ impl<T: Config> Pallet<T> {
         }
     }
 
-    // 分派一个machineId给随机的委员会
-    // 返回3个随机顺序的账户及其对应的验证顺序
-    pub fn get_work_index() -> Option<Vec<VerifySequence<T::AccountId>>> {
-        let mut committee = <committee::Pallet<T>>::available_committee()?;
-        if committee.len() < 3 {
+    // 分派一个machineId给随机的委员会，质押越多、信誉越好的委员会越容易被选中
+    // 返回随机顺序的账户及其对应的验证顺序，以及可以复现分派过程的证明
+    pub fn get_work_index(
+        machine_id: &MachineId,
+    ) -> Option<(Vec<VerifySequence<T::AccountId>>, SelectionProof<T::AccountId, T::BlockNumber>)>
+    {
+        let committee = <committee::Pallet<T>>::available_committee()?;
+        // 可用委员会不足审核数量时不分派，机器等待之后重新分派
+        let verifier_count = T::ManageCommittee::verifier_count() as usize;
+        if committee.len() < verifier_count {
             return None
-        };
-
-        let mut verify_sequence = Vec::new();
-        for i in 0..3 {
-            let lucky_index =
-                <generic_func::Pallet<T>>::random_u32(committee.len() as u32) as usize;
-            verify_sequence.push(VerifySequence {
-                who: committee[lucky_index].clone(),
-                index: (i..DISTRIBUTION as usize).step_by(3).collect(),
-            });
-            committee.remove(lucky_index);
         }
-        Some(verify_sequence)
+
+        // 按 质押倍数 × 信誉分 加权抽取
+        let weights: Vec<_> = committee.iter().map(T::ManageCommittee::selection_weight).collect();
+        let proof = <generic_func::Pallet<T>>::random_select(
+            machine_id,
+            &committee,
+            &weights,
+            verifier_count,
+        );
+        let verify_sequence = proof
+            .selected
+            .iter()
+            .enumerate()
+            .map(|(i, who)| VerifySequence {
+                who: who.clone(),
+                // 提交原始值的时段轮流分给各委员会
+                index: (0..DISTRIBUTION as usize)
+                    .filter(|slot| slot % verifier_count == i)
+                    .collect(),
+            })
+            .collect();
+        Some((verify_sequence, proof))
     }
 
     // 一个委员会进行操作
@@ -1713,6 +1852,11 @@ impl<T: Config> Pallet<T> {
             committee_ops.machine_status = VerifyMachineStatus::Booked;
         });
 
+        Self::schedule_task_reminders(
+            work_index.who.clone(),
+            CommitteeTaskId::Machine(machine_id.clone()),
+            now,
+        );
         Self::deposit_event(Event::MachineDistributed(machine_id.to_vec(), work_index.who));
         Ok(())
     }
@@ -1742,6 +1886,58 @@ impl<T: Config> Pallet<T> {
         }
     }
 
+    // 分派审核任务或抢单时，按提交Hash及原始信息的时间窗口安排截止提醒
+    fn schedule_task_reminders(
+        committee: T::AccountId,
+        task_id: CommitteeTaskId,
+        now: T::BlockNumber,
+    ) {
+        let reminders = <T as Config>::ManageCommittee::deadline_reminders();
+        if reminders.is_empty() {
+            return
+        }
+        let task = match Self::get_committee_tasks(committee.clone())
+            .into_iter()
+            .find(|task| task.task_id == task_id)
+        {
+            Some(task) => task,
+            None => return,
+        };
+
+        for block in task.reminder_blocks(now, &reminders) {
+            DeadlineReminder::<T>::mutate(block, |reminder| {
+                ItemList::add_item(reminder, (committee.clone(), task_id.clone()));
+            });
+        }
+    }
+
+    fn deadline_reminder_weight(now: T::BlockNumber) -> Weight {
+        let count = DeadlineReminder::<T>::decode_len(now).unwrap_or_default() as u64;
+        // 每个提醒：读取委员会的机器及报告列表，以及任务信息
+        T::DbWeight::get().reads_writes(1 + count * 4, 1)
+    }
+
+    // 委员会提交Hash/原始信息的截止时间临近时(审核上线及故障报告)，发出提醒事件
+    fn remind_task_deadline(now: T::BlockNumber) {
+        for (committee, task_id) in DeadlineReminder::<T>::take(now) {
+            let task = match Self::get_committee_tasks(committee.clone())
+                .into_iter()
+                .find(|task| task.task_id == task_id)
+            {
+                Some(task) => task,
+                None => continue,
+            };
+            if let Some(deadline) = task.reminded_deadline(now) {
+                Self::deposit_event(Event::TaskDeadlineApproaching(
+                    committee,
+                    task.task_kind,
+                    task.task_id,
+                    deadline,
+                ));
+            }
+        }
+    }
+
     // 对已经提交完原始值的机器进行处理
     fn summary_raw(
         machine_id: MachineId,
@@ -1800,6 +1996,17 @@ impl<T: Config> Pallet<T> {
             },
         }
 
+        // 更新委员会信誉：无共识时无法判断委员会提交的对错，只扣除未完成工作的委员会信誉
+        if matches!(summary.verify_result, VerifyResult::NoConsensus) {
+            <T as Config>::ManageCommittee::update_reputation(&[], &[], &summary.unruly);
+        } else {
+            <T as Config>::ManageCommittee::update_reputation(
+                &summary.valid_vote,
+                &summary.invalid_vote,
+                &summary.unruly,
+            );
+        }
+
         // NOTE: 添加惩罚
         if stash_slash.is_some() || summary.should_slash_committee() {
             let (machine_stash, stash_slash_amount) = if let Some(tmp) = stash_slash {
@@ -1963,19 +2170,149 @@ impl<T: Config> Pallet<T> {
     // standard_point / machine_point ==  standard_price / machine_price
     // =>
     // machine_price = standard_price * machine_point / standard_point
-    fn get_machine_price(machine_point: u64, need_gpu: u32, total_gpu: u32) -> Option<u64> {
+    // 再根据该型号GPU的使用率，按动态定价曲线调整
+    fn get_machine_price(
+        gpu_type: &[u8],
+        machine_point: u64,
+        need_gpu: u32,
+        total_gpu: u32,
+    ) -> Option<u64> {
         if total_gpu == 0 {
             return None
         }
         let standard_gpu_point_price = Self::standard_gpu_point_price()?;
-        standard_gpu_point_price
+        let system_price = standard_gpu_point_price
             .gpu_price
             .checked_mul(machine_point)?
             .checked_mul(10_000)?
             .checked_div(standard_gpu_point_price.gpu_point)?
             .checked_mul(need_gpu as u64)?
             .checked_div(total_gpu as u64)?
-            .checked_div(10_000)
+            .checked_div(10_000)?;
+        T::SurgePricing::adjust_machine_price(gpu_type, system_price)
+    }
+
+    fn rent_machine_by_block(
+        renter: T::AccountId,
+        machine_id: MachineId,
+        rent_gpu_num: u32,
+        duration: T::BlockNumber,
+        rent_asset: Option<AssetIdOf<T>>,
+    ) -> DispatchResultWithPostInfo {
+        let now = <frame_system::Pallet<T>>::block_number();
+        let machine_info = Self::machines_info(&machine_id).ok_or(Error::<T>::Unknown)?;
+        let machine_rented_gpu = Self::machine_rented_gpu(&machine_id);
+        let gpu_num = machine_info.gpu_num();
+
+        if gpu_num == 0 || duration == Zero::zero() {
+            return Ok(().into())
+        }
+
+        // 检查还有空闲的GPU
+        ensure!(rent_gpu_num + machine_rented_gpu <= gpu_num, Error::<T>::GPUNotEnough);
+        // 只允许半小时整数倍的租用
+        ensure!(
+            duration % HALF_HOUR.into() == Zero::zero(),
+            Error::<T>::OnlyAllowIntegerMultipleOfHour
+        );
+
+        // 检查machine_id状态是否可以租用
+        ensure!(machine_info.can_rent(), Error::<T>::MachineNotRentable);
+
+        // 最大租用时间限制MaximumRentalDuration
+        let duration =
+            duration.min((Self::maximum_rental_duration().saturating_mul(ONE_DAY)).into());
+
+        // NOTE: 用户提交订单，需要扣除10个DBC
+        Self::pay_fixed_tx_fee(renter.clone())?;
+
+        // 获得machine_price(每天的价格) = 系统自动定价 + 卡主额外加价
+        // 根据租用GPU数量计算价格
+        let system_price = Self::get_machine_price(
+            &machine_info.gpu_type_and_mem().0,
+            machine_info.calc_point(),
+            rent_gpu_num,
+            gpu_num,
+        )
+        .ok_or(Error::<T>::GetMachinePriceFailed)?;
+        let extra_price = Self::machine_extra_price(&machine_id)
+            .checked_mul(rent_gpu_num as u64).ok_or(Error::<T>::Overflow)?;
+        let machine_price = system_price.checked_add(extra_price).ok_or(Error::<T>::Overflow)?;
+
+        // 根据租用时长计算rent_fee
+        let rent_fee_value = machine_price
+            .checked_mul(duration.saturated_into::<u64>())
+            .ok_or(Error::<T>::Overflow)?
+            .checked_div(ONE_DAY.into())
+            .ok_or(Error::<T>::Overflow)?;
+
+        // 获取用户租用的结束时间(块高)
+        let rent_end = now.checked_add(&duration).ok_or(Error::<T>::Overflow)?;
+
+        let rent_id = Self::get_new_rent_id();
+
+        // 质押用户的资金，并修改机器状态
+        // 使用资产支付时，租金转入托管账户，在租用结束时结算
+        let rent_fee = match rent_asset {
+            Some(asset_id) => {
+                Self::lock_asset_rent_fee(&renter, rent_id, asset_id, rent_fee_value)?
+            },
+            None => {
+                let rent_fee = <T as Config>::DbcPrice::get_dbc_amount_by_value(rent_fee_value)
+                    .ok_or(Error::<T>::Overflow)?;
+                Self::change_renter_total_stake(&renter, rent_fee, true)
+                    .map_err(|_| Error::<T>::InsufficientValue)?;
+                rent_fee
+            },
+        };
+
+        let mut machine_rent_order = Self::machine_rent_order(&machine_id);
+        let rentable_gpu_index = machine_rent_order.gen_rentable_gpu(rent_gpu_num, gpu_num);
+        ItemList::add_item(&mut machine_rent_order.rent_order, rent_id);
+        MachineRentOrder::<T>::insert(&machine_id, machine_rent_order);
+
+        RentOrder::<T>::insert(
+            &rent_id,
+            RentOrderDetail::new(
+                machine_id.clone(),
+                renter.clone(),
+                now,
+                rent_end,
+                rent_fee,
+                rent_gpu_num,
+                rentable_gpu_index,
+            ),
+        );
+
+        // 改变online_profile状态，影响机器佣金
+        Self::change_machine_status_on_rent_start(&machine_id, rent_gpu_num)
+            .map_err(|_| Error::<T>::Unknown)?;
+
+        UserRented::<T>::mutate(&renter, |user_rented| {
+            ItemList::add_item(user_rented, rent_id);
+        });
+        PendingRentEnding::<T>::mutate(rent_end, |pending_rent_ending| {
+            ItemList::add_item(pending_rent_ending, rent_id);
+        });
+        PendingConfirming::<T>::mutate(
+            now + WAITING_CONFIRMING_DELAY.into(),
+            |pending_confirming| {
+                ItemList::add_item(pending_confirming, rent_id);
+            },
+        );
+
+        if let Some(asset_id) = rent_asset {
+            Self::deposit_event(Event::RentWithAsset(rent_id, renter.clone(), asset_id, rent_fee));
+        }
+        Self::deposit_event(Event::RentBlockNum(
+            rent_id,
+            renter,
+            machine_id,
+            rent_fee,
+            duration.into(),
+            gpu_num,
+        ));
+        Ok(().into())
     }
 
     // - Write: RenterTotalStake
@@ -1999,6 +2336,19 @@ impl<T: Config> Pallet<T> {
         Ok(())
     }
 
+    // 机器重新上线时记录这次下线的时段，只保留最近一天内结束的记录
+    fn record_offline_interval(
+        machine_id: &MachineId,
+        offline_time: T::BlockNumber,
+        now: T::BlockNumber,
+    ) {
+        let keep_from = now.saturating_sub(ONE_DAY.into());
+        MachineOfflineRecords::<T>::mutate(machine_id, |records| {
+            records.retain(|(_, online_time)| *online_time > keep_from);
+            records.push((offline_time, now));
+        });
+    }
+
     // 获取一个新的租用订单的ID
     pub fn get_new_rent_id() -> RentOrderId {
         let rent_id = Self::next_rent_id();
@@ -2057,13 +2407,26 @@ impl<T: Config> Pallet<T> {
     // 当租用结束，或者租用被终止时，将保留的金额支付给stash账户，剩余部分解锁给租用人
     // 规则：租金 95% 给卡主 stash，5% 销毁
     fn pay_rent_fee(
+        rent_id: RentOrderId,
         rent_order: &RentOrderDetail<T::AccountId, T::BlockNumber, BalanceOf<T>>,
         rent_fee: BalanceOf<T>,
         machine_id: MachineId,
     ) -> DispatchResult {
-        let mut machine_info = Self::machines_info(&machine_id).ok_or(Error::<T>::Unknown)?;
-
+        if let Some(asset_id) = Self::rent_order_asset(rent_id) {
+            return Self::pay_asset_rent_fee(rent_id, rent_order, asset_id, rent_fee, machine_id)
+        }
         <T as Config>::Currency::unreserve(&rent_order.renter, rent_order.stake_amount);
+        Self::transfer_rent_fee(&rent_order.renter, rent_fee, machine_id, KeepAlive)
+    }
+
+    // 从付款账户(租用人或托管账户)的可用余额中支付租金
+    fn transfer_rent_fee(
+        payer: &T::AccountId,
+        rent_fee: BalanceOf<T>,
+        machine_id: MachineId,
+        existence: ExistenceRequirement,
+    ) -> DispatchResult {
+        let mut machine_info = Self::machines_info(&machine_id).ok_or(Error::<T>::Unknown)?;
 
         // 规则：租金 95% 归卡主，5% 销毁
         // 使用可配置的销毁比例（默认5%）
@@ -2076,12 +2439,7 @@ impl<T: Config> Pallet<T> {
             Some(burn_pot) => {
                 let burn = destroy_percent * rent_fee;
                 let stash = rent_fee.saturating_sub(burn);
-                <T as Config>::Currency::transfer(
-                    &rent_order.renter,
-                    &burn_pot,
-                    burn,
-                    KeepAlive,
-                )?;
+                <T as Config>::Currency::transfer(payer, &burn_pot, burn, existence)?;
                 (burn, stash)
             },
             None => (Zero::zero(), rent_fee),
@@ -2093,12 +2451,8 @@ impl<T: Config> Pallet<T> {
         // 回退到 stash；若 stash 也失败才 bail。事件可观测，不静默吞错。
         let rent_receiver = Self::stash_rent_receiver(&machine_info.machine_stash)
             .unwrap_or_else(|| machine_info.machine_stash.clone());
-        let primary = <T as Config>::Currency::transfer(
-            &rent_order.renter,
-            &rent_receiver,
-            stash_amount,
-            KeepAlive,
-        );
+        let primary =
+            <T as Config>::Currency::transfer(payer, &rent_receiver, stash_amount, existence);
         if let Err(_) = primary {
             if rent_receiver != machine_info.machine_stash {
                 Self::deposit_event(Event::RentReceiverPayoutFallback(
@@ -2107,10 +2461,10 @@ impl<T: Config> Pallet<T> {
                     stash_amount,
                 ));
                 <T as Config>::Currency::transfer(
-                    &rent_order.renter,
+                    payer,
                     &machine_info.machine_stash,
                     stash_amount,
-                    KeepAlive,
+                    existence,
                 )?;
             } else {
                 return primary;
@@ -2151,7 +2505,14 @@ impl<T: Config> Pallet<T> {
             let rent_duration = rent_order.rent_end.saturating_sub(rent_order.rent_start);
 
             // S2 修复：显式处理 pay_rent_fee 失败，发事件便于监控；后续 cleanup 仍执行。
-            if let Err(_) = Self::pay_rent_fee(&rent_order, rent_order.stake_amount, machine_id.clone()) {
+            if OrderEscrow::<T>::contains_key(rent_id) {
+                Self::close_escrow(rent_id, now);
+            } else if let Err(_) = Self::pay_rent_fee(
+                rent_id,
+                &rent_order,
+                rent_order.stake_amount,
+                machine_id.clone(),
+            ) {
                 Self::deposit_event(Event::RentFeePayoutFailed(rent_id));
             }
 
@@ -2259,6 +2620,7 @@ impl<T: Config> Pallet<T> {
         }
 
         RentOrder::<T>::remove(rent_order_id);
+        RentOrderAsset::<T>::remove(rent_order_id);
         Ok(())
     }
 
@@ -239,3 +239,79 @@ pub mod v1 {
         weight
     }
 }
+
+// 报告中新增创建时的审核委员会数量(verifier_count)，升级前的报告固定由3个委员会审核
+pub mod v2 {
+    use super::*;
+    use dbc_support::report::MTReportInfoDetail;
+    use frame_support::traits::StorageVersion as PalletStorageVersion;
+
+    const LEGACY_VERIFIER_COUNT: u32 = 3;
+
+    #[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+    pub struct OldMTReportInfoDetail<AccountId, BlockNumber, Balance> {
+        pub reporter: AccountId,
+        pub report_time: BlockNumber,
+        pub reporter_stake: Balance,
+        pub first_book_time: BlockNumber,
+        pub machine_id: MachineId,
+        pub rent_order_id: RentOrderId,
+        pub err_info: Vec<u8>,
+        pub verifying_committee: Option<AccountId>,
+        pub booked_committee: Vec<AccountId>,
+        pub get_encrypted_info_committee: Vec<AccountId>,
+        pub hashed_committee: Vec<AccountId>,
+        pub confirm_start: BlockNumber,
+        pub confirmed_committee: Vec<AccountId>,
+        pub support_committee: Vec<AccountId>,
+        pub against_committee: Vec<AccountId>,
+        pub report_status: ReportStatus,
+        pub machine_fault_type: MachineFaultType,
+    }
+
+    // A: AccountId, B: BlockNumber, C: Balance
+    impl<A, B, C> From<OldMTReportInfoDetail<A, B, C>> for MTReportInfoDetail<A, B, C> {
+        fn from(info: OldMTReportInfoDetail<A, B, C>) -> MTReportInfoDetail<A, B, C> {
+            MTReportInfoDetail {
+                reporter: info.reporter,
+                report_time: info.report_time,
+                reporter_stake: info.reporter_stake,
+                first_book_time: info.first_book_time,
+                machine_id: info.machine_id,
+                rent_order_id: info.rent_order_id,
+                err_info: info.err_info,
+                verifying_committee: info.verifying_committee,
+                booked_committee: info.booked_committee,
+                get_encrypted_info_committee: info.get_encrypted_info_committee,
+                hashed_committee: info.hashed_committee,
+                confirm_start: info.confirm_start,
+                confirmed_committee: info.confirmed_committee,
+                support_committee: info.support_committee,
+                against_committee: info.against_committee,
+                report_status: info.report_status,
+                machine_fault_type: info.machine_fault_type,
+                verifier_count: LEGACY_VERIFIER_COUNT,
+            }
+        }
+    }
+
+    pub fn migrate<T: Config>() -> Weight {
+        if Pallet::<T>::on_chain_storage_version() >= 2 {
+            return T::DbWeight::get().reads(1)
+        }
+
+        log::info!(target: TARGET, "migrate report info executing");
+
+        let mut report_num = 0u64;
+        ReportInfo::<T>::translate(
+            |_report_id, old: OldMTReportInfoDetail<AccountIdOf<T>, BlockNumberOf<T>, BalanceOf<T>>| {
+                report_num += 1;
+                Some(old.into())
+            },
+        );
+        PalletStorageVersion::new(2).put::<Pallet<T>>();
+
+        log::info!(target: TARGET, "migrate report info ok, {} reports", report_num);
+        T::DbWeight::get().reads_writes(1 + report_num, 1 + report_num)
+    }
+}
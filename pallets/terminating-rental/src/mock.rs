@@ -1,11 +1,11 @@
 use crate as terminating_rental;
-use dbc_support::report::ReporterStakeParamsInfo;
+use dbc_support::{report::ReporterStakeParamsInfo, ONE_DAY};
 use frame_support::{
     assert_ok, parameter_types,
-    traits::{ConstU32, OnFinalize, OnInitialize},
+    traits::{AsEnsureOriginWithArg, ConstU128, ConstU32, OnFinalize, OnInitialize},
     PalletId,
 };
-use frame_system::{EnsureRoot, EnsureWithSuccess, RawOrigin};
+use frame_system::{EnsureRoot, EnsureSigned, EnsureWithSuccess, RawOrigin};
 pub use sp_core::{
     sr25519::{self, Signature},
     H256,
@@ -28,6 +28,9 @@ pub type BlockNumber = u32;
 pub const ONE_DBC: u128 = 1_000_000_000_000_000;
 // 初始1000WDBC
 pub const INIT_BALANCE: u128 = 10_000_000 * ONE_DBC;
+// 用于支付租金的DLC资产
+pub const DLC_ASSET_ID: u32 = 88;
+pub const ONE_DLC: u128 = 1_000_000_000_000_000;
 
 parameter_types! {
     pub const BlockHashCount: BlockNumber = 250;
@@ -126,7 +129,14 @@ impl pallet_treasury::Config for TestRuntime {
 impl committee::Config for TestRuntime {
     type Currency = Balances;
     type RuntimeEvent = RuntimeEvent;
-    // type WeightInfo = ();
+    type SlashAndReward = GenericFunc;
+    type CurrentEra = ();
+    type WeightInfo = ();
+}
+
+parameter_types! {
+    pub const DlcAssetId: u32 = DLC_ASSET_ID;
+    pub const TerminatingRentalPalletId: PalletId = PalletId(*b"dbc/term");
 }
 
 impl terminating_rental::Config for TestRuntime {
@@ -135,7 +145,12 @@ impl terminating_rental::Config for TestRuntime {
     type Slash = Treasury;
     type ManageCommittee = Committee;
     type DbcPrice = DBCPriceOCW;
-    type SlashAndReward = GenericFunc;
+    type SlashAndReward = Committee;
+    type DlcPrice = DLCPriceOCW;
+    type SurgePricing = ();
+    type Assets = Assets;
+    type DlcAssetId = DlcAssetId;
+    type PalletId = TerminatingRentalPalletId;
 }
 
 impl dbc_price_ocw::Config for TestRuntime {
@@ -144,6 +159,40 @@ impl dbc_price_ocw::Config for TestRuntime {
     type RandomnessSource = RandomnessCollectiveFlip;
 }
 
+impl dlc_price_ocw::Config for TestRuntime {
+    type Currency = Balances;
+    type RuntimeEvent = RuntimeEvent;
+    type RandomnessSource = RandomnessCollectiveFlip;
+}
+
+parameter_types! {
+    pub const MinLockAmount: Balance = 100 * ONE_DLC;
+    pub const MaxLockDuration: BlockNumber = 1800 * ONE_DAY;
+}
+
+impl pallet_assets::Config for TestRuntime {
+    type RuntimeEvent = RuntimeEvent;
+    type Balance = Balance;
+    type AssetId = u32;
+    type AssetIdParameter = parity_scale_codec::Compact<u32>;
+    type Currency = Balances;
+    type CreateOrigin = AsEnsureOriginWithArg<EnsureSigned<Self::AccountId>>;
+    type ForceOrigin = EnsureRoot<Self::AccountId>;
+    type AssetDeposit = ConstU128<1>;
+    type AssetAccountDeposit = ConstU128<1>;
+    type MetadataDepositBase = ConstU128<1>;
+    type MetadataDepositPerByte = ConstU128<1>;
+    type ApprovalDeposit = ConstU128<1>;
+    type StringLimit = ConstU32<50>;
+    type Freezer = ();
+    type Extra = ();
+    type CallbackHandle = ();
+    type WeightInfo = ();
+    type RemoveItemsLimit = ConstU32<1000>;
+    type MinLockAmount = MinLockAmount;
+    type MaxLockDuration = MaxLockDuration;
+}
+
 type TestExtrinsic = TestXt<RuntimeCall, ()>;
 impl<LocalCall> frame_system::offchain::CreateSignedTransaction<LocalCall> for TestRuntime
 where
@@ -188,6 +237,8 @@ frame_support::construct_runtime!(
         Balances: pallet_balances,
         Committee: committee,
         DBCPriceOCW: dbc_price_ocw,
+        DLCPriceOCW: dlc_price_ocw,
+        Assets: pallet_assets,
         Treasury: pallet_treasury,
         GenericFunc: generic_func,
         // RentMachine: rent_machine::{Module, Storage, Call, Event<T>},
@@ -244,6 +295,14 @@ pub fn new_test_with_init_params_ext() -> sp_io::TestExternalities {
     .assimilate_storage(&mut storage)
     .unwrap();
 
+    pallet_assets::GenesisConfig::<TestRuntime> {
+        assets: vec![(DLC_ASSET_ID, sr25519::Public::from(Sr25519Keyring::Alice), true, 1)],
+        metadata: vec![],
+        accounts: vec![(DLC_ASSET_ID, sr25519::Public::from(Sr25519Keyring::Bob), INIT_BALANCE)],
+    }
+    .assimilate_storage(&mut storage)
+    .unwrap();
+
     let mut ext = sp_io::TestExternalities::from(storage);
 
     ext.execute_with(|| {
@@ -255,6 +314,12 @@ pub fn new_test_with_init_params_ext() -> sp_io::TestExternalities {
         DBCPriceOCW::add_avg_price();
         assert_eq!(DBCPriceOCW::avg_price(), Some(12_000u64));
 
+        // 初始化DLC价格 (0.003$)
+        for _ in 0..64 {
+            DLCPriceOCW::add_price(3_000u64);
+        }
+        DLCPriceOCW::add_avg_price();
+
         // 设置标准GPU租金价格: (3080得分1000；租金每月1000RMB) {1000; 150_000_000};
         assert_ok!(TerminatingRental::set_standard_gpu_point_price(
             RawOrigin::Root.into(),
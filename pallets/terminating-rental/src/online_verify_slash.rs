@@ -15,6 +15,11 @@ impl<T: Config> Pallet<T> {
         <T as Config>::ManageCommittee::stake_per_order().ok_or(Error::<T>::GetStakeAmountFailed)
     }
 
+    // 审核报告的委员会数量
+    pub fn get_verifier_count() -> u32 {
+        <T as Config>::ManageCommittee::verifier_count()
+    }
+
     pub fn is_valid_committee(who: &T::AccountId) -> DispatchResultWithPostInfo {
         ensure!(<T as Config>::ManageCommittee::is_valid_committee(who), Error::<T>::NotCommittee);
         Ok(().into())
@@ -27,12 +32,7 @@ impl<T: Config> Pallet<T> {
     ) -> Result<(), ()> {
         for a_committee in committee_list {
             if is_slash {
-                <T as Config>::ManageCommittee::change_total_stake(
-                    a_committee.clone(),
-                    amount,
-                    false,
-                    false,
-                )?;
+                <T as Config>::ManageCommittee::slash_stake(a_committee.clone(), amount)?;
             }
 
             <T as Config>::ManageCommittee::change_used_stake(a_committee, amount, false)?;
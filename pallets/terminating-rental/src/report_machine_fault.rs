@@ -1,5 +1,6 @@
 use crate::*;
 use dbc_support::{
+    committee_task::CommitteeTaskId,
     machine_type::MachineStatus,
     report::{
         MCSlashResult, MTLiveReportList, MTReportInfoDetail, MTReportResultInfo,
@@ -118,6 +119,7 @@ impl<T: Config> Pallet<T> {
                 report_time,
                 machine_fault_type.clone(),
                 stake_params.stake_per_report,
+                Self::get_verifier_count(),
             ),
         );
 
@@ -135,6 +137,46 @@ impl<T: Config> Pallet<T> {
         report_id
     }
 
+    // 记录报告人提交的加密信息及加密时使用的委员会公钥版本
+    pub fn do_add_encrypted_error_info(
+        reporter: T::AccountId,
+        report_id: ReportId,
+        to_committee: T::AccountId,
+        key_version: u32,
+        encrypted_err_info: Vec<u8>,
+    ) -> DispatchResultWithPostInfo {
+        let now = <frame_system::Pallet<T>>::block_number();
+
+        let mut report_info = Self::report_info(&report_id).ok_or(Error::<T>::Unknown)?;
+        let mut committee_ops = Self::committee_report_ops(&to_committee, &report_id);
+
+        // 检查报告可以提供加密信息
+        // 该orde处于验证中, 且还没有提交过加密信息
+        report_info
+            .can_submit_encrypted_info(&reporter, &to_committee)
+            .map_err::<Error<T>, _>(Into::into)?;
+        ensure!(
+            committee_ops.order_status == MTOrderStatus::WaitingEncrypt,
+            Error::<T>::OrderStatusNotFeat
+        );
+        // 需使用委员会当前的公钥，或宽限期内的旧公钥加密
+        ensure!(
+            <T as Config>::ManageCommittee::box_key_valid(&to_committee, key_version),
+            Error::<T>::InvalidBoxKeyVersion
+        );
+
+        // report_info中插入已经收到了加密信息的委员会
+        ItemList::add_item(&mut report_info.get_encrypted_info_committee, to_committee.clone());
+        ReportInfo::<T>::insert(&report_id, report_info);
+
+        committee_ops.add_encry_info(encrypted_err_info, now);
+        CommitteeReportOps::<T>::insert(&to_committee, &report_id, committee_ops);
+        EncryptedInfoKeyVersion::<T>::insert(&to_committee, &report_id, key_version);
+
+        Self::deposit_event(Event::EncryptedInfoSent(reporter, to_committee, report_id));
+        Ok(().into())
+    }
+
     pub fn book_report(
         committee: T::AccountId,
         report_id: ReportId,
@@ -152,10 +194,16 @@ impl<T: Config> Pallet<T> {
             committee_ops.book_report(mft.clone(), now, order_stake);
         });
         LiveReport::<T>::mutate(|live_report| {
-            live_report.book_report(report_id, mft, report_info.booked_committee.len());
+            live_report.book_report(
+                report_id,
+                mft,
+                report_info.booked_committee.len(),
+                report_info.book_limit(),
+            );
         });
 
         ReportInfo::<T>::insert(&report_id, report_info);
+        Self::schedule_task_reminders(committee, CommitteeTaskId::Report(report_id), now);
     }
 
     pub fn exec_report_slash() -> Result<(), ()> {
@@ -263,12 +311,7 @@ impl<T: Config> Pallet<T> {
     ) -> Result<(), ()> {
         for a_committee in committee_list {
             if is_slash {
-                <T as Config>::ManageCommittee::change_total_stake(
-                    a_committee.clone(),
-                    amount,
-                    false,
-                    false,
-                )?;
+                <T as Config>::ManageCommittee::slash_stake(a_committee.clone(), amount)?;
             }
 
             <T as Config>::ManageCommittee::change_used_stake(a_committee, amount, false)?;
@@ -380,6 +423,7 @@ impl<T: Config> Pallet<T> {
                     false,
                 );
                 CommitteeReportOps::<T>::remove(a_committee, report_id);
+                EncryptedInfoKeyVersion::<T>::remove(a_committee, report_id);
 
                 CommitteeReportOrder::<T>::mutate(a_committee, |committee_order| {
                     committee_order.clean_unfinished_order(&report_id);
@@ -405,6 +449,7 @@ impl<T: Config> Pallet<T> {
 
             ReportInfo::<T>::insert(report_id, report_info.clone());
             CommitteeReportOps::<T>::remove(&verifying_committee, &report_id);
+            EncryptedInfoKeyVersion::<T>::remove(&verifying_committee, &report_id);
 
             // NOTE: should not insert directly when summary result, but should alert exist data
             ItemList::add_item(&mut report_result.unruly_committee, verifying_committee.clone());
@@ -483,6 +528,7 @@ impl<T: Config> Pallet<T> {
                 // 所有booked_committee都应该被惩罚
                 report_info.booked_committee.clone().iter().for_each(|a_committee| {
                     CommitteeReportOps::<T>::remove(&a_committee, report_id);
+                    EncryptedInfoKeyVersion::<T>::remove(&a_committee, report_id);
 
                     CommitteeReportOrder::<T>::mutate(&a_committee, |committee_order| {
                         ItemList::rm_item(&mut committee_order.booked_report, &report_id);
@@ -508,6 +554,7 @@ impl<T: Config> Pallet<T> {
         if report_info.report_status != ReportStatus::Reported {
             report_info.report_status = ReportStatus::CommitteeConfirmed;
         }
+        Self::update_report_reputation(&report_result);
         ReportResult::<T>::insert(report_id, report_result);
         ReportInfo::<T>::insert(report_id, report_info);
         Ok(())
@@ -548,6 +595,7 @@ impl<T: Config> Pallet<T> {
             );
 
             CommitteeReportOps::<T>::remove(&verifying_committee, report_id);
+            EncryptedInfoKeyVersion::<T>::remove(&verifying_committee, report_id);
 
             ReportInfo::<T>::try_mutate(report_id, |report_info| {
                 // 将最后一个委员会移除，不惩罚
@@ -572,4 +620,15 @@ impl<T: Config> Pallet<T> {
             }
         });
     }
+
+    // 报告处理完成后更新委员会信誉
+    pub fn update_report_reputation(
+        report_result: &MTReportResultInfo<T::AccountId, T::BlockNumber, BalanceOf<T>>,
+    ) {
+        <T as Config>::ManageCommittee::update_reputation(
+            &report_result.reward_committee,
+            &report_result.inconsistent_committee,
+            &report_result.unruly_committee,
+        );
+    }
 }
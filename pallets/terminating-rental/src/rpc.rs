@@ -3,9 +3,10 @@ use crate::{
     BalanceOf, Config, Pallet, RentOrderDetail, RentOrderId, StashMachines,
 };
 use dbc_support::{
+    committee_task::{CommitteeTask, CommitteeTaskId, CommitteeTaskKind},
     live_machine::LiveMachine,
     machine_info::MachineInfo,
-    rental_type::MachineGPUOrder,
+    rental_type::{MachineGPUOrder, RentEscrowDetail},
     verify_online::{OCCommitteeMachineList, OCMachineCommitteeList},
     MachineId,
 };
@@ -84,6 +85,43 @@ impl<T: Config> Pallet<T> {
             machine_info: oc_committee_ops.machine_info,
         })
     }
+
+    /// 获取委员会未完成的任务：审核上线的机器及处理中的故障报告
+    pub fn get_committee_tasks(committee: T::AccountId) -> Vec<CommitteeTask<T::BlockNumber>> {
+        let committee_machine = Self::committee_machine(&committee);
+        let committee_order = Self::committee_report_order(&committee);
+
+        let booked_machine = committee_machine.booked_machine.into_iter().map(|id| (id, false));
+        let hashed_machine = committee_machine.hashed_machine.into_iter().map(|id| (id, true));
+        let machine_tasks =
+            booked_machine.chain(hashed_machine).map(|(machine_id, hash_submitted)| {
+                let machine_committee = Self::machine_committee(&machine_id);
+                CommitteeTask {
+                    task_kind: CommitteeTaskKind::IROnlineVerify,
+                    task_id: CommitteeTaskId::Machine(machine_id),
+                    hash_submitted,
+                    hash_deadline: machine_committee.hash_deadline(),
+                    raw_deadline: machine_committee.raw_deadline(),
+                }
+            });
+
+        let booked_report = committee_order.booked_report.into_iter().map(|id| (id, false));
+        let hashed_report = committee_order.hashed_report.into_iter().map(|id| (id, true));
+        let report_tasks =
+            booked_report.chain(hashed_report).filter_map(|(report_id, hash_submitted)| {
+                let report_info = Self::report_info(report_id)?;
+                let committee_ops = Self::committee_report_ops(&committee, report_id);
+                Some(CommitteeTask {
+                    task_kind: CommitteeTaskKind::IRFaultReport,
+                    task_id: CommitteeTaskId::Report(report_id),
+                    hash_submitted,
+                    hash_deadline: report_info.hash_deadline(committee_ops.booked_time),
+                    raw_deadline: report_info.raw_deadline(),
+                })
+            });
+
+        machine_tasks.chain(report_tasks).collect()
+    }
 }
 
 impl<T: Config> Pallet<T> {
@@ -113,4 +151,10 @@ impl<T: Config> Pallet<T> {
     pub fn get_machine_rent_id(machine_id: MachineId) -> MachineGPUOrder {
         Self::machine_rent_order(machine_id)
     }
+
+    pub fn get_order_escrow(
+        rent_id: RentOrderId,
+    ) -> Option<RentEscrowDetail<T::AccountId, T::BlockNumber, BalanceOf<T>>> {
+        Self::order_escrow(rent_id)
+    }
 }
@@ -33,8 +33,7 @@ pub fn new_test_after_machine_distribute() -> sp_io::TestExternalities {
 
         let committee1 = sr25519::Public::from(Sr25519Keyring::Alice);
         let committee2 = sr25519::Public::from(Sr25519Keyring::Charlie);
-        let committee3 = sr25519::Public::from(Sr25519Keyring::Dave);
-        let _committee4 = sr25519::Public::from(Sr25519Keyring::Eve);
+        let committee4 = sr25519::Public::from(Sr25519Keyring::Eve);
 
         assert_ok!(IRMachine::bond_machine(
             RuntimeOrigin::signed(controller),
@@ -64,7 +63,7 @@ pub fn new_test_after_machine_distribute() -> sp_io::TestExternalities {
                 IRMachine::machine_committee(&machine_id),
                 OCMachineCommitteeList {
                     book_time: 2,
-                    booked_committee: vec![committee3, committee2, committee1],
+                    booked_committee: vec![committee2, committee1, committee4],
                     confirm_start_time: 2 + 36 * ONE_HOUR,
                     hashed_committee: vec![],
                     confirmed_committee: vec![],
@@ -91,7 +90,7 @@ fn committee_not_submit_slash_works() {
         let committee3 = sr25519::Public::from(Sr25519Keyring::Dave);
         let committee4 = sr25519::Public::from(Sr25519Keyring::Eve);
 
-        // committee 1, 2提交Hash
+        // committee 2, 4提交Hash
         // 委员会添加机器Hash
         let hash1: [u8; 16] =
             hex::decode("53cf058dfa07ef517b2f28bccff88c2b").unwrap().try_into().unwrap();
@@ -99,13 +98,13 @@ fn committee_not_submit_slash_works() {
             hex::decode("3f775d3f4a144b94d6d551f6091a5126").unwrap().try_into().unwrap();
 
         assert_ok!(IRMachine::submit_confirm_hash(
-            RuntimeOrigin::signed(committee2),
+            RuntimeOrigin::signed(committee4),
             machine_id.clone(),
             hash1
         ));
 
         assert_ok!(IRMachine::submit_confirm_hash(
-            RuntimeOrigin::signed(committee3),
+            RuntimeOrigin::signed(committee2),
             machine_id.clone(),
             hash2
         ));
@@ -118,8 +117,8 @@ fn committee_not_submit_slash_works() {
                 IRMachine::machine_committee(&machine_id),
                 OCMachineCommitteeList {
                     book_time: 2,
-                    booked_committee: vec![committee3, committee2, committee1],
-                    hashed_committee: vec![committee3, committee2],
+                    booked_committee: vec![committee2, committee1, committee4],
+                    hashed_committee: vec![committee2, committee4],
                     confirm_start_time: 2 + 36 * ONE_HOUR,
                     status: OCVerifyStatus::SubmittingRaw,
                     confirmed_committee: vec![],
@@ -148,12 +147,12 @@ fn committee_not_submit_slash_works() {
         };
 
         assert_ok!(IRMachine::submit_confirm_raw(
-            RuntimeOrigin::signed(committee2),
+            RuntimeOrigin::signed(committee4),
             upload_info.clone()
         ));
         upload_info.rand_str = "abcdefg2".as_bytes().to_vec();
         assert_ok!(IRMachine::submit_confirm_raw(
-            RuntimeOrigin::signed(committee3),
+            RuntimeOrigin::signed(committee2),
             upload_info.clone()
         ));
 
@@ -171,7 +170,7 @@ fn committee_not_submit_slash_works() {
                     machine_id: machine_id.clone(),
                     inconsistent_committee: vec![],
                     unruly_committee: vec![committee1],
-                    reward_committee: vec![committee3, committee2],
+                    reward_committee: vec![committee2, committee4],
                     committee_stake: 1000 * ONE_DBC,
                     slash_time: 4 + 36 * ONE_HOUR,
                     slash_exec_time: 4 + 36 * ONE_HOUR + 2 * ONE_DAY,
@@ -188,7 +187,7 @@ fn committee_not_submit_slash_works() {
             );
         }
 
-        // 自动执行惩罚: committee4 被惩罚，惩罚到国库
+        // 自动执行惩罚: committee1 被惩罚，惩罚到国库
         run_to_block(4 + 36 * ONE_HOUR + 2 * ONE_DAY + 1);
         {
             assert_eq!(Balances::free_balance(committee1), INIT_BALANCE - 20000 * ONE_DBC);
@@ -202,7 +201,7 @@ fn committee_not_submit_slash_works() {
 
             assert_eq!(Balances::reserved_balance(committee1), 20000 * ONE_DBC - 1000 * ONE_DBC);
             assert_eq!(Balances::reserved_balance(committee2), 20000 * ONE_DBC);
-            assert_eq!(Balances::reserved_balance(committee3), 20000 * ONE_DBC);
+            assert_eq!(Balances::reserved_balance(committee4), 20000 * ONE_DBC);
         }
     })
 }
@@ -241,12 +240,12 @@ fn machine_refused_slash_works() {
             hex::decode("73af18cb31a2ebbea4eab9e9e519539e").unwrap().try_into().unwrap();
 
         assert_ok!(IRMachine::submit_confirm_hash(
-            RuntimeOrigin::signed(committee2),
+            RuntimeOrigin::signed(committee4),
             machine_id.clone(),
             hash1
         ));
         assert_ok!(IRMachine::submit_confirm_hash(
-            RuntimeOrigin::signed(committee3),
+            RuntimeOrigin::signed(committee2),
             machine_id.clone(),
             hash2
         ));
@@ -277,12 +276,12 @@ fn machine_refused_slash_works() {
 
         // 委员会添加机器原始值
         assert_ok!(IRMachine::submit_confirm_raw(
-            RuntimeOrigin::signed(committee2),
+            RuntimeOrigin::signed(committee4),
             upload_info.clone()
         ));
         upload_info.rand_str = "abcdefg2".as_bytes().to_vec();
         assert_ok!(IRMachine::submit_confirm_raw(
-            RuntimeOrigin::signed(committee3),
+            RuntimeOrigin::signed(committee2),
             upload_info.clone()
         ));
         upload_info.rand_str = "abcdefg3".as_bytes().to_vec();
@@ -313,7 +312,7 @@ fn machine_refused_slash_works() {
                     machine_stash: Some(stash),
                     stash_slash_amount: 10000 * ONE_DBC,
                     committee_stake: 1000 * ONE_DBC,
-                    reward_committee: vec![committee3, committee2, committee1],
+                    reward_committee: vec![committee2, committee1, committee4],
                     slash_time: 4,
                     slash_exec_time: 4 + 2 * ONE_DAY,
                     book_result: OCBookResultType::OnlineRefused,
@@ -339,8 +338,8 @@ fn machine_refused_slash_works() {
             );
 
             assert_eq!(Balances::reserved_balance(committee2), 20000 * ONE_DBC);
-            assert_eq!(Balances::reserved_balance(committee3), 20000 * ONE_DBC);
             assert_eq!(Balances::reserved_balance(committee1), 20000 * ONE_DBC);
+            assert_eq!(Balances::reserved_balance(committee4), 20000 * ONE_DBC);
         }
     })
 }
@@ -8,7 +8,7 @@ use dbc_support::ONE_MINUTE;
 use dbc_support::{
     live_machine::LiveMachine,
     machine_type::{CommitteeUploadInfo, MachineStatus},
-    rental_type::MachineGPUOrder,
+    rental_type::{DlcBurnDetail, MachineGPUOrder},
     report::{
         MTCommitteeOpsDetail, MTCommitteeOrderList, MTLiveReportList, MTOrderStatus,
         MTReportInfoDetail, MachineFaultType, ReportStatus, ReporterReportList, ReporterStakeInfo,
@@ -245,6 +245,71 @@ fn rent_machine_works() {
     })
 }
 
+// 使用DLC支付租金，租用结束时按比例销毁并支付给stash
+#[test]
+fn rent_machine_with_asset_works() {
+    new_test_with_machine_online_ext().execute_with(|| {
+        let stash = sr25519::Public::from(Sr25519Keyring::Ferdie);
+        let renter1 = sr25519::Public::from(Sr25519Keyring::Bob);
+        let machine_id = "8eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a48"
+            .as_bytes()
+            .to_vec();
+
+        // 只能使用DLC支付
+        assert_noop!(
+            IRMachine::rent_machine_with_asset(
+                RuntimeOrigin::signed(renter1),
+                machine_id.clone(),
+                8,
+                30 * ONE_MINUTE,
+                DLC_ASSET_ID + 1
+            ),
+            Error::<TestRuntime>::AssetNotSupported
+        );
+
+        assert_ok!(IRMachine::rent_machine_with_asset(
+            RuntimeOrigin::signed(renter1),
+            machine_id.clone(),
+            8,
+            30 * ONE_MINUTE,
+            DLC_ASSET_ID
+        ));
+
+        // 租金: 119780 / 1000 * 5000000 / 3000 * (0.5h / 24h)
+        let rent_fee = 4159027666666666666;
+        let rent_fee_account = IRMachine::rent_fee_account();
+        assert_eq!(IRMachine::rent_order(0).unwrap().stake_amount, rent_fee);
+        assert_eq!(IRMachine::rent_order_asset(0), Some(DLC_ASSET_ID));
+        assert_eq!(Assets::balance(DLC_ASSET_ID, renter1), INIT_BALANCE - rent_fee);
+        assert_eq!(Assets::balance(DLC_ASSET_ID, rent_fee_account), rent_fee);
+        // 不质押DBC
+        assert_eq!(IRMachine::renter_total_stake(renter1), 0);
+        assert_eq!(Balances::reserved_balance(renter1), 0);
+
+        assert_ok!(IRMachine::confirm_rent(RuntimeOrigin::signed(renter1), 0));
+
+        run_to_block(5 + 30 * ONE_MINUTE);
+
+        let fee_to_burn = Perbill::from_percent(5) * rent_fee;
+        assert_eq!(Assets::balance(DLC_ASSET_ID, renter1), INIT_BALANCE - rent_fee);
+        assert_eq!(Assets::balance(DLC_ASSET_ID, stash), rent_fee - fee_to_burn);
+        assert_eq!(Assets::balance(DLC_ASSET_ID, rent_fee_account), 0);
+        assert_eq!(Assets::total_supply(DLC_ASSET_ID), INIT_BALANCE - fee_to_burn);
+        assert_eq!(IRMachine::total_burned_dlc(), fee_to_burn);
+        assert_eq!(
+            IRMachine::dlc_burn_details(0),
+            vec![DlcBurnDetail {
+                rent_id: 0,
+                renter: renter1,
+                burned_amount: fee_to_burn,
+                at: 5 + 30 * ONE_MINUTE
+            }]
+        );
+        assert_eq!(IRMachine::rent_order(0), None);
+        assert_eq!(IRMachine::rent_order_asset(0), None);
+    })
+}
+
 // 用户下线，将按照使用时长付租金
 #[test]
 fn machine_offline_works() {
@@ -420,7 +485,8 @@ fn machine_online_inaccessible_slash_works() {
                     confirmed_committee: vec![],
                     support_committee: vec![],
                     against_committee: vec![],
-                    report_status: ReportStatus::default()
+                    report_status: ReportStatus::default(),
+                    verifier_count: 3,
                 })
             );
         }
@@ -454,7 +520,8 @@ fn machine_online_inaccessible_slash_works() {
                     hashed_committee: vec![],
                     confirmed_committee: vec![],
                     support_committee: vec![],
-                    against_committee: vec![]
+                    against_committee: vec![],
+                    verifier_count: 3,
                 })
             );
             assert_eq!(
@@ -504,7 +571,8 @@ fn machine_online_inaccessible_slash_works() {
                     hashed_committee: vec![],
                     confirmed_committee: vec![],
                     support_committee: vec![],
-                    against_committee: vec![]
+                    against_committee: vec![],
+                    verifier_count: 3,
                 })
             );
             assert_eq!(
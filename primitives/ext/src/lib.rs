@@ -66,3 +66,53 @@ pub trait TracingExt {
         Event::CallListNew().emit();
     }
 }
+
+/// Network probes for offchain workers. The offchain HTTP API only reports parsed HTTP
+/// responses, so services that do not speak HTTP would always look unreachable.
+#[runtime_interface]
+pub trait ProbeExt {
+    /// Whether a TCP connection to `host:port` can be established within `timeout_ms`.
+    ///
+    /// Only public addresses are probed: loopback, private, link-local and unspecified
+    /// addresses are skipped so an endpoint cannot point the probing nodes at themselves or
+    /// their local network. Returns `None` when `host` does not resolve to any public address.
+    fn tcp_connect(host: Vec<u8>, port: u16, timeout_ms: u64) -> Option<bool> {
+        use std::net::{TcpStream, ToSocketAddrs};
+
+        let host = std::str::from_utf8(&host).ok()?;
+        let addrs = (host, port).to_socket_addrs().ok()?;
+        let addrs: Vec<_> = addrs.filter(|addr| is_public_ip(&addr.ip())).collect();
+        if addrs.is_empty() {
+            return None
+        }
+
+        let timeout = std::time::Duration::from_millis(timeout_ms);
+        Some(addrs.iter().any(|addr| TcpStream::connect_timeout(addr, timeout).is_ok()))
+    }
+}
+
+#[cfg(feature = "std")]
+fn is_public_ip(ip: &std::net::IpAddr) -> bool {
+    use std::net::IpAddr;
+
+    match ip {
+        IpAddr::V4(ip) => {
+            !(ip.is_loopback() ||
+                ip.is_private() ||
+                ip.is_link_local() ||
+                ip.is_unspecified() ||
+                ip.is_broadcast())
+        },
+        IpAddr::V6(ip) => {
+            if let Some(ip) = ip.to_ipv4_mapped() {
+                return is_public_ip(&IpAddr::V4(ip))
+            }
+            let first = ip.segments()[0];
+            // fc00::/7 is unique local and fe80::/10 is link-local
+            !(ip.is_loopback() ||
+                ip.is_unspecified() ||
+                first & 0xfe00 == 0xfc00 ||
+                first & 0xffc0 == 0xfe80)
+        },
+    }
+}
@@ -33,7 +33,7 @@ pub use dbc_primitives::{
 // Fix `unused_crate_dependencies` warnings.
 use dbc_primitives_rpc_evm_tracing_events as _;
 use dbc_primitives_rpc_txpool::TxPoolResponse;
-use dbc_support::{rental_type::MachineGPUOrder, EraIndex, MachineId, RentOrderId};
+use dbc_support::{rental_type::MachineGPUOrder, EraIndex, MachineId, RentOrderId, ReportId};
 use fp_evm::weight_per_gas;
 use fp_rpc::TransactionStatus;
 use frame_election_provider_support::{
@@ -1345,7 +1345,9 @@ impl pallet_nfts::Config for Runtime {
 impl generic_func::Config for Runtime {
     type Currency = Balances;
     type RuntimeEvent = RuntimeEvent;
-    type RandomnessSource = RandomnessCollectiveFlip;
+    // 使用BABE上一个epoch的VRF随机数，区块生产者无法操纵分派委员会的结果；
+    // 分派时再与机器ID及nonce混合，使每台机器、每次抽取的种子都不相同
+    type RandomnessSource = pallet_babe::RandomnessFromOneEpochAgo<Runtime>;
     type FixedTxFee = Treasury;
     type Slash = Treasury;
 }
@@ -1392,13 +1394,20 @@ impl online_profile::Config for Runtime {
     type Slash = Treasury;
     type CancelSlashOrigin =
         pallet_collective::EnsureProportionAtLeast<AccountId, TechnicalCollective, 1, 5>;
-    type SlashAndReward = GenericFunc;
+    type SlashAndReward = Committee;
+    type WeightInfo = online_profile::weights::SubstrateWeight<Runtime>;
+}
+
+parameter_types! {
+    pub CommitteeCurrentEra: EraIndex = OnlineProfile::current_era();
 }
 
 impl committee::Config for Runtime {
     type Currency = Balances;
     type RuntimeEvent = RuntimeEvent;
-    // type WeightInfo = committee::weights::SubstrateWeight<Runtime>;
+    type SlashAndReward = GenericFunc;
+    type CurrentEra = CommitteeCurrentEra;
+    type WeightInfo = committee::weights::SubstrateWeight<Runtime>;
 }
 
 impl online_committee::Config for Runtime {
@@ -1408,7 +1417,12 @@ impl online_committee::Config for Runtime {
     type ManageCommittee = Committee;
     type CancelSlashOrigin =
         pallet_collective::EnsureProportionAtLeast<AccountId, TechnicalCollective, 1, 5>;
-    type SlashAndReward = GenericFunc;
+    type SlashAndReward = Committee;
+}
+
+parameter_types! {
+    pub const DlcAssetId: u32 = 88;
+    pub const RentMachinePalletId: PalletId = PalletId(*b"dbc/rent");
 }
 
 impl rent_machine::Config for Runtime {
@@ -1416,6 +1430,16 @@ impl rent_machine::Config for Runtime {
     type RuntimeEvent = RuntimeEvent;
     type RTOps = OnlineProfile;
     type DbcPrice = DBCPriceOCW;
+    type DlcPrice = DLCPriceOCW;
+    type SurgePricing = OnlineProfile;
+    type ManageCommittee = Committee;
+    type CancelSlashOrigin =
+        pallet_collective::EnsureProportionAtLeast<AccountId, TechnicalCollective, 1, 5>;
+    type SlashAndReward = Committee;
+    type Assets = Assets;
+    type DlcAssetId = DlcAssetId;
+    type PalletId = RentMachinePalletId;
+    type WeightInfo = rent_machine::weights::SubstrateWeight<Runtime>;
 }
 
 impl maintain_committee::Config for Runtime {
@@ -1426,7 +1450,14 @@ impl maintain_committee::Config for Runtime {
     type Slash = Treasury;
     type CancelSlashOrigin =
         pallet_collective::EnsureProportionAtLeast<AccountId, TechnicalCollective, 1, 5>;
-    type SlashAndReward = GenericFunc;
+    type SlashAndReward = Committee;
+    type AuthorityId = maintain_committee::probe::crypto::ProbeAuthId;
+    type EndpointProbe = maintain_committee::probe::TcpProbe;
+    type WeightInfo = maintain_committee::weights::SubstrateWeight<Runtime>;
+}
+
+parameter_types! {
+    pub const TerminatingRentalPalletId: PalletId = PalletId(*b"dbc/term");
 }
 
 impl terminating_rental::Config for Runtime {
@@ -1435,7 +1466,19 @@ impl terminating_rental::Config for Runtime {
     type Slash = Treasury;
     type ManageCommittee = Committee;
     type DbcPrice = DBCPriceOCW;
-    type SlashAndReward = GenericFunc;
+    type SlashAndReward = Committee;
+    type DlcPrice = DLCPriceOCW;
+    type SurgePricing = OnlineProfile;
+    type Assets = Assets;
+    type DlcAssetId = DlcAssetId;
+    type PalletId = TerminatingRentalPalletId;
+}
+
+impl machine_heartbeat::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type HBOps = OnlineProfile;
+    type MTOps = OnlineProfile;
+    type WeightInfo = machine_heartbeat::weights::SubstrateWeight<Runtime>;
 }
 
 impl simple_rpc::Config for Runtime {
@@ -1562,6 +1605,13 @@ impl eth_precompile_whitelist::Config for Runtime {
     type WhitelistLimit = ConstU32<10>;
 }
 
+impl evm_bridge::Config for Runtime {
+    type RuntimeEvent = RuntimeEvent;
+    type Currency = Balances;
+    type AddressMapping = <Runtime as pallet_evm::Config>::AddressMapping;
+    type WeightInfo = evm_bridge::weights::SubstrateWeight<Runtime>;
+}
+
 parameter_types! {
     pub DefaultBaseFeePerGas: U256 = U256::from(10_000_000_000u128);
     // No gas price adjustment for now. default is 125_000 (12.5%)
@@ -1661,6 +1711,8 @@ construct_runtime!(
         TerminatingRental: terminating_rental = 113,
         EthPrecompileWhitelist: eth_precompile_whitelist = 114,
         DLCPriceOCW: dlc_price_ocw = 115,
+        MachineHeartbeat: machine_heartbeat = 116,
+        EvmBridge: evm_bridge = 117,
 
     }
 );
@@ -2542,6 +2594,14 @@ impl_runtime_apis! {
             OnlineProfile::get_stash_era_released_reward(stash, era_index)
         }
 
+        fn search_machines(filter: online_profile::rpc_types::MachineSearchFilter, page: u32, per_page: u32) -> Vec<dbc_support::machine_info::MachineInfo<AccountId, BlockNumber, Balance>> {
+            OnlineProfile::search_machines(filter, page, per_page)
+        }
+
+        fn get_machine_availability(machine_id: MachineId, from_day: u32, to_day: u32) -> Vec<online_profile::rpc_types::DayAvailability> {
+            RentMachine::get_machine_availability(machine_id, from_day, to_day)
+        }
+
     }
 
     impl online_committee_runtime_api::OcRpcApi<Block, AccountId, BlockNumber, Balance> for Runtime {
@@ -2574,12 +2634,31 @@ impl_runtime_apis! {
         fn get_machine_rent_id(machine_id: MachineId) -> MachineGPUOrder {
             RentMachine::get_machine_rent_id(machine_id)
         }
+
+        fn get_order_escrow(rent_id: RentOrderId) -> Option<dbc_support::rental_type::RentEscrowDetail<AccountId, BlockNumber, Balance>> {
+            RentMachine::get_order_escrow(rent_id)
+        }
+
+        fn get_machine_dispute_stats(machine_id: MachineId) -> dbc_support::rental_type::MachineDisputeStats {
+            RentMachine::get_machine_dispute_stats(machine_id)
+        }
     }
 
-    impl committee_runtime_api::CmRpcApi<Block, AccountId> for Runtime {
+    impl committee_runtime_api::CmRpcApi<Block, AccountId, BlockNumber, Balance> for Runtime {
         fn get_committee_list() -> committee::CommitteeList<AccountId> {
             Committee::get_committee_list()
         }
+
+        fn get_committee_reputation(who: AccountId) -> committee::CommitteeReputationInfo {
+            Committee::get_committee_reputation(who)
+        }
+
+        fn get_committee_overview(who: AccountId) -> committee::CommitteeOverview<BlockNumber, Balance> {
+            let mut pending_tasks = OnlineCommittee::get_committee_tasks(who.clone());
+            pending_tasks.extend(MaintainCommittee::get_committee_tasks(who.clone()));
+            pending_tasks.extend(TerminatingRental::get_committee_tasks(who.clone()));
+            Committee::get_committee_overview(who, pending_tasks)
+        }
     }
 
     impl terminating_rental_runtime_api::IrRpcApi<Block, AccountId, Balance, BlockNumber> for Runtime {
@@ -2626,6 +2705,44 @@ impl_runtime_apis! {
         fn get_machine_rent_id(machine_id: MachineId) -> MachineGPUOrder {
             TerminatingRental::get_machine_rent_id(machine_id)
         }
+
+        fn get_order_escrow(rent_id: RentOrderId) -> Option<dbc_support::rental_type::RentEscrowDetail<AccountId, BlockNumber, Balance>> {
+            TerminatingRental::get_order_escrow(rent_id)
+        }
+    }
+
+    impl maintain_committee_runtime_api::MtRpcApi<Block, AccountId, Balance, BlockNumber> for Runtime {
+        fn get_report_info(report_id: ReportId) -> Option<dbc_support::report::MTReportInfoDetail<AccountId, BlockNumber, Balance>> {
+            MaintainCommittee::get_report_info(report_id)
+        }
+
+        fn get_report_result(report_id: ReportId) -> Option<dbc_support::report::MTReportResultInfo<AccountId, BlockNumber, Balance>> {
+            MaintainCommittee::get_report_result(report_id)
+        }
+
+        fn get_reporter_report(reporter: AccountId) -> dbc_support::report::ReporterReportList {
+            MaintainCommittee::get_reporter_report(reporter)
+        }
+
+        fn get_committee_order(committee: AccountId) -> dbc_support::report::MTCommitteeOrderList {
+            MaintainCommittee::get_committee_order(committee)
+        }
+
+        fn get_committee_ops(committee: AccountId, report_id: ReportId) -> dbc_support::report::MTCommitteeOpsDetail<BlockNumber, Balance> {
+            MaintainCommittee::get_committee_ops(committee, report_id)
+        }
+
+        fn get_bookable_report() -> Vec<ReportId> {
+            MaintainCommittee::get_bookable_report()
+        }
+
+        fn get_pending_slash_review(report_id: ReportId) -> Option<maintain_committee::types::MTPendingSlashReviewInfo<AccountId, Balance, BlockNumber>> {
+            MaintainCommittee::get_pending_slash_review(report_id)
+        }
+
+        fn get_pending_slash_review_list() -> Vec<ReportId> {
+            MaintainCommittee::get_pending_slash_review_list()
+        }
     }
 }
 
@@ -5,7 +5,7 @@ use fp_evm::{
 use sp_core::{Get, U256};
 use sp_runtime::RuntimeDebug;
 extern crate alloc;
-use crate::precompiles::LOG_TARGET;
+use crate::precompiles::{logs::LogsBuilder, LOG_TARGET};
 use alloc::format;
 use core::marker::PhantomData;
 use dbc_primitives::AccountId;
@@ -26,11 +26,17 @@ pub enum Selector {
     Transfer = "transfer(address,string,uint256)",
 }
 
+// Transfer(address indexed from, bytes32 indexed to, uint256 value)
+pub const SELECTOR_LOG_BRIDGE_TRANSFER: [u8; 32] =
+    *evm_macro::keccak256!("Transfer(address,bytes32,uint256)");
+
 type BalanceOf<T> = <T as pallet_balances::Config>::Balance;
 
 impl<T> Precompile for Bridge<T>
 where
-    T: pallet_evm::Config + pallet_balances::Config,
+    T: pallet_evm::Config
+        + pallet_balances::Config
+        + evm_bridge::Config<Currency = pallet_balances::Pallet<T>>,
     BalanceOf<T>: TryFrom<U256> + Into<U256>,
     T::AccountId: IsType<AccountId>,
 {
@@ -66,12 +72,12 @@ where
                     output: format!("decode param failed: {:?}", e).into(),
                 })?;
 
-                let from =
+                let from_address =
                     param[0].clone().into_address().ok_or_else(|| PrecompileFailure::Revert {
                         exit_status: ExitRevert::Reverted,
                         output: "decode param[0] failed".into(),
                     })?;
-                let from: T::AccountId = T::AddressMapping::into_account_id(from);
+                let from: T::AccountId = T::AddressMapping::into_account_id(from_address);
 
                 let to =
                     param[1].clone().into_string().ok_or_else(|| PrecompileFailure::Revert {
@@ -101,22 +107,12 @@ where
                         output: "decode param[1] failed".into(),
                     })?;
 
-                // evm decimals is 18, native balance decimals is 15
-                let amount = origin_amount.checked_div(U256::from(1000)).expect("checked. qed!");
-                // check suffix is 000
-                ensure!(
-                    origin_amount == amount.saturating_mul(U256::from(1000)),
-                    PrecompileFailure::Revert {
+                // evm decimals is 18, native balance decimals is 15, the suffix must be 000
+                let amount: BalanceOf<T> = evm_bridge::evm_amount_to_balance(origin_amount)
+                    .ok_or_else(|| PrecompileFailure::Revert {
                         exit_status: ExitRevert::Reverted,
                         output: format!("invalid amount, origin amount: {:?}", origin_amount)
                             .into(),
-                    }
-                );
-
-                let amount: BalanceOf<T> =
-                    amount.try_into().map_err(|_| PrecompileFailure::Revert {
-                        exit_status: ExitRevert::Reverted,
-                        output: format!("invalid amount: {:?}", amount).into(),
                     })?;
 
                 log::debug!(
@@ -137,6 +133,7 @@ where
                     exit_status: ExitRevert::Reverted,
                     output: format!("transfer failed: {:?}", e).into(),
                 })?;
+                evm_bridge::Pallet::<T>::on_transfer_from_evm(from_address, to, amount);
 
                 let weight = Weight::default()
                     .saturating_add(<T as frame_system::Config>::DbWeight::get().reads(2))
@@ -144,6 +141,15 @@ where
 
                 handle.record_cost(T::GasWeightMapping::weight_to_gas(weight))?;
 
+                LogsBuilder::new(handle.code_address())
+                    .log3(
+                        SELECTOR_LOG_BRIDGE_TRANSFER,
+                        from_address,
+                        to_hex,
+                        ethabi::encode(&[ethabi::Token::Uint(origin_amount)]),
+                    )
+                    .record(handle)?;
+
                 Ok(PrecompileOutput {
                     exit_status: ExitSucceed::Returned,
                     output: Default::default(),
@@ -0,0 +1,411 @@
+use fp_evm::{
+    ExitRevert, ExitSucceed, Precompile, PrecompileFailure, PrecompileHandle, PrecompileOutput,
+    PrecompileResult,
+};
+use sp_core::{Get, H160, U256};
+use sp_runtime::{traits::StaticLookup, DispatchError, RuntimeDebug, TransactionOutcome};
+extern crate alloc;
+use crate::precompiles::{logs::LogsBuilder, LOG_TARGET};
+use alloc::{format, string::String, vec::Vec};
+use core::marker::PhantomData;
+use frame_support::{
+    ensure,
+    pallet_prelude::Weight,
+    storage::with_transaction,
+    traits::fungibles::{approvals, metadata, Inspect},
+};
+use frame_system::RawOrigin;
+use num_enum::{IntoPrimitive, TryFromPrimitive};
+use pallet_assets::WeightInfo;
+use pallet_evm::{AddressMapping, GasWeightMapping};
+
+/// Address of an asset precompile: 0xFFFFFFFF + 12 zero bytes + asset id (big endian u32)
+pub const ASSET_PRECOMPILE_PREFIX: [u8; 4] = [0xff; 4];
+
+pub const SELECTOR_LOG_TRANSFER: [u8; 32] =
+    *evm_macro::keccak256!("Transfer(address,address,uint256)");
+pub const SELECTOR_LOG_APPROVAL: [u8; 32] =
+    *evm_macro::keccak256!("Approval(address,address,uint256)");
+
+pub struct Erc20Assets<T>(PhantomData<T>);
+
+#[evm_macro::generate_function_selector]
+#[derive(RuntimeDebug, Eq, PartialEq, TryFromPrimitive, IntoPrimitive)]
+#[repr(u32)]
+pub enum Selector {
+    Name = "name()",
+    Symbol = "symbol()",
+    Decimals = "decimals()",
+    TotalSupply = "totalSupply()",
+    BalanceOf = "balanceOf(address)",
+    Allowance = "allowance(address,address)",
+    Transfer = "transfer(address,uint256)",
+    Approve = "approve(address,uint256)",
+    TransferFrom = "transferFrom(address,address,uint256)",
+    TransferAndLock = "transferAndLock(address,uint256,uint256)",
+}
+
+type BalanceOf<T> = <T as pallet_assets::Config>::Balance;
+
+impl<T> Erc20Assets<T>
+where
+    T: pallet_evm::Config + pallet_assets::Config<AssetId = u32>,
+{
+    /// 地址前缀为0xFFFFFFFF时，解析出对应的资产ID
+    pub fn asset_id_of(address: H160) -> Option<u32> {
+        let bytes = address.as_bytes();
+        if bytes[..4] != ASSET_PRECOMPILE_PREFIX || bytes[4..16].iter().any(|b| *b != 0) {
+            return None
+        }
+        Some(u32::from_be_bytes(bytes[16..].try_into().expect("checked. qed!")))
+    }
+
+    pub fn address_of(asset_id: u32) -> H160 {
+        let mut bytes = [0u8; 20];
+        bytes[..4].copy_from_slice(&ASSET_PRECOMPILE_PREFIX);
+        bytes[16..].copy_from_slice(&asset_id.to_be_bytes());
+        H160(bytes)
+    }
+
+    /// 只有已创建的资产才对应预编译合约
+    pub fn is_asset_precompile(address: H160) -> bool {
+        Self::asset_id_of(address)
+            .map(pallet_assets::Pallet::<T>::asset_exists)
+            .unwrap_or_default()
+    }
+}
+
+impl<T> Precompile for Erc20Assets<T>
+where
+    T: pallet_evm::Config + pallet_assets::Config<AssetId = u32>,
+    BalanceOf<T>: TryFrom<U256> + Into<U256>,
+{
+    fn execute(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+        let asset_id = Self::asset_id_of(handle.code_address())
+            .ok_or_else(|| Self::revert("invalid asset address"))?;
+
+        let input = handle.input();
+        ensure!(
+            input.len() >= 4,
+            PrecompileFailure::Revert {
+                exit_status: ExitRevert::Reverted,
+                output: "invalid input".into(),
+            }
+        );
+
+        let selector = u32::from_be_bytes(input[..4].try_into().expect("checked. qed!"));
+        let selector: Selector = selector.try_into().map_err(|e| PrecompileFailure::Revert {
+            exit_status: ExitRevert::Reverted,
+            output: format!("invalid selector: {:?}", e).into(),
+        })?;
+        let input = input.get(4..).unwrap_or_default().to_vec();
+
+        let caller_address = handle.context().caller;
+        let caller: T::AccountId = T::AddressMapping::into_account_id(caller_address);
+
+        match selector {
+            Selector::Name => {
+                Self::record_weight(handle, <T as frame_system::Config>::DbWeight::get().reads(1))?;
+                let name = <pallet_assets::Pallet<T> as metadata::Inspect<_>>::name(asset_id);
+
+                Ok(Self::succeed(ethabi::encode(&[ethabi::Token::String(
+                    String::from_utf8_lossy(&name).into(),
+                )])))
+            },
+
+            Selector::Symbol => {
+                Self::record_weight(handle, <T as frame_system::Config>::DbWeight::get().reads(1))?;
+                let symbol = <pallet_assets::Pallet<T> as metadata::Inspect<_>>::symbol(asset_id);
+
+                Ok(Self::succeed(ethabi::encode(&[ethabi::Token::String(
+                    String::from_utf8_lossy(&symbol).into(),
+                )])))
+            },
+
+            Selector::Decimals => {
+                Self::record_weight(handle, <T as frame_system::Config>::DbWeight::get().reads(1))?;
+                let decimals =
+                    <pallet_assets::Pallet<T> as metadata::Inspect<_>>::decimals(asset_id);
+
+                Ok(Self::succeed(ethabi::encode(&[ethabi::Token::Uint(decimals.into())])))
+            },
+
+            Selector::TotalSupply => {
+                Self::record_weight(handle, <T as frame_system::Config>::DbWeight::get().reads(1))?;
+                let total_supply = pallet_assets::Pallet::<T>::total_issuance(asset_id);
+
+                Ok(Self::succeed(ethabi::encode(&[ethabi::Token::Uint(total_supply.into())])))
+            },
+
+            Selector::BalanceOf => {
+                let param = Self::decode(&[ethabi::ParamType::Address], &input)?;
+                let who = T::AddressMapping::into_account_id(Self::address_param(&param, 0)?);
+
+                Self::record_weight(handle, <T as frame_system::Config>::DbWeight::get().reads(1))?;
+                let balance = pallet_assets::Pallet::<T>::balance(asset_id, who);
+
+                Ok(Self::succeed(ethabi::encode(&[ethabi::Token::Uint(balance.into())])))
+            },
+
+            Selector::Allowance => {
+                let param = Self::decode(
+                    &[
+                        ethabi::ParamType::Address, // owner
+                        ethabi::ParamType::Address, // spender
+                    ],
+                    &input,
+                )?;
+                let owner = T::AddressMapping::into_account_id(Self::address_param(&param, 0)?);
+                let spender = T::AddressMapping::into_account_id(Self::address_param(&param, 1)?);
+
+                Self::record_weight(handle, <T as frame_system::Config>::DbWeight::get().reads(1))?;
+                let allowance = <pallet_assets::Pallet<T> as approvals::Inspect<_>>::allowance(
+                    asset_id, &owner, &spender,
+                );
+
+                Ok(Self::succeed(ethabi::encode(&[ethabi::Token::Uint(allowance.into())])))
+            },
+
+            Selector::Transfer => {
+                Self::ensure_not_static(handle)?;
+                let param = Self::decode(
+                    &[
+                        ethabi::ParamType::Address,   // to
+                        ethabi::ParamType::Uint(256), // value
+                    ],
+                    &input,
+                )?;
+                let to_address = Self::address_param(&param, 0)?;
+                let value = Self::uint_param(&param, 1)?;
+                let to = T::AddressMapping::into_account_id(to_address);
+
+                Self::record_weight(handle, <T as pallet_assets::Config>::WeightInfo::transfer())?;
+                let amount = Self::balance_param(value)?;
+                Self::dispatch(|| {
+                    pallet_assets::Pallet::<T>::transfer(
+                        RawOrigin::Signed(caller).into(),
+                        asset_id.into(),
+                        T::Lookup::unlookup(to),
+                        amount,
+                    )
+                })?;
+                Self::log3(handle, SELECTOR_LOG_TRANSFER, caller_address, to_address, value)?;
+
+                Ok(Self::succeed(ethabi::encode(&[ethabi::Token::Bool(true)])))
+            },
+
+            Selector::Approve => {
+                Self::ensure_not_static(handle)?;
+                let param = Self::decode(
+                    &[
+                        ethabi::ParamType::Address,   // spender
+                        ethabi::ParamType::Uint(256), // value
+                    ],
+                    &input,
+                )?;
+                let spender_address = Self::address_param(&param, 0)?;
+                let value = Self::uint_param(&param, 1)?;
+                let amount = Self::balance_param(value)?;
+                let spender = T::AddressMapping::into_account_id(spender_address);
+
+                Self::record_weight(
+                    handle,
+                    <T as pallet_assets::Config>::WeightInfo::cancel_approval().saturating_add(
+                        <T as pallet_assets::Config>::WeightInfo::approve_transfer(),
+                    ),
+                )?;
+
+                // pallet_assets的授权是累加的，ERC20的approve为覆盖原有授权额度
+                // 授权时从调用者的DBC中保留ApprovalDeposit，取消授权或额度用完时退还
+                let allowance = <pallet_assets::Pallet<T> as approvals::Inspect<_>>::allowance(
+                    asset_id, &caller, &spender,
+                );
+                Self::dispatch(|| {
+                    if allowance != Default::default() {
+                        pallet_assets::Pallet::<T>::cancel_approval(
+                            RawOrigin::Signed(caller.clone()).into(),
+                            asset_id.into(),
+                            T::Lookup::unlookup(spender.clone()),
+                        )?;
+                    }
+                    if amount != Default::default() {
+                        pallet_assets::Pallet::<T>::approve_transfer(
+                            RawOrigin::Signed(caller).into(),
+                            asset_id.into(),
+                            T::Lookup::unlookup(spender),
+                            amount,
+                        )?;
+                    }
+                    Ok(())
+                })?;
+                Self::log3(handle, SELECTOR_LOG_APPROVAL, caller_address, spender_address, value)?;
+
+                Ok(Self::succeed(ethabi::encode(&[ethabi::Token::Bool(true)])))
+            },
+
+            Selector::TransferFrom => {
+                Self::ensure_not_static(handle)?;
+                let param = Self::decode(
+                    &[
+                        ethabi::ParamType::Address,   // from
+                        ethabi::ParamType::Address,   // to
+                        ethabi::ParamType::Uint(256), // value
+                    ],
+                    &input,
+                )?;
+                let from_address = Self::address_param(&param, 0)?;
+                let to_address = Self::address_param(&param, 1)?;
+                let value = Self::uint_param(&param, 2)?;
+                let from = T::AddressMapping::into_account_id(from_address);
+                let to = T::AddressMapping::into_account_id(to_address);
+
+                Self::record_weight(
+                    handle,
+                    <T as pallet_assets::Config>::WeightInfo::transfer_approved(),
+                )?;
+                let amount = Self::balance_param(value)?;
+                Self::dispatch(|| {
+                    pallet_assets::Pallet::<T>::transfer_approved(
+                        RawOrigin::Signed(caller).into(),
+                        asset_id.into(),
+                        T::Lookup::unlookup(from),
+                        T::Lookup::unlookup(to),
+                        amount,
+                    )
+                })?;
+                Self::log3(handle, SELECTOR_LOG_TRANSFER, from_address, to_address, value)?;
+
+                Ok(Self::succeed(ethabi::encode(&[ethabi::Token::Bool(true)])))
+            },
+
+            Selector::TransferAndLock => {
+                Self::ensure_not_static(handle)?;
+                let param = Self::decode(
+                    &[
+                        ethabi::ParamType::Address,   // to
+                        ethabi::ParamType::Uint(256), // value
+                        ethabi::ParamType::Uint(256), // lock_duration, in blocks
+                    ],
+                    &input,
+                )?;
+                let to_address = Self::address_param(&param, 0)?;
+                let value = Self::uint_param(&param, 1)?;
+                let lock_duration = Self::uint_param(&param, 2)?;
+                ensure!(
+                    lock_duration <= U256::from(u32::MAX),
+                    Self::revert("invalid lock duration")
+                );
+                let to = T::AddressMapping::into_account_id(to_address);
+
+                Self::record_weight(
+                    handle,
+                    <T as pallet_assets::Config>::WeightInfo::transfer_and_lock(),
+                )?;
+                let amount = Self::balance_param(value)?;
+                Self::dispatch(|| {
+                    pallet_assets::Pallet::<T>::transfer_and_lock(
+                        RawOrigin::Signed(caller.clone()).into(),
+                        asset_id,
+                        T::Lookup::unlookup(to.clone()),
+                        amount,
+                        lock_duration.low_u32().into(),
+                    )
+                })?;
+                Self::log3(handle, SELECTOR_LOG_TRANSFER, caller_address, to_address, value)?;
+
+                log::debug!(
+                    target: LOG_TARGET,
+                    "asset transfer and lock: asset: {:?}, from: {:?}, to: {:?}, value: {:?}",
+                    asset_id,
+                    caller,
+                    to,
+                    value
+                );
+                Ok(Self::succeed(ethabi::encode(&[ethabi::Token::Bool(true)])))
+            },
+        }
+    }
+}
+
+impl<T> Erc20Assets<T>
+where
+    T: pallet_evm::Config + pallet_assets::Config<AssetId = u32>,
+    BalanceOf<T>: TryFrom<U256> + Into<U256>,
+{
+    fn revert(output: &str) -> PrecompileFailure {
+        PrecompileFailure::Revert { exit_status: ExitRevert::Reverted, output: output.into() }
+    }
+
+    fn succeed(output: Vec<u8>) -> PrecompileOutput {
+        PrecompileOutput { exit_status: ExitSucceed::Returned, output }
+    }
+
+    fn ensure_not_static(handle: &impl PrecompileHandle) -> Result<(), PrecompileFailure> {
+        ensure!(!handle.is_static(), Self::revert("cannot modify state in static call"));
+        Ok(())
+    }
+
+    fn decode(
+        types: &[ethabi::ParamType],
+        input: &[u8],
+    ) -> Result<Vec<ethabi::Token>, PrecompileFailure> {
+        ethabi::decode(types, input).map_err(|e| PrecompileFailure::Revert {
+            exit_status: ExitRevert::Reverted,
+            output: format!("decode param failed: {:?}", e).into(),
+        })
+    }
+
+    fn address_param(param: &[ethabi::Token], index: usize) -> Result<H160, PrecompileFailure> {
+        param[index].clone().into_address().ok_or_else(|| PrecompileFailure::Revert {
+            exit_status: ExitRevert::Reverted,
+            output: format!("decode param[{}] failed", index).into(),
+        })
+    }
+
+    fn uint_param(param: &[ethabi::Token], index: usize) -> Result<U256, PrecompileFailure> {
+        param[index].clone().into_uint().ok_or_else(|| PrecompileFailure::Revert {
+            exit_status: ExitRevert::Reverted,
+            output: format!("decode param[{}] failed", index).into(),
+        })
+    }
+
+    // asset balance uses the decimals in asset metadata, no conversion needed
+    fn balance_param(value: U256) -> Result<BalanceOf<T>, PrecompileFailure> {
+        value.try_into().map_err(|_| PrecompileFailure::Revert {
+            exit_status: ExitRevert::Reverted,
+            output: format!("invalid amount: {:?}", value).into(),
+        })
+    }
+
+    // emit `event(address indexed, address indexed, uint256)`
+    fn log3(
+        handle: &mut impl PrecompileHandle,
+        selector: [u8; 32],
+        from: H160,
+        to: H160,
+        value: U256,
+    ) -> Result<(), PrecompileFailure> {
+        LogsBuilder::new(handle.code_address())
+            .log3(selector, from, to, ethabi::encode(&[ethabi::Token::Uint(value)]))
+            .record(handle)
+    }
+
+    fn record_weight(
+        handle: &mut impl PrecompileHandle,
+        weight: Weight,
+    ) -> Result<(), PrecompileFailure> {
+        handle.record_cost(T::GasWeightMapping::weight_to_gas(weight))?;
+        Ok(())
+    }
+
+    fn dispatch(call: impl FnOnce() -> Result<(), DispatchError>) -> Result<(), PrecompileFailure> {
+        with_transaction(|| match call() {
+            Ok(()) => TransactionOutcome::Commit(Ok(())),
+            Err(e) => TransactionOutcome::Rollback(Err(e)),
+        })
+        .map_err(|e| PrecompileFailure::Revert {
+            exit_status: ExitRevert::Reverted,
+            output: format!("dispatch failed: {:?}", e).into(),
+        })
+    }
+}
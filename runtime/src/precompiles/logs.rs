@@ -0,0 +1,89 @@
+use fp_evm::{ExitError, PrecompileFailure, PrecompileHandle};
+use sp_core::{H160, H256};
+extern crate alloc;
+use alloc::{vec, vec::Vec};
+
+// Gas of LOG opcode: 375 + 375 * topics + 8 * data bytes
+pub const LOG_GAS: u64 = 375;
+pub const LOG_TOPIC_GAS: u64 = 375;
+pub const LOG_DATA_BYTE_GAS: u64 = 8;
+
+/// Gas cost of a log with `topics` topics and `data_len` bytes of data
+pub fn log_costs(topics: usize, data_len: usize) -> Result<u64, PrecompileFailure> {
+    // LOG4 at most
+    if topics > 4 {
+        return Err(PrecompileFailure::Error {
+            exit_status: ExitError::Other("too many topics".into()),
+        })
+    }
+
+    let data_cost = LOG_DATA_BYTE_GAS
+        .checked_mul(data_len as u64)
+        .ok_or(PrecompileFailure::Error { exit_status: ExitError::OutOfGas })?;
+    LOG_GAS
+        .saturating_add(LOG_TOPIC_GAS * topics as u64)
+        .checked_add(data_cost)
+        .ok_or(PrecompileFailure::Error { exit_status: ExitError::OutOfGas })
+}
+
+/// A log to be emitted by a precompile
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Log {
+    pub address: H160,
+    pub topics: Vec<H256>,
+    pub data: Vec<u8>,
+}
+
+impl Log {
+    pub fn cost(&self) -> Result<u64, PrecompileFailure> {
+        log_costs(self.topics.len(), self.data.len())
+    }
+
+    /// Charge the gas of the log, then emit it
+    pub fn record(self, handle: &mut impl PrecompileHandle) -> Result<(), PrecompileFailure> {
+        handle.record_cost(self.cost()?)?;
+        handle.log(self.address, self.topics, self.data)?;
+        Ok(())
+    }
+}
+
+/// Build logs emitted from the precompile `address`, e.g.
+/// `LogsBuilder::new(address).log3(SELECTOR_LOG_TRANSFER, from, to, data).record(handle)?`
+pub struct LogsBuilder {
+    address: H160,
+}
+
+impl LogsBuilder {
+    pub fn new(address: H160) -> Self {
+        Self { address }
+    }
+
+    pub fn log1(&self, topic0: impl Into<H256>, data: Vec<u8>) -> Log {
+        Log { address: self.address, topics: vec![topic0.into()], data }
+    }
+
+    pub fn log2(&self, topic0: impl Into<H256>, topic1: impl Into<H256>, data: Vec<u8>) -> Log {
+        Log { address: self.address, topics: vec![topic0.into(), topic1.into()], data }
+    }
+
+    pub fn log3(
+        &self,
+        topic0: impl Into<H256>,
+        topic1: impl Into<H256>,
+        topic2: impl Into<H256>,
+        data: Vec<u8>,
+    ) -> Log {
+        Log {
+            address: self.address,
+            topics: vec![topic0.into(), topic1.into(), topic2.into()],
+            data,
+        }
+    }
+}
+
+/// Indexed `uint256` topic
+pub fn uint_topic(value: impl Into<sp_core::U256>) -> H256 {
+    let mut topic = [0u8; 32];
+    value.into().to_big_endian(&mut topic);
+    H256(topic)
+}
@@ -0,0 +1,346 @@
+use fp_evm::{
+    ExitRevert, ExitSucceed, Precompile, PrecompileFailure, PrecompileHandle, PrecompileOutput,
+    PrecompileResult,
+};
+use sp_core::{Get, U256};
+use sp_runtime::{DispatchError, RuntimeDebug, TransactionOutcome};
+extern crate alloc;
+use crate::precompiles::{
+    logs::{uint_topic, LogsBuilder},
+    LOG_TARGET,
+};
+use alloc::format;
+use core::marker::PhantomData;
+use dbc_primitives::AccountId;
+use dbc_support::rental_type::RentStatus;
+use frame_support::{
+    ensure,
+    pallet_prelude::{IsType, Weight},
+    storage::with_transaction,
+};
+use num_enum::{IntoPrimitive, TryFromPrimitive};
+use pallet_evm::{AddressMapping, GasWeightMapping};
+use rent_machine::WeightInfo;
+use sp_runtime::traits::SaturatedConversion;
+
+pub struct MachineRent<T>(PhantomData<T>);
+
+#[evm_macro::generate_function_selector]
+#[derive(RuntimeDebug, Eq, PartialEq, TryFromPrimitive, IntoPrimitive)]
+#[repr(u32)]
+pub enum Selector {
+    RentMachine = "rentMachine(string,uint256,uint256)",
+    ConfirmRent = "confirmRent(uint256)",
+    ReletMachine = "reletMachine(uint256,uint256)",
+    GetRentOrder = "getRentOrder(uint256)",
+}
+
+// RentMachine(address indexed renter, uint256 indexed rentId, string machineId, uint256 gpuNum,
+//   uint256 duration)
+pub const SELECTOR_LOG_RENT_MACHINE: [u8; 32] =
+    *evm_macro::keccak256!("RentMachine(address,uint256,string,uint256,uint256)");
+// ConfirmRent(address indexed renter, uint256 indexed rentId)
+pub const SELECTOR_LOG_CONFIRM_RENT: [u8; 32] =
+    *evm_macro::keccak256!("ConfirmRent(address,uint256)");
+// ReletMachine(address indexed renter, uint256 indexed rentId, uint256 duration)
+pub const SELECTOR_LOG_RELET_MACHINE: [u8; 32] =
+    *evm_macro::keccak256!("ReletMachine(address,uint256,uint256)");
+
+impl<T> Precompile for MachineRent<T>
+where
+    T: pallet_evm::Config + rent_machine::Config,
+    T::AccountId: IsType<AccountId>,
+{
+    fn execute(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+        let input = handle.input();
+        ensure!(
+            input.len() >= 4,
+            PrecompileFailure::Revert {
+                exit_status: ExitRevert::Reverted,
+                output: "invalid input".into(),
+            }
+        );
+
+        let selector = u32::from_be_bytes(input[..4].try_into().expect("checked. qed!"));
+        let selector: Selector = selector.try_into().map_err(|e| PrecompileFailure::Revert {
+            exit_status: ExitRevert::Reverted,
+            output: format!("invalid selector: {:?}", e).into(),
+        })?;
+
+        match selector {
+            Selector::RentMachine => {
+                let param = ethabi::decode(
+                    &[
+                        ethabi::ParamType::String,    // machine_id
+                        ethabi::ParamType::Uint(256), // rent_gpu_count
+                        ethabi::ParamType::Uint(256), // rent_block_numbers
+                    ],
+                    &input.get(4..).unwrap_or_default(),
+                )
+                .map_err(|e| PrecompileFailure::Revert {
+                    exit_status: ExitRevert::Reverted,
+                    output: format!("decode param failed: {:?}", e).into(),
+                })?;
+
+                let machine_id_str =
+                    param[0].clone().into_string().ok_or_else(|| PrecompileFailure::Revert {
+                        exit_status: ExitRevert::Reverted,
+                        output: "decode param[0] failed".into(),
+                    })?;
+                let machine_id = machine_id_str.as_bytes().to_vec();
+
+                let rent_gpu_count: u32 = Self::uint_param(&param, 1)?;
+                let rent_duration: u64 = Self::uint_param(&param, 2)?;
+                let duration = Self::block_number(rent_duration)?;
+
+                Self::record_weight(
+                    handle,
+                    <T as rent_machine::Config>::WeightInfo::rent_machine(),
+                )?;
+
+                let renter: T::AccountId =
+                    T::AddressMapping::into_account_id(handle.context().caller);
+                // no order is created for a machine without GPU or a zero duration
+                let rent_id = Self::dispatch(|| {
+                    <rent_machine::Pallet<T>>::rent_machine_by_block(
+                        renter.clone(),
+                        machine_id.clone(),
+                        rent_gpu_count,
+                        duration,
+                        None,
+                    )
+                })?
+                .ok_or_else(|| PrecompileFailure::Revert {
+                    exit_status: ExitRevert::Reverted,
+                    output: "no rent order created".into(),
+                })?;
+
+                LogsBuilder::new(handle.code_address())
+                    .log3(
+                        SELECTOR_LOG_RENT_MACHINE,
+                        handle.context().caller,
+                        uint_topic(rent_id),
+                        ethabi::encode(&[
+                            ethabi::Token::String(machine_id_str.clone()),
+                            ethabi::Token::Uint(rent_gpu_count.into()),
+                            ethabi::Token::Uint(rent_duration.into()),
+                        ]),
+                    )
+                    .record(handle)?;
+
+                log::debug!(
+                    target: LOG_TARGET,
+                    "rent_machine: renter: {:?}, machine_id: {}, rent_id: {}",
+                    renter,
+                    machine_id_str,
+                    rent_id
+                );
+
+                Ok(PrecompileOutput {
+                    exit_status: ExitSucceed::Returned,
+                    output: ethabi::encode(&[ethabi::Token::Uint(rent_id.into())]),
+                })
+            },
+
+            Selector::ConfirmRent => {
+                let param = ethabi::decode(
+                    &[ethabi::ParamType::Uint(256)], // rent_id
+                    &input.get(4..).unwrap_or_default(),
+                )
+                .map_err(|e| PrecompileFailure::Revert {
+                    exit_status: ExitRevert::Reverted,
+                    output: format!("decode param failed: {:?}", e).into(),
+                })?;
+
+                let rent_id: u64 = Self::uint_param(&param, 0)?;
+
+                Self::record_weight(
+                    handle,
+                    <T as rent_machine::Config>::WeightInfo::confirm_rent(),
+                )?;
+
+                let renter: T::AccountId =
+                    T::AddressMapping::into_account_id(handle.context().caller);
+                Self::dispatch(|| {
+                    <rent_machine::Pallet<T>>::confirm_rent_order(renter.clone(), rent_id)
+                })?;
+
+                LogsBuilder::new(handle.code_address())
+                    .log3(
+                        SELECTOR_LOG_CONFIRM_RENT,
+                        handle.context().caller,
+                        uint_topic(rent_id),
+                        Default::default(),
+                    )
+                    .record(handle)?;
+
+                log::debug!(
+                    target: LOG_TARGET,
+                    "confirm_rent: renter: {:?}, rent_id: {}",
+                    renter,
+                    rent_id
+                );
+
+                Ok(PrecompileOutput {
+                    exit_status: ExitSucceed::Returned,
+                    output: ethabi::encode(&[ethabi::Token::Bool(true)]),
+                })
+            },
+
+            Selector::ReletMachine => {
+                let param = ethabi::decode(
+                    &[
+                        ethabi::ParamType::Uint(256), // rent_id
+                        ethabi::ParamType::Uint(256), // relet_block_numbers
+                    ],
+                    &input.get(4..).unwrap_or_default(),
+                )
+                .map_err(|e| PrecompileFailure::Revert {
+                    exit_status: ExitRevert::Reverted,
+                    output: format!("decode param failed: {:?}", e).into(),
+                })?;
+
+                let rent_id: u64 = Self::uint_param(&param, 0)?;
+                let relet_duration: u64 = Self::uint_param(&param, 1)?;
+                let duration = Self::block_number(relet_duration)?;
+
+                Self::record_weight(
+                    handle,
+                    <T as rent_machine::Config>::WeightInfo::relet_machine(),
+                )?;
+
+                let renter: T::AccountId =
+                    T::AddressMapping::into_account_id(handle.context().caller);
+                Self::dispatch(|| {
+                    <rent_machine::Pallet<T>>::relet_machine_by_block(
+                        renter.clone(),
+                        rent_id,
+                        duration,
+                    )
+                    .map(|_| ())
+                    .map_err(|e| e.error)
+                })?;
+
+                LogsBuilder::new(handle.code_address())
+                    .log3(
+                        SELECTOR_LOG_RELET_MACHINE,
+                        handle.context().caller,
+                        uint_topic(rent_id),
+                        ethabi::encode(&[ethabi::Token::Uint(relet_duration.into())]),
+                    )
+                    .record(handle)?;
+
+                log::debug!(
+                    target: LOG_TARGET,
+                    "relet_machine: renter: {:?}, rent_id: {}, relet_duration: {}",
+                    renter,
+                    rent_id,
+                    relet_duration
+                );
+
+                Ok(PrecompileOutput {
+                    exit_status: ExitSucceed::Returned,
+                    output: ethabi::encode(&[ethabi::Token::Bool(true)]),
+                })
+            },
+
+            Selector::GetRentOrder => {
+                let param = ethabi::decode(
+                    &[ethabi::ParamType::Uint(256)], // rent_id
+                    &input.get(4..).unwrap_or_default(),
+                )
+                .map_err(|e| PrecompileFailure::Revert {
+                    exit_status: ExitRevert::Reverted,
+                    output: format!("decode param failed: {:?}", e).into(),
+                })?;
+
+                let rent_id: u64 = Self::uint_param(&param, 0)?;
+
+                Self::record_weight(handle, <T as frame_system::Config>::DbWeight::get().reads(1))?;
+
+                let rent_info = <rent_machine::Pallet<T>>::rent_info(rent_id).ok_or_else(|| {
+                    PrecompileFailure::Revert {
+                        exit_status: ExitRevert::Reverted,
+                        output: format!("rent order not found, rent_id: {}", rent_id).into(),
+                    }
+                })?;
+
+                let renter: AccountId = rent_info.renter.into();
+                let renter: [u8; 32] = renter.into();
+                let rent_status: u8 = match rent_info.rent_status {
+                    RentStatus::WaitingVerifying => 0,
+                    RentStatus::Renting => 1,
+                    RentStatus::RentExpired => 2,
+                };
+                let stake_amount: u128 = rent_info.stake_amount.saturated_into();
+
+                Ok(PrecompileOutput {
+                    exit_status: ExitSucceed::Returned,
+                    output: ethabi::encode(&[
+                        ethabi::Token::FixedBytes(renter.to_vec()),
+                        ethabi::Token::String(
+                            alloc::string::String::from_utf8_lossy(&rent_info.machine_id).into(),
+                        ),
+                        ethabi::Token::Uint(rent_info.gpu_num.into()),
+                        ethabi::Token::Uint(rent_info.rent_start.saturated_into::<u64>().into()),
+                        ethabi::Token::Uint(rent_info.confirm_rent.saturated_into::<u64>().into()),
+                        ethabi::Token::Uint(rent_info.rent_end.saturated_into::<u64>().into()),
+                        ethabi::Token::Uint(U256::from(stake_amount)),
+                        ethabi::Token::Uint(rent_status.into()),
+                    ]),
+                })
+            },
+        }
+    }
+}
+
+impl<T> MachineRent<T>
+where
+    T: pallet_evm::Config + rent_machine::Config,
+{
+    // revert instead of truncating when the value does not fit into the target type
+    fn uint_param<N: TryFrom<U256>>(
+        param: &[ethabi::Token],
+        index: usize,
+    ) -> Result<N, PrecompileFailure> {
+        let value = param[index].clone().into_uint().ok_or_else(|| PrecompileFailure::Revert {
+            exit_status: ExitRevert::Reverted,
+            output: format!("decode param[{}] failed", index).into(),
+        })?;
+        value.try_into().map_err(|_| PrecompileFailure::Revert {
+            exit_status: ExitRevert::Reverted,
+            output: format!("param[{}] out of range: {:?}", index, value).into(),
+        })
+    }
+
+    fn block_number(duration: u64) -> Result<T::BlockNumber, PrecompileFailure> {
+        duration.try_into().map_err(|_| PrecompileFailure::Revert {
+            exit_status: ExitRevert::Reverted,
+            output: format!("duration out of range: {}", duration).into(),
+        })
+    }
+
+    // charge the gas before dispatching, so that out of gas calls do not touch the storage
+    fn record_weight(
+        handle: &mut impl PrecompileHandle,
+        weight: Weight,
+    ) -> Result<(), PrecompileFailure> {
+        handle.record_cost(T::GasWeightMapping::weight_to_gas(weight))?;
+        Ok(())
+    }
+
+    // rent_machine calls are not transactional when called directly, roll back the storage
+    // changes if the call fails halfway
+    fn dispatch<R>(
+        call: impl FnOnce() -> Result<R, DispatchError>,
+    ) -> Result<R, PrecompileFailure> {
+        with_transaction(|| match call() {
+            Ok(result) => TransactionOutcome::Commit(Ok(result)),
+            Err(e) => TransactionOutcome::Rollback(Err(e)),
+        })
+        .map_err(|e| PrecompileFailure::Revert {
+            exit_status: ExitRevert::Reverted,
+            output: format!("dispatch failed: {:?}", e).into(),
+        })
+    }
+}
@@ -0,0 +1,654 @@
+use dbc_primitives::{AccountId, Balance};
+use dbc_support::{
+    machine_type::{
+        CommitteeUploadInfo, Latitude, Longitude, StakerCustomizeInfo, StandardGpuPointPrice,
+    },
+    EraIndex, ONE_DAY,
+};
+use fp_evm::{Context, ExitError, ExitReason, PrecompileHandle, Transfer};
+use frame_election_provider_support::NoElection;
+use frame_support::{
+    assert_ok, parameter_types,
+    traits::{
+        AsEnsureOriginWithArg, ConstU128, ConstU32, ConstU64, Currency, OnFinalize, OnInitialize,
+    },
+    weights::Weight,
+    PalletId,
+};
+use frame_system::{EnsureRoot, EnsureWithSuccess, RawOrigin};
+use pallet_evm::{
+    AddressMapping, EnsureAddressNever, EnsureAddressRoot, FixedGasWeightMapping,
+    HashedAddressMapping, SubstrateBlockHashMapping,
+};
+use sp_core::{H160, H256, U256};
+use sp_runtime::{
+    testing::{Header, TestXt},
+    traits::{BlakeTwo256, IdentityLookup, Verify},
+    MultiSignature, Perbill,
+};
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<TestRuntime>;
+type Block = frame_system::mocking::MockBlock<TestRuntime>;
+
+pub const ONE_DBC: Balance = 1_000_000_000_000_000;
+pub const INIT_BALANCE: Balance = 10_000_000 * ONE_DBC;
+// evm decimals is 18, native balance decimals is 15
+pub const ONE_EVM_DBC: u128 = ONE_DBC * 1000;
+
+parameter_types! {
+    pub const BlockHashCount: u64 = 250;
+    pub const SS58Prefix: u8 = 42;
+}
+
+impl frame_system::Config for TestRuntime {
+    type BaseCallFilter = frame_support::traits::Everything;
+    type BlockWeights = ();
+    type BlockLength = ();
+    type DbWeight = ();
+    type RuntimeOrigin = RuntimeOrigin;
+    type RuntimeCall = RuntimeCall;
+    type Index = u64;
+    type BlockNumber = u64;
+    type Hash = H256;
+    type Hashing = BlakeTwo256;
+    type AccountId = AccountId;
+    type Lookup = IdentityLookup<Self::AccountId>;
+    type Header = Header;
+    type RuntimeEvent = RuntimeEvent;
+    type BlockHashCount = BlockHashCount;
+    type Version = ();
+    type PalletInfo = PalletInfo;
+    type AccountData = pallet_balances::AccountData<Balance>;
+    type OnNewAccount = ();
+    type OnKilledAccount = ();
+    type SystemWeightInfo = ();
+    type SS58Prefix = SS58Prefix;
+    type OnSetCode = ();
+    type MaxConsumers = ConstU32<16>;
+}
+
+impl pallet_balances::Config for TestRuntime {
+    type MaxLocks = ConstU32<50>;
+    type MaxReserves = ConstU32<50>;
+    type ReserveIdentifier = [u8; 8];
+    type Balance = Balance;
+    type RuntimeEvent = RuntimeEvent;
+    type DustRemoval = ();
+    type ExistentialDeposit = ConstU128<ONE_DBC>;
+    type AccountStore = System;
+    type WeightInfo = ();
+    type HoldIdentifier = ();
+    type FreezeIdentifier = ();
+    type MaxHolds = ();
+    type MaxFreezes = ();
+}
+
+pub const ASSET_ID: u32 = 88;
+pub const ASSET_MIN_BALANCE: u128 = 1;
+
+parameter_types! {
+    pub const MinLockAmount: u128 = 100;
+    pub const MaxLockDuration: u64 = 1000;
+}
+
+impl pallet_assets::Config for TestRuntime {
+    type RuntimeEvent = RuntimeEvent;
+    type Balance = u128;
+    type AssetId = u32;
+    type AssetIdParameter = u32;
+    type Currency = Balances;
+    type CreateOrigin = AsEnsureOriginWithArg<frame_system::EnsureSigned<AccountId>>;
+    type ForceOrigin = frame_system::EnsureRoot<AccountId>;
+    type AssetDeposit = ConstU128<ONE_DBC>;
+    type AssetAccountDeposit = ConstU128<ONE_DBC>;
+    type MetadataDepositBase = ConstU128<ONE_DBC>;
+    type MetadataDepositPerByte = ConstU128<ONE_DBC>;
+    type ApprovalDeposit = ConstU128<ONE_DBC>;
+    type StringLimit = ConstU32<50>;
+    type Freezer = ();
+    type Extra = ();
+    type CallbackHandle = ();
+    type WeightInfo = pallet_assets::weights::SubstrateWeight<Self>;
+    type RemoveItemsLimit = ConstU32<1000>;
+    #[cfg(feature = "runtime-benchmarks")]
+    type BenchmarkHelper = ();
+    type MinLockAmount = MinLockAmount;
+    type MaxLockDuration = MaxLockDuration;
+}
+
+impl pallet_timestamp::Config for TestRuntime {
+    type Moment = u64;
+    type OnTimestampSet = ();
+    type MinimumPeriod = ConstU64<5>;
+    type WeightInfo = ();
+}
+
+parameter_types! {
+    pub BlockGasLimit: U256 = U256::from(u64::MAX);
+    pub const WeightPerGas: Weight = Weight::from_parts(20_000, 0);
+    pub const ChainId: u64 = 19880818;
+}
+
+impl pallet_evm::Config for TestRuntime {
+    type FeeCalculator = ();
+    type GasWeightMapping = FixedGasWeightMapping<Self>;
+    type WeightPerGas = WeightPerGas;
+    type BlockHashMapping = SubstrateBlockHashMapping<Self>;
+    type CallOrigin = EnsureAddressRoot<AccountId>;
+    type WithdrawOrigin = EnsureAddressNever<AccountId>;
+    type AddressMapping = HashedAddressMapping<BlakeTwo256>;
+    type Currency = Balances;
+    type RuntimeEvent = RuntimeEvent;
+    type PrecompilesType = ();
+    type PrecompilesValue = ();
+    type ChainId = ChainId;
+    type BlockGasLimit = BlockGasLimit;
+    type Runner = pallet_evm::runner::stack::Runner<Self>;
+    type OnChargeTransaction = ();
+    type OnCreate = ();
+    type FindAuthor = ();
+    type GasLimitPovSizeRatio = ConstU64<4>;
+    type Timestamp = Timestamp;
+    type WeightInfo = ();
+}
+
+parameter_types! {
+    pub const OffendingValidatorsThreshold: Perbill = Perbill::from_percent(17);
+}
+
+impl pallet_staking::Config for TestRuntime {
+    type MaxNominations = ConstU32<16>;
+    type Currency = Balances;
+    type CurrencyBalance = Balance;
+    type UnixTime = Timestamp;
+    type CurrencyToVote = frame_support::traits::SaturatingCurrencyToVote;
+    type RewardRemainder = ();
+    type RuntimeEvent = RuntimeEvent;
+    type Slash = ();
+    type Reward = ();
+    type SessionsPerEra = ConstU32<3>;
+    type BondingDuration = ConstU32<3>;
+    type SlashDeferDuration = ConstU32<0>;
+    type AdminOrigin = frame_system::EnsureRoot<AccountId>;
+    type SessionInterface = ();
+    type EraPayout = ();
+    type NextNewSession = ();
+    type MaxNominatorRewardedPerValidator = ConstU32<64>;
+    type OffendingValidatorsThreshold = OffendingValidatorsThreshold;
+    type ElectionProvider = NoElection<(AccountId, u64, Staking, ConstU32<100>)>;
+    type GenesisElectionProvider = Self::ElectionProvider;
+    type VoterList = pallet_staking::UseNominatorsAndValidatorsMap<Self>;
+    type TargetList = pallet_staking::UseValidatorsMap<Self>;
+    type MaxUnlockingChunks = ConstU32<32>;
+    type HistoryDepth = ConstU32<84>;
+    type OnStakerSlash = ();
+    type BenchmarkingConfig = pallet_staking::TestBenchmarkingConfig;
+    type WeightInfo = pallet_staking::weights::SubstrateWeight<Self>;
+    type MinimumPeriod = ConstU64<5>;
+}
+
+impl evm_bridge::Config for TestRuntime {
+    type RuntimeEvent = RuntimeEvent;
+    type Currency = Balances;
+    type AddressMapping = <TestRuntime as pallet_evm::Config>::AddressMapping;
+    type WeightInfo = ();
+}
+
+impl pallet_insecure_randomness_collective_flip::Config for TestRuntime {}
+
+impl generic_func::Config for TestRuntime {
+    type Currency = Balances;
+    type RuntimeEvent = RuntimeEvent;
+    type RandomnessSource = RandomnessCollectiveFlip;
+    type FixedTxFee = Treasury;
+    type Slash = Treasury;
+}
+
+parameter_types! {
+    pub const TreasuryPalletId: PalletId = PalletId(*b"py/trsry");
+    pub const MaxBalance: Balance = Balance::max_value();
+}
+
+impl pallet_treasury::Config for TestRuntime {
+    type PalletId = TreasuryPalletId;
+    type Currency = Balances;
+    type ApproveOrigin = EnsureRoot<AccountId>;
+    type RejectOrigin = EnsureRoot<AccountId>;
+    type RuntimeEvent = RuntimeEvent;
+    type OnSlash = ();
+    type ProposalBond = ();
+    type ProposalBondMinimum = ();
+    type SpendPeriod = ConstU64<2>;
+    type Burn = ();
+    type BurnDestination = ();
+    type WeightInfo = ();
+    type SpendFunds = ();
+    type ProposalBondMaximum = ();
+    type MaxApprovals = ConstU32<100>;
+    type SpendOrigin = EnsureWithSuccess<EnsureRoot<AccountId>, AccountId, MaxBalance>;
+}
+
+parameter_types! {
+    pub MaxProposalWeight: Weight = Weight::from_parts(u64::MAX / 2, u64::MAX / 2);
+}
+
+type TechnicalCollective = pallet_collective::Instance2;
+impl pallet_collective::Config<TechnicalCollective> for TestRuntime {
+    type RuntimeOrigin = RuntimeOrigin;
+    type Proposal = RuntimeCall;
+    type RuntimeEvent = RuntimeEvent;
+    type MotionDuration = ConstU64<{ 5 * ONE_DAY as u64 }>;
+    type MaxProposals = ConstU32<100>;
+    type MaxMembers = ConstU32<100>;
+    type DefaultVote = pallet_collective::PrimeDefaultVote;
+    type WeightInfo = ();
+    type SetMembersOrigin = EnsureRoot<AccountId>;
+    type MaxProposalWeight = MaxProposalWeight;
+}
+
+parameter_types! {
+    pub CommitteeCurrentEra: EraIndex = OnlineProfile::current_era();
+}
+
+impl committee::Config for TestRuntime {
+    type Currency = Balances;
+    type RuntimeEvent = RuntimeEvent;
+    type SlashAndReward = GenericFunc;
+    type CurrentEra = CommitteeCurrentEra;
+    type WeightInfo = ();
+}
+
+impl online_profile::Config for TestRuntime {
+    type Currency = Balances;
+    type RuntimeEvent = RuntimeEvent;
+    type BondingDuration = ConstU32<7>;
+    type DbcPrice = DBCPriceOCW;
+    type ManageCommittee = Committee;
+    type Slash = Treasury;
+    type CancelSlashOrigin =
+        pallet_collective::EnsureProportionAtLeast<AccountId, TechnicalCollective, 2, 3>;
+    type SlashAndReward = Committee;
+    type WeightInfo = ();
+}
+
+impl online_committee::Config for TestRuntime {
+    type RuntimeEvent = RuntimeEvent;
+    type Currency = Balances;
+    type OCOps = OnlineProfile;
+    type ManageCommittee = Committee;
+    type CancelSlashOrigin =
+        pallet_collective::EnsureProportionAtLeast<AccountId, TechnicalCollective, 2, 3>;
+    type SlashAndReward = Committee;
+}
+
+impl dbc_price_ocw::Config for TestRuntime {
+    type Currency = Balances;
+    type RuntimeEvent = RuntimeEvent;
+    type RandomnessSource = RandomnessCollectiveFlip;
+}
+
+impl dlc_price_ocw::Config for TestRuntime {
+    type Currency = Balances;
+    type RuntimeEvent = RuntimeEvent;
+    type RandomnessSource = RandomnessCollectiveFlip;
+}
+
+parameter_types! {
+    pub const DlcAssetId: u32 = ASSET_ID;
+    pub const RentMachinePalletId: PalletId = PalletId(*b"dbc/rent");
+}
+
+impl rent_machine::Config for TestRuntime {
+    type Currency = Balances;
+    type RuntimeEvent = RuntimeEvent;
+    type RTOps = OnlineProfile;
+    type DbcPrice = DBCPriceOCW;
+    type DlcPrice = DLCPriceOCW;
+    type SurgePricing = OnlineProfile;
+    type ManageCommittee = Committee;
+    type CancelSlashOrigin =
+        pallet_collective::EnsureProportionAtLeast<AccountId, TechnicalCollective, 2, 3>;
+    type SlashAndReward = Committee;
+    type Assets = Assets;
+    type DlcAssetId = DlcAssetId;
+    type PalletId = RentMachinePalletId;
+    type WeightInfo = rent_machine::weights::SubstrateWeight<Self>;
+}
+
+type TestExtrinsic = TestXt<RuntimeCall, ()>;
+impl<LocalCall> frame_system::offchain::CreateSignedTransaction<LocalCall> for TestRuntime
+where
+    RuntimeCall: From<LocalCall>,
+{
+    fn create_transaction<C: frame_system::offchain::AppCrypto<Self::Public, Self::Signature>>(
+        call: RuntimeCall,
+        _public: <MultiSignature as Verify>::Signer,
+        _account: AccountId,
+        index: u64,
+    ) -> Option<(RuntimeCall, <TestExtrinsic as sp_runtime::traits::Extrinsic>::SignaturePayload)>
+    {
+        Some((call, (index, ())))
+    }
+}
+
+impl frame_system::offchain::SigningTypes for TestRuntime {
+    type Public = <MultiSignature as Verify>::Signer;
+    type Signature = MultiSignature;
+}
+
+impl<C> frame_system::offchain::SendTransactionTypes<C> for TestRuntime
+where
+    RuntimeCall: From<C>,
+{
+    type OverarchingCall = RuntimeCall;
+    type Extrinsic = TestExtrinsic;
+}
+
+frame_support::construct_runtime!(
+    pub enum TestRuntime where
+        Block = Block,
+        NodeBlock = Block,
+        UncheckedExtrinsic = UncheckedExtrinsic,
+    {
+        System: frame_system,
+        Balances: pallet_balances,
+        Timestamp: pallet_timestamp,
+        EVM: pallet_evm,
+        Staking: pallet_staking,
+        Assets: pallet_assets,
+        EvmBridge: evm_bridge,
+        RandomnessCollectiveFlip: pallet_insecure_randomness_collective_flip,
+        GenericFunc: generic_func,
+        Treasury: pallet_treasury,
+        TechnicalCommittee: pallet_collective::<Instance2>,
+        Committee: committee,
+        OnlineProfile: online_profile,
+        OnlineCommittee: online_committee,
+        DBCPriceOCW: dbc_price_ocw,
+        DLCPriceOCW: dlc_price_ocw,
+        RentMachine: rent_machine,
+    }
+);
+
+pub fn evm_address(index: u64) -> H160 {
+    H160::from_low_u64_be(0x1000 + index)
+}
+
+pub fn account_of(address: H160) -> AccountId {
+    <TestRuntime as pallet_evm::Config>::AddressMapping::into_account_id(address)
+}
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+    let mut storage =
+        frame_system::GenesisConfig::default().build_storage::<TestRuntime>().unwrap();
+
+    pallet_balances::GenesisConfig::<TestRuntime> {
+        balances: (1..=4).map(|index| (account_of(evm_address(index)), INIT_BALANCE)).collect(),
+    }
+    .assimilate_storage(&mut storage)
+    .unwrap();
+
+    pallet_assets::GenesisConfig::<TestRuntime> {
+        assets: vec![(ASSET_ID, account_of(evm_address(1)), true, ASSET_MIN_BALANCE)],
+        metadata: vec![(ASSET_ID, b"DeepLink Coin".to_vec(), b"DLC".to_vec(), 18)],
+        accounts: vec![(ASSET_ID, account_of(evm_address(1)), 1_000_000)],
+    }
+    .assimilate_storage(&mut storage)
+    .unwrap();
+
+    let mut ext = sp_io::TestExternalities::from(storage);
+    ext.execute_with(|| System::set_block_number(1));
+    ext
+}
+
+pub const MACHINE_ID: &str = "8eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a48";
+
+/// 机器通过委员会审核上线，可以被evm_address(1..=4)租用
+pub fn new_test_with_machine_online() -> sp_io::TestExternalities {
+    let mut ext = new_test_ext();
+
+    let machine_id = MACHINE_ID.as_bytes().to_vec();
+    // 机器(Bob)签名绑定到stash(Ferdie)的消息
+    let msg = "8eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a48\
+               5CiPPseXPECbkjWCa6MnjNokrgYjMqmKndv2rSnekmSK2DjL";
+    let sig = "b4084f70730b183127e9db78c6d8dcf79039f23466cd1ee8b536c40c3027a83d\
+               ab040be4ed2db57b67eaac406817a69ce72a13f8ac11ba460e15d318b1504481";
+
+    ext.execute_with(|| {
+        let stash = OnlineProfile::get_account_from_str(&msg.as_bytes()[64..]).unwrap();
+        let controller = AccountId::new([1; 32]);
+        let committees: Vec<AccountId> = (2..=4).map(|index| AccountId::new([index; 32])).collect();
+        for who in committees.iter().chain([&stash, &controller]) {
+            let _ = Balances::deposit_creating(who, INIT_BALANCE);
+        }
+
+        // DBC价格 (0.012$)
+        for _ in 0..dbc_price_ocw::MAX_LEN {
+            DBCPriceOCW::add_price(12_000u64);
+        }
+        DBCPriceOCW::add_avg_price();
+        // DLC价格 (0.003$)
+        for _ in 0..dlc_price_ocw::MAX_LEN {
+            DLCPriceOCW::add_price(3_000u64);
+        }
+        DLCPriceOCW::add_avg_price();
+
+        assert_ok!(Committee::set_committee_stake_params(
+            RawOrigin::Root.into(),
+            committee::CommitteeStakeParamsInfo {
+                stake_baseline: 20000 * ONE_DBC,
+                stake_per_order: 1000 * ONE_DBC,
+                min_free_stake_percent: Perbill::from_percent(40),
+            },
+        ));
+        assert_ok!(GenericFunc::set_fixed_tx_fee(RawOrigin::Root.into(), 10 * ONE_DBC));
+        assert_ok!(OnlineProfile::set_online_stake_params(
+            RawOrigin::Root.into(),
+            online_profile::OnlineStakeParamsInfo {
+                online_stake_per_gpu: 100000 * ONE_DBC,
+                online_stake_usd_limit: 7700_000_000,
+                reonline_stake: 46_000_000,
+                slash_review_stake: 1000 * ONE_DBC,
+            },
+        ));
+        assert_ok!(OnlineProfile::set_reward_info(
+            RawOrigin::Root.into(),
+            online_profile::PhaseRewardInfoDetail {
+                online_reward_start_era: 0,
+                first_phase_duration: 1095,
+                galaxy_on_era: 0,
+                phase_0_reward_per_era: 1_100_000 * ONE_DBC,
+                phase_1_reward_per_era: 550_000 * ONE_DBC,
+                phase_2_reward_per_era: 275_000 * ONE_DBC,
+            },
+        ));
+        assert_ok!(OnlineProfile::set_standard_gpu_point_price(
+            RawOrigin::Root.into(),
+            StandardGpuPointPrice { gpu_point: 1000, gpu_price: 5_000_000 }
+        ));
+        assert_ok!(RentMachine::set_rent_fee_pot(RawOrigin::Root.into(), controller.clone()));
+
+        run_to_block(2);
+
+        assert_ok!(OnlineProfile::set_controller(
+            RuntimeOrigin::signed(stash.clone()),
+            controller.clone()
+        ));
+        assert_ok!(OnlineProfile::gen_server_room(RuntimeOrigin::signed(controller.clone())));
+        let server_room = OnlineProfile::stash_server_rooms(&stash);
+        assert_ok!(OnlineProfile::bond_machine(
+            RuntimeOrigin::signed(controller.clone()),
+            machine_id.clone(),
+            msg.as_bytes().to_vec(),
+            hex::decode(sig).unwrap()
+        ));
+        assert_ok!(OnlineProfile::add_machine_info(
+            RuntimeOrigin::signed(controller),
+            machine_id.clone(),
+            StakerCustomizeInfo {
+                server_room: server_room[0],
+                upload_net: 10000,
+                download_net: 10000,
+                longitude: Longitude::East(1157894),
+                latitude: Latitude::North(235678),
+                telecom_operators: vec!["China Unicom".into()],
+                is_bare_machine: false
+            }
+        ));
+
+        run_to_block(3);
+
+        for committee in committees.iter() {
+            assert_ok!(Committee::add_committee(RawOrigin::Root.into(), committee.clone()));
+            assert_ok!(Committee::committee_set_box_pubkey(
+                RuntimeOrigin::signed(committee.clone()),
+                [1; 32]
+            ));
+        }
+
+        run_to_block(5);
+
+        // 委员会提交机器信息的Hash及原始信息
+        let machine_info_hash = [
+            "fd8885a22a9d9784adaa36effcd77522",
+            "c016090e0943c17f5d4999dc6eb52683",
+            "4a6b2df1e1a77b9bcdab5e31dc7950d2",
+        ];
+        for (committee, hash) in committees.iter().zip(machine_info_hash) {
+            assert_ok!(OnlineCommittee::submit_confirm_hash(
+                RuntimeOrigin::signed(committee.clone()),
+                machine_id.clone(),
+                hex::decode(hash).unwrap().try_into().unwrap()
+            ));
+        }
+        for (index, committee) in committees.iter().enumerate() {
+            assert_ok!(OnlineCommittee::submit_confirm_raw(
+                RuntimeOrigin::signed(committee.clone()),
+                CommitteeUploadInfo {
+                    machine_id: machine_id.clone(),
+                    gpu_type: "GeForceRTX3080".as_bytes().to_vec(),
+                    gpu_num: 4,
+                    cuda_core: 8704,
+                    gpu_mem: 10,
+                    calc_point: 59890,
+                    sys_disk: 500,
+                    data_disk: 3905,
+                    cpu_type: "Intel(R) Xeon(R) Silver 4214R".as_bytes().to_vec(),
+                    cpu_core_num: 46,
+                    cpu_rate: 2400,
+                    mem_num: 440,
+                    rand_str: format!("abcdefg{}", index + 1).as_bytes().to_vec(),
+                    is_support: true,
+                }
+            ));
+        }
+
+        run_to_block(10);
+    });
+    ext
+}
+
+pub fn run_to_block(n: u64) {
+    for b in System::block_number()..=n {
+        OnlineProfile::on_finalize(b);
+        OnlineCommittee::on_finalize(b);
+        Committee::on_finalize(b);
+        RentMachine::on_finalize(b);
+        System::on_finalize(b);
+        RandomnessCollectiveFlip::on_finalize(b);
+
+        System::set_block_number(b + 1);
+        Timestamp::set_timestamp(System::block_number() * 6000);
+
+        System::on_initialize(b + 1);
+        RandomnessCollectiveFlip::on_initialize(b + 1);
+        OnlineCommittee::on_initialize(b + 1);
+        Committee::on_initialize(b + 1);
+        OnlineProfile::on_initialize(b + 1);
+        RentMachine::on_initialize(b + 1);
+    }
+}
+
+/// 模拟EVM调用预编译合约时的上下文
+pub struct MockHandle {
+    pub input: Vec<u8>,
+    pub context: Context,
+    pub code_address: H160,
+    pub gas_used: u64,
+    pub gas_limit: u64,
+    pub logs: Vec<(H160, Vec<H256>, Vec<u8>)>,
+}
+
+impl MockHandle {
+    pub fn new(code_address: H160, caller: H160, input: Vec<u8>) -> Self {
+        Self {
+            input,
+            context: Context { address: code_address, caller, apparent_value: U256::zero() },
+            code_address,
+            gas_used: 0,
+            gas_limit: u64::MAX,
+            logs: vec![],
+        }
+    }
+}
+
+impl PrecompileHandle for MockHandle {
+    fn call(
+        &mut self,
+        _: H160,
+        _: Option<Transfer>,
+        _: Vec<u8>,
+        _: Option<u64>,
+        _: bool,
+        _: &Context,
+    ) -> (ExitReason, Vec<u8>) {
+        // precompiles under test do not call other contracts, fail the subcall if one does
+        (
+            ExitReason::Error(ExitError::Other("subcall is not supported by MockHandle".into())),
+            vec![],
+        )
+    }
+
+    fn record_cost(&mut self, cost: u64) -> Result<(), ExitError> {
+        self.gas_used = self.gas_used.saturating_add(cost);
+        if self.gas_used > self.gas_limit {
+            return Err(ExitError::OutOfGas)
+        }
+        Ok(())
+    }
+
+    fn record_external_cost(&mut self, _: Option<u64>, _: Option<u64>) -> Result<(), ExitError> {
+        Ok(())
+    }
+
+    fn refund_external_cost(&mut self, _: Option<u64>, _: Option<u64>) {}
+
+    fn remaining_gas(&self) -> u64 {
+        self.gas_limit.saturating_sub(self.gas_used)
+    }
+
+    fn log(&mut self, address: H160, topics: Vec<H256>, data: Vec<u8>) -> Result<(), ExitError> {
+        self.logs.push((address, topics, data));
+        Ok(())
+    }
+
+    fn code_address(&self) -> H160 {
+        self.code_address
+    }
+
+    fn input(&self) -> &[u8] {
+        &self.input
+    }
+
+    fn context(&self) -> &Context {
+        &self.context
+    }
+
+    fn is_static(&self) -> bool {
+        false
+    }
+
+    fn gas_limit(&self) -> Option<u64> {
+        Some(self.gas_limit)
+    }
+}
@@ -19,10 +19,23 @@ mod dbc_price;
 use dbc_price::DBCPrice;
 
 mod dlc_price;
+mod erc20_assets;
+mod logs;
 mod machine_info;
+mod machine_rent;
+mod staking;
 use dlc_price::DLCPrice;
+use erc20_assets::Erc20Assets;
 
 use machine_info::MachineInfo;
+use machine_rent::MachineRent;
+use staking::Staking;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
 const LOG_TARGET: &str = "evm";
 
 pub struct DBCPrecompiles<T>(PhantomData<T>);
@@ -34,7 +47,7 @@ where
     pub fn new() -> Self {
         Self(Default::default())
     }
-    pub fn used_addresses() -> [H160; 11] {
+    pub fn used_addresses() -> [H160; 13] {
         [
             hash(1),
             hash(2),
@@ -47,17 +60,22 @@ where
             hash(2048),
             hash(2049),
             hash(2051),
+            hash(2052),
+            hash(2053),
         ]
     }
 }
 impl<T> PrecompileSet for DBCPrecompiles<T>
 where
-    T: pallet_evm::Config + eth_precompile_whitelist::Config,
+    T: pallet_evm::Config + eth_precompile_whitelist::Config + pallet_assets::Config<AssetId = u32>,
     Dispatch<T>: Precompile,
     Bridge<T>: Precompile,
     DBCPrice<T>: Precompile,
     MachineInfo<T>: Precompile,
+    MachineRent<T>: Precompile,
+    Staking<T>: Precompile,
     DLCPrice<T>: Precompile,
+    Erc20Assets<T>: Precompile,
 {
     fn execute(&self, handle: &mut impl PrecompileHandle) -> Option<PrecompileResult> {
         let address = handle.code_address();
@@ -115,6 +133,13 @@ where
             a if a == hash(2049) => Some(DBCPrice::<T>::execute(handle)),
             a if a == hash(2051) => Some(MachineInfo::<T>::execute(handle)),
             a if a == hash(2050) => Some(DLCPrice::<T>::execute(handle)),
+            a if a == hash(2052) => Some(MachineRent::<T>::execute(handle)),
+            a if a == hash(2053) => Some(Staking::<T>::execute(handle)),
+
+            // pallet_assets ERC20 precompiles: 0xFFFFFFFF + asset id
+            a if Erc20Assets::<T>::is_asset_precompile(a) => {
+                Some(Erc20Assets::<T>::execute(handle))
+            },
 
             _ => None,
         }
@@ -122,7 +147,8 @@ where
 
     fn is_precompile(&self, address: H160, _gas: u64) -> IsPrecompileResult {
         IsPrecompileResult::Answer {
-            is_precompile: Self::used_addresses().contains(&address),
+            is_precompile: Self::used_addresses().contains(&address) ||
+                Erc20Assets::<T>::is_asset_precompile(address),
             extra_cost: 0,
         }
     }
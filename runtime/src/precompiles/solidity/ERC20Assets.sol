@@ -0,0 +1,48 @@
+// SPDX-License-Identifier: GPL-3.0-only
+pragma solidity >=0.8.3;
+
+/// @title pallet_assets ERC20 interface
+/// @notice Each asset in pallet_assets is exposed at `0xFFFFFFFF` followed by 12 zero bytes and
+/// the big endian u32 asset id, e.g. asset 88 lives at 0xFFFFFFFF00000000000000000000000000000058.
+/// Amounts use the decimals of the asset metadata, no conversion is applied.
+/// Addresses are mapped to substrate accounts as `blake2_256("evm:" ++ address)`.
+interface ERC20Assets {
+    /// @dev Name from the asset metadata.
+    function name() external view returns (string memory);
+
+    /// @dev Symbol from the asset metadata.
+    function symbol() external view returns (string memory);
+
+    /// @dev Decimals from the asset metadata.
+    function decimals() external view returns (uint8);
+
+    /// @dev Total issuance of the asset.
+    function totalSupply() external view returns (uint256);
+
+    /// @dev Free balance of `who`, locked balance is not included.
+    function balanceOf(address who) external view returns (uint256);
+
+    /// @dev Amount that `spender` is still allowed to transfer from `owner`.
+    function allowance(address owner, address spender) external view returns (uint256);
+
+    /// @dev Transfer `value` to `to`.
+    function transfer(address to, uint256 value) external returns (bool);
+
+    /// @dev Set the allowance of `spender` to `value`, replacing the previous one.
+    /// A non-zero allowance reserves the pallet_assets approval deposit (1 DBC) from the caller's
+    /// native DBC balance, not from the asset, and reverts if the caller cannot reserve it.
+    /// The deposit is returned to the caller when the allowance is set to zero or is used up
+    /// by `transferFrom`. Replacing an allowance returns the old deposit and reserves a new one.
+    function approve(address spender, uint256 value) external returns (bool);
+
+    /// @dev Transfer `value` from `from` to `to` using the caller's allowance.
+    function transferFrom(address from, address to, uint256 value) external returns (bool);
+
+    /// @dev Transfer `value` to `to` and lock it for `lockDuration` blocks.
+    /// `to` can claim it with `Assets.unlock` once the lock expires.
+    function transferAndLock(address to, uint256 value, uint256 lockDuration) external returns (bool);
+
+    event Transfer(address indexed from, address indexed to, uint256 value);
+
+    event Approval(address indexed owner, address indexed spender, uint256 value);
+}
@@ -0,0 +1,75 @@
+// SPDX-License-Identifier: GPL-3.0-only
+pragma solidity >=0.8.3;
+
+/// @dev The Staking precompile address.
+address constant STAKING_ADDRESS = 0x0000000000000000000000000000000000000805;
+
+/// @dev The Staking precompile instance.
+Staking constant STAKING_CONTRACT = Staking(STAKING_ADDRESS);
+
+/// @title DBC staking interface
+/// @notice Bond, nominate and manage the staking ledger of the caller's mapped substrate account.
+/// The mapped account is `blake2_256("evm:" ++ caller)`, see `accountOf`.
+/// All amounts use 18 decimals, and must be a multiple of 1000 (native DBC has 15 decimals).
+/// Reward destination `payee`: 0 = Staked, 1 = Stash, 2 = Controller, 3 = None.
+interface Staking {
+    /// @dev Bond `value` of the caller's free balance and set the reward destination.
+    function bond(uint256 value, uint8 payee) external;
+
+    /// @dev Bond more of the caller's free balance.
+    function bondExtra(uint256 value) external;
+
+    /// @dev Schedule `value` to be unlocked after the bonding duration.
+    function unbond(uint256 value) external;
+
+    /// @dev Withdraw the unlocked funds.
+    function withdrawUnbonded(uint32 numSlashingSpans) external;
+
+    /// @dev Nominate the validators, identified by their substrate account ids.
+    function nominate(bytes32[] calldata targets) external;
+
+    /// @dev Stop nominating or validating.
+    function chill() external;
+
+    /// @dev Change the reward destination.
+    function setPayee(uint8 payee) external;
+
+    /// @dev Pay out the rewards of `era` to the validator and its nominators.
+    function payoutStakers(bytes32 validatorStash, uint32 era) external;
+
+    /// @dev The substrate account id mapped from an EVM address.
+    function accountOf(address evmAddress) external view returns (bytes32);
+
+    /// @dev The staking ledger of a stash, all zero if not bonded.
+    function getLedger(bytes32 stash)
+        external
+        view
+        returns (uint256 total, uint256 active, uint256 unlocking);
+
+    /// @dev The current era index.
+    function currentEra() external view returns (uint32);
+
+    /// @dev The total reward paid to validators and nominators in `era`.
+    function getEraReward(uint32 era) external view returns (uint256);
+
+    /// @dev Emitted by `bond` and `bondExtra`, `stash` is the caller.
+    event Bonded(address indexed stash, uint256 value);
+
+    /// @dev Emitted by `unbond`.
+    event Unbonded(address indexed stash, uint256 value);
+
+    /// @dev Emitted by `withdrawUnbonded` with the amount actually withdrawn.
+    event Withdrawn(address indexed stash, uint256 value);
+
+    /// @dev Emitted by `nominate`.
+    event Nominated(address indexed stash, bytes32[] targets);
+
+    /// @dev Emitted by `chill`.
+    event Chilled(address indexed stash);
+
+    /// @dev Emitted by `setPayee`.
+    event PayeeSet(address indexed stash, uint8 payee);
+
+    /// @dev Emitted by `payoutStakers`.
+    event PayoutStarted(bytes32 indexed validatorStash, uint32 indexed era);
+}
@@ -0,0 +1,447 @@
+use fp_evm::{
+    ExitRevert, ExitSucceed, Precompile, PrecompileFailure, PrecompileHandle, PrecompileOutput,
+    PrecompileResult,
+};
+use sp_core::{Get, U256};
+use sp_runtime::{
+    traits::{Saturating, StaticLookup},
+    DispatchError, RuntimeDebug,
+};
+extern crate alloc;
+use crate::precompiles::{
+    logs::{uint_topic, LogsBuilder},
+    LOG_TARGET,
+};
+use alloc::{format, vec::Vec};
+use core::marker::PhantomData;
+use dbc_primitives::AccountId;
+use frame_support::{
+    ensure,
+    pallet_prelude::{IsType, Weight},
+};
+use frame_system::RawOrigin;
+use num_enum::{IntoPrimitive, TryFromPrimitive};
+use pallet_evm::{AddressMapping, GasWeightMapping};
+use pallet_staking::{RewardDestination, WeightInfo};
+
+pub struct Staking<T>(PhantomData<T>);
+
+#[evm_macro::generate_function_selector]
+#[derive(RuntimeDebug, Eq, PartialEq, TryFromPrimitive, IntoPrimitive)]
+#[repr(u32)]
+pub enum Selector {
+    Bond = "bond(uint256,uint8)",
+    BondExtra = "bondExtra(uint256)",
+    Unbond = "unbond(uint256)",
+    WithdrawUnbonded = "withdrawUnbonded(uint32)",
+    Nominate = "nominate(bytes32[])",
+    Chill = "chill()",
+    SetPayee = "setPayee(uint8)",
+    PayoutStakers = "payoutStakers(bytes32,uint32)",
+    AccountOf = "accountOf(address)",
+    GetLedger = "getLedger(bytes32)",
+    CurrentEra = "currentEra()",
+    GetEraReward = "getEraReward(uint32)",
+}
+
+// Bonded(address indexed stash, uint256 value)
+pub const SELECTOR_LOG_BONDED: [u8; 32] = *evm_macro::keccak256!("Bonded(address,uint256)");
+// Unbonded(address indexed stash, uint256 value)
+pub const SELECTOR_LOG_UNBONDED: [u8; 32] = *evm_macro::keccak256!("Unbonded(address,uint256)");
+// Withdrawn(address indexed stash, uint256 value)
+pub const SELECTOR_LOG_WITHDRAWN: [u8; 32] = *evm_macro::keccak256!("Withdrawn(address,uint256)");
+// Nominated(address indexed stash, bytes32[] targets)
+pub const SELECTOR_LOG_NOMINATED: [u8; 32] = *evm_macro::keccak256!("Nominated(address,bytes32[])");
+// Chilled(address indexed stash)
+pub const SELECTOR_LOG_CHILLED: [u8; 32] = *evm_macro::keccak256!("Chilled(address)");
+// PayeeSet(address indexed stash, uint8 payee)
+pub const SELECTOR_LOG_PAYEE_SET: [u8; 32] = *evm_macro::keccak256!("PayeeSet(address,uint8)");
+// PayoutStarted(bytes32 indexed validatorStash, uint32 indexed era)
+pub const SELECTOR_LOG_PAYOUT_STARTED: [u8; 32] =
+    *evm_macro::keccak256!("PayoutStarted(bytes32,uint32)");
+
+type BalanceOf<T> = pallet_staking::BalanceOf<T>;
+
+impl<T> Precompile for Staking<T>
+where
+    T: pallet_evm::Config + pallet_staking::Config,
+    BalanceOf<T>: TryFrom<U256> + Into<U256>,
+    T::AccountId: IsType<AccountId>,
+{
+    fn execute(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+        let input = handle.input();
+        ensure!(
+            input.len() >= 4,
+            PrecompileFailure::Revert {
+                exit_status: ExitRevert::Reverted,
+                output: "invalid input".into(),
+            }
+        );
+
+        let selector = u32::from_be_bytes(input[..4].try_into().expect("checked. qed!"));
+        let selector: Selector = selector.try_into().map_err(|e| PrecompileFailure::Revert {
+            exit_status: ExitRevert::Reverted,
+            output: format!("invalid selector: {:?}", e).into(),
+        })?;
+        let input = input.get(4..).unwrap_or_default().to_vec();
+
+        let caller: T::AccountId = T::AddressMapping::into_account_id(handle.context().caller);
+
+        match selector {
+            Selector::Bond => {
+                let param = Self::decode(
+                    &[
+                        ethabi::ParamType::Uint(256), // value
+                        ethabi::ParamType::Uint(8),   // payee
+                    ],
+                    &input,
+                )?;
+                let value = Self::evm_amount_to_balance(Self::uint_param(&param, 0)?)?;
+                let payee = Self::payee_param(&param, 1)?;
+
+                Self::record_weight(handle, <T as pallet_staking::Config>::WeightInfo::bond())?;
+                Self::dispatch(pallet_staking::Pallet::<T>::bond(
+                    RawOrigin::Signed(caller.clone()).into(),
+                    value,
+                    payee,
+                ))?;
+                Self::log2(handle, SELECTOR_LOG_BONDED, Self::amount_data(value))?;
+
+                log::debug!(target: LOG_TARGET, "staking bond: who: {:?}, value: {:?}", caller, value);
+                Ok(Self::succeed(Default::default()))
+            },
+
+            Selector::BondExtra => {
+                let param = Self::decode(&[ethabi::ParamType::Uint(256)], &input)?;
+                let value = Self::evm_amount_to_balance(Self::uint_param(&param, 0)?)?;
+
+                Self::record_weight(
+                    handle,
+                    <T as pallet_staking::Config>::WeightInfo::bond_extra(),
+                )?;
+                Self::dispatch(pallet_staking::Pallet::<T>::bond_extra(
+                    RawOrigin::Signed(caller).into(),
+                    value,
+                ))?;
+                Self::log2(handle, SELECTOR_LOG_BONDED, Self::amount_data(value))?;
+
+                Ok(Self::succeed(Default::default()))
+            },
+
+            Selector::Unbond => {
+                let param = Self::decode(&[ethabi::ParamType::Uint(256)], &input)?;
+                let value = Self::evm_amount_to_balance(Self::uint_param(&param, 0)?)?;
+
+                Self::record_weight(handle, <T as pallet_staking::Config>::WeightInfo::unbond())?;
+                Self::dispatch(
+                    pallet_staking::Pallet::<T>::unbond(RawOrigin::Signed(caller).into(), value)
+                        .map(|_| ())
+                        .map_err(|e| e.error),
+                )?;
+                Self::log2(handle, SELECTOR_LOG_UNBONDED, Self::amount_data(value))?;
+
+                Ok(Self::succeed(Default::default()))
+            },
+
+            Selector::WithdrawUnbonded => {
+                let param = Self::decode(&[ethabi::ParamType::Uint(32)], &input)?;
+                let num_slashing_spans = Self::uint_param(&param, 0)?.low_u32();
+
+                // withdraw may reap the stash, charge the heavier one
+                Self::record_weight(
+                    handle,
+                    <T as pallet_staking::Config>::WeightInfo::withdraw_unbonded_kill(
+                        num_slashing_spans,
+                    ),
+                )?;
+                let total_before = Self::ledger_total(&caller);
+                Self::dispatch(
+                    pallet_staking::Pallet::<T>::withdraw_unbonded(
+                        RawOrigin::Signed(caller.clone()).into(),
+                        num_slashing_spans,
+                    )
+                    .map(|_| ())
+                    .map_err(|e| e.error),
+                )?;
+                let withdrawn = total_before.saturating_sub(Self::ledger_total(&caller));
+                Self::log2(handle, SELECTOR_LOG_WITHDRAWN, Self::amount_data(withdrawn))?;
+
+                Ok(Self::succeed(Default::default()))
+            },
+
+            Selector::Nominate => {
+                let param = Self::decode(
+                    &[ethabi::ParamType::Array(alloc::boxed::Box::new(
+                        ethabi::ParamType::FixedBytes(32),
+                    ))],
+                    &input,
+                )?;
+                let target_tokens = param[0]
+                    .clone()
+                    .into_array()
+                    .ok_or_else(|| Self::revert("decode param[0] failed"))?;
+                let targets = target_tokens
+                    .clone()
+                    .into_iter()
+                    .map(|target| Self::account_token(target).map(T::Lookup::unlookup))
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                Self::record_weight(
+                    handle,
+                    <T as pallet_staking::Config>::WeightInfo::nominate(targets.len() as u32),
+                )?;
+                Self::dispatch(pallet_staking::Pallet::<T>::nominate(
+                    RawOrigin::Signed(caller).into(),
+                    targets,
+                ))?;
+                Self::log2(
+                    handle,
+                    SELECTOR_LOG_NOMINATED,
+                    ethabi::encode(&[ethabi::Token::Array(target_tokens)]),
+                )?;
+
+                Ok(Self::succeed(Default::default()))
+            },
+
+            Selector::Chill => {
+                Self::record_weight(handle, <T as pallet_staking::Config>::WeightInfo::chill())?;
+                Self::dispatch(pallet_staking::Pallet::<T>::chill(
+                    RawOrigin::Signed(caller).into(),
+                ))?;
+                Self::log2(handle, SELECTOR_LOG_CHILLED, Default::default())?;
+
+                Ok(Self::succeed(Default::default()))
+            },
+
+            Selector::SetPayee => {
+                let param = Self::decode(&[ethabi::ParamType::Uint(8)], &input)?;
+                let payee_index = Self::uint_param(&param, 0)?;
+                let payee = Self::payee_param(&param, 0)?;
+
+                Self::record_weight(
+                    handle,
+                    <T as pallet_staking::Config>::WeightInfo::set_payee(),
+                )?;
+                Self::dispatch(pallet_staking::Pallet::<T>::set_payee(
+                    RawOrigin::Signed(caller).into(),
+                    payee,
+                ))?;
+                Self::log2(
+                    handle,
+                    SELECTOR_LOG_PAYEE_SET,
+                    ethabi::encode(&[ethabi::Token::Uint(payee_index)]),
+                )?;
+
+                Ok(Self::succeed(Default::default()))
+            },
+
+            Selector::PayoutStakers => {
+                let param = Self::decode(
+                    &[
+                        ethabi::ParamType::FixedBytes(32), // validator_stash
+                        ethabi::ParamType::Uint(32),       // era
+                    ],
+                    &input,
+                )?;
+                let validator_topic: [u8; 32] = param[0]
+                    .clone()
+                    .into_fixed_bytes()
+                    .and_then(|account| account.try_into().ok())
+                    .ok_or_else(|| Self::revert("invalid account"))?;
+                let validator_stash = Self::account_token(param[0].clone())?;
+                let era = Self::uint_param(&param, 1)?.low_u32();
+
+                Self::record_weight(
+                    handle,
+                    <T as pallet_staking::Config>::WeightInfo::payout_stakers_alive_staked(
+                        <T as pallet_staking::Config>::MaxNominatorRewardedPerValidator::get(),
+                    ),
+                )?;
+                Self::dispatch(
+                    pallet_staking::Pallet::<T>::payout_stakers(
+                        RawOrigin::Signed(caller).into(),
+                        validator_stash,
+                        era,
+                    )
+                    .map(|_| ())
+                    .map_err(|e| e.error),
+                )?;
+                LogsBuilder::new(handle.code_address())
+                    .log3(
+                        SELECTOR_LOG_PAYOUT_STARTED,
+                        validator_topic,
+                        uint_topic(era),
+                        Default::default(),
+                    )
+                    .record(handle)?;
+
+                Ok(Self::succeed(Default::default()))
+            },
+
+            Selector::AccountOf => {
+                let param = Self::decode(&[ethabi::ParamType::Address], &input)?;
+                let evm_address = param[0]
+                    .clone()
+                    .into_address()
+                    .ok_or_else(|| Self::revert("decode param[0] failed"))?;
+                let account: AccountId = T::AddressMapping::into_account_id(evm_address).into();
+                let account: [u8; 32] = account.into();
+
+                Ok(Self::succeed(ethabi::encode(&[ethabi::Token::FixedBytes(account.to_vec())])))
+            },
+
+            Selector::GetLedger => {
+                let param = Self::decode(&[ethabi::ParamType::FixedBytes(32)], &input)?;
+                let stash = Self::account_token(param[0].clone())?;
+
+                Self::record_weight(handle, <T as frame_system::Config>::DbWeight::get().reads(2))?;
+
+                let (total, active, unlocking) = pallet_staking::Pallet::<T>::bonded(&stash)
+                    .and_then(|controller| pallet_staking::Pallet::<T>::ledger(&controller))
+                    .map(|ledger| {
+                        let unlocking = ledger.unlocking.iter().fold(U256::zero(), |acc, chunk| {
+                            acc.saturating_add(Self::balance_to_evm_amount(chunk.value))
+                        });
+                        (
+                            Self::balance_to_evm_amount(ledger.total),
+                            Self::balance_to_evm_amount(ledger.active),
+                            unlocking,
+                        )
+                    })
+                    .unwrap_or_default();
+
+                Ok(Self::succeed(ethabi::encode(&[
+                    ethabi::Token::Uint(total),
+                    ethabi::Token::Uint(active),
+                    ethabi::Token::Uint(unlocking),
+                ])))
+            },
+
+            Selector::CurrentEra => {
+                Self::record_weight(handle, <T as frame_system::Config>::DbWeight::get().reads(1))?;
+                let current_era = pallet_staking::Pallet::<T>::current_era().unwrap_or_default();
+
+                Ok(Self::succeed(ethabi::encode(&[ethabi::Token::Uint(current_era.into())])))
+            },
+
+            Selector::GetEraReward => {
+                let param = Self::decode(&[ethabi::ParamType::Uint(32)], &input)?;
+                let era = Self::uint_param(&param, 0)?.low_u32();
+
+                Self::record_weight(handle, <T as frame_system::Config>::DbWeight::get().reads(1))?;
+                let reward = pallet_staking::Pallet::<T>::eras_validator_reward(era)
+                    .map(Self::balance_to_evm_amount)
+                    .unwrap_or_default();
+
+                Ok(Self::succeed(ethabi::encode(&[ethabi::Token::Uint(reward)])))
+            },
+        }
+    }
+}
+
+impl<T> Staking<T>
+where
+    T: pallet_evm::Config + pallet_staking::Config,
+    BalanceOf<T>: TryFrom<U256> + Into<U256>,
+    T::AccountId: IsType<AccountId>,
+{
+    fn revert(output: &str) -> PrecompileFailure {
+        PrecompileFailure::Revert { exit_status: ExitRevert::Reverted, output: output.into() }
+    }
+
+    fn succeed(output: Vec<u8>) -> PrecompileOutput {
+        PrecompileOutput { exit_status: ExitSucceed::Returned, output }
+    }
+
+    fn decode(
+        types: &[ethabi::ParamType],
+        input: &[u8],
+    ) -> Result<Vec<ethabi::Token>, PrecompileFailure> {
+        ethabi::decode(types, input).map_err(|e| PrecompileFailure::Revert {
+            exit_status: ExitRevert::Reverted,
+            output: format!("decode param failed: {:?}", e).into(),
+        })
+    }
+
+    fn uint_param(param: &[ethabi::Token], index: usize) -> Result<U256, PrecompileFailure> {
+        param[index].clone().into_uint().ok_or_else(|| PrecompileFailure::Revert {
+            exit_status: ExitRevert::Reverted,
+            output: format!("decode param[{}] failed", index).into(),
+        })
+    }
+
+    // 0: Staked, 1: Stash, 2: Controller, 3: None
+    fn payee_param(
+        param: &[ethabi::Token],
+        index: usize,
+    ) -> Result<RewardDestination<T::AccountId>, PrecompileFailure> {
+        match Self::uint_param(param, index)?.low_u32() {
+            0 => Ok(RewardDestination::Staked),
+            1 => Ok(RewardDestination::Stash),
+            2 => Ok(RewardDestination::Controller),
+            3 => Ok(RewardDestination::None),
+            payee => Err(PrecompileFailure::Revert {
+                exit_status: ExitRevert::Reverted,
+                output: format!("invalid payee: {}", payee).into(),
+            }),
+        }
+    }
+
+    fn account_token(token: ethabi::Token) -> Result<T::AccountId, PrecompileFailure> {
+        let account: [u8; 32] = token
+            .into_fixed_bytes()
+            .and_then(|account| account.try_into().ok())
+            .ok_or_else(|| Self::revert("invalid account"))?;
+        Ok(T::AccountId::from(AccountId::from(account)))
+    }
+
+    // evm decimals is 18, native balance decimals is 15
+    fn evm_amount_to_balance(origin_amount: U256) -> Result<BalanceOf<T>, PrecompileFailure> {
+        evm_bridge::evm_amount_to_balance(origin_amount).ok_or_else(|| PrecompileFailure::Revert {
+            exit_status: ExitRevert::Reverted,
+            output: format!("invalid amount, origin amount: {:?}", origin_amount).into(),
+        })
+    }
+
+    fn balance_to_evm_amount(balance: BalanceOf<T>) -> U256 {
+        evm_bridge::balance_to_evm_amount(balance)
+    }
+
+    fn ledger_total(stash: &T::AccountId) -> BalanceOf<T> {
+        pallet_staking::Pallet::<T>::bonded(stash)
+            .and_then(|controller| pallet_staking::Pallet::<T>::ledger(&controller))
+            .map(|ledger| ledger.total)
+            .unwrap_or_default()
+    }
+
+    fn amount_data(amount: BalanceOf<T>) -> Vec<u8> {
+        ethabi::encode(&[ethabi::Token::Uint(Self::balance_to_evm_amount(amount))])
+    }
+
+    // emit `event(address indexed stash, ...)` of the caller
+    fn log2(
+        handle: &mut impl PrecompileHandle,
+        selector: [u8; 32],
+        data: Vec<u8>,
+    ) -> Result<(), PrecompileFailure> {
+        let caller = handle.context().caller;
+        LogsBuilder::new(handle.code_address())
+            .log2(selector, caller, data)
+            .record(handle)
+    }
+
+    fn record_weight(
+        handle: &mut impl PrecompileHandle,
+        weight: Weight,
+    ) -> Result<(), PrecompileFailure> {
+        handle.record_cost(T::GasWeightMapping::weight_to_gas(weight))?;
+        Ok(())
+    }
+
+    fn dispatch(result: Result<(), DispatchError>) -> Result<(), PrecompileFailure> {
+        result.map_err(|e| PrecompileFailure::Revert {
+            exit_status: ExitRevert::Reverted,
+            output: format!("dispatch failed: {:?}", e).into(),
+        })
+    }
+}
@@ -0,0 +1,4 @@
+mod test_bridge;
+mod test_erc20_assets;
+mod test_machine_rent;
+mod test_staking;
@@ -0,0 +1,80 @@
+use super::super::{
+    bridge::{Bridge, Selector, SELECTOR_LOG_BRIDGE_TRANSFER},
+    logs::log_costs,
+    mock::*,
+};
+use fp_evm::{ExitSucceed, Precompile, PrecompileFailure};
+use sp_core::{H160, H256, U256};
+
+fn bridge_address() -> H160 {
+    H160::from_low_u64_be(2048)
+}
+
+fn call_bridge(
+    caller: H160,
+    params: &[ethabi::Token],
+) -> (Result<Vec<u8>, PrecompileFailure>, MockHandle) {
+    let mut input = u32::from(Selector::Transfer).to_be_bytes().to_vec();
+    input.extend(ethabi::encode(params));
+
+    let mut handle = MockHandle::new(bridge_address(), caller, input);
+    let result = Bridge::<TestRuntime>::execute(&mut handle).map(|output| {
+        assert_eq!(output.exit_status, ExitSucceed::Returned);
+        output.output
+    });
+    (result, handle)
+}
+
+#[test]
+fn bridge_transfer_emits_log() {
+    new_test_ext().execute_with(|| {
+        let from = evm_address(1);
+        let to: [u8; 32] = account_of(evm_address(2)).into();
+        let amount = U256::from(10 * ONE_EVM_DBC);
+
+        let (result, handle) = call_bridge(
+            from,
+            &[
+                ethabi::Token::Address(from),
+                ethabi::Token::String(array_bytes::bytes2hex("0x", to)),
+                ethabi::Token::Uint(amount),
+            ],
+        );
+        assert!(result.is_ok());
+        assert_eq!(Balances::free_balance(account_of(from)), INIT_BALANCE - 10 * ONE_DBC);
+        assert_eq!(Balances::free_balance(account_of(evm_address(2))), INIT_BALANCE + 10 * ONE_DBC);
+        System::assert_last_event(
+            evm_bridge::Event::TransferFromEVM(
+                from,
+                account_of(evm_address(2)),
+                10 * ONE_DBC,
+                amount,
+            )
+            .into(),
+        );
+
+        // Transfer(from, to, value)，金额为EVM精度
+        let data = ethabi::encode(&[ethabi::Token::Uint(amount)]);
+        assert!(handle.gas_used >= log_costs(3, data.len()).unwrap());
+        assert_eq!(
+            handle.logs,
+            vec![(
+                bridge_address(),
+                vec![H256(SELECTOR_LOG_BRIDGE_TRANSFER), H256::from(from), H256(to)],
+                data,
+            )]
+        );
+
+        // 精度不匹配时不转账，也不产生日志
+        let (result, handle) = call_bridge(
+            from,
+            &[
+                ethabi::Token::Address(from),
+                ethabi::Token::String(array_bytes::bytes2hex("", to)),
+                ethabi::Token::Uint(amount + 1),
+            ],
+        );
+        assert!(result.is_err());
+        assert!(handle.logs.is_empty());
+    })
+}
@@ -0,0 +1,209 @@
+use super::super::{
+    erc20_assets::{Erc20Assets, Selector, SELECTOR_LOG_APPROVAL, SELECTOR_LOG_TRANSFER},
+    mock::*,
+};
+use fp_evm::{ExitSucceed, Precompile, PrecompileFailure};
+use sp_core::{H160, H256, U256};
+
+fn call_asset(
+    caller: H160,
+    selector: Selector,
+    params: &[ethabi::Token],
+) -> (Result<Vec<u8>, PrecompileFailure>, MockHandle) {
+    let mut input = u32::from(selector).to_be_bytes().to_vec();
+    input.extend(ethabi::encode(params));
+
+    let mut handle =
+        MockHandle::new(Erc20Assets::<TestRuntime>::address_of(ASSET_ID), caller, input);
+    let result = Erc20Assets::<TestRuntime>::execute(&mut handle).map(|output| {
+        assert_eq!(output.exit_status, ExitSucceed::Returned);
+        output.output
+    });
+    (result, handle)
+}
+
+fn uint_of(output: Vec<u8>) -> U256 {
+    ethabi::decode(&[ethabi::ParamType::Uint(256)], &output).unwrap()[0]
+        .clone()
+        .into_uint()
+        .unwrap()
+}
+
+fn balance_of(who: H160) -> U256 {
+    let (result, _) = call_asset(who, Selector::BalanceOf, &[ethabi::Token::Address(who)]);
+    uint_of(result.unwrap())
+}
+
+fn allowance_of(owner: H160, spender: H160) -> U256 {
+    let (result, _) = call_asset(
+        owner,
+        Selector::Allowance,
+        &[ethabi::Token::Address(owner), ethabi::Token::Address(spender)],
+    );
+    uint_of(result.unwrap())
+}
+
+fn uint(value: u128) -> ethabi::Token {
+    ethabi::Token::Uint(U256::from(value))
+}
+
+#[test]
+fn asset_address_works() {
+    new_test_ext().execute_with(|| {
+        let address = Erc20Assets::<TestRuntime>::address_of(ASSET_ID);
+        assert_eq!(address, "ffffffff00000000000000000000000000000058".parse::<H160>().unwrap());
+        assert_eq!(Erc20Assets::<TestRuntime>::asset_id_of(address), Some(ASSET_ID));
+        assert!(Erc20Assets::<TestRuntime>::is_asset_precompile(address));
+
+        // 资产不存在或地址前缀不正确时，不是预编译合约
+        assert!(!Erc20Assets::<TestRuntime>::is_asset_precompile(
+            Erc20Assets::<TestRuntime>::address_of(ASSET_ID + 1)
+        ));
+        assert_eq!(
+            Erc20Assets::<TestRuntime>::asset_id_of(H160::from_low_u64_be(ASSET_ID as u64)),
+            None
+        );
+    })
+}
+
+#[test]
+fn asset_metadata_works() {
+    new_test_ext().execute_with(|| {
+        let caller = evm_address(1);
+
+        let (result, _) = call_asset(caller, Selector::Name, &[]);
+        assert_eq!(
+            ethabi::decode(&[ethabi::ParamType::String], &result.unwrap()).unwrap(),
+            vec![ethabi::Token::String("DeepLink Coin".into())]
+        );
+        let (result, _) = call_asset(caller, Selector::Symbol, &[]);
+        assert_eq!(
+            ethabi::decode(&[ethabi::ParamType::String], &result.unwrap()).unwrap(),
+            vec![ethabi::Token::String("DLC".into())]
+        );
+        let (result, _) = call_asset(caller, Selector::Decimals, &[]);
+        assert_eq!(uint_of(result.unwrap()), U256::from(18));
+        let (result, _) = call_asset(caller, Selector::TotalSupply, &[]);
+        assert_eq!(uint_of(result.unwrap()), U256::from(1_000_000));
+
+        assert_eq!(balance_of(caller), U256::from(1_000_000));
+        assert_eq!(balance_of(evm_address(2)), U256::zero());
+    })
+}
+
+#[test]
+fn transfer_works() {
+    new_test_ext().execute_with(|| {
+        let from = evm_address(1);
+        let to = evm_address(2);
+
+        let (result, handle) =
+            call_asset(from, Selector::Transfer, &[ethabi::Token::Address(to), uint(1000)]);
+        assert_eq!(result.unwrap(), ethabi::encode(&[ethabi::Token::Bool(true)]));
+        assert!(handle.gas_used > 0);
+        assert_eq!(balance_of(from), U256::from(999_000));
+        assert_eq!(balance_of(to), U256::from(1000));
+
+        // Transfer(from, to, value)
+        assert_eq!(
+            handle.logs,
+            vec![(
+                Erc20Assets::<TestRuntime>::address_of(ASSET_ID),
+                vec![H256(SELECTOR_LOG_TRANSFER), H256::from(from), H256::from(to)],
+                ethabi::encode(&[uint(1000)]),
+            )]
+        );
+
+        // 余额不足
+        let (result, handle) =
+            call_asset(to, Selector::Transfer, &[ethabi::Token::Address(from), uint(1001)]);
+        assert!(result.is_err());
+        assert!(handle.logs.is_empty());
+    })
+}
+
+#[test]
+fn approve_and_transfer_from_works() {
+    new_test_ext().execute_with(|| {
+        let owner = evm_address(1);
+        let spender = evm_address(2);
+        let to = evm_address(3);
+
+        let (result, handle) =
+            call_asset(owner, Selector::Approve, &[ethabi::Token::Address(spender), uint(500)]);
+        assert!(result.is_ok());
+        assert_eq!(
+            handle.logs,
+            vec![(
+                Erc20Assets::<TestRuntime>::address_of(ASSET_ID),
+                vec![H256(SELECTOR_LOG_APPROVAL), H256::from(owner), H256::from(spender)],
+                ethabi::encode(&[uint(500)]),
+            )]
+        );
+        assert_eq!(allowance_of(owner, spender), U256::from(500));
+
+        // approve 覆盖原有授权额度，而不是累加
+        let (result, _) =
+            call_asset(owner, Selector::Approve, &[ethabi::Token::Address(spender), uint(300)]);
+        assert!(result.is_ok());
+        assert_eq!(allowance_of(owner, spender), U256::from(300));
+
+        let (result, _) = call_asset(
+            spender,
+            Selector::TransferFrom,
+            &[ethabi::Token::Address(owner), ethabi::Token::Address(to), uint(400)],
+        );
+        assert!(result.is_err());
+
+        let (result, handle) = call_asset(
+            spender,
+            Selector::TransferFrom,
+            &[ethabi::Token::Address(owner), ethabi::Token::Address(to), uint(200)],
+        );
+        assert!(result.is_ok());
+        assert_eq!(
+            handle.logs[0].1,
+            vec![H256(SELECTOR_LOG_TRANSFER), H256::from(owner), H256::from(to)]
+        );
+        assert_eq!(allowance_of(owner, spender), U256::from(100));
+        assert_eq!(balance_of(owner), U256::from(999_800));
+        assert_eq!(balance_of(to), U256::from(200));
+
+        // approve 0 取消授权
+        let (result, _) =
+            call_asset(owner, Selector::Approve, &[ethabi::Token::Address(spender), uint(0)]);
+        assert!(result.is_ok());
+        assert_eq!(allowance_of(owner, spender), U256::zero());
+    })
+}
+
+#[test]
+fn transfer_and_lock_works() {
+    new_test_ext().execute_with(|| {
+        let from = evm_address(1);
+        let to = evm_address(2);
+
+        // 锁定时间超过 MaxLockDuration
+        let (result, _) = call_asset(
+            from,
+            Selector::TransferAndLock,
+            &[ethabi::Token::Address(to), uint(1000), uint(1001)],
+        );
+        assert!(result.is_err());
+
+        let (result, handle) = call_asset(
+            from,
+            Selector::TransferAndLock,
+            &[ethabi::Token::Address(to), uint(1000), uint(100)],
+        );
+        assert!(result.is_ok());
+        assert_eq!(
+            handle.logs[0].1,
+            vec![H256(SELECTOR_LOG_TRANSFER), H256::from(from), H256::from(to)]
+        );
+
+        assert_eq!(balance_of(from), U256::from(999_000));
+        assert_eq!(balance_of(to), U256::zero());
+        assert_eq!(pallet_assets::Locked::<TestRuntime>::get(ASSET_ID, account_of(to)), 1000);
+    })
+}
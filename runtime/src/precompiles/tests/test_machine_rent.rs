@@ -0,0 +1,215 @@
+use super::super::{
+    machine_rent::{
+        MachineRent as MachineRentPrecompile, Selector, SELECTOR_LOG_CONFIRM_RENT,
+        SELECTOR_LOG_RELET_MACHINE, SELECTOR_LOG_RENT_MACHINE,
+    },
+    mock::*,
+};
+use dbc_support::{rental_type::RentStatus, ONE_DAY};
+use fp_evm::{ExitSucceed, Precompile, PrecompileFailure};
+use pallet_evm::GasWeightMapping;
+use rent_machine::WeightInfo;
+use sp_core::{H160, H256, U256};
+use sp_runtime::traits::Zero;
+
+fn machine_rent_address() -> H160 {
+    H160::from_low_u64_be(2052)
+}
+
+fn call_machine_rent(
+    caller: H160,
+    selector: Selector,
+    params: &[ethabi::Token],
+) -> (Result<Vec<u8>, PrecompileFailure>, MockHandle) {
+    let mut input = u32::from(selector).to_be_bytes().to_vec();
+    input.extend(ethabi::encode(params));
+
+    let mut handle = MockHandle::new(machine_rent_address(), caller, input);
+    let result = MachineRentPrecompile::<TestRuntime>::execute(&mut handle).map(|output| {
+        assert_eq!(output.exit_status, ExitSucceed::Returned);
+        output.output
+    });
+    (result, handle)
+}
+
+fn weight_gas(weight: frame_support::weights::Weight) -> u64 {
+    <TestRuntime as pallet_evm::Config>::GasWeightMapping::weight_to_gas(weight)
+}
+
+fn rent_params(gpu_num: U256, duration: U256) -> Vec<ethabi::Token> {
+    vec![
+        ethabi::Token::String(MACHINE_ID.into()),
+        ethabi::Token::Uint(gpu_num),
+        ethabi::Token::Uint(duration),
+    ]
+}
+
+#[test]
+fn rent_confirm_and_relet_works() {
+    new_test_with_machine_online().execute_with(|| {
+        let renter = evm_address(1);
+
+        let (result, handle) = call_machine_rent(
+            renter,
+            Selector::RentMachine,
+            &rent_params(4.into(), ONE_DAY.into()),
+        );
+        assert_eq!(result.unwrap(), ethabi::encode(&[ethabi::Token::Uint(0.into())]));
+        assert_eq!(
+            handle.gas_used,
+            weight_gas(<TestRuntime as rent_machine::Config>::WeightInfo::rent_machine())
+        );
+        // RentMachine(renter, rent_id, machine_id, gpu_num, duration)
+        assert_eq!(
+            handle.logs,
+            vec![(
+                machine_rent_address(),
+                vec![H256(SELECTOR_LOG_RENT_MACHINE), H256::from(renter), H256::from_low_u64_be(0)],
+                ethabi::encode(&[
+                    ethabi::Token::String(MACHINE_ID.into()),
+                    ethabi::Token::Uint(4.into()),
+                    ethabi::Token::Uint(ONE_DAY.into()),
+                ]),
+            )]
+        );
+        let rent_info = RentMachine::rent_info(0).unwrap();
+        assert_eq!(rent_info.renter, account_of(renter));
+        assert_eq!(rent_info.gpu_num, 4);
+        assert_eq!(rent_info.rent_status, RentStatus::WaitingVerifying);
+
+        // 只有租用人可以确认租用
+        let (result, _) = call_machine_rent(
+            evm_address(2),
+            Selector::ConfirmRent,
+            &[ethabi::Token::Uint(0.into())],
+        );
+        assert!(result.is_err());
+
+        run_to_block(20);
+
+        let (result, handle) =
+            call_machine_rent(renter, Selector::ConfirmRent, &[ethabi::Token::Uint(0.into())]);
+        assert_eq!(result.unwrap(), ethabi::encode(&[ethabi::Token::Bool(true)]));
+        assert_eq!(
+            handle.gas_used,
+            weight_gas(<TestRuntime as rent_machine::Config>::WeightInfo::confirm_rent())
+        );
+        assert_eq!(
+            handle.logs,
+            vec![(
+                machine_rent_address(),
+                vec![H256(SELECTOR_LOG_CONFIRM_RENT), H256::from(renter), H256::from_low_u64_be(0)],
+                vec![],
+            )]
+        );
+        assert_eq!(RentMachine::rent_info(0).unwrap().rent_status, RentStatus::Renting);
+
+        let rent_end = RentMachine::rent_info(0).unwrap().rent_end;
+        let (result, handle) = call_machine_rent(
+            renter,
+            Selector::ReletMachine,
+            &[ethabi::Token::Uint(0.into()), ethabi::Token::Uint(ONE_DAY.into())],
+        );
+        assert_eq!(result.unwrap(), ethabi::encode(&[ethabi::Token::Bool(true)]));
+        assert_eq!(
+            handle.gas_used,
+            weight_gas(<TestRuntime as rent_machine::Config>::WeightInfo::relet_machine())
+        );
+        assert_eq!(
+            handle.logs,
+            vec![(
+                machine_rent_address(),
+                vec![
+                    H256(SELECTOR_LOG_RELET_MACHINE),
+                    H256::from(renter),
+                    H256::from_low_u64_be(0)
+                ],
+                ethabi::encode(&[ethabi::Token::Uint(ONE_DAY.into())]),
+            )]
+        );
+        assert_eq!(RentMachine::rent_info(0).unwrap().rent_end, rent_end + ONE_DAY as u64);
+    });
+}
+
+#[test]
+fn out_of_range_params_revert_without_charging() {
+    new_test_with_machine_online().execute_with(|| {
+        let renter = evm_address(1);
+        let balance = Balances::free_balance(account_of(renter));
+
+        // GPU数量超出u32
+        let (result, handle) = call_machine_rent(
+            renter,
+            Selector::RentMachine,
+            &rent_params(U256::from(u32::MAX) + 4, ONE_DAY.into()),
+        );
+        assert!(result.is_err());
+        assert!(handle.gas_used.is_zero());
+
+        // 租用时长超出u64
+        let (result, handle) = call_machine_rent(
+            renter,
+            Selector::RentMachine,
+            &rent_params(4.into(), U256::from(u64::MAX) + ONE_DAY),
+        );
+        assert!(result.is_err());
+        assert!(handle.gas_used.is_zero());
+
+        assert_eq!(RentMachine::rent_info(0), None);
+        assert_eq!(Balances::free_balance(account_of(renter)), balance);
+
+        let (result, _) = call_machine_rent(
+            renter,
+            Selector::RentMachine,
+            &rent_params(4.into(), ONE_DAY.into()),
+        );
+        assert!(result.is_ok());
+
+        // 订单ID超出u64时不能截断为已有订单
+        let rent_id = (U256::from(u64::MAX) + 1) * 2;
+        let (result, handle) =
+            call_machine_rent(renter, Selector::ConfirmRent, &[ethabi::Token::Uint(rent_id)]);
+        assert!(result.is_err());
+        assert!(handle.gas_used.is_zero());
+
+        let (result, handle) = call_machine_rent(
+            renter,
+            Selector::ReletMachine,
+            &[ethabi::Token::Uint(rent_id), ethabi::Token::Uint(ONE_DAY.into())],
+        );
+        assert!(result.is_err());
+        assert!(handle.gas_used.is_zero());
+
+        let (result, handle) = call_machine_rent(
+            renter,
+            Selector::ReletMachine,
+            &[ethabi::Token::Uint(0.into()), ethabi::Token::Uint(U256::from(u64::MAX) + 1)],
+        );
+        assert!(result.is_err());
+        assert!(handle.gas_used.is_zero());
+
+        assert_eq!(RentMachine::rent_info(0).unwrap().rent_status, RentStatus::WaitingVerifying);
+    });
+}
+
+#[test]
+fn unknown_rent_order_reverts() {
+    new_test_with_machine_online().execute_with(|| {
+        let renter = evm_address(1);
+
+        let (result, _) =
+            call_machine_rent(renter, Selector::ConfirmRent, &[ethabi::Token::Uint(1.into())]);
+        assert!(result.is_err());
+
+        let (result, _) = call_machine_rent(
+            renter,
+            Selector::ReletMachine,
+            &[ethabi::Token::Uint(1.into()), ethabi::Token::Uint(ONE_DAY.into())],
+        );
+        assert!(result.is_err());
+
+        let (result, _) =
+            call_machine_rent(renter, Selector::GetRentOrder, &[ethabi::Token::Uint(1.into())]);
+        assert!(result.is_err());
+    });
+}
@@ -0,0 +1,183 @@
+use super::super::{
+    mock::*,
+    staking::{
+        Selector, Staking as StakingPrecompile, SELECTOR_LOG_BONDED, SELECTOR_LOG_WITHDRAWN,
+    },
+};
+use fp_evm::{ExitSucceed, Precompile, PrecompileFailure};
+use pallet_staking::{RewardDestination, StakingLedger};
+use sp_core::{H160, H256, U256};
+
+fn staking_address() -> H160 {
+    H160::from_low_u64_be(2053)
+}
+
+fn call_staking(
+    caller: H160,
+    selector: Selector,
+    params: &[ethabi::Token],
+) -> (Result<Vec<u8>, PrecompileFailure>, MockHandle) {
+    let mut input = u32::from(selector).to_be_bytes().to_vec();
+    input.extend(ethabi::encode(params));
+
+    let mut handle = MockHandle::new(staking_address(), caller, input);
+    let result = StakingPrecompile::<TestRuntime>::execute(&mut handle).map(|output| {
+        assert_eq!(output.exit_status, ExitSucceed::Returned);
+        output.output
+    });
+    (result, handle)
+}
+
+fn evm_amount(dbc: u128) -> ethabi::Token {
+    ethabi::Token::Uint(U256::from(dbc) * U256::from(ONE_EVM_DBC))
+}
+
+fn account_token(address: H160) -> ethabi::Token {
+    let account: [u8; 32] = account_of(address).into();
+    ethabi::Token::FixedBytes(account.to_vec())
+}
+
+#[test]
+fn bond_and_nominate_works() {
+    new_test_ext().execute_with(|| {
+        let stash = evm_address(1);
+        let validator = evm_address(2);
+
+        // 金额需为原生精度的整数倍
+        let (result, _) = call_staking(
+            stash,
+            Selector::Bond,
+            &[ethabi::Token::Uint(U256::from(ONE_EVM_DBC + 1)), ethabi::Token::Uint(0.into())],
+        );
+        assert!(result.is_err());
+        // payee 只能为 0..=3
+        let (result, _) =
+            call_staking(stash, Selector::Bond, &[evm_amount(1000), ethabi::Token::Uint(4.into())]);
+        assert!(result.is_err());
+
+        let (result, handle) =
+            call_staking(stash, Selector::Bond, &[evm_amount(1000), ethabi::Token::Uint(1.into())]);
+        assert!(result.is_ok());
+        assert!(handle.gas_used > 0);
+        // Bonded(stash, value)
+        assert_eq!(
+            handle.logs,
+            vec![(
+                staking_address(),
+                vec![H256(SELECTOR_LOG_BONDED), H256::from(stash)],
+                ethabi::encode(&[evm_amount(1000)]),
+            )]
+        );
+        assert_eq!(Staking::bonded(account_of(stash)), Some(account_of(stash)));
+        assert_eq!(Staking::payee(account_of(stash)), RewardDestination::Stash);
+        assert_eq!(Balances::usable_balance(account_of(stash)), INIT_BALANCE - 1000 * ONE_DBC);
+
+        // 不能重复bond
+        let (result, _) =
+            call_staking(stash, Selector::Bond, &[evm_amount(1000), ethabi::Token::Uint(1.into())]);
+        assert!(result.is_err());
+
+        let (result, _) = call_staking(stash, Selector::BondExtra, &[evm_amount(500)]);
+        assert!(result.is_ok());
+        let (result, _) = call_staking(stash, Selector::SetPayee, &[ethabi::Token::Uint(0.into())]);
+        assert!(result.is_ok());
+        assert_eq!(Staking::payee(account_of(stash)), RewardDestination::Staked);
+
+        let (result, _) = call_staking(
+            stash,
+            Selector::Nominate,
+            &[ethabi::Token::Array(vec![account_token(validator)])],
+        );
+        assert!(result.is_ok());
+        assert_eq!(
+            Staking::nominators(account_of(stash)).unwrap().targets.into_inner(),
+            vec![account_of(validator)]
+        );
+
+        let (result, _) = call_staking(stash, Selector::Chill, &[]);
+        assert!(result.is_ok());
+        assert_eq!(Staking::nominators(account_of(stash)), None);
+    })
+}
+
+#[test]
+fn unbond_and_withdraw_works() {
+    new_test_ext().execute_with(|| {
+        let stash = evm_address(1);
+
+        // 未bond时不能unbond
+        let (result, _) = call_staking(stash, Selector::Unbond, &[evm_amount(100)]);
+        assert!(result.is_err());
+
+        let (result, _) =
+            call_staking(stash, Selector::Bond, &[evm_amount(1000), ethabi::Token::Uint(0.into())]);
+        assert!(result.is_ok());
+        let (result, _) = call_staking(stash, Selector::Unbond, &[evm_amount(400)]);
+        assert!(result.is_ok());
+
+        // ledger 以EVM精度返回
+        let (result, _) = call_staking(stash, Selector::GetLedger, &[account_token(stash)]);
+        assert_eq!(
+            result.unwrap(),
+            ethabi::encode(&[evm_amount(1000), evm_amount(600), evm_amount(400)])
+        );
+
+        // 解绑期未过，不能提取
+        let (result, _) =
+            call_staking(stash, Selector::WithdrawUnbonded, &[ethabi::Token::Uint(0.into())]);
+        assert!(result.is_ok());
+        assert_eq!(Staking::ledger(account_of(stash)).unwrap().total, 1000 * ONE_DBC);
+
+        pallet_staking::CurrentEra::<TestRuntime>::put(3);
+        let (result, handle) =
+            call_staking(stash, Selector::WithdrawUnbonded, &[ethabi::Token::Uint(0.into())]);
+        assert!(result.is_ok());
+        // Withdrawn(stash, value)
+        assert_eq!(
+            handle.logs,
+            vec![(
+                staking_address(),
+                vec![H256(SELECTOR_LOG_WITHDRAWN), H256::from(stash)],
+                ethabi::encode(&[evm_amount(400)]),
+            )]
+        );
+        assert_eq!(
+            Staking::ledger(account_of(stash)),
+            Some(StakingLedger {
+                stash: account_of(stash),
+                total: 600 * ONE_DBC,
+                active: 600 * ONE_DBC,
+                unlocking: Default::default(),
+                claimed_rewards: Default::default(),
+            })
+        );
+        assert_eq!(Balances::usable_balance(account_of(stash)), INIT_BALANCE - 600 * ONE_DBC);
+    })
+}
+
+#[test]
+fn staking_views_works() {
+    new_test_ext().execute_with(|| {
+        let stash = evm_address(1);
+
+        let (result, _) =
+            call_staking(stash, Selector::AccountOf, &[ethabi::Token::Address(stash)]);
+        assert_eq!(result.unwrap(), ethabi::encode(&[account_token(stash)]));
+
+        // 未bond的账户返回0
+        let (result, _) = call_staking(stash, Selector::GetLedger, &[account_token(stash)]);
+        assert_eq!(result.unwrap(), ethabi::encode(&[evm_amount(0), evm_amount(0), evm_amount(0)]));
+
+        pallet_staking::CurrentEra::<TestRuntime>::put(5);
+        pallet_staking::ErasValidatorReward::<TestRuntime>::insert(4, 2000 * ONE_DBC);
+
+        let (result, _) = call_staking(stash, Selector::CurrentEra, &[]);
+        assert_eq!(result.unwrap(), ethabi::encode(&[ethabi::Token::Uint(5.into())]));
+        let (result, _) =
+            call_staking(stash, Selector::GetEraReward, &[ethabi::Token::Uint(4.into())]);
+        assert_eq!(result.unwrap(), ethabi::encode(&[evm_amount(2000)]));
+        let (result, _) =
+            call_staking(stash, Selector::GetEraReward, &[ethabi::Token::Uint(5.into())]);
+        assert_eq!(result.unwrap(), ethabi::encode(&[evm_amount(0)]));
+    })
+}